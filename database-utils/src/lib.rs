@@ -90,6 +90,21 @@ pub struct UpstreamConfig {
     #[clap(long, default_value = "50")]
     #[serde(default)]
     pub replication_pool_size: usize,
+
+    /// Guarantee that each client session can read its own writes.
+    ///
+    /// After every write proxied upstream, the position of the write in the upstream database's
+    /// replication log (the GTID for MySQL, or the LSN for PostgreSQL) is recorded, and reads of
+    /// caches over the written tables from the same session are only served by ReadySet once the
+    /// replicator has applied at least that position. Until then, reads wait for the replicator to
+    /// catch up, and fall back to the upstream database if it doesn't do so in time (or
+    /// immediately, with --non-blocking-reads).
+    ///
+    /// For MySQL, this requires GTIDs to be enabled on the upstream database. When running the
+    /// adapter and server separately, this must be passed to both.
+    #[clap(long, env = "READ_YOUR_WRITES")]
+    #[serde(default)]
+    pub read_your_writes: bool,
 }
 
 impl UpstreamConfig {
@@ -143,6 +158,7 @@ impl Default for UpstreamConfig {
             snapshot_report_interval_secs: 30,
             ssl_root_cert: None,
             replication_pool_size: 50,
+            read_your_writes: false,
         }
     }
 }
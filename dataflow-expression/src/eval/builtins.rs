@@ -3,19 +3,25 @@ use std::cmp::Ordering;
 use std::fmt::Write;
 use std::ops::{Add, Div, Mul, Sub};
 
-use chrono::{Datelike, LocalResult, Month, NaiveDate, NaiveDateTime, TimeZone, Timelike, Weekday};
+use chrono::{
+    DateTime, Datelike, Duration, FixedOffset, LocalResult, Month, NaiveDate, NaiveDateTime,
+    TimeZone, Timelike, Weekday,
+};
 use chrono_tz::Tz;
 use itertools::Either;
 use mysql_time::MySqlTime;
-use readyset_data::{DfType, DfValue};
-use readyset_errors::{invalid_err, ReadySetError, ReadySetResult};
+use nom_sql::{IntervalUnit, TimestampField};
+use readyset_data::dialect::SqlEngine;
+use readyset_data::{Array, DfType, DfValue};
+use readyset_errors::{internal, invalid, invalid_err, ReadySetError, ReadySetResult};
 use readyset_util::math::integer_rnd;
 use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
 use rust_decimal::Decimal;
 use serde_json::Value as JsonValue;
 use vec1::Vec1;
 
-use crate::{BuiltinFunction, Expr};
+use crate::regexp::{self, RegexFlags, Replacement};
+use crate::{BuiltinFunction, Expr, TrimSide};
 
 macro_rules! try_cast_or_none {
     ($df_value:expr, $to_ty:expr, $from_ty:expr) => {{
@@ -26,6 +32,25 @@ macro_rules! try_cast_or_none {
     }};
 }
 
+/// Evaluate the optional flags argument to one of the `regexp_*` functions, returning [`None`] if
+/// it evaluated to NULL
+fn eval_regex_flags<D>(
+    flags: Option<&Expr>,
+    engine: SqlEngine,
+    record: &[D],
+) -> ReadySetResult<Option<RegexFlags>>
+where
+    D: Borrow<DfValue>,
+{
+    let Some(flags) = flags else {
+        return Ok(Some(RegexFlags::new(engine)));
+    };
+    match flags.eval(record)? {
+        DfValue::None => Ok(None),
+        flags => Ok(Some(RegexFlags::parse((&flags).try_into()?, engine)?)),
+    }
+}
+
 /// Returns the type of data stored in a JSON value as a string.
 fn get_json_value_type(json: &serde_json::Value) -> &'static str {
     match json {
@@ -108,84 +133,81 @@ fn addtime_times(time1: &MySqlTime, time2: &MySqlTime) -> MySqlTime {
     time1.add(*time2)
 }
 
-/// Format the given time value according to the given `format_string`, using the [MySQL date
-/// formatting rules][mysql-docs]. Since these rules don't match up well with anything available in
-/// the Rust crate ecosystem, this is done manually.
+/// Calculate the week (and year!) number of a date-like value according to the ...algorithm...
+/// that MySQL uses. Returns a tuple of (week number, year), since in some operating modes a day
+/// may be part of the first week of the next year, or last week of the previous year.
 ///
-/// [mysql-docs]: https://dev.mysql.com/doc/refman/8.0/en/date-and-time-functions.html#function_date-format
-fn mysql_date_format<T>(time: T, format_string: &str) -> ReadySetResult<String>
+/// The actual algorithm here, and the arguments passed, are pretty close to a line-for-line
+/// translation of what MySQL uses, hence being quite impressively unidiomatic Rust: basically,
+/// I'd recommend thinking of this function and all of its callers as a black-box, at least for
+/// the time being (until we can dedicate the energy to actually understand what's going on
+/// here)
+fn week_and_year<T>(
+    time: &T,
+    monday_first: bool,
+    mut week_year: bool,
+    first_weekday: bool,
+) -> (u32, i32)
 where
-    T: Timelike + Datelike,
+    T: Datelike,
 {
-    /// Calculate the week (and year!) number of a date-like value according to the ...algorithm...
-    /// that MySQL uses. Returns a tuple of (week number, year), since in some operating modes a day
-    /// may be part of the first week of the next year, or last week of the previous year.
-    ///
-    /// The actual algorithm here, and the arguments passed, are pretty close to a line-for-line
-    /// translation of what MySQL uses, hence being quite impressively unidiomatic Rust: basically,
-    /// I'd recommend thinking of this function and all of its callers as a black-box, at least for
-    /// the time being (until we can dedicate the energy to actually understand what's going on
-    /// here)
-    fn week_and_year<T>(
-        time: &T,
-        monday_first: bool,
-        mut week_year: bool,
-        first_weekday: bool,
-    ) -> (u32, i32)
-    where
-        T: Datelike,
-    {
-        fn days_in_year(year: i32) -> i32 {
-            if (year & 3) == 0 && ((year % 100 != 0) || (year % 400 == 0 && year != 0)) {
-                366
-            } else {
-                365
-            }
-        }
-
-        let mut days;
-        let daynr = time.num_days_from_ce();
-        let mut first_daynr = time
-            .with_day(1)
-            .unwrap()
-            .with_month(1)
-            .unwrap()
-            .num_days_from_ce();
-        let mut weekday = if monday_first {
-            time.weekday().num_days_from_monday()
+    fn days_in_year(year: i32) -> i32 {
+        if (year & 3) == 0 && ((year % 100 != 0) || (year % 400 == 0 && year != 0)) {
+            366
         } else {
-            time.weekday().num_days_from_sunday()
-        } as i32;
-        let mut year = time.year();
-
-        if time.month() == 1 && time.day() <= (7 - weekday) as u32 {
-            if !week_year && ((first_weekday && weekday != 0) || (!first_weekday && weekday >= 4)) {
-                return (0, year);
-            }
-            week_year = true;
-            year -= 1;
-            days = days_in_year(year);
-            first_daynr -= days;
-            weekday = (weekday + 53 * 7 - days) % 7;
+            365
         }
+    }
 
-        if (first_weekday && weekday != 0) || (!first_weekday && weekday >= 4) {
-            days = daynr - (first_daynr + (7 - weekday))
-        } else {
-            days = daynr - (first_daynr - weekday)
+    let mut days;
+    let daynr = time.num_days_from_ce();
+    let first_day = time.with_day(1).unwrap().with_month(1).unwrap();
+    let mut first_daynr = first_day.num_days_from_ce();
+    // The weekday of the first day of the year
+    let mut weekday = if monday_first {
+        first_day.weekday().num_days_from_monday()
+    } else {
+        first_day.weekday().num_days_from_sunday()
+    } as i32;
+    let mut year = time.year();
+
+    if time.month() == 1 && time.day() <= (7 - weekday) as u32 {
+        if !week_year && ((first_weekday && weekday != 0) || (!first_weekday && weekday >= 4)) {
+            return (0, year);
         }
+        week_year = true;
+        year -= 1;
+        days = days_in_year(year);
+        first_daynr -= days;
+        weekday = (weekday + 53 * 7 - days) % 7;
+    }
 
-        if week_year && days >= 52 * 7 {
-            weekday = (weekday + days_in_year(year)) % 7;
-            if (!first_weekday && weekday < 4) || (first_weekday && weekday == 0) {
-                year += 1;
-                return (1, year);
-            }
-        }
+    if (first_weekday && weekday != 0) || (!first_weekday && weekday >= 4) {
+        days = daynr - (first_daynr + (7 - weekday))
+    } else {
+        days = daynr - (first_daynr - weekday)
+    }
 
-        ((days / 7 + 1) as u32, year)
+    if week_year && days >= 52 * 7 {
+        weekday = (weekday + days_in_year(year)) % 7;
+        if (!first_weekday && weekday < 4) || (first_weekday && weekday == 0) {
+            year += 1;
+            return (1, year);
+        }
     }
 
+    ((days / 7 + 1) as u32, year)
+}
+
+/// Format the given time value according to the given `format_string`, using the [MySQL date
+/// formatting rules][mysql-docs]. Since these rules don't match up well with anything available in
+/// the Rust crate ecosystem, this is done manually.
+///
+/// [mysql-docs]: https://dev.mysql.com/doc/refman/8.0/en/date-and-time-functions.html#function_date-format
+fn mysql_date_format<T>(time: T, format_string: &str) -> ReadySetResult<String>
+where
+    T: Timelike + Datelike,
+{
     // | %a   | Abbreviated weekday name (Sun..Sat)
     // | %b   | Abbreviated month name (Jan..Dec)
     // | %c   | Month, numeric (0..12)
@@ -294,19 +316,11 @@ where
                     time.second()
                 )
                 .unwrap(),
-                'U' => write!(res, "{:02}", week_and_year(&time, false, false, false).0).unwrap(),
+                'U' => write!(res, "{:02}", week_and_year(&time, false, false, true).0).unwrap(),
                 'u' => write!(res, "{:02}", week_and_year(&time, true, false, false).0).unwrap(),
                 'V' => write!(res, "{:02}", week_and_year(&time, false, true, true).0).unwrap(),
                 'v' => write!(res, "{:02}", week_and_year(&time, true, true, false).0).unwrap(),
-                'W' => res.push_str(match time.weekday() {
-                    Weekday::Mon => "Monday",
-                    Weekday::Tue => "Tuesday",
-                    Weekday::Wed => "Wednesday",
-                    Weekday::Thu => "Thursday",
-                    Weekday::Fri => "Friday",
-                    Weekday::Sat => "Saturday",
-                    Weekday::Sun => "Sunday",
-                }),
+                'W' => res.push_str(weekday_name(time.weekday())),
                 'w' => write!(res, "{}", time.weekday().num_days_from_sunday()).unwrap(),
                 'X' => write!(res, "{:02}", week_and_year(&time, false, true, true).1).unwrap(),
                 'x' => write!(res, "{:02}", week_and_year(&time, true, true, false).1).unwrap(),
@@ -350,6 +364,658 @@ where
     Ok(try_cast_or_none!(res, ty, res_ty))
 }
 
+/// Returns the given string, padded to `len` characters with repetitions of `pad` on the left (if
+/// `left` is true) or the right, or truncated to `len` characters if it's already longer than
+/// that.
+fn pad(s: &str, len: i64, pad: &str, left: bool, engine: SqlEngine) -> DfValue {
+    let Ok(len) = usize::try_from(len) else {
+        return match engine {
+            SqlEngine::MySQL => DfValue::None,
+            SqlEngine::PostgreSQL => "".into(),
+        };
+    };
+
+    let char_len = s.chars().count();
+    if char_len >= len {
+        return s.chars().take(len).collect::<String>().into();
+    }
+    if pad.is_empty() {
+        return match engine {
+            SqlEngine::MySQL => DfValue::None,
+            SqlEngine::PostgreSQL => s.into(),
+        };
+    }
+
+    let padding = pad.chars().cycle().take(len - char_len);
+    if left {
+        padding.chain(s.chars()).collect::<String>().into()
+    } else {
+        s.chars().chain(padding).collect::<String>().into()
+    }
+}
+
+/// Returns the 1-based character position of the first occurrence of `substring` in `string` at
+/// or after the character position `start`, or 0 if there is no such occurrence.
+fn locate(substring: &str, string: &str, start: i64) -> i64 {
+    let Some(skip) = start.checked_sub(1).and_then(|s| usize::try_from(s).ok()) else {
+        return 0;
+    };
+    let Some((offset, _)) = string.char_indices().chain([(string.len(), ' ')]).nth(skip) else {
+        return 0;
+    };
+
+    match string[offset..].find(substring) {
+        Some(idx) => start + string[offset..offset + idx].chars().count() as i64,
+        None => 0,
+    }
+}
+
+/// Converts the first letter of each word in the given string to uppercase and the rest to
+/// lowercase, where words are sequences of alphanumeric characters.
+fn initcap(s: &str) -> String {
+    let mut res = String::with_capacity(s.len());
+    let mut in_word = false;
+    for c in s.chars() {
+        if in_word {
+            res.extend(c.to_lowercase());
+        } else {
+            res.extend(c.to_uppercase());
+        }
+        in_word = c.is_alphanumeric();
+    }
+    res
+}
+
+/// Truncates the given numeric value towards zero, to `places` digits after the decimal point (or
+/// before it, if `places` is negative).
+fn truncate(value: DfValue, places: i64) -> ReadySetResult<DfValue> {
+    let places = places.clamp(-40, 40) as i32;
+    match value {
+        DfValue::Int(i) if places < 0 => {
+            let factor = 10i128.pow(places.unsigned_abs());
+            DfValue::try_from(i as i128 / factor * factor)
+        }
+        DfValue::UnsignedInt(i) if places < 0 => {
+            let factor = 10i128.pow(places.unsigned_abs());
+            DfValue::try_from(i as i128 / factor * factor)
+        }
+        v @ (DfValue::Int(_) | DfValue::UnsignedInt(_)) => Ok(v),
+        DfValue::Numeric(d) => Ok(if places >= 0 {
+            d.round_dp_with_strategy(places as _, rust_decimal::RoundingStrategy::ToZero)
+        } else {
+            let factor = Decimal::from_f64(10.0f64.powi(-places)).unwrap_or(Decimal::MAX);
+            d.div(factor).trunc().mul(factor)
+        }
+        .into()),
+        v => {
+            let value = f64::try_from(&v)?;
+            let factor = 10.0f64.powi(places.abs());
+            DfValue::try_from(if places >= 0 {
+                (value * factor).trunc() / factor
+            } else {
+                (value / factor).trunc() * factor
+            })
+        }
+    }
+}
+
+/// An interval of time to add to a date or timestamp.
+///
+/// Months and days are kept separate from the rest of the interval, since they don't have a fixed
+/// length: adding a month to January 31st gives the last day of February, and adding a day across
+/// a daylight saving time transition doesn't add exactly 24 hours.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+struct Interval {
+    months: i64,
+    days: i64,
+    microseconds: i64,
+}
+
+impl Interval {
+    /// Build an interval of `amount` of the given non-compound unit. As in PostgreSQL, fractional
+    /// amounts of months and days are carried over into the smaller units, so that for example 1.5
+    /// months is 1 month and 15 days.
+    fn from_amount(amount: f64, unit: IntervalUnit) -> Option<Self> {
+        use IntervalUnit::*;
+
+        if !amount.is_finite() {
+            return None;
+        }
+        let microseconds = |per_unit: f64| {
+            let microseconds = (amount * per_unit).round();
+            (microseconds.abs() < i64::MAX as f64).then(|| Self {
+                microseconds: microseconds as i64,
+                ..Default::default()
+            })
+        };
+
+        match unit {
+            Microsecond => microseconds(1.0),
+            Second => microseconds(1_000_000.0),
+            Minute => microseconds(60_000_000.0),
+            Hour => microseconds(3_600_000_000.0),
+            Day | Week => {
+                let days = if unit == Week { amount * 7.0 } else { amount };
+                let whole = days.trunc();
+                Some(Self {
+                    days: whole as i64,
+                    ..Self::from_amount((days - whole) * 24.0, Hour)?
+                })
+            }
+            Month | Quarter | Year => {
+                let months = amount
+                    * match unit {
+                        Month => 1.0,
+                        Quarter => 3.0,
+                        _ => 12.0,
+                    };
+                let whole = months.trunc();
+                Some(Self {
+                    months: whole as i64,
+                    ..Self::from_amount((months - whole) * 30.0, Day)?
+                })
+            }
+            _ => None,
+        }
+    }
+
+    /// Parse the value of a MySQL interval with a compound unit, such as the `'1:30'` in `INTERVAL
+    /// '1:30' HOUR_MINUTE`. Any non-digit characters separate the parts of the interval, and if
+    /// fewer parts are given than the unit has they're taken to be the smallest ones.
+    fn parse_compound(s: &str, unit: IntervalUnit) -> Option<Self> {
+        use IntervalUnit::*;
+
+        let parts: &[IntervalUnit] = match unit {
+            SecondMicrosecond => &[Second, Microsecond],
+            MinuteMicrosecond => &[Minute, Second, Microsecond],
+            MinuteSecond => &[Minute, Second],
+            HourMicrosecond => &[Hour, Minute, Second, Microsecond],
+            HourSecond => &[Hour, Minute, Second],
+            HourMinute => &[Hour, Minute],
+            DayMicrosecond => &[Day, Hour, Minute, Second, Microsecond],
+            DaySecond => &[Day, Hour, Minute, Second],
+            DayMinute => &[Day, Hour, Minute],
+            DayHour => &[Day, Hour],
+            YearMonth => &[Year, Month],
+            _ => return None,
+        };
+
+        let s = s.trim();
+        let (s, negative) = match s.strip_prefix('-') {
+            Some(s) => (s, true),
+            None => (s, false),
+        };
+        let values = s
+            .split(|c: char| !c.is_ascii_digit())
+            .filter(|v| !v.is_empty())
+            .collect::<Vec<_>>();
+        if values.is_empty() || values.len() > parts.len() {
+            return None;
+        }
+
+        let mut res = Self::default();
+        for (i, (value, unit)) in values
+            .iter()
+            .zip(&parts[parts.len() - values.len()..])
+            .enumerate()
+        {
+            let amount = if *unit == Microsecond && i > 0 {
+                // Microseconds written after seconds are a fraction of a second, so `1.5` is 1
+                // second and 500000 microseconds
+                format!("{:0<6.6}", value).parse::<i64>().ok()?
+            } else {
+                value.parse::<i64>().ok()?
+            };
+            res = res.checked_add(Self::from_amount(amount as f64, *unit)?)?;
+        }
+
+        Some(if negative { res.negate() } else { res })
+    }
+
+    /// Parse a PostgreSQL interval string, such as `'1 day 2 hours'`, `'3 mons'`, `'01:30:00'` or
+    /// `'2 weeks ago'`
+    fn parse_postgres(s: &str) -> Option<Self> {
+        let s = s.trim().to_ascii_lowercase();
+        let s = s.strip_prefix('@').unwrap_or(&s).trim();
+        let (s, ago) = match s.strip_suffix("ago") {
+            Some(s) => (s.trim_end(), true),
+            None => (s, false),
+        };
+
+        let mut res = Self::default();
+        let mut tokens = s.split_whitespace().peekable();
+        if tokens.peek().is_none() {
+            return None;
+        }
+        while let Some(token) = tokens.next() {
+            let interval = if token.contains(':') {
+                Self::parse_time(token)?
+            } else {
+                let (amount, unit) =
+                    token.split_at(token.find(char::is_alphabetic).unwrap_or(token.len()));
+                let amount = amount.parse::<f64>().ok()?;
+                let unit = if unit.is_empty() {
+                    // A number without a unit is a number of seconds
+                    tokens
+                        .next_if(|t| t.starts_with(char::is_alphabetic))
+                        .unwrap_or("second")
+                } else {
+                    unit
+                };
+                Self::from_postgres_unit(amount, unit)?
+            };
+            res = res.checked_add(interval)?;
+        }
+
+        Some(if ago { res.negate() } else { res })
+    }
+
+    /// Parse the time part of a PostgreSQL interval string, such as `'-01:30'` or `'1:02:03.5'`
+    fn parse_time(s: &str) -> Option<Self> {
+        let (s, negative) = match s.strip_prefix('-') {
+            Some(s) => (s, true),
+            None => (s.strip_prefix('+').unwrap_or(s), false),
+        };
+        let mut parts = s.split(':');
+        let hours = parts.next()?.parse::<f64>().ok()?;
+        let minutes = parts.next()?.parse::<f64>().ok()?;
+        let seconds = parts.next().map_or(Ok(0.0), str::parse::<f64>).ok()?;
+        if parts.next().is_some() {
+            return None;
+        }
+
+        let res = Self::from_amount(
+            hours * 3600.0 + minutes * 60.0 + seconds,
+            IntervalUnit::Second,
+        )?;
+        Some(if negative { res.negate() } else { res })
+    }
+
+    fn from_postgres_unit(amount: f64, unit: &str) -> Option<Self> {
+        use IntervalUnit::*;
+
+        let (amount, unit) = match unit {
+            "microsecond" | "microseconds" | "us" | "usec" | "usecs" => (amount, Microsecond),
+            "millisecond" | "milliseconds" | "ms" | "msec" | "msecs" => {
+                (amount * 1000.0, Microsecond)
+            }
+            "second" | "seconds" | "s" | "sec" | "secs" => (amount, Second),
+            "minute" | "minutes" | "m" | "min" | "mins" => (amount, Minute),
+            "hour" | "hours" | "h" | "hr" | "hrs" => (amount, Hour),
+            "day" | "days" | "d" => (amount, Day),
+            "week" | "weeks" | "w" => (amount, Week),
+            "month" | "months" | "mon" | "mons" => (amount, Month),
+            "year" | "years" | "y" | "yr" | "yrs" => (amount, Year),
+            "decade" | "decades" => (amount * 10.0, Year),
+            "century" | "centuries" => (amount * 100.0, Year),
+            "millennium" | "millennia" | "millenniums" => (amount * 1000.0, Year),
+            _ => return None,
+        };
+        Self::from_amount(amount, unit)
+    }
+
+    /// Interpret the value of an `INTERVAL` expression with the given unit
+    fn from_value(value: &DfValue, unit: Option<IntervalUnit>, engine: SqlEngine) -> Option<Self> {
+        use IntervalUnit::*;
+
+        match unit {
+            None => Self::parse_postgres(<&str>::try_from(value).ok()?),
+            Some(
+                unit @ (Microsecond | Second | Minute | Hour | Day | Week | Month | Quarter | Year),
+            ) => {
+                let amount = match <&str>::try_from(value) {
+                    Ok(s) => match s.trim().parse::<f64>() {
+                        Ok(amount) => amount,
+                        // PostgreSQL allows a full interval string along with a unit, such as
+                        // `INTERVAL '1 day' HOUR`
+                        Err(_) if engine == SqlEngine::PostgreSQL => {
+                            return Self::parse_postgres(s)
+                        }
+                        Err(_) => return None,
+                    },
+                    Err(_) => f64::try_from(value).ok()?,
+                };
+                // MySQL rounds fractional amounts of anything other than seconds to an integer
+                let amount = if engine == SqlEngine::MySQL && !matches!(unit, Second | Microsecond)
+                {
+                    amount.round()
+                } else {
+                    amount
+                };
+                Self::from_amount(amount, unit)
+            }
+            Some(unit) => Self::parse_compound(&value.to_string(), unit),
+        }
+    }
+
+    fn negate(self) -> Self {
+        Self {
+            months: -self.months,
+            days: -self.days,
+            microseconds: -self.microseconds,
+        }
+    }
+
+    fn checked_add(self, other: Self) -> Option<Self> {
+        Some(Self {
+            months: self.months.checked_add(other.months)?,
+            days: self.days.checked_add(other.days)?,
+            microseconds: self.microseconds.checked_add(other.microseconds)?,
+        })
+    }
+
+    /// Add this interval to the given timestamp, returning [`None`] if the result is out of range
+    fn add_to(self, datetime: NaiveDateTime) -> Option<NaiveDateTime> {
+        // Bounds check before constructing the `Duration`, which panics on overflow
+        if self.days.abs() > 1_000_000_000 {
+            return None;
+        }
+
+        let datetime = if self.months != 0 {
+            add_months(datetime, self.months)?
+        } else {
+            datetime
+        };
+        datetime
+            .checked_add_signed(Duration::days(self.days))?
+            .checked_add_signed(Duration::microseconds(self.microseconds))
+    }
+}
+
+/// Add the given number of months to the timestamp, clamping the day to the end of the resulting
+/// month if necessary
+fn add_months(datetime: NaiveDateTime, months: i64) -> Option<NaiveDateTime> {
+    let total_months = i64::from(datetime.year()) * 12 + i64::from(datetime.month0()) + months;
+    let year = i32::try_from(total_months.div_euclid(12)).ok()?;
+    let month = total_months.rem_euclid(12) as u32 + 1;
+    let last_day = (28..=31)
+        .rev()
+        .find(|day| NaiveDate::from_ymd_opt(year, month, *day).is_some())?;
+    Some(
+        NaiveDate::from_ymd_opt(year, month, datetime.day().min(last_day))?
+            .and_time(datetime.time()),
+    )
+}
+
+/// Returns the local date and time of a timestamp value, along with its offset from UTC if it has
+/// a timezone
+fn local_datetime(value: &DfValue) -> Option<(NaiveDateTime, Option<FixedOffset>)> {
+    match value {
+        DfValue::TimestampTz(ts) => {
+            let datetime = ts.to_chrono();
+            Some((
+                datetime.naive_local(),
+                ts.has_timezone().then(|| *datetime.offset()),
+            ))
+        }
+        _ => None,
+    }
+}
+
+/// Convert a local date and time computed by a date/time function back into a value of the given
+/// type, re-attaching the timezone offset of the function's argument if it had one
+fn timestamp_result(
+    datetime: NaiveDateTime,
+    offset: Option<FixedOffset>,
+    ty: &DfType,
+) -> Option<DfValue> {
+    let value = match offset {
+        Some(offset) => DfValue::from(offset.from_local_datetime(&datetime).single()?),
+        None => DfValue::TimestampTz(datetime.into()),
+    };
+    match ty {
+        DfType::Date | DfType::DateTime { .. } | DfType::Timestamp { .. } => {
+            value.coerce_to(ty, &DfType::Unknown).ok()
+        }
+        _ => Some(value),
+    }
+}
+
+/// Extract the given field from a date, timestamp, or (in MySQL) time value.
+///
+/// The field is returned as a decimal so that PostgreSQL's fractional seconds can be represented,
+/// or [`None`] if the value doesn't have the field (such as the year of a MySQL time).
+fn extract(
+    field: TimestampField,
+    value: &DfValue,
+    engine: SqlEngine,
+) -> ReadySetResult<Option<Decimal>> {
+    use TimestampField::*;
+
+    let (datetime, hour, minute, second, microsecond) = match value {
+        DfValue::Time(time) => (
+            None,
+            i64::from(time.hour()),
+            i64::from(time.minutes()),
+            i64::from(time.seconds()),
+            i64::from(time.microseconds()),
+        ),
+        _ => {
+            let datetime = DateTime::<FixedOffset>::try_from(value)?;
+            let local = datetime.naive_local();
+            (
+                Some(datetime),
+                i64::from(local.hour()),
+                i64::from(local.minute()),
+                i64::from(local.second()),
+                i64::from(local.nanosecond() / 1000),
+            )
+        }
+    };
+    let postgres = engine == SqlEngine::PostgreSQL;
+    let seconds_with_fraction = second * 1_000_000 + microsecond;
+
+    let res = match field {
+        Hour => hour.into(),
+        Minute => minute.into(),
+        Second if postgres => Decimal::new(seconds_with_fraction, 6),
+        Second => second.into(),
+        Millisecond => Decimal::new(seconds_with_fraction, 3),
+        Microsecond if postgres => seconds_with_fraction.into(),
+        Microsecond => microsecond.into(),
+        HourMinute => (hour * 100 + minute).into(),
+        HourSecond => (hour * 10_000 + minute * 100 + second).into(),
+        HourMicrosecond => {
+            ((hour * 10_000 + minute * 100) * 1_000_000 + seconds_with_fraction).into()
+        }
+        MinuteSecond => (minute * 100 + second).into(),
+        MinuteMicrosecond => (minute * 100_000_000 + seconds_with_fraction).into(),
+        SecondMicrosecond => seconds_with_fraction.into(),
+        _ => {
+            let Some(datetime) = datetime else {
+                return Ok(None);
+            };
+            let date = datetime.naive_local().date();
+            let year = i64::from(date.year());
+            let day = i64::from(date.day());
+            match field {
+                // Years are numbered astronomically by chrono, so year 0 is 1 BC
+                Century if year > 0 => ((year - 1) / 100 + 1).into(),
+                Century => (-(-year / 100 + 1)).into(),
+                Decade => year.div_euclid(10).into(),
+                Millennium if year > 0 => ((year - 1) / 1000 + 1).into(),
+                Millennium => (-(-year / 1000 + 1)).into(),
+                Year => year.into(),
+                Isoyear => date.iso_week().year().into(),
+                Quarter => (date.month0() / 3 + 1).into(),
+                Month => date.month().into(),
+                Week if postgres => date.iso_week().week().into(),
+                // MySQL uses `WEEK()` mode 0 for `EXTRACT(WEEK ...)`
+                Week => week_and_year(&date, false, false, true).0.into(),
+                Day => day.into(),
+                Dow => date.weekday().num_days_from_sunday().into(),
+                Isodow => date.weekday().number_from_monday().into(),
+                Doy => date.ordinal().into(),
+                Epoch => Decimal::new(datetime.timestamp() * 1_000_000 + microsecond, 6),
+                YearMonth => (year * 100 + i64::from(date.month())).into(),
+                DayHour => (day * 100 + hour).into(),
+                DayMinute => (day * 10_000 + hour * 100 + minute).into(),
+                DaySecond => (day * 1_000_000 + hour * 10_000 + minute * 100 + second).into(),
+                DayMicrosecond => ((day * 1_000_000 + hour * 10_000 + minute * 100) * 1_000_000
+                    + seconds_with_fraction)
+                    .into(),
+                Hour | Minute | Second | Millisecond | Microsecond | HourMinute | HourSecond
+                | HourMicrosecond | MinuteSecond | MinuteMicrosecond | SecondMicrosecond => {
+                    internal!("Time fields are handled above")
+                }
+            }
+        }
+    };
+
+    Ok(Some(res))
+}
+
+/// Truncate the given timestamp to the precision given by `field_name`, according to the rules of
+/// PostgreSQL's [`date_trunc`][pg-docs]
+///
+/// [pg-docs]: https://www.postgresql.org/docs/current/functions-datetime.html#FUNCTIONS-DATETIME-TRUNC
+fn date_trunc(field_name: &str, datetime: NaiveDateTime) -> ReadySetResult<NaiveDateTime> {
+    use TimestampField::*;
+
+    let field = TimestampField::from_name(field_name)
+        .ok_or_else(|| invalid_err!("unit \"{field_name}\" not recognized"))?;
+    let date = datetime.date();
+    let start_of_year = |year: i32| NaiveDate::from_ymd_opt(year, 1, 1);
+    let truncated_date = match field {
+        Microsecond => return Ok(datetime),
+        Millisecond => {
+            return datetime
+                .with_nanosecond(datetime.nanosecond() / 1_000_000 * 1_000_000)
+                .ok_or_else(|| invalid_err!("timestamp out of range"))
+        }
+        Second => {
+            return datetime
+                .with_nanosecond(0)
+                .ok_or_else(|| invalid_err!("timestamp out of range"))
+        }
+        Minute => return Ok(date.and_hms(datetime.hour(), datetime.minute(), 0)),
+        Hour => return Ok(date.and_hms(datetime.hour(), 0, 0)),
+        Day => Some(date),
+        Week => date.checked_sub_signed(Duration::days(i64::from(
+            date.weekday().num_days_from_monday(),
+        ))),
+        Month => date.with_day(1),
+        Quarter => NaiveDate::from_ymd_opt(date.year(), date.month0() / 3 * 3 + 1, 1),
+        Year => start_of_year(date.year()),
+        Decade => start_of_year(date.year().div_euclid(10) * 10),
+        Century => start_of_year((date.year() - 1).div_euclid(100) * 100 + 1),
+        Millennium => start_of_year((date.year() - 1).div_euclid(1000) * 1000 + 1),
+        _ => {
+            return Err(invalid_err!(
+                "unit \"{field_name}\" not supported for date_trunc"
+            ))
+        }
+    };
+
+    Ok(truncated_date
+        .ok_or_else(|| invalid_err!("timestamp out of range"))?
+        .and_hms(0, 0, 0))
+}
+
+fn weekday_name(weekday: Weekday) -> &'static str {
+    match weekday {
+        Weekday::Mon => "Monday",
+        Weekday::Tue => "Tuesday",
+        Weekday::Wed => "Wednesday",
+        Weekday::Thu => "Thursday",
+        Weekday::Fri => "Friday",
+        Weekday::Sat => "Saturday",
+        Weekday::Sun => "Sunday",
+    }
+}
+
+/// Format the given timestamp according to a [PostgreSQL template pattern][pg-docs], such as
+/// `'YYYY-MM-DD HH24:MI:SS'`.
+///
+/// Only the most common date and time patterns are supported, along with the `FM` (fill mode)
+/// prefix and double-quoted literal text. Anything else is copied to the output unchanged.
+///
+/// [pg-docs]: https://www.postgresql.org/docs/current/functions-formatting.html#FUNCTIONS-FORMATTING-DATETIME-TABLE
+fn postgres_to_char(datetime: &NaiveDateTime, format_string: &str) -> String {
+    const PATTERNS: &[&str] = &[
+        "HH24", "HH12", "IYYY", "YYYY", "MONTH", "Month", "month", "MON", "Mon", "mon", "DAY",
+        "Day", "day", "DDD", "HH", "MI", "SS", "MS", "US", "AM", "PM", "am", "pm", "YY", "MM",
+        "DD", "DY", "Dy", "dy", "IW", "D", "Q",
+    ];
+
+    let mut res = String::with_capacity(format_string.len().next_power_of_two());
+    let mut rest = format_string;
+    let mut fill_mode = false;
+    while let Some(c) = rest.chars().next() {
+        if let Some(r) = rest.strip_prefix("FM") {
+            fill_mode = true;
+            rest = r;
+            continue;
+        }
+        if let Some(r) = rest.strip_prefix('"') {
+            let end = r.find('"').unwrap_or(r.len());
+            res.push_str(&r[..end]);
+            rest = r.get(end + 1..).unwrap_or_default();
+            continue;
+        }
+        let Some(pattern) = PATTERNS.iter().find(|p| rest.starts_with(*p)) else {
+            res.push(c);
+            rest = &rest[c.len_utf8()..];
+            continue;
+        };
+
+        let number = |n: i64, width: usize| {
+            if fill_mode {
+                n.to_string()
+            } else {
+                format!("{n:0width$}")
+            }
+        };
+        // Names are upper-, lower- or title-cased to match the pattern, and full names are padded
+        // to 9 characters
+        let name = |name: &str, pad: bool| {
+            let name = if pattern.chars().all(|c| c.is_ascii_uppercase()) {
+                name.to_ascii_uppercase()
+            } else if pattern.starts_with(|c: char| c.is_ascii_lowercase()) {
+                name.to_ascii_lowercase()
+            } else {
+                name.to_owned()
+            };
+            if fill_mode || !pad {
+                name
+            } else {
+                format!("{name:<9}")
+            }
+        };
+
+        let month_name = Month::from_u32(datetime.month()).unwrap().name();
+        let day_name = weekday_name(datetime.weekday());
+        let formatted = match *pattern {
+            "HH24" => number(datetime.hour().into(), 2),
+            "HH12" | "HH" => number(datetime.hour12().1.into(), 2),
+            "IYYY" => number(datetime.iso_week().year().into(), 4),
+            "YYYY" => number(datetime.year().into(), 4),
+            "YY" => number((datetime.year() % 100).into(), 2),
+            "MONTH" | "Month" | "month" => name(month_name, true),
+            "MON" | "Mon" | "mon" => name(&month_name[..3], false),
+            "DAY" | "Day" | "day" => name(day_name, true),
+            "DY" | "Dy" | "dy" => name(&day_name[..3], false),
+            "DDD" => number(datetime.ordinal().into(), 3),
+            "DD" => number(datetime.day().into(), 2),
+            "D" => number(datetime.weekday().number_from_sunday().into(), 1),
+            "MM" => number(datetime.month().into(), 2),
+            "MI" => number(datetime.minute().into(), 2),
+            "SS" => number(datetime.second().into(), 2),
+            "MS" => number((datetime.nanosecond() / 1_000_000).into(), 3),
+            "US" => number((datetime.nanosecond() / 1_000).into(), 6),
+            "IW" => number(datetime.iso_week().week().into(), 2),
+            "Q" => number((datetime.month0() / 3 + 1).into(), 1),
+            "AM" | "PM" => (if datetime.hour12().0 { "PM" } else { "AM" }).to_owned(),
+            _ => (if datetime.hour12().0 { "pm" } else { "am" }).to_owned(),
+        };
+        res.push_str(&formatted);
+        rest = &rest[pattern.len()..];
+        fill_mode = false;
+    }
+
+    res
+}
+
 impl BuiltinFunction {
     pub(crate) fn eval<D>(&self, ty: &DfType, record: &[D]) -> ReadySetResult<DfValue>
     where
@@ -796,8 +1462,448 @@ impl BuiltinFunction {
             BuiltinFunction::Greatest { args, compare_as } => {
                 greatest_or_least(args, record, compare_as, ty, |v1, v2| v1 > v2)
             }
-            BuiltinFunction::Least { args, compare_as } => {
-                greatest_or_least(args, record, compare_as, ty, |v1, v2| v1 < v2)
+            BuiltinFunction::Least { args, compare_as } => {
+                greatest_or_least(args, record, compare_as, ty, |v1, v2| v1 < v2)
+            }
+            BuiltinFunction::Lower(string) => {
+                let string = non_null!(string.eval(record)?);
+                Ok(<&str>::try_from(&string)?.to_lowercase().into())
+            }
+            BuiltinFunction::Upper(string) => {
+                let string = non_null!(string.eval(record)?);
+                Ok(<&str>::try_from(&string)?.to_uppercase().into())
+            }
+            BuiltinFunction::Length { expr, in_bytes } => {
+                let string = non_null!(expr.eval(record)?);
+                let s = <&str>::try_from(&string)?;
+                let len = if *in_bytes {
+                    s.len()
+                } else {
+                    s.chars().count()
+                };
+                Ok(DfValue::from(len as i64))
+            }
+            BuiltinFunction::Trim {
+                string,
+                chars,
+                side,
+            } => {
+                let string = non_null!(string.eval(record)?);
+                let s = <&str>::try_from(&string)?;
+                let chars = match chars {
+                    Some(chars) => String::try_from(non_null!(chars.eval(record)?))?,
+                    None => " ".to_owned(),
+                };
+                let is_trimmed = |c: char| chars.contains(c);
+                Ok(match side {
+                    TrimSide::Both => s.trim_matches(is_trimmed),
+                    TrimSide::Leading => s.trim_start_matches(is_trimmed),
+                    TrimSide::Trailing => s.trim_end_matches(is_trimmed),
+                }
+                .into())
+            }
+            BuiltinFunction::Replace(string, from, to) => {
+                let string = non_null!(string.eval(record)?);
+                let from = non_null!(from.eval(record)?);
+                let to = non_null!(to.eval(record)?);
+                let from = <&str>::try_from(&from)?;
+                if from.is_empty() {
+                    return Ok(string);
+                }
+                Ok(<&str>::try_from(&string)?
+                    .replace(from, <&str>::try_from(&to)?)
+                    .into())
+            }
+            BuiltinFunction::Left {
+                string,
+                len,
+                engine,
+            }
+            | BuiltinFunction::Right {
+                string,
+                len,
+                engine,
+            } => {
+                let string = non_null!(string.eval(record)?);
+                let s = <&str>::try_from(&string)?;
+                let len = i64::try_from(non_null!(len.eval(record)?))?;
+                let char_len = s.chars().count() as i64;
+                // A negative length means "all but the last (or first) |len| characters" in
+                // PostgreSQL, but always returns an empty string in MySQL
+                let len = match engine {
+                    SqlEngine::PostgreSQL if len < 0 => (char_len + len).max(0),
+                    _ => len.clamp(0, char_len),
+                } as usize;
+                let res = if matches!(self, BuiltinFunction::Left { .. }) {
+                    s.chars().take(len).collect::<String>()
+                } else {
+                    s.chars().skip(char_len as usize - len).collect::<String>()
+                };
+                Ok(res.into())
+            }
+            BuiltinFunction::Pad {
+                string,
+                len,
+                pad: pad_string,
+                left,
+                engine,
+            } => {
+                let string = non_null!(string.eval(record)?);
+                let len = i64::try_from(non_null!(len.eval(record)?))?;
+                let pad_string = non_null!(pad_string.eval(record)?);
+                Ok(pad(
+                    (&string).try_into()?,
+                    len,
+                    (&pad_string).try_into()?,
+                    *left,
+                    *engine,
+                ))
+            }
+            BuiltinFunction::Repeat(string, count) => {
+                let string = non_null!(string.eval(record)?);
+                let count = i64::try_from(non_null!(count.eval(record)?))?;
+                Ok(<&str>::try_from(&string)?
+                    .repeat(usize::try_from(count).unwrap_or(0))
+                    .into())
+            }
+            BuiltinFunction::Reverse(string) => {
+                let string = non_null!(string.eval(record)?);
+                Ok(<&str>::try_from(&string)?
+                    .chars()
+                    .rev()
+                    .collect::<String>()
+                    .into())
+            }
+            BuiltinFunction::ConcatWs(separator, args) => {
+                let separator = non_null!(separator.eval(record)?);
+                let separator = <&str>::try_from(&separator)?;
+                let mut res = String::new();
+                // NULL arguments are skipped, rather than making the result NULL
+                for (i, arg) in args
+                    .iter()
+                    .map(|arg| arg.eval(record))
+                    .filter(|arg| !matches!(arg, Ok(DfValue::None)))
+                    .enumerate()
+                {
+                    if i != 0 {
+                        res.push_str(separator);
+                    }
+                    res.push_str((&arg?).try_into()?);
+                }
+                Ok(res.into())
+            }
+            BuiltinFunction::Locate {
+                substring,
+                string,
+                start,
+            } => {
+                let substring = non_null!(substring.eval(record)?);
+                let string = non_null!(string.eval(record)?);
+                let start = match start {
+                    Some(start) => i64::try_from(non_null!(start.eval(record)?))?,
+                    None => 1,
+                };
+                Ok(locate((&substring).try_into()?, (&string).try_into()?, start).into())
+            }
+            BuiltinFunction::Initcap(string) => {
+                let string = non_null!(string.eval(record)?);
+                Ok(initcap((&string).try_into()?).into())
+            }
+            BuiltinFunction::Md5(string) => {
+                let string = non_null!(string.eval(record)?);
+                Ok(format!("{:x}", md5::compute(<&str>::try_from(&string)?)).into())
+            }
+            BuiltinFunction::Abs(expr) => match non_null!(expr.eval(record)?) {
+                DfValue::Int(i) => i
+                    .checked_abs()
+                    .map(DfValue::Int)
+                    .ok_or_else(|| invalid_err!("BIGINT value is out of range in 'abs({i})'")),
+                DfValue::Float(f) => Ok(DfValue::Float(f.abs())),
+                DfValue::Numeric(d) => Ok(d.abs().into()),
+                v @ DfValue::UnsignedInt(_) => Ok(v),
+                v => DfValue::try_from(f64::try_from(&v)?.abs()),
+            },
+            BuiltinFunction::Floor(expr) => match non_null!(expr.eval(record)?) {
+                v @ (DfValue::Int(_) | DfValue::UnsignedInt(_)) => Ok(v),
+                DfValue::Numeric(d) => Ok(d.floor().into()),
+                v => DfValue::try_from(f64::try_from(&v)?.floor()),
+            },
+            BuiltinFunction::Ceil(expr) => match non_null!(expr.eval(record)?) {
+                v @ (DfValue::Int(_) | DfValue::UnsignedInt(_)) => Ok(v),
+                DfValue::Numeric(d) => Ok(d.ceil().into()),
+                v => DfValue::try_from(f64::try_from(&v)?.ceil()),
+            },
+            BuiltinFunction::Mod {
+                dividend,
+                divisor,
+                engine,
+            } => {
+                let dividend = non_null!(dividend.eval(record)?);
+                let divisor = non_null!(divisor.eval(record)?);
+                let division_by_zero = || match engine {
+                    SqlEngine::MySQL => Ok(DfValue::None),
+                    SqlEngine::PostgreSQL => Err(invalid_err!("division by zero")),
+                };
+                // The sign of the result always follows the dividend, which is what `%` does too
+                match (&dividend, &divisor) {
+                    (
+                        DfValue::Int(_) | DfValue::UnsignedInt(_),
+                        DfValue::Int(_) | DfValue::UnsignedInt(_),
+                    ) => {
+                        let dividend = i128::try_from(&dividend)?;
+                        let divisor = i128::try_from(&divisor)?;
+                        if divisor == 0 {
+                            return division_by_zero();
+                        }
+                        DfValue::try_from(dividend % divisor)
+                    }
+                    (DfValue::Numeric(_), _) | (_, DfValue::Numeric(_)) => {
+                        let dividend = Decimal::try_from(&dividend)?;
+                        let divisor = Decimal::try_from(&divisor)?;
+                        if divisor.is_zero() {
+                            return division_by_zero();
+                        }
+                        Ok((dividend % divisor).into())
+                    }
+                    _ => {
+                        let dividend = f64::try_from(&dividend)?;
+                        let divisor = f64::try_from(&divisor)?;
+                        if divisor == 0.0 {
+                            return division_by_zero();
+                        }
+                        DfValue::try_from(dividend % divisor)
+                    }
+                }
+            }
+            BuiltinFunction::Power(base, exponent) => {
+                let base = f64::try_from(&non_null!(base.eval(record)?))?;
+                let exponent = f64::try_from(&non_null!(exponent.eval(record)?))?;
+                DfValue::try_from(base.powf(exponent))
+                    .map_err(|_| invalid_err!("value out of range in 'pow({base}, {exponent})'"))
+            }
+            BuiltinFunction::Sqrt { expr, engine } => {
+                let value = f64::try_from(&non_null!(expr.eval(record)?))?;
+                if value < 0.0 {
+                    return match engine {
+                        SqlEngine::MySQL => Ok(DfValue::None),
+                        SqlEngine::PostgreSQL => {
+                            Err(invalid_err!("cannot take square root of a negative number"))
+                        }
+                    };
+                }
+                DfValue::try_from(value.sqrt())
+            }
+            BuiltinFunction::Exp(expr) => {
+                let value = f64::try_from(&non_null!(expr.eval(record)?))?;
+                DfValue::try_from(value.exp())
+                    .map_err(|_| invalid_err!("value out of range in 'exp({value})'"))
+            }
+            BuiltinFunction::Log { expr, base, engine } => {
+                let value = f64::try_from(&non_null!(expr.eval(record)?))?;
+                let base = match base {
+                    Some(base) => Some(f64::try_from(&non_null!(base.eval(record)?))?),
+                    None => None,
+                };
+                // MySQL returns NULL for values the logarithm is undefined for, but PostgreSQL
+                // raises an error
+                let undefined = value <= 0.0 || base.map_or(false, |b| b <= 0.0 || b == 1.0);
+                if undefined {
+                    return match engine {
+                        SqlEngine::MySQL => Ok(DfValue::None),
+                        SqlEngine::PostgreSQL => {
+                            Err(invalid_err!("cannot take logarithm of {value}"))
+                        }
+                    };
+                }
+                DfValue::try_from(match base {
+                    None => value.ln(),
+                    Some(base) if base == 2.0 => value.log2(),
+                    Some(base) if base == 10.0 => value.log10(),
+                    Some(base) => value.log(base),
+                })
+            }
+            BuiltinFunction::Sign(expr) => {
+                let value = non_null!(expr.eval(record)?);
+                let sign = match &value {
+                    DfValue::Int(i) => i.signum(),
+                    DfValue::UnsignedInt(u) => (*u > 0) as i64,
+                    DfValue::Numeric(d) if d.is_zero() => 0,
+                    DfValue::Numeric(d) => d.signum().to_i64().unwrap_or_default(),
+                    v => {
+                        let f = f64::try_from(v)?;
+                        if f == 0.0 {
+                            0
+                        } else {
+                            f.signum() as i64
+                        }
+                    }
+                };
+                DfValue::from(sign).coerce_to(ty, &DfType::BigInt)
+            }
+            BuiltinFunction::Pi => DfValue::try_from(std::f64::consts::PI),
+            BuiltinFunction::Truncate(expr, places) => {
+                let value = non_null!(expr.eval(record)?);
+                let places = i64::try_from(non_null!(places.eval(record)?))?;
+                truncate(value, places)
+            }
+            BuiltinFunction::Degrees(expr) => {
+                let value = f64::try_from(&non_null!(expr.eval(record)?))?;
+                DfValue::try_from(value.to_degrees())
+            }
+            BuiltinFunction::Radians(expr) => {
+                let value = f64::try_from(&non_null!(expr.eval(record)?))?;
+                DfValue::try_from(value.to_radians())
+            }
+            BuiltinFunction::DateAdd {
+                datetime,
+                interval,
+                unit,
+                subtract,
+                engine,
+            } => {
+                let datetime = non_null!(datetime.eval(record)?);
+                let interval = non_null!(interval.eval(record)?);
+                // MySQL returns NULL for invalid dates and intervals, but PostgreSQL raises an
+                // error
+                let invalid = |message: String| match engine {
+                    SqlEngine::MySQL => Ok(DfValue::None),
+                    SqlEngine::PostgreSQL => Err(invalid_err!("{message}")),
+                };
+
+                let Some(interval) = Interval::from_value(&interval, *unit, *engine) else {
+                    return invalid(format!("invalid input syntax for type interval: \"{interval}\""));
+                };
+                let interval = if *subtract {
+                    interval.negate()
+                } else {
+                    interval
+                };
+                let Some((datetime, offset)) = local_datetime(&datetime) else {
+                    return invalid(format!("invalid value for date arithmetic: \"{datetime}\""));
+                };
+                match interval
+                    .add_to(datetime)
+                    .and_then(|res| timestamp_result(res, offset, ty))
+                {
+                    Some(res) => Ok(res),
+                    None => invalid("timestamp out of range".to_owned()),
+                }
+            }
+            BuiltinFunction::Extract {
+                field,
+                expr,
+                engine,
+            } => {
+                let value = non_null!(expr.eval(record)?);
+                match extract(*field, &value, *engine)? {
+                    Some(res) => DfValue::from(res).coerce_to(ty, &DfType::DEFAULT_NUMERIC),
+                    None => Ok(DfValue::None),
+                }
+            }
+            BuiltinFunction::DateTrunc(field, expr) => {
+                let field = non_null!(field.eval(record)?);
+                let value = non_null!(expr.eval(record)?);
+                let (datetime, offset) = local_datetime(&value)
+                    .ok_or_else(|| invalid_err!("invalid value for date_trunc: \"{value}\""))?;
+                let truncated = date_trunc((&field).try_into()?, datetime)?;
+                timestamp_result(truncated, offset, ty)
+                    .ok_or_else(|| invalid_err!("timestamp out of range"))
+            }
+            BuiltinFunction::ToChar(expr, format) => {
+                let value = non_null!(expr.eval(record)?);
+                let format = non_null!(format.eval(record)?);
+                let (datetime, _) = local_datetime(&value)
+                    .ok_or_else(|| invalid_err!("invalid value for to_char: \"{value}\""))?;
+                Ok(postgres_to_char(&datetime, (&format).try_into()?).into())
+            }
+            BuiltinFunction::RegexpLike {
+                string,
+                pattern,
+                flags,
+                engine,
+                cache,
+            } => {
+                let string = non_null!(string.eval(record)?);
+                let pattern = non_null!(pattern.eval(record)?);
+                let Some(flags) = eval_regex_flags(flags.as_ref(), *engine, record)? else {
+                    return Ok(DfValue::None);
+                };
+                if flags.global {
+                    invalid!("regexp_like() does not support the \"global\" option");
+                }
+                let regex = cache.get((&pattern).try_into()?, flags, *engine)?;
+                Ok(regex.is_match((&string).try_into()?).into())
+            }
+            BuiltinFunction::RegexpReplace {
+                string,
+                pattern,
+                replacement,
+                position,
+                occurrence,
+                flags,
+                engine,
+                cache,
+            } => {
+                let string = non_null!(string.eval(record)?);
+                let pattern = non_null!(pattern.eval(record)?);
+                let replacement = non_null!(replacement.eval(record)?);
+                let position = match position {
+                    Some(position) => i64::try_from(non_null!(position.eval(record)?))?,
+                    None => 1,
+                };
+                let occurrence = match occurrence {
+                    Some(occurrence) => i64::try_from(non_null!(occurrence.eval(record)?))?,
+                    None => 0,
+                };
+                let Some(flags) = eval_regex_flags(flags.as_ref(), *engine, record)? else {
+                    return Ok(DfValue::None);
+                };
+
+                let string = <&str>::try_from(&string)?;
+                if position < 1 || position as usize > string.chars().count() + 1 {
+                    invalid!("Index out of bounds in regular expression search");
+                }
+                let regex = cache.get((&pattern).try_into()?, flags, *engine)?;
+                let replacement = Replacement::parse((&replacement).try_into()?, &regex, *engine)?;
+                let occurrence = match engine {
+                    SqlEngine::MySQL => usize::try_from(occurrence).ok().filter(|n| *n > 0),
+                    SqlEngine::PostgreSQL => (!flags.global).then_some(1),
+                };
+
+                Ok(
+                    regexp::replace(string, &regex, &replacement, position as usize, occurrence)
+                        .into(),
+                )
+            }
+            BuiltinFunction::RegexpMatch {
+                string,
+                pattern,
+                flags,
+                cache,
+            } => {
+                let string = non_null!(string.eval(record)?);
+                let pattern = non_null!(pattern.eval(record)?);
+                let Some(flags) =
+                    eval_regex_flags(flags.as_ref(), SqlEngine::PostgreSQL, record)?
+                else {
+                    return Ok(DfValue::None);
+                };
+                if flags.global {
+                    invalid!("regexp_match() does not support the \"global\" option");
+                }
+                let regex = cache.get((&pattern).try_into()?, flags, SqlEngine::PostgreSQL)?;
+                let Some(captures) = regex.captures((&string).try_into()?) else {
+                    return Ok(DfValue::None);
+                };
+
+                // The result contains the substrings matching each capture group, or the whole
+                // match if the pattern has no capture groups
+                let values = captures
+                    .iter()
+                    .skip(usize::from(captures.len() > 1))
+                    .map(|m| m.map_or(DfValue::None, |m| m.as_str().into()))
+                    .collect::<Vec<_>>();
+                Ok(DfValue::from(Array::from(values)))
             }
             BuiltinFunction::ArrayToString(array, delimiter, null_string) => {
                 let elem_type = match array.ty() {
@@ -1646,6 +2752,604 @@ mod tests {
         );
     }
 
+    #[test]
+    fn lower_and_upper() {
+        assert_eq!(eval_expr("lower('AbC')", MySQL), "abc".into());
+        assert_eq!(eval_expr("lcase('AbC')", MySQL), "abc".into());
+        assert_eq!(eval_expr("upper('AbC')", PostgreSQL), "ABC".into());
+        assert_eq!(eval_expr("UPPER(null)", PostgreSQL), DfValue::None);
+        try_eval_expr("ucase('AbC')", PostgreSQL).unwrap_err();
+    }
+
+    #[test]
+    fn length() {
+        assert_eq!(eval_expr("length('héllo')", MySQL), 6.into());
+        assert_eq!(eval_expr("length('héllo')", PostgreSQL), 5.into());
+        assert_eq!(eval_expr("char_length('héllo')", MySQL), 5.into());
+        assert_eq!(eval_expr("octet_length('héllo')", PostgreSQL), 6.into());
+        assert_eq!(eval_expr("length(null)", MySQL), DfValue::None);
+    }
+
+    #[test]
+    fn trim() {
+        assert_eq!(eval_expr("trim('  abc  ')", MySQL), "abc".into());
+        assert_eq!(eval_expr("ltrim('  abc  ')", MySQL), "abc  ".into());
+        assert_eq!(eval_expr("rtrim('  abc  ')", PostgreSQL), "  abc".into());
+        assert_eq!(
+            eval_expr("btrim('xyabcyx', 'xy')", PostgreSQL),
+            "abc".into()
+        );
+        assert_eq!(eval_expr("ltrim('xyabc', null)", PostgreSQL), DfValue::None);
+        try_eval_expr("btrim('abc')", MySQL).unwrap_err();
+    }
+
+    #[test]
+    fn replace() {
+        assert_eq!(
+            eval_expr("replace('abcabc', 'b', 'xy')", MySQL),
+            "axycaxyc".into()
+        );
+        assert_eq!(
+            eval_expr("replace('abc', '', 'x')", PostgreSQL),
+            "abc".into()
+        );
+        assert_eq!(eval_expr("replace('abc', 'b', null)", MySQL), DfValue::None);
+    }
+
+    #[test]
+    fn left_and_right() {
+        assert_eq!(eval_expr("left('héllo', 2)", MySQL), "hé".into());
+        assert_eq!(eval_expr("right('héllo', 3)", PostgreSQL), "llo".into());
+        assert_eq!(eval_expr("left('abc', 10)", MySQL), "abc".into());
+        assert_eq!(eval_expr("left('abcde', -2)", MySQL), "".into());
+        assert_eq!(eval_expr("left('abcde', -2)", PostgreSQL), "abc".into());
+        assert_eq!(eval_expr("right('abcde', -2)", PostgreSQL), "cde".into());
+        assert_eq!(eval_expr("right('abcde', -10)", PostgreSQL), "".into());
+        assert_eq!(eval_expr("right(null, 1)", MySQL), DfValue::None);
+    }
+
+    #[test]
+    fn lpad_and_rpad() {
+        assert_eq!(eval_expr("lpad('hi', 5, 'xy')", MySQL), "xyxhi".into());
+        assert_eq!(eval_expr("rpad('hi', 5, 'xy')", MySQL), "hixyx".into());
+        assert_eq!(eval_expr("lpad('hello', 2, 'x')", MySQL), "he".into());
+        assert_eq!(eval_expr("rpad('hi', 4)", PostgreSQL), "hi  ".into());
+        assert_eq!(eval_expr("lpad('hi', -1, 'x')", MySQL), DfValue::None);
+        assert_eq!(eval_expr("lpad('hi', -1, 'x')", PostgreSQL), "".into());
+        assert_eq!(eval_expr("lpad('hi', 5, '')", MySQL), DfValue::None);
+        assert_eq!(eval_expr("lpad('hi', 5, '')", PostgreSQL), "hi".into());
+        try_eval_expr("lpad('hi', 5)", MySQL).unwrap_err();
+    }
+
+    #[test]
+    fn repeat_and_reverse() {
+        assert_eq!(eval_expr("repeat('ab', 3)", MySQL), "ababab".into());
+        assert_eq!(eval_expr("repeat('ab', -1)", PostgreSQL), "".into());
+        assert_eq!(eval_expr("reverse('héllo')", MySQL), "olléh".into());
+        assert_eq!(eval_expr("reverse(null)", PostgreSQL), DfValue::None);
+    }
+
+    #[test]
+    fn concat_ws() {
+        assert_eq!(
+            eval_expr("concat_ws(', ', 'a', null, 'b', 1)", MySQL),
+            "a, b, 1".into()
+        );
+        assert_eq!(
+            eval_expr("concat_ws(null, 'a', 'b')", PostgreSQL),
+            DfValue::None
+        );
+        try_eval_expr("concat_ws(',')", MySQL).unwrap_err();
+    }
+
+    #[test]
+    fn locate() {
+        assert_eq!(eval_expr("locate('bar', 'foobarbar')", MySQL), 4.into());
+        assert_eq!(eval_expr("locate('bar', 'foobarbar', 5)", MySQL), 7.into());
+        assert_eq!(eval_expr("locate('xbar', 'foobar')", MySQL), 0.into());
+        assert_eq!(eval_expr("locate('bar', 'foobar', 0)", MySQL), 0.into());
+        assert_eq!(eval_expr("locate('', 'foobar', 3)", MySQL), 3.into());
+        assert_eq!(eval_expr("instr('héllo', 'l')", MySQL), 3.into());
+        assert_eq!(eval_expr("strpos('héllo', 'lo')", PostgreSQL), 4.into());
+        assert_eq!(
+            eval_expr("strpos('héllo', null)", PostgreSQL),
+            DfValue::None
+        );
+        try_eval_expr("strpos('abc', 'b')", MySQL).unwrap_err();
+        try_eval_expr("locate('b', 'abc')", PostgreSQL).unwrap_err();
+    }
+
+    #[test]
+    fn initcap() {
+        assert_eq!(
+            eval_expr("initcap('hi THOMAS, neil-smith 2nd')", PostgreSQL),
+            "Hi Thomas, Neil-Smith 2nd".into()
+        );
+        try_eval_expr("initcap('abc')", MySQL).unwrap_err();
+    }
+
+    #[test]
+    fn md5() {
+        assert_eq!(
+            eval_expr("md5('abc')", MySQL),
+            "900150983cd24fb0d6963f7d28e17f72".into()
+        );
+        assert_eq!(eval_expr("md5(null)", PostgreSQL), DfValue::None);
+    }
+
+    #[test]
+    fn abs() {
+        assert_eq!(eval_expr("abs(-3)", MySQL), 3.into());
+        assert_eq!(
+            eval_expr("abs(-2.5)", PostgreSQL),
+            eval_expr("2.5", PostgreSQL)
+        );
+        assert_eq!(eval_expr("abs(null)", MySQL), DfValue::None);
+    }
+
+    #[test]
+    fn floor_and_ceil() {
+        let expr = parse_and_lower("floor(c0)", MySQL);
+        assert_eq!(
+            expr.eval::<DfValue>(&[DfValue::Double(-1.5)]).unwrap(),
+            DfValue::Double(-2.0)
+        );
+        let expr = parse_and_lower("ceiling(c0)", PostgreSQL);
+        assert_eq!(
+            expr.eval::<DfValue>(&[DfValue::Double(1.2)]).unwrap(),
+            DfValue::Double(2.0)
+        );
+        assert_eq!(eval_expr("floor(3)", MySQL), 3.into());
+        assert_eq!(eval_expr("ceil(3)", PostgreSQL), DfValue::Double(3.0));
+        assert_eq!(
+            eval_expr("ceil(cast(1.25 as decimal(5, 2)))", MySQL),
+            Decimal::from(2).into()
+        );
+        assert_eq!(eval_expr("floor(null)", MySQL), DfValue::None);
+    }
+
+    #[test]
+    fn mod_() {
+        assert_eq!(eval_expr("mod(7, 3)", MySQL), 1.into());
+        assert_eq!(eval_expr("mod(-7, 3)", PostgreSQL), (-1).into());
+        assert_eq!(eval_expr("mod(7, 0)", MySQL), DfValue::None);
+        try_eval_expr("mod(7, 0)", PostgreSQL).unwrap_err();
+        let expr = parse_and_lower("mod(c0, c1)", MySQL);
+        assert_eq!(
+            expr.eval::<DfValue>(&[DfValue::Double(7.5), DfValue::Double(2.0)])
+                .unwrap(),
+            DfValue::Double(1.5)
+        );
+        assert_eq!(eval_expr("mod(null, 2)", MySQL), DfValue::None);
+    }
+
+    #[test]
+    fn power_sqrt_and_exp() {
+        assert_eq!(eval_expr("power(2, 10)", MySQL), DfValue::Double(1024.0));
+        assert_eq!(eval_expr("pow(4, 0.5)", MySQL), DfValue::Double(2.0));
+        assert_eq!(eval_expr("sqrt(16)", PostgreSQL), DfValue::Double(4.0));
+        assert_eq!(eval_expr("sqrt(-1)", MySQL), DfValue::None);
+        try_eval_expr("sqrt(-1)", PostgreSQL).unwrap_err();
+        assert_eq!(eval_expr("exp(0)", MySQL), DfValue::Double(1.0));
+        assert_eq!(eval_expr("exp(null)", PostgreSQL), DfValue::None);
+    }
+
+    #[test]
+    fn logarithms() {
+        assert_eq!(eval_expr("ln(1)", MySQL), DfValue::Double(0.0));
+        assert_eq!(eval_expr("log(2, 8)", MySQL), DfValue::Double(3.0));
+        assert_eq!(eval_expr("log2(8)", MySQL), DfValue::Double(3.0));
+        assert_eq!(eval_expr("log10(100)", PostgreSQL), DfValue::Double(2.0));
+        // LOG with one argument is the natural log in MySQL, but the base 10 log in PostgreSQL
+        assert_eq!(eval_expr("log(1000)", PostgreSQL), DfValue::Double(3.0));
+        assert_eq!(eval_expr("log(1000)", MySQL), DfValue::Double(1000f64.ln()));
+        assert_eq!(eval_expr("ln(0)", MySQL), DfValue::None);
+        try_eval_expr("ln(0)", PostgreSQL).unwrap_err();
+        try_eval_expr("log2(8)", PostgreSQL).unwrap_err();
+    }
+
+    #[test]
+    fn sign_and_pi() {
+        assert_eq!(eval_expr("sign(-3)", MySQL), (-1).into());
+        assert_eq!(eval_expr("sign(0)", MySQL), 0.into());
+        assert_eq!(eval_expr("sign(3)", PostgreSQL), DfValue::Double(1.0));
+        assert_eq!(eval_expr("sign(null)", MySQL), DfValue::None);
+        assert_eq!(
+            eval_expr("pi()", MySQL),
+            DfValue::Double(std::f64::consts::PI)
+        );
+    }
+
+    #[test]
+    fn truncate() {
+        let expr = parse_and_lower("truncate(c0, c1)", MySQL);
+        let call_with =
+            |val: f64, places: i64| expr.eval::<DfValue>(&[val.try_into().unwrap(), places.into()]);
+        assert_eq!(call_with(1.239, 2).unwrap(), DfValue::Double(1.23));
+        assert_eq!(call_with(-1.999, 0).unwrap(), DfValue::Double(-1.0));
+        assert_eq!(call_with(1299.0, -2).unwrap(), DfValue::Double(1200.0));
+        assert_eq!(eval_expr("truncate(1299, -2)", MySQL), 1200.into());
+        assert_eq!(
+            eval_expr("trunc(cast(-1.25 as decimal(5, 2)), 1)", PostgreSQL),
+            Decimal::new(-12, 1).into()
+        );
+        assert_eq!(eval_expr("trunc(null)", PostgreSQL), DfValue::None);
+        try_eval_expr("trunc(1.5)", MySQL).unwrap_err();
+        try_eval_expr("truncate(1.5)", MySQL).unwrap_err();
+    }
+
+    #[test]
+    fn degrees_and_radians() {
+        assert_eq!(eval_expr("degrees(pi())", MySQL), DfValue::Double(180.0));
+        assert_eq!(
+            eval_expr("radians(180)", PostgreSQL),
+            DfValue::Double(std::f64::consts::PI)
+        );
+    }
+
+    #[track_caller]
+    fn eval_datetime(expr: &str, dialect: nom_sql::Dialect) -> NaiveDateTime {
+        NaiveDateTime::try_from(&eval_expr(expr, dialect)).unwrap()
+    }
+
+    #[track_caller]
+    fn datetime(s: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S%.f").unwrap()
+    }
+
+    #[test]
+    fn date_add_mysql() {
+        assert_eq!(
+            eval_datetime(
+                "cast('2020-01-31 10:00:00' as datetime) + INTERVAL 1 MONTH",
+                MySQL
+            ),
+            datetime("2020-02-29 10:00:00")
+        );
+        assert_eq!(
+            eval_datetime(
+                "date_sub(cast('2020-03-01 00:00:00' as datetime), INTERVAL '1:30' HOUR_MINUTE)",
+                MySQL
+            ),
+            datetime("2020-02-29 22:30:00")
+        );
+        assert_eq!(
+            eval_datetime(
+                "date_add(cast('2020-01-01 00:00:00' as datetime), INTERVAL '1.5' SECOND_MICROSECOND)",
+                MySQL
+            ),
+            datetime("2020-01-01 00:00:01.5")
+        );
+        assert_eq!(
+            eval_datetime(
+                "cast('2020-01-01 00:00:00' as datetime) - INTERVAL -2 DAY",
+                MySQL
+            ),
+            datetime("2020-01-03 00:00:00")
+        );
+        assert_eq!(
+            eval_datetime("INTERVAL 1 YEAR + cast('2020-02-29' as date)", MySQL),
+            datetime("2021-02-28 00:00:00")
+        );
+        assert_eq!(
+            eval_datetime("adddate(cast('2020-12-31' as date), 1)", MySQL),
+            datetime("2021-01-01 00:00:00")
+        );
+        assert_eq!(
+            eval_expr("date_add('not a date', INTERVAL 1 DAY)", MySQL),
+            DfValue::None
+        );
+        assert_eq!(
+            eval_expr(
+                "date_add(cast('2020-01-01' as date), INTERVAL 'x' DAY)",
+                MySQL
+            ),
+            DfValue::None
+        );
+        assert_eq!(
+            eval_expr("date_add(null, INTERVAL 1 DAY)", MySQL),
+            DfValue::None
+        );
+    }
+
+    #[test]
+    fn date_add_postgres() {
+        assert_eq!(
+            eval_datetime(
+                "cast('2020-01-01 12:00:00' as timestamp) + interval '1 day 2 hours'",
+                PostgreSQL
+            ),
+            datetime("2020-01-02 14:00:00")
+        );
+        assert_eq!(
+            eval_datetime(
+                "cast('2020-03-31 00:00:00' as timestamp) - interval '1 mon'",
+                PostgreSQL
+            ),
+            datetime("2020-02-29 00:00:00")
+        );
+        assert_eq!(
+            eval_datetime(
+                "cast('2020-01-01' as date) + interval '90 minutes'",
+                PostgreSQL
+            ),
+            datetime("2020-01-01 01:30:00")
+        );
+        assert_eq!(
+            eval_datetime(
+                "cast('2020-01-01 00:00:00' as timestamp) + interval '2' hour",
+                PostgreSQL
+            ),
+            datetime("2020-01-01 02:00:00")
+        );
+        assert_eq!(
+            eval_datetime(
+                "cast('2020-01-10 00:00:00' as timestamp) + interval '1 week ago'",
+                PostgreSQL
+            ),
+            datetime("2020-01-03 00:00:00")
+        );
+        assert_eq!(
+            eval_datetime(
+                "cast('2020-01-01 00:00:00' as timestamp) + interval '1 day 01:02:03.5'",
+                PostgreSQL
+            ),
+            datetime("2020-01-02 01:02:03.5")
+        );
+        assert_eq!(
+            eval_datetime(
+                "cast('2020-01-01 00:00:00' as timestamp) + interval '1.5 months'",
+                PostgreSQL
+            ),
+            datetime("2020-02-16 00:00:00")
+        );
+        try_eval_expr(
+            "cast('2020-01-01 00:00:00' as timestamp) + interval 'fortnight'",
+            PostgreSQL,
+        )
+        .unwrap_err();
+    }
+
+    #[test]
+    fn extract_postgres() {
+        let extract = |field: &str| {
+            eval_expr(
+                &format!("extract({field} from cast('2021-03-14 15:09:26.535897' as timestamp))"),
+                PostgreSQL,
+            )
+        };
+        assert_eq!(extract("year"), 2021.into());
+        assert_eq!(extract("month"), 3.into());
+        assert_eq!(extract("quarter"), 1.into());
+        assert_eq!(extract("second"), Decimal::new(26_535_897, 6).into());
+        assert_eq!(extract("millisecond"), Decimal::new(26_535_897, 3).into());
+        assert_eq!(extract("microseconds"), 26_535_897.into());
+        // 2021-03-14 is a Sunday
+        assert_eq!(extract("dow"), 0.into());
+        assert_eq!(extract("isodow"), 7.into());
+        assert_eq!(extract("doy"), 73.into());
+        assert_eq!(extract("week"), 10.into());
+        assert_eq!(extract("decade"), 202.into());
+        assert_eq!(extract("century"), 21.into());
+        assert_eq!(extract("millennium"), 3.into());
+        assert_eq!(
+            eval_expr(
+                "extract(epoch from cast('1970-01-02 00:00:01.5' as timestamp))",
+                PostgreSQL
+            ),
+            Decimal::new(86_401_500_000, 6).into()
+        );
+        assert_eq!(
+            eval_expr(
+                "date_part('hour', cast('2021-03-14 15:09:26' as timestamp))",
+                PostgreSQL
+            ),
+            DfValue::Double(15.0)
+        );
+        assert_eq!(
+            eval_expr("extract(year from null)", PostgreSQL),
+            DfValue::None
+        );
+    }
+
+    #[test]
+    fn extract_mysql() {
+        let extract = |field: &str| {
+            eval_expr(
+                &format!("extract({field} from cast('2021-03-14 15:09:26.535897' as datetime(6)))"),
+                MySQL,
+            )
+        };
+        assert_eq!(extract("YEAR"), 2021.into());
+        assert_eq!(extract("SECOND"), 26.into());
+        assert_eq!(extract("MICROSECOND"), 535_897.into());
+        assert_eq!(extract("WEEK"), 11.into());
+        assert_eq!(extract("YEAR_MONTH"), 202_103.into());
+        assert_eq!(extract("DAY_HOUR"), 1415.into());
+        assert_eq!(extract("HOUR_SECOND"), 150_926.into());
+        assert_eq!(extract("SECOND_MICROSECOND"), 26_535_897.into());
+        assert_eq!(eval_expr("year('2021-03-14')", MySQL), 2021.into());
+        assert_eq!(eval_expr("dayofyear('2021-03-14')", MySQL), 73.into());
+        assert_eq!(eval_expr("week('2021-03-14')", MySQL), 11.into());
+        assert_eq!(eval_expr("week('2021-03-20')", MySQL), 11.into());
+        assert_eq!(eval_expr("week('2021-01-02')", MySQL), 0.into());
+        assert_eq!(
+            eval_expr("hour(cast('10:30:00' as time))", MySQL),
+            10.into()
+        );
+        assert_eq!(
+            eval_expr("year(cast('10:30:00' as time))", MySQL),
+            DfValue::None
+        );
+        assert_eq!(eval_expr("year('not a date')", MySQL), DfValue::None);
+    }
+
+    #[test]
+    fn date_trunc() {
+        let date_trunc = |field: &str| {
+            eval_datetime(
+                &format!("date_trunc('{field}', cast('2021-08-19 15:09:26.5' as timestamp))"),
+                PostgreSQL,
+            )
+        };
+        assert_eq!(date_trunc("second"), datetime("2021-08-19 15:09:26"));
+        assert_eq!(date_trunc("minute"), datetime("2021-08-19 15:09:00"));
+        assert_eq!(date_trunc("HOUR"), datetime("2021-08-19 15:00:00"));
+        assert_eq!(date_trunc("day"), datetime("2021-08-19 00:00:00"));
+        assert_eq!(date_trunc("week"), datetime("2021-08-16 00:00:00"));
+        assert_eq!(date_trunc("month"), datetime("2021-08-01 00:00:00"));
+        assert_eq!(date_trunc("quarter"), datetime("2021-07-01 00:00:00"));
+        assert_eq!(date_trunc("year"), datetime("2021-01-01 00:00:00"));
+        assert_eq!(date_trunc("decade"), datetime("2020-01-01 00:00:00"));
+        assert_eq!(date_trunc("century"), datetime("2001-01-01 00:00:00"));
+        assert_eq!(
+            eval_datetime(
+                "date_trunc('month', cast('2021-08-19' as date))",
+                PostgreSQL
+            ),
+            datetime("2021-08-01 00:00:00")
+        );
+        try_eval_expr(
+            "date_trunc('fortnight', cast('2021-08-19' as date))",
+            PostgreSQL,
+        )
+        .unwrap_err();
+    }
+
+    #[test]
+    fn to_char() {
+        let to_char = |format: &str| {
+            eval_expr(
+                &format!("to_char(cast('2021-03-04 15:09:26.535' as timestamp), '{format}')"),
+                PostgreSQL,
+            )
+        };
+        assert_eq!(
+            to_char("YYYY-MM-DD HH24:MI:SS"),
+            "2021-03-04 15:09:26".into()
+        );
+        assert_eq!(to_char("HH12:MI am"), "03:09 pm".into());
+        assert_eq!(
+            to_char("Day, DD MONTH YYYY"),
+            "Thursday , 04 MARCH     2021".into()
+        );
+        assert_eq!(to_char("FMDay, FMDD FMMonth"), "Thursday, 4 March".into());
+        assert_eq!(to_char("Dy mon YY"), "Thu mar 21".into());
+        assert_eq!(
+            to_char("\"Q\"Q IW MS US DDD D"),
+            "Q1 09 535 535000 063 5".into()
+        );
+    }
+
+    #[test]
+    fn regex_match_operators() {
+        assert_eq!(eval_expr("'Hello World' ~ 'o W'", PostgreSQL), true.into());
+        assert_eq!(eval_expr("'Hello World' ~ 'o w'", PostgreSQL), false.into());
+        assert_eq!(eval_expr("'Hello World' ~* 'o w'", PostgreSQL), true.into());
+        assert_eq!(eval_expr("'Hello World' !~ '^W'", PostgreSQL), true.into());
+        assert_eq!(
+            eval_expr("'Hello World' !~* '^h'", PostgreSQL),
+            false.into()
+        );
+        assert_eq!(eval_expr("null ~ 'a'", PostgreSQL), DfValue::None);
+        // `.` matches newlines by default in PostgreSQL, but not in MySQL
+        assert_eq!(eval_expr("'a\nb' ~ 'a.b'", PostgreSQL), true.into());
+        assert_eq!(eval_expr("'a\nb' REGEXP 'a.b'", MySQL), false.into());
+        assert_eq!(eval_expr("'abc' REGEXP '^a[bc]+$'", MySQL), true.into());
+        assert_eq!(eval_expr("'abc' NOT REGEXP 'b'", MySQL), false.into());
+        assert_eq!(eval_expr("'abc' RLIKE 'B'", MySQL), false.into());
+        assert_eq!(
+            eval_expr("regexp_like('abc', 'B', 'i')", MySQL),
+            true.into()
+        );
+        assert_eq!(
+            eval_expr("regexp_like('abc', 'B', null)", MySQL),
+            DfValue::None
+        );
+        try_eval_expr("regexp_like('abc', 'b', 'g')", PostgreSQL).unwrap_err();
+    }
+
+    #[test]
+    fn regex_match_non_constant_pattern() {
+        let expr = parse_and_lower("c0 ~ c1", PostgreSQL);
+        let matches = |string: &str, pattern: &str| {
+            expr.eval::<DfValue>(&[string.into(), pattern.into()])
+                .unwrap()
+        };
+        assert_eq!(matches("abc", "^a"), true.into());
+        assert_eq!(matches("abc", "^a"), true.into());
+        assert_eq!(matches("abc", "^b"), false.into());
+        assert_eq!(matches("bcd", "^b"), true.into());
+        expr.eval::<DfValue>(&["abc".into(), "(".into()])
+            .unwrap_err();
+    }
+
+    #[test]
+    fn regexp_replace() {
+        assert_eq!(
+            eval_expr(
+                "regexp_replace('foobarbaz', 'b(..)', 'X\\\\1Y')",
+                PostgreSQL
+            ),
+            "fooXarYbaz".into()
+        );
+        assert_eq!(
+            eval_expr(
+                "regexp_replace('foobarbaz', 'b(..)', 'X\\\\1Y', 'g')",
+                PostgreSQL
+            ),
+            "fooXarYXazY".into()
+        );
+        assert_eq!(
+            eval_expr("regexp_replace('Abc abc', 'a', '-', 'gi')", PostgreSQL),
+            "-bc -bc".into()
+        );
+        assert_eq!(
+            eval_expr("regexp_replace('foobarbaz', 'b(..)', 'X$1Y')", MySQL),
+            "fooXarYXazY".into()
+        );
+        assert_eq!(
+            eval_expr("regexp_replace('abc abc abc', 'b', 'X', 1, 2)", MySQL),
+            "abc aXc abc".into()
+        );
+        assert_eq!(
+            eval_expr("regexp_replace('abc abc abc', 'b', 'X', 5)", MySQL),
+            "abc aXc aXc".into()
+        );
+        assert_eq!(
+            eval_expr("regexp_replace('Abc', 'a', 'X', 1, 0, 'i')", MySQL),
+            "Xbc".into()
+        );
+        assert_eq!(
+            eval_expr("regexp_replace('abc', 'b', null)", MySQL),
+            DfValue::None
+        );
+        try_eval_expr("regexp_replace('abc', 'b', 'X', 5)", MySQL).unwrap_err();
+    }
+
+    #[test]
+    fn regexp_match() {
+        assert_eq!(
+            eval_expr("regexp_match('foobarbequebaz', 'bar.*que')", PostgreSQL),
+            DfValue::from(vec![DfValue::from("barbeque")])
+        );
+        assert_eq!(
+            eval_expr(
+                "regexp_match('foobarbequebaz', '(bar)(x)?(beque)')",
+                PostgreSQL
+            ),
+            DfValue::from(vec![
+                DfValue::from("bar"),
+                DfValue::None,
+                DfValue::from("beque")
+            ])
+        );
+        assert_eq!(
+            eval_expr("regexp_match('foo', 'bar')", PostgreSQL),
+            DfValue::None
+        );
+    }
+
     #[track_caller]
     fn date_format(time: &str, fmt: &str) -> DfValue {
         lazy_static! {
@@ -1761,6 +3465,7 @@ mod tests {
             date_format("2001-01-15 00:00:00.000000", "%d-%y-%c"),
             "15-01-1".into()
         );
+        assert_eq!(date_format("2021-03-20", "%U"), "11".into());
         assert_eq!(date_format("2002-01-01", "%D"), "1st".into());
         assert_eq!(date_format("2002-01-31", "%D"), "31st".into());
         assert_eq!(date_format("2002-01-22", "%D"), "22nd".into());
@@ -5,11 +5,14 @@ mod eval;
 pub mod like;
 mod lower;
 mod post_lookup;
+pub mod regexp;
 pub mod utils;
 
 use std::fmt::{self, Display, Formatter};
 
 use itertools::Itertools;
+use nom_sql::{IntervalUnit, TimestampField};
+use readyset_data::dialect::SqlEngine;
 pub use readyset_data::Dialect;
 use readyset_data::{DfType, DfValue};
 use serde::{Deserialize, Serialize};
@@ -21,6 +24,7 @@ pub use crate::post_lookup::{
     PostLookup, PostLookupAggregate, PostLookupAggregateFunction, PostLookupAggregates,
     PreInsertion, ReaderProcessing,
 };
+use crate::regexp::RegexCache;
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub enum BuiltinFunction {
@@ -108,6 +112,302 @@ pub enum BuiltinFunction {
 
     /// [`array_to_string`](https://www.postgresql.org/docs/current/functions-array.html)
     ArrayToString(Expr, Expr, Option<Expr>),
+
+    /// `lower`:
+    ///
+    /// * [MySQL](https://dev.mysql.com/doc/refman/8.0/en/string-functions.html#function_lower)
+    /// * [PostgreSQL](https://www.postgresql.org/docs/current/functions-string.html)
+    Lower(Expr),
+
+    /// `upper`:
+    ///
+    /// * [MySQL](https://dev.mysql.com/doc/refman/8.0/en/string-functions.html#function_upper)
+    /// * [PostgreSQL](https://www.postgresql.org/docs/current/functions-string.html)
+    Upper(Expr),
+
+    /// `length`, `char_length` and `octet_length`:
+    ///
+    /// * [MySQL](https://dev.mysql.com/doc/refman/8.0/en/string-functions.html#function_length)
+    /// * [PostgreSQL](https://www.postgresql.org/docs/current/functions-string.html)
+    Length {
+        expr: Expr,
+        /// If true, count the length of the string in bytes rather than characters. `length` is
+        /// the length in bytes in MySQL, but the length in characters in PostgreSQL.
+        in_bytes: bool,
+    },
+
+    /// `trim`, `ltrim`, `rtrim` and `btrim`:
+    ///
+    /// * [MySQL](https://dev.mysql.com/doc/refman/8.0/en/string-functions.html#function_trim)
+    /// * [PostgreSQL](https://www.postgresql.org/docs/current/functions-string.html)
+    Trim {
+        string: Expr,
+        /// The set of characters to remove, which defaults to a single space
+        chars: Option<Expr>,
+        side: TrimSide,
+    },
+
+    /// `replace`:
+    ///
+    /// * [MySQL](https://dev.mysql.com/doc/refman/8.0/en/string-functions.html#function_replace)
+    /// * [PostgreSQL](https://www.postgresql.org/docs/current/functions-string.html)
+    Replace(Expr, Expr, Expr),
+
+    /// `left`:
+    ///
+    /// * [MySQL](https://dev.mysql.com/doc/refman/8.0/en/string-functions.html#function_left)
+    /// * [PostgreSQL](https://www.postgresql.org/docs/current/functions-string.html)
+    Left {
+        string: Expr,
+        len: Expr,
+        /// PostgreSQL returns all but the last `-len` characters for a negative `len`, whereas
+        /// MySQL returns the empty string
+        engine: SqlEngine,
+    },
+
+    /// `right`:
+    ///
+    /// * [MySQL](https://dev.mysql.com/doc/refman/8.0/en/string-functions.html#function_right)
+    /// * [PostgreSQL](https://www.postgresql.org/docs/current/functions-string.html)
+    Right {
+        string: Expr,
+        len: Expr,
+        /// PostgreSQL returns all but the first `-len` characters for a negative `len`, whereas
+        /// MySQL returns the empty string
+        engine: SqlEngine,
+    },
+
+    /// `lpad` and `rpad`:
+    ///
+    /// * [MySQL](https://dev.mysql.com/doc/refman/8.0/en/string-functions.html#function_lpad)
+    /// * [PostgreSQL](https://www.postgresql.org/docs/current/functions-string.html)
+    Pad {
+        string: Expr,
+        len: Expr,
+        pad: Expr,
+        /// If true, pad on the left (`lpad`), otherwise pad on the right (`rpad`)
+        left: bool,
+        /// MySQL returns NULL for a negative `len`, or if padding is needed but `pad` is empty,
+        /// whereas PostgreSQL returns the empty string or the (possibly truncated) string
+        /// respectively
+        engine: SqlEngine,
+    },
+
+    /// `repeat`:
+    ///
+    /// * [MySQL](https://dev.mysql.com/doc/refman/8.0/en/string-functions.html#function_repeat)
+    /// * [PostgreSQL](https://www.postgresql.org/docs/current/functions-string.html)
+    Repeat(Expr, Expr),
+
+    /// `reverse`:
+    ///
+    /// * [MySQL](https://dev.mysql.com/doc/refman/8.0/en/string-functions.html#function_reverse)
+    /// * [PostgreSQL](https://www.postgresql.org/docs/current/functions-string.html)
+    Reverse(Expr),
+
+    /// `concat_ws`:
+    ///
+    /// * [MySQL](https://dev.mysql.com/doc/refman/8.0/en/string-functions.html#function_concat-ws)
+    /// * [PostgreSQL](https://www.postgresql.org/docs/current/functions-string.html)
+    ConcatWs(Expr, Vec1<Expr>),
+
+    /// The 1-based position of the first occurrence of a substring in a string, or 0 if it doesn't
+    /// occur. Lowered from:
+    ///
+    /// * [`locate`](https://dev.mysql.com/doc/refman/8.0/en/string-functions.html#function_locate)
+    ///   and [`instr`](https://dev.mysql.com/doc/refman/8.0/en/string-functions.html#function_instr)
+    ///   in MySQL
+    /// * [`strpos`](https://www.postgresql.org/docs/current/functions-string.html) in PostgreSQL
+    Locate {
+        substring: Expr,
+        string: Expr,
+        /// The 1-based position to start searching at
+        start: Option<Expr>,
+    },
+
+    /// [`initcap`](https://www.postgresql.org/docs/current/functions-string.html)
+    Initcap(Expr),
+
+    /// `md5`:
+    ///
+    /// * [MySQL](https://dev.mysql.com/doc/refman/8.0/en/encryption-functions.html#function_md5)
+    /// * [PostgreSQL](https://www.postgresql.org/docs/current/functions-string.html)
+    Md5(Expr),
+
+    /// `abs`:
+    ///
+    /// * [MySQL](https://dev.mysql.com/doc/refman/8.0/en/mathematical-functions.html#function_abs)
+    /// * [PostgreSQL](https://www.postgresql.org/docs/current/functions-math.html)
+    Abs(Expr),
+
+    /// `floor`:
+    ///
+    /// * [MySQL](https://dev.mysql.com/doc/refman/8.0/en/mathematical-functions.html#function_floor)
+    /// * [PostgreSQL](https://www.postgresql.org/docs/current/functions-math.html)
+    Floor(Expr),
+
+    /// `ceil` and `ceiling`:
+    ///
+    /// * [MySQL](https://dev.mysql.com/doc/refman/8.0/en/mathematical-functions.html#function_ceil)
+    /// * [PostgreSQL](https://www.postgresql.org/docs/current/functions-math.html)
+    Ceil(Expr),
+
+    /// `mod`:
+    ///
+    /// * [MySQL](https://dev.mysql.com/doc/refman/8.0/en/mathematical-functions.html#function_mod)
+    /// * [PostgreSQL](https://www.postgresql.org/docs/current/functions-math.html)
+    Mod {
+        dividend: Expr,
+        divisor: Expr,
+        /// MySQL returns NULL for a zero divisor, whereas PostgreSQL returns an error
+        engine: SqlEngine,
+    },
+
+    /// `power` and `pow`:
+    ///
+    /// * [MySQL](https://dev.mysql.com/doc/refman/8.0/en/mathematical-functions.html#function_power)
+    /// * [PostgreSQL](https://www.postgresql.org/docs/current/functions-math.html)
+    Power(Expr, Expr),
+
+    /// `sqrt`:
+    ///
+    /// * [MySQL](https://dev.mysql.com/doc/refman/8.0/en/mathematical-functions.html#function_sqrt)
+    /// * [PostgreSQL](https://www.postgresql.org/docs/current/functions-math.html)
+    Sqrt {
+        expr: Expr,
+        /// MySQL returns NULL for negative arguments, whereas PostgreSQL returns an error
+        engine: SqlEngine,
+    },
+
+    /// `exp`:
+    ///
+    /// * [MySQL](https://dev.mysql.com/doc/refman/8.0/en/mathematical-functions.html#function_exp)
+    /// * [PostgreSQL](https://www.postgresql.org/docs/current/functions-math.html)
+    Exp(Expr),
+
+    /// `ln`, `log`, `log2` and `log10`:
+    ///
+    /// * [MySQL](https://dev.mysql.com/doc/refman/8.0/en/mathematical-functions.html#function_log)
+    /// * [PostgreSQL](https://www.postgresql.org/docs/current/functions-math.html)
+    Log {
+        expr: Expr,
+        /// The base of the logarithm, or [`None`] for the natural logarithm. Note that
+        /// single-argument `log` is the natural logarithm in MySQL, but the base 10 logarithm in
+        /// PostgreSQL.
+        base: Option<Expr>,
+        /// MySQL returns NULL for non-positive arguments, whereas PostgreSQL returns an error
+        engine: SqlEngine,
+    },
+
+    /// `sign`:
+    ///
+    /// * [MySQL](https://dev.mysql.com/doc/refman/8.0/en/mathematical-functions.html#function_sign)
+    /// * [PostgreSQL](https://www.postgresql.org/docs/current/functions-math.html)
+    Sign(Expr),
+
+    /// `pi`:
+    ///
+    /// * [MySQL](https://dev.mysql.com/doc/refman/8.0/en/mathematical-functions.html#function_pi)
+    /// * [PostgreSQL](https://www.postgresql.org/docs/current/functions-math.html)
+    Pi,
+
+    /// `truncate` in
+    /// [MySQL](https://dev.mysql.com/doc/refman/8.0/en/mathematical-functions.html#function_truncate)
+    /// and `trunc` in [PostgreSQL](https://www.postgresql.org/docs/current/functions-math.html)
+    Truncate(Expr, Expr),
+
+    /// `degrees`:
+    ///
+    /// * [MySQL](https://dev.mysql.com/doc/refman/8.0/en/mathematical-functions.html#function_degrees)
+    /// * [PostgreSQL](https://www.postgresql.org/docs/current/functions-math.html)
+    Degrees(Expr),
+
+    /// `radians`:
+    ///
+    /// * [MySQL](https://dev.mysql.com/doc/refman/8.0/en/mathematical-functions.html#function_radians)
+    /// * [PostgreSQL](https://www.postgresql.org/docs/current/functions-math.html)
+    Radians(Expr),
+
+    /// Adding an interval to (or subtracting an interval from) a date or timestamp, via either the
+    /// `+` and `-` operators with an `INTERVAL` operand or MySQL's
+    /// [`date_add`](https://dev.mysql.com/doc/refman/8.0/en/date-and-time-functions.html#function_date-add),
+    /// `date_sub`, `adddate` and `subdate` functions
+    DateAdd {
+        datetime: Expr,
+        /// The value of the interval, which is interpreted according to `unit`
+        interval: Expr,
+        /// The unit of the interval, or [`None`] for a PostgreSQL interval string such as `'1 day
+        /// 2 hours'`
+        unit: Option<IntervalUnit>,
+        subtract: bool,
+        /// MySQL returns NULL for invalid intervals, whereas PostgreSQL returns an error
+        engine: SqlEngine,
+    },
+
+    /// `EXTRACT(field FROM expr)`, along with PostgreSQL's `date_part` and MySQL's single-field
+    /// functions such as `year` and `hour`:
+    ///
+    /// * [MySQL](https://dev.mysql.com/doc/refman/8.0/en/date-and-time-functions.html#function_extract)
+    /// * [PostgreSQL](https://www.postgresql.org/docs/current/functions-datetime.html#FUNCTIONS-DATETIME-EXTRACT)
+    Extract {
+        field: TimestampField,
+        expr: Expr,
+        /// Some fields (such as `SECOND` and `WEEK`) have different semantics between MySQL and
+        /// PostgreSQL
+        engine: SqlEngine,
+    },
+
+    /// [`date_trunc`](https://www.postgresql.org/docs/current/functions-datetime.html#FUNCTIONS-DATETIME-TRUNC)
+    DateTrunc(Expr, Expr),
+
+    /// [`to_char`](https://www.postgresql.org/docs/current/functions-formatting.html) for
+    /// timestamps
+    ToChar(Expr, Expr),
+
+    /// Whether a string matches a regular expression. Lowered from `regexp_like`, as well as
+    /// PostgreSQL's `~` and `~*` operators and MySQL's `REGEXP` operator:
+    ///
+    /// * [MySQL](https://dev.mysql.com/doc/refman/8.0/en/regexp.html#function_regexp-like)
+    /// * [PostgreSQL](https://www.postgresql.org/docs/current/functions-matching.html#FUNCTIONS-POSIX-REGEXP)
+    RegexpLike {
+        string: Expr,
+        pattern: Expr,
+        /// Flags (`match_type` in MySQL) modifying how the pattern is matched, such as `i` for a
+        /// case-insensitive match
+        flags: Option<Expr>,
+        /// The dialect whose flavor of regular expressions (and flags) to follow
+        engine: SqlEngine,
+        #[serde(skip)]
+        cache: RegexCache,
+    },
+
+    /// `regexp_replace`:
+    ///
+    /// * [MySQL](https://dev.mysql.com/doc/refman/8.0/en/regexp.html#function_regexp-replace)
+    /// * [PostgreSQL](https://www.postgresql.org/docs/current/functions-matching.html#FUNCTIONS-POSIX-REGEXP)
+    RegexpReplace {
+        string: Expr,
+        pattern: Expr,
+        replacement: Expr,
+        /// The 1-based character position to start searching at (MySQL only)
+        position: Option<Expr>,
+        /// Which match to replace, or 0 for all matches (MySQL only). PostgreSQL instead replaces
+        /// only the first match unless the `g` flag is given.
+        occurrence: Option<Expr>,
+        flags: Option<Expr>,
+        engine: SqlEngine,
+        #[serde(skip)]
+        cache: RegexCache,
+    },
+
+    /// [`regexp_match`](https://www.postgresql.org/docs/current/functions-matching.html#FUNCTIONS-POSIX-REGEXP)
+    RegexpMatch {
+        string: Expr,
+        pattern: Expr,
+        flags: Option<Expr>,
+        #[serde(skip)]
+        cache: RegexCache,
+    },
 }
 
 impl BuiltinFunction {
@@ -141,6 +441,59 @@ impl BuiltinFunction {
             Greatest { .. } => "greatest",
             Least { .. } => "least",
             ArrayToString { .. } => "array_to_string",
+            Lower { .. } => "lower",
+            Upper { .. } => "upper",
+            Length { in_bytes: true, .. } => "octet_length",
+            Length {
+                in_bytes: false, ..
+            } => "char_length",
+            Trim {
+                side: TrimSide::Both,
+                ..
+            } => "btrim",
+            Trim {
+                side: TrimSide::Leading,
+                ..
+            } => "ltrim",
+            Trim {
+                side: TrimSide::Trailing,
+                ..
+            } => "rtrim",
+            Replace { .. } => "replace",
+            Left { .. } => "left",
+            Right { .. } => "right",
+            Pad { left: true, .. } => "lpad",
+            Pad { left: false, .. } => "rpad",
+            Repeat { .. } => "repeat",
+            Reverse { .. } => "reverse",
+            ConcatWs { .. } => "concat_ws",
+            Locate { .. } => "locate",
+            Initcap { .. } => "initcap",
+            Md5 { .. } => "md5",
+            Abs { .. } => "abs",
+            Floor { .. } => "floor",
+            Ceil { .. } => "ceil",
+            Mod { .. } => "mod",
+            Power { .. } => "power",
+            Sqrt { .. } => "sqrt",
+            Exp { .. } => "exp",
+            Log { base: None, .. } => "ln",
+            Log { base: Some(_), .. } => "log",
+            Sign { .. } => "sign",
+            Pi => "pi",
+            Truncate { .. } => "truncate",
+            Degrees { .. } => "degrees",
+            Radians { .. } => "radians",
+            DateAdd {
+                subtract: false, ..
+            } => "date_add",
+            DateAdd { subtract: true, .. } => "date_sub",
+            Extract { .. } => "extract",
+            DateTrunc { .. } => "date_trunc",
+            ToChar { .. } => "to_char",
+            RegexpLike { .. } => "regexp_like",
+            RegexpReplace { .. } => "regexp_replace",
+            RegexpMatch { .. } => "regexp_match",
         }
     }
 }
@@ -236,10 +589,137 @@ impl Display for BuiltinFunction {
                 }
                 write!(f, ")")
             }
+            Lower(arg)
+            | Upper(arg)
+            | Reverse(arg)
+            | Initcap(arg)
+            | Md5(arg)
+            | Abs(arg)
+            | Floor(arg)
+            | Ceil(arg)
+            | Exp(arg)
+            | Sign(arg)
+            | Degrees(arg)
+            | Radians(arg)
+            | Length { expr: arg, .. }
+            | Sqrt { expr: arg, .. }
+            | Log {
+                expr: arg,
+                base: None,
+                ..
+            } => {
+                write!(f, "({arg})")
+            }
+            Trim { string, chars, .. } => {
+                write!(f, "({string}")?;
+                if let Some(chars) = chars {
+                    write!(f, ", {chars}")?;
+                }
+                write!(f, ")")
+            }
+            Replace(arg1, arg2, arg3)
+            | Pad {
+                string: arg1,
+                len: arg2,
+                pad: arg3,
+                ..
+            } => {
+                write!(f, "({arg1}, {arg2}, {arg3})")
+            }
+            Left { string, len, .. } | Right { string, len, .. } => {
+                write!(f, "({string}, {len})")
+            }
+            Repeat(arg1, arg2)
+            | Power(arg1, arg2)
+            | Truncate(arg1, arg2)
+            | DateTrunc(arg1, arg2)
+            | ToChar(arg1, arg2) => {
+                write!(f, "({arg1}, {arg2})")
+            }
+            Mod {
+                dividend, divisor, ..
+            } => write!(f, "({dividend}, {divisor})"),
+            Log {
+                expr,
+                base: Some(base),
+                ..
+            } => write!(f, "({base}, {expr})"),
+            ConcatWs(separator, args) => {
+                write!(f, "({separator}, {})", args.iter().join(", "))
+            }
+            Locate {
+                substring,
+                string,
+                start,
+            } => {
+                write!(f, "({substring}, {string}")?;
+                if let Some(start) = start {
+                    write!(f, ", {start}")?;
+                }
+                write!(f, ")")
+            }
+            Pi => write!(f, "()"),
+            DateAdd {
+                datetime,
+                interval,
+                unit,
+                ..
+            } => {
+                write!(f, "({datetime}, INTERVAL {interval}")?;
+                if let Some(unit) = unit {
+                    write!(f, " {unit}")?;
+                }
+                write!(f, ")")
+            }
+            Extract { field, expr, .. } => write!(f, "({field} FROM {expr})"),
+            RegexpLike {
+                string,
+                pattern,
+                flags,
+                ..
+            }
+            | RegexpMatch {
+                string,
+                pattern,
+                flags,
+                ..
+            } => {
+                write!(f, "({string}, {pattern}")?;
+                if let Some(flags) = flags {
+                    write!(f, ", {flags}")?;
+                }
+                write!(f, ")")
+            }
+            RegexpReplace {
+                string,
+                pattern,
+                replacement,
+                position,
+                occurrence,
+                flags,
+                ..
+            } => {
+                write!(f, "({string}, {pattern}, {replacement}")?;
+                for arg in [position, occurrence, flags].into_iter().flatten() {
+                    write!(f, ", {arg}")?;
+                }
+                write!(f, ")")
+            }
         }
     }
 }
 
+/// Which side(s) of a string [`BuiltinFunction::Trim`] removes characters from
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TrimSide {
+    /// `trim` and `btrim`
+    Both,
+    /// `ltrim`
+    Leading,
+    /// `rtrim`
+    Trailing,
+}
+
 /// Argument for [`BuiltinFunction::JsonbSet`] that differentiates between `jsonb_set` and
 /// `jsonb_set_lax` behavior.
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
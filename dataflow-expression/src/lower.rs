@@ -1,19 +1,21 @@
 use std::iter;
 
 use nom_sql::{
-    BinaryOperator as SqlBinaryOperator, Column, Expr as AstExpr, FunctionExpr, InValue, Relation,
-    UnaryOperator,
+    BinaryOperator as SqlBinaryOperator, Column, Expr as AstExpr, FunctionExpr, InValue,
+    IntervalUnit, Relation, TimestampField, UnaryOperator,
 };
 use readyset_data::dialect::SqlEngine;
 use readyset_data::{DfType, DfValue};
 use readyset_errors::{
-    internal, internal_err, invalid, invalid_err, unsupported, ReadySetError, ReadySetResult,
+    internal, internal_err, invalid, invalid_err, unsupported, unsupported_err, ReadySetError,
+    ReadySetResult,
 };
 use readyset_util::redacted::Sensitive;
 use vec1::Vec1;
 
+use crate::regexp::{RegexCache, RegexFlags};
 use crate::{
-    BinaryOperator, BuiltinFunction, CaseWhenBranch, Dialect, Expr, NullValueTreatmentArg,
+    BinaryOperator, BuiltinFunction, CaseWhenBranch, Dialect, Expr, NullValueTreatmentArg, TrimSide,
 };
 
 /// Context supplied to expression lowering to allow resolving references to objects within the
@@ -125,6 +127,77 @@ fn mysql_least_greatest_compare_as(arg_types: Vec<&DfType>) -> DfType {
     DfType::VarBinary(u16::MAX)
 }
 
+/// Convert the argument to a date or time function to a timestamp, unless it already has a date or
+/// time type. Invalid values are converted to NULL in MySQL, but are an error in PostgreSQL.
+fn temporal_arg(expr: Expr, dialect: Dialect) -> Expr {
+    if matches!(
+        expr.ty(),
+        DfType::Date
+            | DfType::DateTime { .. }
+            | DfType::Timestamp { .. }
+            | DfType::TimestampTz { .. }
+            | DfType::Time { .. }
+    ) {
+        return expr;
+    }
+
+    Expr::Cast {
+        expr: Box::new(expr),
+        ty: DfType::Timestamp {
+            subsecond_digits: dialect.default_subsecond_digits(),
+        },
+        null_on_failure: dialect.engine() == SqlEngine::MySQL,
+    }
+}
+
+/// Returns an error if the given field can't be extracted from a timestamp in the given dialect
+fn check_extract_field(field: TimestampField, engine: SqlEngine) -> ReadySetResult<()> {
+    use TimestampField::*;
+    match (engine, field) {
+        (
+            SqlEngine::MySQL,
+            Century | Decade | Millennium | Isoyear | Dow | Isodow | Doy | Millisecond | Epoch,
+        ) => unsupported!("Field {field} is not supported by EXTRACT in MySQL"),
+        (
+            SqlEngine::PostgreSQL,
+            YearMonth | DayHour | DayMinute | DaySecond | DayMicrosecond | HourMinute | HourSecond
+            | HourMicrosecond | MinuteSecond | MinuteMicrosecond | SecondMicrosecond,
+        ) => unsupported!("Field {field} is not supported by EXTRACT in PostgreSQL"),
+        _ => Ok(()),
+    }
+}
+
+/// Construct the [`RegexCache`] for a regular expression function, given its (not yet coerced)
+/// pattern and flags arguments. If both are constant, the pattern is compiled up-front, which both
+/// populates the cache and rejects patterns we can't support (such as ones using backreferences)
+/// so that the query can be proxied upstream instead.
+fn regex_cache(
+    pattern: &Expr,
+    flags: Option<&Expr>,
+    engine: SqlEngine,
+) -> ReadySetResult<RegexCache> {
+    let literal_str = |expr: &Expr| match expr {
+        Expr::Literal { val, .. } => val.as_str().map(str::to_owned),
+        _ => None,
+    };
+
+    let flags = match flags {
+        None => Some(RegexFlags::new(engine)),
+        Some(flags) => literal_str(flags)
+            .map(|flags| RegexFlags::parse(&flags, engine))
+            .transpose()
+            .map_err(|e| unsupported_err!("Unsupported regular expression flags: {e}"))?,
+    };
+
+    let cache = RegexCache::default();
+    if let (Some(pattern), Some(flags)) = (literal_str(pattern), flags) {
+        cache
+            .get(&pattern, flags, engine)
+            .map_err(|e| unsupported_err!("Unsupported regular expression: {e}"))?;
+    }
+    Ok(cache)
+}
+
 impl BuiltinFunction {
     pub(crate) fn from_name_and_args<A>(
         name: &str,
@@ -174,6 +247,38 @@ impl BuiltinFunction {
             null_on_failure: true,
         };
 
+        // Arguments to string functions are converted to text, keeping the type (and collation) of
+        // arguments which are already text
+        let text_arg = |expr: Expr| {
+            let ty = if expr.ty().is_any_text() {
+                expr.ty().clone()
+            } else {
+                DfType::DEFAULT_TEXT
+            };
+            (cast(expr, ty.clone()), ty)
+        };
+        // Arguments to math functions which preserve their argument's type keep numeric types, and
+        // are converted to DOUBLE otherwise
+        let numeric_arg = |expr: Expr| {
+            let ty = match expr.ty() {
+                ty @ DfType::Numeric { .. } => ty.clone(),
+                ty if ty.is_any_int() || ty.is_any_float() => ty.clone(),
+                _ => DfType::Double,
+            };
+            (cast(expr, ty.clone()), ty)
+        };
+        let double_literal = |val: f64| Expr::Literal {
+            val: DfValue::Double(val),
+            ty: DfType::Double,
+        };
+        // Functions which only exist in one dialect are rejected in the other one
+        let only_in = |engine: SqlEngine| -> ReadySetResult<()> {
+            if dialect.engine() != engine {
+                unsupported!("Function {name} does not exist")
+            }
+            Ok(())
+        };
+
         let result = match name {
             "convert_tz" => {
                 // Type is inferred from input argument
@@ -428,6 +533,422 @@ impl BuiltinFunction {
                     DfType::DEFAULT_TEXT,
                 )
             }
+            "lower" | "lcase" | "upper" | "ucase" => {
+                if name.ends_with("case") {
+                    only_in(SqlEngine::MySQL)?;
+                }
+                let (string, ty) = text_arg(next_arg()?);
+                (
+                    if name.starts_with('l') {
+                        Self::Lower(string)
+                    } else {
+                        Self::Upper(string)
+                    },
+                    ty,
+                )
+            }
+            "length" | "char_length" | "character_length" | "octet_length" => (
+                Self::Length {
+                    expr: text_arg(next_arg()?).0,
+                    // MySQL's LENGTH counts bytes, but PostgreSQL's counts characters
+                    in_bytes: name == "octet_length"
+                        || (name == "length" && dialect.engine() == SqlEngine::MySQL),
+                },
+                match dialect.engine() {
+                    SqlEngine::MySQL => DfType::BigInt,
+                    SqlEngine::PostgreSQL => DfType::Int,
+                },
+            ),
+            "trim" | "btrim" | "ltrim" | "rtrim" => {
+                if name == "btrim" {
+                    only_in(SqlEngine::PostgreSQL)?;
+                }
+                let (string, ty) = text_arg(next_arg()?);
+                // Only PostgreSQL allows specifying the characters to remove as an argument
+                let chars = match dialect.engine() {
+                    SqlEngine::MySQL => None,
+                    SqlEngine::PostgreSQL => args.next().map(|chars| text_arg(chars).0),
+                };
+                let side = match name {
+                    "ltrim" => TrimSide::Leading,
+                    "rtrim" => TrimSide::Trailing,
+                    _ => TrimSide::Both,
+                };
+                (
+                    Self::Trim {
+                        string,
+                        chars,
+                        side,
+                    },
+                    ty,
+                )
+            }
+            "replace" => {
+                let (string, ty) = text_arg(next_arg()?);
+                (
+                    Self::Replace(string, text_arg(next_arg()?).0, text_arg(next_arg()?).0),
+                    ty,
+                )
+            }
+            "left" | "right" => {
+                let (string, ty) = text_arg(next_arg()?);
+                let len = cast(next_arg()?, DfType::BigInt);
+                let engine = dialect.engine();
+                (
+                    if name == "left" {
+                        Self::Left {
+                            string,
+                            len,
+                            engine,
+                        }
+                    } else {
+                        Self::Right {
+                            string,
+                            len,
+                            engine,
+                        }
+                    },
+                    ty,
+                )
+            }
+            "lpad" | "rpad" => {
+                let (string, ty) = text_arg(next_arg()?);
+                let len = cast(next_arg()?, DfType::BigInt);
+                // The padding string is required in MySQL, but defaults to a single space in
+                // PostgreSQL
+                let pad = match dialect.engine() {
+                    SqlEngine::MySQL => next_arg()?,
+                    SqlEngine::PostgreSQL => args.next().unwrap_or(Expr::Literal {
+                        val: " ".into(),
+                        ty: DfType::DEFAULT_TEXT,
+                    }),
+                };
+                (
+                    Self::Pad {
+                        string,
+                        len,
+                        pad: text_arg(pad).0,
+                        left: name == "lpad",
+                        engine: dialect.engine(),
+                    },
+                    ty,
+                )
+            }
+            "repeat" => {
+                let (string, ty) = text_arg(next_arg()?);
+                (Self::Repeat(string, cast(next_arg()?, DfType::BigInt)), ty)
+            }
+            "reverse" => {
+                let (string, ty) = text_arg(next_arg()?);
+                (Self::Reverse(string), ty)
+            }
+            "concat_ws" => {
+                let (separator, ty) = text_arg(next_arg()?);
+                let strings =
+                    Vec1::try_from_vec(args.by_ref().map(|arg| text_arg(arg).0).collect())
+                        .map_err(|_| arity_error())?;
+                (Self::ConcatWs(separator, strings), ty)
+            }
+            "locate" => {
+                only_in(SqlEngine::MySQL)?;
+                let substring = text_arg(next_arg()?).0;
+                let string = text_arg(next_arg()?).0;
+                (
+                    Self::Locate {
+                        substring,
+                        string,
+                        start: args.next().map(|start| cast(start, DfType::BigInt)),
+                    },
+                    DfType::BigInt,
+                )
+            }
+            "instr" | "strpos" => {
+                // Unlike LOCATE, both of these take the string to search in first
+                only_in(if name == "instr" {
+                    SqlEngine::MySQL
+                } else {
+                    SqlEngine::PostgreSQL
+                })?;
+                let string = text_arg(next_arg()?).0;
+                let substring = text_arg(next_arg()?).0;
+                (
+                    Self::Locate {
+                        substring,
+                        string,
+                        start: None,
+                    },
+                    match dialect.engine() {
+                        SqlEngine::MySQL => DfType::BigInt,
+                        SqlEngine::PostgreSQL => DfType::Int,
+                    },
+                )
+            }
+            "initcap" => {
+                only_in(SqlEngine::PostgreSQL)?;
+                let (string, ty) = text_arg(next_arg()?);
+                (Self::Initcap(string), ty)
+            }
+            "md5" => (Self::Md5(text_arg(next_arg()?).0), DfType::DEFAULT_TEXT),
+            "abs" => {
+                let (expr, ty) = numeric_arg(next_arg()?);
+                (Self::Abs(expr), ty)
+            }
+            "floor" | "ceil" | "ceiling" => {
+                let expr = next_arg()?;
+                let ty = match expr.ty() {
+                    ty @ DfType::Numeric { .. } => ty.clone(),
+                    // MySQL returns integers unchanged, but PostgreSQL converts them to double
+                    ty if ty.is_any_int() && dialect.engine() == SqlEngine::MySQL => DfType::BigInt,
+                    _ => DfType::Double,
+                };
+                let expr = cast(expr, ty.clone());
+                (
+                    if name == "floor" {
+                        Self::Floor(expr)
+                    } else {
+                        Self::Ceil(expr)
+                    },
+                    ty,
+                )
+            }
+            "mod" => {
+                let dividend = next_arg()?;
+                let divisor = next_arg()?;
+                let ty = match (dividend.ty(), divisor.ty()) {
+                    (l, r) if l.is_any_int() && r.is_any_int() => DfType::BigInt,
+                    (l, r) if l.is_any_float() || r.is_any_float() => DfType::Double,
+                    (DfType::Numeric { .. }, _) | (_, DfType::Numeric { .. }) => {
+                        DfType::DEFAULT_NUMERIC
+                    }
+                    _ => DfType::Double,
+                };
+                (
+                    Self::Mod {
+                        dividend: cast(dividend, ty.clone()),
+                        divisor: cast(divisor, ty.clone()),
+                        engine: dialect.engine(),
+                    },
+                    ty,
+                )
+            }
+            "power" | "pow" => (
+                Self::Power(
+                    cast(next_arg()?, DfType::Double),
+                    cast(next_arg()?, DfType::Double),
+                ),
+                DfType::Double,
+            ),
+            "sqrt" => (
+                Self::Sqrt {
+                    expr: cast(next_arg()?, DfType::Double),
+                    engine: dialect.engine(),
+                },
+                DfType::Double,
+            ),
+            "exp" => (Self::Exp(cast(next_arg()?, DfType::Double)), DfType::Double),
+            "ln" | "log" | "log2" | "log10" => {
+                if name == "log2" {
+                    only_in(SqlEngine::MySQL)?;
+                }
+                let arg1 = cast(next_arg()?, DfType::Double);
+                let (expr, base) = match name {
+                    "log2" => (arg1, Some(double_literal(2.0))),
+                    "log10" => (arg1, Some(double_literal(10.0))),
+                    // The two-argument form of LOG takes the base first
+                    "log" => match args.next() {
+                        Some(arg2) => (cast(arg2, DfType::Double), Some(arg1)),
+                        // With one argument, LOG is the natural logarithm in MySQL, but the base 10
+                        // logarithm in PostgreSQL
+                        None => match dialect.engine() {
+                            SqlEngine::MySQL => (arg1, None),
+                            SqlEngine::PostgreSQL => (arg1, Some(double_literal(10.0))),
+                        },
+                    },
+                    _ => (arg1, None),
+                };
+                (
+                    Self::Log {
+                        expr,
+                        base,
+                        engine: dialect.engine(),
+                    },
+                    DfType::Double,
+                )
+            }
+            "sign" => {
+                let (expr, arg_ty) = numeric_arg(next_arg()?);
+                let ty = match dialect.engine() {
+                    SqlEngine::MySQL => DfType::Int,
+                    SqlEngine::PostgreSQL if matches!(arg_ty, DfType::Numeric { .. }) => arg_ty,
+                    SqlEngine::PostgreSQL => DfType::Double,
+                };
+                (Self::Sign(expr), ty)
+            }
+            "pi" => (Self::Pi, DfType::Double),
+            "truncate" | "trunc" => {
+                only_in(if name == "truncate" {
+                    SqlEngine::MySQL
+                } else {
+                    SqlEngine::PostgreSQL
+                })?;
+                let expr = next_arg()?;
+                // The number of decimal places is required in MySQL, but defaults to 0 in
+                // PostgreSQL
+                let places = match dialect.engine() {
+                    SqlEngine::MySQL => next_arg()?,
+                    SqlEngine::PostgreSQL => args.next().unwrap_or(Expr::Literal {
+                        val: DfValue::Int(0),
+                        ty: DfType::Int,
+                    }),
+                };
+                let ty = type_for_round(&expr, &places);
+                (
+                    Self::Truncate(cast(expr, ty.clone()), cast(places, DfType::BigInt)),
+                    ty,
+                )
+            }
+            "degrees" => (
+                Self::Degrees(cast(next_arg()?, DfType::Double)),
+                DfType::Double,
+            ),
+            "radians" => (
+                Self::Radians(cast(next_arg()?, DfType::Double)),
+                DfType::Double,
+            ),
+            "year" | "quarter" | "day" | "dayofmonth" | "dayofyear" | "week" | "hour"
+            | "minute" | "second" | "microsecond" => {
+                only_in(SqlEngine::MySQL)?;
+                let field = match name {
+                    "year" => TimestampField::Year,
+                    "quarter" => TimestampField::Quarter,
+                    "day" | "dayofmonth" => TimestampField::Day,
+                    "dayofyear" => TimestampField::Doy,
+                    "week" => TimestampField::Week,
+                    "hour" => TimestampField::Hour,
+                    "minute" => TimestampField::Minute,
+                    "second" => TimestampField::Second,
+                    _ => TimestampField::Microsecond,
+                };
+                let expr = temporal_arg(next_arg()?, dialect);
+                if args.next().is_some() {
+                    unsupported!("{name} only supports a single argument");
+                }
+                (
+                    Self::Extract {
+                        field,
+                        expr,
+                        engine: SqlEngine::MySQL,
+                    },
+                    DfType::Int,
+                )
+            }
+            "date_part" => {
+                only_in(SqlEngine::PostgreSQL)?;
+                let field = match next_arg()? {
+                    Expr::Literal { val, .. } => <&str>::try_from(&val)
+                        .ok()
+                        .and_then(TimestampField::from_name)
+                        .ok_or_else(|| invalid_err!("unit \"{val}\" not recognized"))?,
+                    _ => unsupported!("date_part is only supported with a constant field"),
+                };
+                check_extract_field(field, SqlEngine::PostgreSQL)?;
+                (
+                    Self::Extract {
+                        field,
+                        expr: temporal_arg(next_arg()?, dialect),
+                        engine: SqlEngine::PostgreSQL,
+                    },
+                    DfType::Double,
+                )
+            }
+            "date_trunc" => {
+                only_in(SqlEngine::PostgreSQL)?;
+                let field = cast(next_arg()?, DfType::DEFAULT_TEXT);
+                let expr = temporal_arg(next_arg()?, dialect);
+                let ty = match expr.ty() {
+                    ty @ (DfType::Timestamp { .. } | DfType::TimestampTz { .. }) => ty.clone(),
+                    _ => DfType::Timestamp {
+                        subsecond_digits: dialect.default_subsecond_digits(),
+                    },
+                };
+                (Self::DateTrunc(field, expr), ty)
+            }
+            "to_char" => {
+                only_in(SqlEngine::PostgreSQL)?;
+                let expr = next_arg()?;
+                if expr.ty().is_any_int()
+                    || expr.ty().is_any_float()
+                    || matches!(expr.ty(), DfType::Numeric { .. })
+                {
+                    unsupported!("to_char is only supported for dates and timestamps");
+                }
+                (
+                    Self::ToChar(
+                        temporal_arg(expr, dialect),
+                        cast(next_arg()?, DfType::DEFAULT_TEXT),
+                    ),
+                    DfType::DEFAULT_TEXT,
+                )
+            }
+            "regexp_like" => {
+                let (string, _) = text_arg(next_arg()?);
+                let pattern = next_arg()?;
+                let flags = next_arg().ok();
+                let cache = regex_cache(&pattern, flags.as_ref(), dialect.engine())?;
+                (
+                    Self::RegexpLike {
+                        string,
+                        pattern: text_arg(pattern).0,
+                        flags: flags.map(|flags| text_arg(flags).0),
+                        engine: dialect.engine(),
+                        cache,
+                    },
+                    DfType::Bool,
+                )
+            }
+            "regexp_replace" => {
+                let (string, ty) = text_arg(next_arg()?);
+                let pattern = next_arg()?;
+                let (replacement, _) = text_arg(next_arg()?);
+                let (position, occurrence) = match dialect.engine() {
+                    SqlEngine::MySQL => (
+                        next_arg().ok().map(|arg| cast(arg, DfType::BigInt)),
+                        next_arg().ok().map(|arg| cast(arg, DfType::BigInt)),
+                    ),
+                    SqlEngine::PostgreSQL => (None, None),
+                };
+                let flags = next_arg().ok();
+                let cache = regex_cache(&pattern, flags.as_ref(), dialect.engine())?;
+                (
+                    Self::RegexpReplace {
+                        string,
+                        pattern: text_arg(pattern).0,
+                        replacement,
+                        position,
+                        occurrence,
+                        flags: flags.map(|flags| text_arg(flags).0),
+                        engine: dialect.engine(),
+                        cache,
+                    },
+                    ty,
+                )
+            }
+            "regexp_match" => {
+                only_in(SqlEngine::PostgreSQL)?;
+                let (string, _) = text_arg(next_arg()?);
+                let pattern = next_arg()?;
+                let flags = next_arg().ok();
+                let cache = regex_cache(&pattern, flags.as_ref(), dialect.engine())?;
+                (
+                    Self::RegexpMatch {
+                        string,
+                        pattern: text_arg(pattern).0,
+                        flags: flags.map(|flags| text_arg(flags).0),
+                        cache,
+                    },
+                    DfType::Array(Box::new(DfType::DEFAULT_TEXT)),
+                )
+            }
             _ => unsupported!("Function {name} does not exist"),
         };
 
@@ -563,6 +1084,11 @@ impl BinaryOperator {
             HashArrow2 => Ok((Self::JsonKeyPathExtractText, false)),
             AtArrowRight => Ok((Self::JsonContains, false)),
             AtArrowLeft => Ok((Self::JsonContainedIn, false)),
+            // Regular expression matches are lowered to a call to `regexp_like` rather than a
+            // binary operator, so that each one can keep a cache of its compiled pattern
+            Regexp | NotRegexp | Regex | NotRegex | IRegex | NotIRegex => {
+                unsupported!("'{op}' is not supported with ANY, SOME, or ALL")
+            }
         }
     }
 
@@ -729,6 +1255,36 @@ impl Expr {
         C: LowerContext,
     {
         match expr {
+            AstExpr::Call(FunctionExpr::Call {
+                name: fname,
+                arguments,
+            }) if dialect.engine() == SqlEngine::MySQL
+                && matches!(
+                    fname.to_lowercase().as_str(),
+                    "date_add" | "date_sub" | "adddate" | "subdate"
+                ) =>
+            {
+                let fname = fname.to_lowercase();
+                let [datetime, interval]: [AstExpr; 2] = arguments
+                    .try_into()
+                    .map_err(|_| ReadySetError::ArityError(fname.clone()))?;
+                let (value, unit) = match interval {
+                    AstExpr::Interval { value, unit } => (*value, unit),
+                    // `adddate(expr, days)` and `subdate(expr, days)` take a number of days
+                    days if fname == "adddate" || fname == "subdate" => {
+                        (days, Some(IntervalUnit::Day))
+                    }
+                    _ => invalid!("The second argument to {fname} must be an INTERVAL"),
+                };
+                Self::lower_date_add(
+                    datetime,
+                    value,
+                    unit,
+                    fname.contains("sub"),
+                    dialect,
+                    context,
+                )
+            }
             AstExpr::Call(FunctionExpr::Call {
                 name: fname,
                 arguments,
@@ -737,7 +1293,8 @@ impl Expr {
                     .into_iter()
                     .map(|arg| Self::lower(arg, dialect, context.clone()))
                     .collect::<Result<Vec<_>, _>>()?;
-                let (func, ty) = BuiltinFunction::from_name_and_args(&fname, args, dialect)?;
+                let (func, ty) =
+                    BuiltinFunction::from_name_and_args(&fname.to_lowercase(), args, dialect)?;
                 Ok(Self::Call {
                     func: Box::new(func),
                     ty,
@@ -760,6 +1317,20 @@ impl Expr {
 
                 Ok(Self::Call { func, ty })
             }
+            AstExpr::Call(FunctionExpr::Extract { field, expr }) => {
+                check_extract_field(field, dialect.engine())?;
+                let ty = match dialect.engine() {
+                    SqlEngine::MySQL => DfType::BigInt,
+                    SqlEngine::PostgreSQL => DfType::DEFAULT_NUMERIC,
+                };
+                let func = Box::new(BuiltinFunction::Extract {
+                    field,
+                    expr: temporal_arg(Self::lower(*expr, dialect, context)?, dialect),
+                    engine: dialect.engine(),
+                });
+
+                Ok(Self::Call { func, ty })
+            }
             AstExpr::Call(call) => internal!(
                 "Unexpected (aggregate?) call node in project expression: {:?}",
                 Sensitive(&call)
@@ -780,6 +1351,77 @@ impl Expr {
                 let (index, ty) = context.resolve_column(col)?;
                 Ok(Self::Column { index, ty })
             }
+            AstExpr::BinaryOp {
+                lhs,
+                op: op @ (SqlBinaryOperator::Add | SqlBinaryOperator::Subtract),
+                rhs,
+            } if matches!(*rhs, AstExpr::Interval { .. })
+                || (op == SqlBinaryOperator::Add && matches!(*lhs, AstExpr::Interval { .. })) =>
+            {
+                let ((datetime, AstExpr::Interval { value, unit })
+                | (AstExpr::Interval { value, unit }, datetime)) = (*lhs, *rhs) else {
+                    internal!("Checked that one of the operands is an INTERVAL above")
+                };
+                Self::lower_date_add(
+                    datetime,
+                    *value,
+                    unit,
+                    op == SqlBinaryOperator::Subtract,
+                    dialect,
+                    context,
+                )
+            }
+            AstExpr::BinaryOp {
+                lhs,
+                op:
+                    op @ (SqlBinaryOperator::Regexp
+                    | SqlBinaryOperator::NotRegexp
+                    | SqlBinaryOperator::Regex
+                    | SqlBinaryOperator::NotRegex
+                    | SqlBinaryOperator::IRegex
+                    | SqlBinaryOperator::NotIRegex),
+                rhs,
+            } => {
+                let engine =
+                    if matches!(op, SqlBinaryOperator::Regexp | SqlBinaryOperator::NotRegexp) {
+                        SqlEngine::MySQL
+                    } else {
+                        SqlEngine::PostgreSQL
+                    };
+                if dialect.engine() != engine {
+                    unsupported!("'{op}' not available in {}", dialect.engine())
+                }
+
+                let mut args = vec![
+                    Self::lower(*lhs, dialect, context.clone())?,
+                    Self::lower(*rhs, dialect, context)?,
+                ];
+                if matches!(op, SqlBinaryOperator::IRegex | SqlBinaryOperator::NotIRegex) {
+                    args.push(Self::Literal {
+                        val: "i".into(),
+                        ty: DfType::DEFAULT_TEXT,
+                    });
+                }
+                let (func, ty) = BuiltinFunction::from_name_and_args("regexp_like", args, dialect)?;
+                let call = Self::Call {
+                    func: Box::new(func),
+                    ty,
+                };
+
+                if matches!(
+                    op,
+                    SqlBinaryOperator::NotRegexp
+                        | SqlBinaryOperator::NotRegex
+                        | SqlBinaryOperator::NotIRegex
+                ) {
+                    Ok(Self::Not {
+                        expr: Box::new(call),
+                        ty: DfType::Bool,
+                    })
+                } else {
+                    Ok(call)
+                }
+            }
             AstExpr::BinaryOp { lhs, op, rhs } => {
                 let mut left = Box::new(Self::lower(*lhs, dialect, context.clone())?);
                 let mut right = Box::new(Self::lower(*rhs, dialect, context)?);
@@ -793,6 +1435,31 @@ impl Expr {
                     unsupported!("'{op}' operator not implemented yet for MySQL");
                 }
 
+                // In PostgreSQL, subtracting two timestamps (or times) yields an INTERVAL, which we
+                // don't have a value type for. Subtracting two dates yields an integer, which is
+                // fine.
+                let is_datetime = |ty: &DfType| {
+                    matches!(
+                        ty,
+                        DfType::Date
+                            | DfType::Time { .. }
+                            | DfType::Timestamp { .. }
+                            | DfType::TimestampTz { .. }
+                    )
+                };
+                if dialect.engine() == SqlEngine::PostgreSQL
+                    && op == BinaryOperator::Subtract
+                    && is_datetime(left.ty())
+                    && is_datetime(right.ty())
+                    && !(*left.ty() == DfType::Date && *right.ty() == DfType::Date)
+                {
+                    unsupported!(
+                        "Subtracting {} from {} produces an INTERVAL, which is not supported",
+                        right.ty(),
+                        left.ty()
+                    );
+                }
+
                 let ty = op.output_type(left.ty(), right.ty())?;
                 let (left_coerce_target, right_coerce_target) =
                     op.argument_type_coercions(left.ty(), right.ty(), dialect)?;
@@ -1003,6 +1670,10 @@ impl Expr {
             }
             AstExpr::Exists(_) => unsupported!("EXISTS not currently supported"),
             AstExpr::Variable(_) => unsupported!("Variables not currently supported"),
+            AstExpr::Interval { .. } => unsupported!(
+                "INTERVAL expressions are only supported when added to or subtracted from a date \
+                 or timestamp"
+            ),
             AstExpr::Between { .. } | AstExpr::NestedSelect(_) | AstExpr::In { .. } => {
                 internal!(
                     "Expression should have been desugared earlier: {}",
@@ -1012,6 +1683,80 @@ impl Expr {
         }
     }
 
+    /// Lower adding the given interval to (or subtracting it from) `datetime`
+    fn lower_date_add<C>(
+        datetime: AstExpr,
+        interval: AstExpr,
+        unit: Option<IntervalUnit>,
+        subtract: bool,
+        dialect: Dialect,
+        context: C,
+    ) -> ReadySetResult<Self>
+    where
+        C: LowerContext,
+    {
+        use IntervalUnit::*;
+
+        let datetime = Self::lower(datetime, dialect, context.clone())?;
+        let interval = Self::lower(interval, dialect, context)?;
+
+        let subsecond_digits = if matches!(
+            unit,
+            Some(
+                Microsecond
+                    | SecondMicrosecond
+                    | MinuteMicrosecond
+                    | HourMicrosecond
+                    | DayMicrosecond
+            )
+        ) {
+            6
+        } else {
+            datetime
+                .ty()
+                .subsecond_digits()
+                .unwrap_or_else(|| dialect.default_subsecond_digits())
+        };
+        // MySQL only returns a DATE when adding whole days to a DATE; PostgreSQL always returns a
+        // timestamp
+        let ty = match (dialect.engine(), datetime.ty()) {
+            (_, DfType::TimestampTz { .. }) => DfType::TimestampTz { subsecond_digits },
+            (SqlEngine::MySQL, DfType::Date)
+                if matches!(unit, Some(Day | Week | Month | Quarter | Year | YearMonth)) =>
+            {
+                DfType::Date
+            }
+            (SqlEngine::MySQL, _) => DfType::DateTime { subsecond_digits },
+            (SqlEngine::PostgreSQL, _) => DfType::Timestamp { subsecond_digits },
+        };
+        let datetime = if matches!(
+            datetime.ty(),
+            DfType::Date
+                | DfType::DateTime { .. }
+                | DfType::Timestamp { .. }
+                | DfType::TimestampTz { .. }
+        ) {
+            datetime
+        } else {
+            Self::Cast {
+                expr: Box::new(datetime),
+                ty: ty.clone(),
+                null_on_failure: dialect.engine() == SqlEngine::MySQL,
+            }
+        };
+
+        Ok(Self::Call {
+            func: Box::new(BuiltinFunction::DateAdd {
+                datetime,
+                interval,
+                unit,
+                subtract,
+                engine: dialect.engine(),
+            }),
+            ty,
+        })
+    }
+
     fn lower_op_any_or_all<C>(
         lhs: AstExpr,
         op: SqlBinaryOperator,
@@ -1279,6 +2024,198 @@ pub(crate) mod tests {
         );
     }
 
+    #[track_caller]
+    fn try_lower(expr: &str, dialect: ParserDialect) -> ReadySetResult<Expr> {
+        let input = parse_expr(dialect, expr).unwrap();
+        let dialect = match dialect {
+            ParserDialect::MySQL => Dialect::DEFAULT_MYSQL,
+            ParserDialect::PostgreSQL => Dialect::DEFAULT_POSTGRESQL,
+        };
+        Expr::lower(input, dialect, no_op_lower_context())
+    }
+
+    #[track_caller]
+    fn lowered_type(expr: &str, dialect: ParserDialect) -> DfType {
+        try_lower(expr, dialect).unwrap().ty().clone()
+    }
+
+    #[test]
+    fn string_and_math_function_types() {
+        use ParserDialect::{MySQL, PostgreSQL};
+        assert_eq!(lowered_type("upper('a')", MySQL), DfType::DEFAULT_TEXT);
+        assert_eq!(
+            lowered_type("lpad('a', 2)", PostgreSQL),
+            DfType::DEFAULT_TEXT
+        );
+        assert_eq!(lowered_type("length('a')", MySQL), DfType::BigInt);
+        assert_eq!(lowered_type("length('a')", PostgreSQL), DfType::Int);
+        assert_eq!(lowered_type("locate('a', 'b')", MySQL), DfType::BigInt);
+        assert_eq!(lowered_type("strpos('a', 'b')", PostgreSQL), DfType::Int);
+        assert_eq!(lowered_type("floor(1)", MySQL), DfType::BigInt);
+        assert_eq!(lowered_type("floor(1)", PostgreSQL), DfType::Double);
+        assert_eq!(lowered_type("ceil('1.5')", MySQL), DfType::Double);
+        assert_eq!(lowered_type("mod(7, 2)", MySQL), DfType::BigInt);
+        assert_eq!(lowered_type("sqrt(4)", PostgreSQL), DfType::Double);
+        assert_eq!(lowered_type("sign(-1)", MySQL), DfType::Int);
+        assert_eq!(lowered_type("sign(-1)", PostgreSQL), DfType::Double);
+        assert_eq!(lowered_type("truncate(1, 2)", MySQL), DfType::BigInt);
+        assert_eq!(lowered_type("trunc(1)", PostgreSQL), DfType::BigInt);
+    }
+
+    #[test]
+    fn date_time_function_types() {
+        use ParserDialect::{MySQL, PostgreSQL};
+        let mysql_datetime = DfType::DateTime {
+            subsecond_digits: Dialect::DEFAULT_MYSQL.default_subsecond_digits(),
+        };
+        let postgres_timestamp = DfType::Timestamp {
+            subsecond_digits: Dialect::DEFAULT_POSTGRESQL.default_subsecond_digits(),
+        };
+
+        assert_eq!(
+            lowered_type("extract(year from '2020-01-01')", MySQL),
+            DfType::BigInt
+        );
+        assert_eq!(
+            lowered_type("extract(year from '2020-01-01')", PostgreSQL),
+            DfType::DEFAULT_NUMERIC
+        );
+        assert_eq!(
+            lowered_type("date_part('year', '2020-01-01')", PostgreSQL),
+            DfType::Double
+        );
+        assert_eq!(lowered_type("hour('10:00:00')", MySQL), DfType::Int);
+        assert_eq!(
+            lowered_type("cast('2020-01-01' as date) + interval 1 day", MySQL),
+            DfType::Date
+        );
+        assert_eq!(
+            lowered_type(
+                "date_sub(cast('2020-01-01' as date), interval 1 hour)",
+                MySQL
+            ),
+            mysql_datetime
+        );
+        assert_eq!(
+            lowered_type("'2020-01-01' - interval 1 month", MySQL),
+            mysql_datetime
+        );
+        assert_eq!(
+            lowered_type("date_add('2020-01-01', interval 1 microsecond)", MySQL),
+            DfType::DateTime {
+                subsecond_digits: 6
+            }
+        );
+        assert_eq!(
+            lowered_type("cast('2020-01-01' as date) + interval '1 day'", PostgreSQL),
+            postgres_timestamp
+        );
+        assert_eq!(
+            lowered_type("date_trunc('day', '2020-01-01 10:00:00')", PostgreSQL),
+            postgres_timestamp
+        );
+        assert_eq!(
+            lowered_type("to_char('2020-01-01', 'YYYY')", PostgreSQL),
+            DfType::DEFAULT_TEXT
+        );
+    }
+
+    #[test]
+    fn unsupported_date_time_expressions() {
+        use ParserDialect::{MySQL, PostgreSQL};
+        try_lower("interval 1 day", MySQL).unwrap_err();
+        try_lower("interval 1 day - '2020-01-01'", MySQL).unwrap_err();
+        try_lower(
+            "cast('2020-01-02' as timestamp) - cast('2020-01-01' as timestamp)",
+            PostgreSQL,
+        )
+        .unwrap_err();
+        try_lower("date_add('2020-01-01', 1)", MySQL).unwrap_err();
+        try_lower("year('2020-01-01')", PostgreSQL).unwrap_err();
+        try_lower("date_trunc('day', '2020-01-01')", MySQL).unwrap_err();
+        try_lower("extract(dow from '2020-01-01')", MySQL).unwrap_err();
+        try_lower("extract(day_hour from '2020-01-01')", PostgreSQL).unwrap_err();
+        try_lower("date_part('fortnight', '2020-01-01')", PostgreSQL).unwrap_err();
+        try_lower("to_char(1.5, '9.9')", PostgreSQL).unwrap_err();
+    }
+
+    #[test]
+    fn regex_match_operators() {
+        use ParserDialect::{MySQL, PostgreSQL};
+        let res = try_lower("'abc' !~* 'B'", PostgreSQL).unwrap();
+        let Expr::Not { expr, .. } = &res else {
+            panic!("Expected NOT regexp_like(...), got {res}")
+        };
+        let Expr::Call { func, ty } = expr.as_ref() else {
+            panic!("Expected NOT regexp_like(...), got {res}")
+        };
+        assert_eq!(*ty, DfType::Bool);
+        assert_eq!(
+            **func,
+            BuiltinFunction::RegexpLike {
+                string: Expr::Cast {
+                    expr: Box::new(Expr::Literal {
+                        val: "abc".into(),
+                        ty: DfType::Unknown
+                    }),
+                    ty: DfType::DEFAULT_TEXT,
+                    null_on_failure: false
+                },
+                pattern: Expr::Cast {
+                    expr: Box::new(Expr::Literal {
+                        val: "B".into(),
+                        ty: DfType::Unknown
+                    }),
+                    ty: DfType::DEFAULT_TEXT,
+                    null_on_failure: false
+                },
+                flags: Some(Expr::Literal {
+                    val: "i".into(),
+                    ty: DfType::DEFAULT_TEXT
+                }),
+                engine: SqlEngine::PostgreSQL,
+                cache: Default::default(),
+            }
+        );
+
+        assert_eq!(lowered_type("'abc' ~ 'b'", PostgreSQL), DfType::Bool);
+        assert_eq!(lowered_type("'abc' REGEXP 'b'", MySQL), DfType::Bool);
+        assert_eq!(lowered_type("'abc' NOT RLIKE 'b'", MySQL), DfType::Bool);
+        try_lower("'abc' ~ 'b'", MySQL).unwrap_err();
+        try_lower("'abc' REGEXP 'b'", PostgreSQL).unwrap_err();
+        try_lower("'abc' ~ ANY('{a,b}')", PostgreSQL).unwrap_err();
+    }
+
+    #[test]
+    fn regex_functions() {
+        use ParserDialect::{MySQL, PostgreSQL};
+        assert_eq!(
+            lowered_type("regexp_like('abc', 'b', 'i')", MySQL),
+            DfType::Bool
+        );
+        assert_eq!(
+            lowered_type("regexp_replace('abc', 'b', 'x', 1, 0, 'c')", MySQL),
+            DfType::DEFAULT_TEXT
+        );
+        assert_eq!(
+            lowered_type("regexp_replace('abc', 'b', 'x', 'g')", PostgreSQL),
+            DfType::DEFAULT_TEXT
+        );
+        assert_eq!(
+            lowered_type("regexp_match('abc', '(b)(c)')", PostgreSQL),
+            DfType::Array(Box::new(DfType::DEFAULT_TEXT))
+        );
+
+        // Patterns and flags that can't be compiled are rejected up-front, so that the query can
+        // be proxied upstream
+        try_lower("'abc' ~ 'a(?=b)'", PostgreSQL).unwrap_err();
+        try_lower("regexp_like('abc', '(')", MySQL).unwrap_err();
+        try_lower("regexp_like('abc', 'b', 'z')", MySQL).unwrap_err();
+        try_lower("regexp_replace('abc', 'b', 'x', 'b')", PostgreSQL).unwrap_err();
+        try_lower("regexp_match('abc', 'b')", MySQL).unwrap_err();
+        try_lower("regexp_replace('abc', 'b', 'x', 1, 0, 'c')", PostgreSQL).unwrap_err();
+    }
+
     #[test]
     fn call_concat_with_texts() {
         let input = parse_expr(ParserDialect::MySQL, "concat('My', 'SQ', 'L')").unwrap();
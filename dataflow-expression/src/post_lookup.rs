@@ -1,4 +1,5 @@
 use std::cmp::{self, Ordering};
+use std::collections::HashSet;
 use std::convert::TryFrom;
 use std::fmt::Debug;
 use std::sync::Arc;
@@ -6,13 +7,13 @@ use std::sync::Arc;
 use nom_sql::OrderType;
 use partial_map::InsertionOrder;
 use readyset_data::DfValue;
-use readyset_errors::{internal, ReadySetResult};
+use readyset_errors::{internal, internal_err, ReadySetError, ReadySetResult};
 use serde::{Deserialize, Serialize};
 
 /// Representation of an aggregate function
 // TODO(grfn): It would be really nice to deduplicate this somehow with the grouped operator itself
 #[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
-pub enum PostLookupAggregateFunction {
+pub enum PostLookupAggregateFunction<Column = usize> {
     /// Add together all the input numbers
     ///
     /// Note that this encapsulates both `SUM` *and* `COUNT` in base SQL, as re-aggregating counts
@@ -26,13 +27,27 @@ pub enum PostLookupAggregateFunction {
     Max,
     /// Take the minimum input value
     Min,
+    /// Concatenate together all the input arrays
+    ArrayConcat,
+    /// Divide the re-aggregated sum in the `sum` column by the re-aggregated count in the `count`
+    /// column, to compute an average
+    Avg { sum: Column, count: Column },
+    /// Count the distinct, non-NULL values in the re-aggregated array in the `values` column
+    CountDistinct { values: Column },
 }
 
-impl PostLookupAggregateFunction {
+impl<Column> PostLookupAggregateFunction<Column> {
     /// Apply this aggregate function to the two input values
     ///
-    /// This forms a semigroup.
+    /// This forms a semigroup, with NULL as its identity element.
     pub fn apply(&self, val1: &DfValue, val2: &DfValue) -> ReadySetResult<DfValue> {
+        if val1.is_none() {
+            return Ok(val2.clone());
+        }
+        if val2.is_none() {
+            return Ok(val1.clone());
+        }
+
         match self {
             PostLookupAggregateFunction::Sum => val1 + val2,
             PostLookupAggregateFunction::Product => val1 * val2,
@@ -45,6 +60,95 @@ impl PostLookupAggregateFunction {
             .into()),
             PostLookupAggregateFunction::Max => Ok(cmp::max(val1, val2).clone()),
             PostLookupAggregateFunction::Min => Ok(cmp::min(val1, val2).clone()),
+            PostLookupAggregateFunction::ArrayConcat => match (val1, val2) {
+                (DfValue::Array(arr1), DfValue::Array(arr2)) => Ok(DfValue::from(
+                    arr1.values()
+                        .chain(arr2.values())
+                        .cloned()
+                        .collect::<Vec<_>>(),
+                )),
+                _ => internal!("ArrayConcat can only be applied to arrays"),
+            },
+            PostLookupAggregateFunction::Avg { .. }
+            | PostLookupAggregateFunction::CountDistinct { .. } => {
+                internal!("Derived aggregates can't be applied to individual values")
+            }
+        }
+    }
+
+    /// Returns true if this aggregate function is computed from the values of other columns once
+    /// they've been re-aggregated, rather than by re-aggregating its own column
+    pub fn is_derived(&self) -> bool {
+        matches!(
+            self,
+            PostLookupAggregateFunction::Avg { .. }
+                | PostLookupAggregateFunction::CountDistinct { .. }
+        )
+    }
+
+    /// Returns the columns this aggregate function is [derived][Self::is_derived] from
+    pub fn derived_from(&self) -> Vec<&Column> {
+        match self {
+            PostLookupAggregateFunction::Avg { sum, count } => vec![sum, count],
+            PostLookupAggregateFunction::CountDistinct { values } => vec![values],
+            _ => vec![],
+        }
+    }
+
+    /// Transform all column references in self by applying a function
+    pub fn map_columns<F, C2, E>(self, mut f: F) -> Result<PostLookupAggregateFunction<C2>, E>
+    where
+        F: FnMut(Column) -> Result<C2, E>,
+    {
+        Ok(match self {
+            PostLookupAggregateFunction::Sum => PostLookupAggregateFunction::Sum,
+            PostLookupAggregateFunction::Product => PostLookupAggregateFunction::Product,
+            PostLookupAggregateFunction::GroupConcat { separator } => {
+                PostLookupAggregateFunction::GroupConcat { separator }
+            }
+            PostLookupAggregateFunction::Max => PostLookupAggregateFunction::Max,
+            PostLookupAggregateFunction::Min => PostLookupAggregateFunction::Min,
+            PostLookupAggregateFunction::ArrayConcat => PostLookupAggregateFunction::ArrayConcat,
+            PostLookupAggregateFunction::Avg { sum, count } => PostLookupAggregateFunction::Avg {
+                sum: f(sum)?,
+                count: f(count)?,
+            },
+            PostLookupAggregateFunction::CountDistinct { values } => {
+                PostLookupAggregateFunction::CountDistinct { values: f(values)? }
+            }
+        })
+    }
+}
+
+impl PostLookupAggregateFunction {
+    /// If this is a [derived][Self::is_derived] aggregate function, compute its value from the
+    /// other (already re-aggregated) columns in the given row
+    pub fn derive(&self, row: &[DfValue]) -> ReadySetResult<Option<DfValue>> {
+        let col = |idx: usize| {
+            row.get(idx)
+                .ok_or_else(|| internal_err!("Post-lookup aggregate column {idx} out of bounds"))
+        };
+
+        match self {
+            PostLookupAggregateFunction::Avg { sum, count } => {
+                let (sum, count) = (col(*sum)?, col(*count)?);
+                if sum.is_none() || *count == DfValue::from(0) {
+                    return Ok(Some(DfValue::None));
+                }
+                // Divide the same way the dataflow aggregate does, so we return the same type
+                Ok(Some((sum / count)?))
+            }
+            PostLookupAggregateFunction::CountDistinct { values } => match col(*values)? {
+                DfValue::None => Ok(Some(DfValue::from(0))),
+                DfValue::Array(arr) => Ok(Some(DfValue::from(
+                    arr.values()
+                        .filter(|v| !v.is_none())
+                        .collect::<HashSet<_>>()
+                        .len() as i64,
+                ))),
+                _ => internal!("CountDistinct must be derived from an array of values"),
+            },
+            _ => Ok(None),
         }
     }
 }
@@ -55,7 +159,7 @@ pub struct PostLookupAggregate<Column = usize> {
     /// The column index in the result set containing the already-aggregated values
     pub column: Column,
     /// The aggregate function to perform
-    pub function: PostLookupAggregateFunction,
+    pub function: PostLookupAggregateFunction<Column>,
 }
 
 impl<Column> PostLookupAggregate<Column> {
@@ -66,7 +170,7 @@ impl<Column> PostLookupAggregate<Column> {
     {
         Ok(PostLookupAggregate {
             column: f(self.column)?,
-            function: self.function,
+            function: self.function.map_columns(&mut f)?,
         })
     }
 }
@@ -133,6 +237,7 @@ impl ReaderProcessing {
             returned_cols,
             default_row: default_row.map(|r| Arc::new(r.into_boxed_slice())),
             aggregates,
+            null_key_columns: vec![],
         };
 
         let pre_processing = PreInsertion {
@@ -148,6 +253,12 @@ impl ReaderProcessing {
             post_processing,
         })
     }
+
+    /// Set the [`null_key_columns`](PostLookup::null_key_columns) of the post-lookup operations
+    pub fn with_null_key_columns(mut self, null_key_columns: Vec<Vec<usize>>) -> Self {
+        self.post_processing.null_key_columns = null_key_columns;
+        self
+    }
 }
 
 /// Operations to perform on the results of a lookup after it's loaded from the map in a
@@ -179,6 +290,33 @@ pub struct PostLookup {
     /// Note that currently these are only performed on each key individually, not the overall
     /// result set returned by all keys in a multi-key lookup
     pub aggregates: Option<PostLookupAggregates>,
+    /// Sets of key column indices to replace with `NULL` in each equality lookup into the reader.
+    ///
+    /// If non-empty, each key is looked up once for each set of indices, with the key columns at
+    /// those indices replaced with `NULL`, rather than being looked up directly. This is used for
+    /// unions whose parents are parametrized by different placeholders, each of which projects
+    /// `NULL` for the key columns of the placeholders it's not parametrized by.
+    pub null_key_columns: Vec<Vec<usize>>,
+}
+
+impl PostLookup {
+    /// Check that a read with the given limit and offset (as provided by the adapter for queries
+    /// with a parametrized `LIMIT`) can be answered from the at most [`limit`](Self::limit) rows
+    /// that are kept for each key, returning [`ReadySetError::PageOutOfBounds`] if it can't.
+    pub fn check_page_bounds(
+        &self,
+        limit: Option<usize>,
+        offset: Option<usize>,
+    ) -> ReadySetResult<()> {
+        let (Some(limit), Some(max)) = (limit, self.limit) else {
+            return Ok(());
+        };
+        let offset = offset.unwrap_or(0);
+        if limit != 0 && limit.saturating_add(offset) > max {
+            return Err(ReadySetError::PageOutOfBounds { limit, offset, max });
+        }
+        Ok(())
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, Default, Eq, PartialEq)]
@@ -0,0 +1,484 @@
+//! Implementation of regular expression matching, for PostgreSQL's `~` family of operators and
+//! MySQL's `REGEXP` operator, as well as the `regexp_*` functions in both dialects
+//!
+//! Patterns are compiled with the [`regex`] crate, whose syntax is close to (but not the same as)
+//! both PostgreSQL's "advanced regular expressions" and the ICU syntax used by MySQL. Notably, it
+//! does not support backreferences or lookaround assertions within patterns - queries with a
+//! constant pattern using those features are rejected when they're lowered, so that they can be
+//! proxied to the upstream database instead.
+
+use std::borrow::Cow;
+use std::sync::{Mutex, MutexGuard};
+
+use readyset_data::dialect::SqlEngine;
+use readyset_errors::{invalid, invalid_err, unsupported, ReadySetResult};
+use regex::{Captures, Regex, RegexBuilder};
+
+/// Options for compiling and applying a regular expression, parsed from the flags argument of the
+/// `regexp_*` functions (called `match_type` in MySQL)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RegexFlags {
+    case_insensitive: bool,
+    /// `^` and `$` match at the beginning and end of each line, rather than only at the beginning
+    /// and end of the string
+    multi_line: bool,
+    /// `.` matches newlines
+    dot_matches_new_line: bool,
+    /// Whitespace and `#` comments in the pattern are ignored
+    ignore_whitespace: bool,
+    /// The pattern is matched as a literal string
+    literal: bool,
+    /// Replace every match rather than just the first one. Only valid for PostgreSQL's
+    /// `regexp_replace`
+    pub(crate) global: bool,
+}
+
+impl RegexFlags {
+    /// The flags used when none are given, which differ between dialects: PostgreSQL lets `.`
+    /// match newlines by default, whereas MySQL does not
+    pub fn new(engine: SqlEngine) -> Self {
+        Self {
+            case_insensitive: false,
+            multi_line: false,
+            dot_matches_new_line: engine == SqlEngine::PostgreSQL,
+            ignore_whitespace: false,
+            literal: false,
+            global: false,
+        }
+    }
+
+    /// Parse the given flags string, according to the rules of the given SQL dialect. Later flags
+    /// override earlier ones, so eg `'ic'` performs a case-sensitive match.
+    pub fn parse(flags: &str, engine: SqlEngine) -> ReadySetResult<Self> {
+        let mut res = Self::new(engine);
+        for flag in flags.chars() {
+            match (engine, flag) {
+                (_, 'c') => res.case_insensitive = false,
+                (_, 'i') => res.case_insensitive = true,
+                (SqlEngine::MySQL, 'm') => res.multi_line = true,
+                (SqlEngine::MySQL, 'n') => res.dot_matches_new_line = true,
+                // Unix-only line endings are all we support anyway
+                (SqlEngine::MySQL, 'u') => {}
+                (SqlEngine::PostgreSQL, 'g') => res.global = true,
+                (SqlEngine::PostgreSQL, 'm' | 'n') => {
+                    res.multi_line = true;
+                    res.dot_matches_new_line = false;
+                }
+                (SqlEngine::PostgreSQL, 'p') => {
+                    res.multi_line = false;
+                    res.dot_matches_new_line = false;
+                }
+                (SqlEngine::PostgreSQL, 's') => {
+                    res.multi_line = false;
+                    res.dot_matches_new_line = true;
+                }
+                (SqlEngine::PostgreSQL, 'w') => {
+                    res.multi_line = true;
+                    res.dot_matches_new_line = true;
+                }
+                (SqlEngine::PostgreSQL, 'q') => res.literal = true,
+                (SqlEngine::PostgreSQL, 't') => res.ignore_whitespace = false,
+                (SqlEngine::PostgreSQL, 'x') => res.ignore_whitespace = true,
+                (SqlEngine::PostgreSQL, 'b' | 'e') => {
+                    unsupported!("Basic and extended regular expression syntax is not supported")
+                }
+                (SqlEngine::PostgreSQL, _) => {
+                    invalid!("invalid regular expression option: \"{flag}\"")
+                }
+                (SqlEngine::MySQL, _) => {
+                    invalid!("Incorrect regular expression match type: {flag}")
+                }
+            }
+        }
+        Ok(res)
+    }
+
+    /// Returns these flags with the ones that don't affect how the pattern is compiled cleared, for
+    /// use as part of the key in a [`RegexCache`]
+    fn compile_options(self) -> Self {
+        Self {
+            global: false,
+            ..self
+        }
+    }
+}
+
+/// Translate the escapes in a PostgreSQL pattern which have a different meaning (or don't exist)
+/// in the syntax of the [`regex`] crate
+fn translate_postgres_pattern(pattern: &str) -> Cow<'_, str> {
+    if !pattern.contains('\\') {
+        return pattern.into();
+    }
+
+    let mut res = String::with_capacity(pattern.len());
+    let mut chars = pattern.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            res.push(c);
+            continue;
+        }
+        match chars.next() {
+            // Beginning of word, end of word, and word boundary respectively
+            Some('m' | 'M' | 'y') => res.push_str(r"\b"),
+            Some('Y') => res.push_str(r"\B"),
+            Some('Z') => res.push_str(r"\z"),
+            Some(c) => {
+                res.push('\\');
+                res.push(c);
+            }
+            None => res.push('\\'),
+        }
+    }
+    res.into()
+}
+
+fn compile(pattern: &str, flags: RegexFlags, engine: SqlEngine) -> ReadySetResult<Regex> {
+    let pattern = if flags.literal {
+        regex::escape(pattern).into()
+    } else if engine == SqlEngine::PostgreSQL {
+        translate_postgres_pattern(pattern)
+    } else {
+        pattern.into()
+    };
+
+    RegexBuilder::new(&pattern)
+        .case_insensitive(flags.case_insensitive)
+        .multi_line(flags.multi_line)
+        .dot_matches_new_line(flags.dot_matches_new_line)
+        .ignore_whitespace(flags.ignore_whitespace)
+        .build()
+        .map_err(|e| invalid_err!("invalid regular expression: {e}"))
+}
+
+#[derive(Debug, Clone)]
+struct CachedRegex {
+    pattern: String,
+    flags: RegexFlags,
+    regex: Regex,
+}
+
+/// A cache of the most recently compiled regular expression, stored in each expression node which
+/// matches against a regular expression so that a pattern is only compiled once if it's constant
+/// (or the same for many consecutive rows).
+///
+/// The cache is ignored for the purposes of equality, and isn't serialized.
+#[derive(Debug, Default)]
+pub struct RegexCache(Mutex<Option<CachedRegex>>);
+
+impl RegexCache {
+    fn cached(&self) -> MutexGuard<'_, Option<CachedRegex>> {
+        // The lock is never held while doing anything that could panic and leave the cache in an
+        // inconsistent state, so it's fine to ignore poisoning
+        self.0.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Returns the compiled form of `pattern` with the given flags, compiling it and storing it in
+    /// the cache if it's not the most recently used pattern
+    pub(crate) fn get(
+        &self,
+        pattern: &str,
+        flags: RegexFlags,
+        engine: SqlEngine,
+    ) -> ReadySetResult<Regex> {
+        let flags = flags.compile_options();
+        if let Some(cached) = &*self.cached()
+            && cached.pattern == pattern
+            && cached.flags == flags
+        {
+            // Cloning a `Regex` is cheap, and lets us release the lock before matching
+            return Ok(cached.regex.clone());
+        }
+
+        let regex = compile(pattern, flags, engine)?;
+        *self.cached() = Some(CachedRegex {
+            pattern: pattern.to_owned(),
+            flags,
+            regex: regex.clone(),
+        });
+        Ok(regex)
+    }
+}
+
+impl Clone for RegexCache {
+    fn clone(&self) -> Self {
+        Self(Mutex::new(self.cached().clone()))
+    }
+}
+
+impl PartialEq for RegexCache {
+    fn eq(&self, _: &Self) -> bool {
+        true
+    }
+}
+
+impl Eq for RegexCache {}
+
+#[derive(Debug, PartialEq, Eq)]
+enum ReplacementPart {
+    Literal(String),
+    Group(usize),
+}
+
+/// The replacement string argument to `regexp_replace`, parsed into literal text and references to
+/// capture groups in the pattern
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct Replacement(Vec<ReplacementPart>);
+
+impl Replacement {
+    /// Parse a replacement string for matches of `regex`.
+    ///
+    /// In PostgreSQL, `\1` through `\9` refer to capture groups, `\&` refers to the whole match,
+    /// and `\\` is a literal backslash. In MySQL (which uses the ICU syntax), `$n` and
+    /// `${name}` refer to capture groups, and a backslash escapes the following character.
+    pub(crate) fn parse(
+        replacement: &str,
+        regex: &Regex,
+        engine: SqlEngine,
+    ) -> ReadySetResult<Self> {
+        let mut parts = vec![];
+        let mut literal = String::new();
+        let mut push_group = |literal: &mut String, group| {
+            if !literal.is_empty() {
+                parts.push(ReplacementPart::Literal(std::mem::take(literal)));
+            }
+            parts.push(ReplacementPart::Group(group));
+        };
+
+        let mut chars = replacement.chars().peekable();
+        while let Some(c) = chars.next() {
+            match (engine, c) {
+                (SqlEngine::PostgreSQL, '\\') => match chars.peek() {
+                    Some(&d @ '1'..='9') => {
+                        chars.next();
+                        push_group(&mut literal, d as usize - '0' as usize);
+                    }
+                    Some('&') => {
+                        chars.next();
+                        push_group(&mut literal, 0);
+                    }
+                    Some('\\') => {
+                        chars.next();
+                        literal.push('\\');
+                    }
+                    _ => literal.push('\\'),
+                },
+                (SqlEngine::MySQL, '\\') => {
+                    if let Some(c) = chars.next() {
+                        literal.push(c);
+                    }
+                }
+                (SqlEngine::MySQL, '$') => {
+                    let group = match chars.next() {
+                        Some('{') => {
+                            let name = chars.by_ref().take_while(|c| *c != '}').collect::<String>();
+                            regex
+                                .capture_names()
+                                .position(|n| n == Some(name.as_str()))
+                                .ok_or_else(|| {
+                                    invalid_err!(
+                                        "Unknown capture group name in replacement: {name}"
+                                    )
+                                })?
+                        }
+                        Some(d) if d.is_ascii_digit() => {
+                            // Like ICU, consume as many digits as still form a valid group number
+                            let mut group = d as usize - '0' as usize;
+                            while let Some(&d) = chars.peek()
+                                && d.is_ascii_digit()
+                                && group * 10 + (d as usize - '0' as usize) < regex.captures_len()
+                            {
+                                group = group * 10 + (d as usize - '0' as usize);
+                                chars.next();
+                            }
+                            if group >= regex.captures_len() {
+                                invalid!("Capture group {group} does not exist in the pattern");
+                            }
+                            group
+                        }
+                        _ => invalid!("Invalid capture group reference in replacement"),
+                    };
+                    push_group(&mut literal, group);
+                }
+                (_, c) => literal.push(c),
+            }
+        }
+
+        if !literal.is_empty() {
+            parts.push(ReplacementPart::Literal(literal));
+        }
+        Ok(Self(parts))
+    }
+
+    fn expand(&self, captures: &Captures<'_>, dst: &mut String) {
+        for part in &self.0 {
+            match part {
+                ReplacementPart::Literal(s) => dst.push_str(s),
+                ReplacementPart::Group(group) => {
+                    if let Some(m) = captures.get(*group) {
+                        dst.push_str(m.as_str())
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Replace matches of `regex` in `string` with `replacement`, searching from the (1-based)
+/// character position `position`. If `occurrence` is [`None`], every match is replaced, otherwise
+/// only the `occurrence`th match (counting from 1) is.
+pub(crate) fn replace(
+    string: &str,
+    regex: &Regex,
+    replacement: &Replacement,
+    position: usize,
+    occurrence: Option<usize>,
+) -> String {
+    let start = string
+        .char_indices()
+        .nth(position.saturating_sub(1))
+        .map_or(string.len(), |(i, _)| i);
+    let (prefix, haystack) = string.split_at(start);
+
+    let mut res = String::with_capacity(string.len());
+    res.push_str(prefix);
+    let mut last_end = 0;
+    for (n, captures) in regex.captures_iter(haystack).enumerate() {
+        if occurrence.iter().any(|occurrence| n + 1 < *occurrence) {
+            continue;
+        }
+        let Some(m) = captures.get(0) else { continue };
+        res.push_str(&haystack[last_end..m.start()]);
+        replacement.expand(&captures, &mut res);
+        last_end = m.end();
+        if occurrence.is_some() {
+            break;
+        }
+    }
+    res.push_str(&haystack[last_end..]);
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn regex(pattern: &str, flags: &str, engine: SqlEngine) -> Regex {
+        compile(pattern, RegexFlags::parse(flags, engine).unwrap(), engine).unwrap()
+    }
+
+    #[test]
+    fn postgres_flags() {
+        let pg = SqlEngine::PostgreSQL;
+        assert!(regex("a.b", "", pg).is_match("a\nb"));
+        assert!(!regex("a.b", "n", pg).is_match("a\nb"));
+        assert!(regex("^b$", "n", pg).is_match("a\nb\nc"));
+        assert!(!regex("^b$", "", pg).is_match("a\nb\nc"));
+        assert!(regex("ABC", "i", pg).is_match("xabcx"));
+        assert!(!regex("ABC", "ic", pg).is_match("xabcx"));
+        assert!(regex("a.c", "q", pg).is_match("a.c"));
+        assert!(!regex("a.c", "q", pg).is_match("abc"));
+        assert!(regex("a b  c", "x", pg).is_match("abc"));
+        assert!(RegexFlags::parse("g", pg).unwrap().global);
+        assert!(RegexFlags::parse("z", pg).is_err());
+    }
+
+    #[test]
+    fn mysql_flags() {
+        let mysql = SqlEngine::MySQL;
+        assert!(!regex("a.b", "", mysql).is_match("a\nb"));
+        assert!(regex("a.b", "n", mysql).is_match("a\nb"));
+        assert!(regex("^b$", "m", mysql).is_match("a\nb\nc"));
+        assert!(regex("ABC", "i", mysql).is_match("abc"));
+        assert!(RegexFlags::parse("g", mysql).is_err());
+    }
+
+    #[test]
+    fn postgres_word_boundaries() {
+        let pg = SqlEngine::PostgreSQL;
+        let re = regex(r"\mcat\M", "", pg);
+        assert!(re.is_match("a cat sat"));
+        assert!(!re.is_match("concatenate"));
+        assert!(regex(r"\ycat\y", "", pg).is_match("cat"));
+        assert!(regex(r"cat\Z", "", pg).is_match("a cat"));
+        assert!(regex(r"a\\m", "", pg).is_match(r"a\m"));
+    }
+
+    #[test]
+    fn unsupported_pattern_syntax() {
+        let flags = RegexFlags::new(SqlEngine::PostgreSQL);
+        compile(r"(a)\1", flags, SqlEngine::PostgreSQL).unwrap_err();
+        compile(r"a(?=b)", flags, SqlEngine::PostgreSQL).unwrap_err();
+    }
+
+    #[test]
+    fn cache_reuses_and_replaces_pattern() {
+        let cache = RegexCache::default();
+        let flags = RegexFlags::new(SqlEngine::MySQL);
+        assert!(cache
+            .get("a+", flags, SqlEngine::MySQL)
+            .unwrap()
+            .is_match("aa"));
+        assert_eq!(cache.cached().as_ref().unwrap().pattern, "a+");
+        assert!(cache
+            .get(
+                "a+",
+                RegexFlags {
+                    global: true,
+                    ..flags
+                },
+                SqlEngine::MySQL
+            )
+            .is_ok());
+        assert!(cache
+            .get("b+", flags, SqlEngine::MySQL)
+            .unwrap()
+            .is_match("bb"));
+        assert_eq!(cache.cached().as_ref().unwrap().pattern, "b+");
+        assert_eq!(cache.clone().cached().as_ref().unwrap().pattern, "b+");
+        assert!(cache.get("(", flags, SqlEngine::MySQL).is_err());
+    }
+
+    #[test]
+    fn postgres_replacement() {
+        let pg = SqlEngine::PostgreSQL;
+        let re = regex("(\\w+)@(\\w+)", "", pg);
+        let replacement = Replacement::parse(r"\2 at \1 (\&) \\ \x", &re, pg).unwrap();
+        assert_eq!(
+            replace("me@host, you@there", &re, &replacement, 1, Some(1)),
+            r"host at me (me@host) \ \x, you@there"
+        );
+        let replacement = Replacement::parse(r"\2", &re, pg).unwrap();
+        assert_eq!(
+            replace("me@host, you@there", &re, &replacement, 1, None),
+            "host, there"
+        );
+    }
+
+    #[test]
+    fn mysql_replacement() {
+        let mysql = SqlEngine::MySQL;
+        let re = regex("(?P<user>\\w+)@(\\w+)", "", mysql);
+        let replacement = Replacement::parse(r"$2 at ${user} \$1", &re, mysql).unwrap();
+        assert_eq!(
+            replace("me@host, you@there", &re, &replacement, 1, None),
+            "host at me $1, there at you $1"
+        );
+        // `$21` refers to group 2 followed by a literal `1`, since there's no group 21
+        let replacement = Replacement::parse("$21", &re, mysql).unwrap();
+        assert_eq!(replace("me@host", &re, &replacement, 1, None), "host1");
+        Replacement::parse("$3", &re, mysql).unwrap_err();
+        Replacement::parse("$x", &re, mysql).unwrap_err();
+    }
+
+    #[test]
+    fn replace_position_and_occurrence() {
+        let re = regex("a", "", SqlEngine::MySQL);
+        let replacement = Replacement::parse("X", &re, SqlEngine::MySQL).unwrap();
+        assert_eq!(replace("banana", &re, &replacement, 1, None), "bXnXnX");
+        assert_eq!(replace("banana", &re, &replacement, 3, None), "banXnX");
+        assert_eq!(replace("banana", &re, &replacement, 1, Some(2)), "banXna");
+        assert_eq!(replace("banana", &re, &replacement, 1, Some(4)), "banana");
+        assert_eq!(replace("banana", &re, &replacement, 7, None), "banana");
+        assert_eq!(replace("ñaña", &re, &replacement, 3, None), "ñañX");
+    }
+}
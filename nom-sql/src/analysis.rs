@@ -54,7 +54,9 @@ impl<'a> ReferredColumnsIter<'a> {
                 self.exprs_to_visit.push(lhs);
                 self.visit_expr(rhs)
             }
-            Expr::UnaryOp { rhs: expr, .. } | Expr::Cast { expr, .. } => self.visit_expr(expr),
+            Expr::UnaryOp { rhs: expr, .. }
+            | Expr::Cast { expr, .. }
+            | Expr::Interval { value: expr, .. } => self.visit_expr(expr),
             Expr::Exists { .. } => None,
             Expr::Between {
                 operand, min, max, ..
@@ -97,6 +99,26 @@ impl<'a> ReferredColumnsIter<'a> {
             Max(arg) => self.visit_expr(arg),
             Min(arg) => self.visit_expr(arg),
             GroupConcat { expr, .. } => self.visit_expr(expr),
+            Stddev { expr, .. } | Variance { expr, .. } => self.visit_expr(expr),
+            BoolAnd(arg) | BoolOr(arg) | BitAnd(arg) | BitOr(arg) => self.visit_expr(arg),
+            ArrayAgg { expr, order_by }
+            | StringAgg { expr, order_by, .. }
+            | JsonAgg { expr, order_by, .. } => {
+                self.exprs_to_visit
+                    .extend(order_by.iter().flat_map(|oc| oc.order_by_exprs()));
+                self.visit_expr(expr)
+            }
+            JsonObjectAgg {
+                key,
+                value,
+                order_by,
+                ..
+            } => {
+                self.exprs_to_visit.push(value);
+                self.exprs_to_visit
+                    .extend(order_by.iter().flat_map(|oc| oc.order_by_exprs()));
+                self.visit_expr(key)
+            }
             Call { arguments, .. } => arguments.first().and_then(|first_arg| {
                 if arguments.len() >= 2 {
                     self.exprs_to_visit.extend(arguments.iter().skip(1));
@@ -108,6 +130,21 @@ impl<'a> ReferredColumnsIter<'a> {
                 self.exprs_to_visit.extend(len.iter().map(|e| e.as_ref()));
                 self.visit_expr(string)
             }
+            Extract { expr, .. } => self.visit_expr(expr),
+            Over {
+                function,
+                partition_by,
+                order_by,
+            } => {
+                self.exprs_to_visit.extend(partition_by);
+                self.exprs_to_visit.extend(order_by.iter().flat_map(|oc| {
+                    oc.order_by.iter().filter_map(|(f, _)| match f {
+                        FieldReference::Expr(expr) => Some(expr),
+                        _ => None,
+                    })
+                }));
+                self.visit_function_expression(function)
+            }
         }
     }
 
@@ -167,7 +204,9 @@ impl<'a> ReferredColumnsMut<'a> {
                 self.exprs_to_visit.push(lhs);
                 self.visit_expr(rhs)
             }
-            Expr::UnaryOp { rhs: expr, .. } | Expr::Cast { expr, .. } => self.visit_expr(expr),
+            Expr::UnaryOp { rhs: expr, .. }
+            | Expr::Cast { expr, .. }
+            | Expr::Interval { value: expr, .. } => self.visit_expr(expr),
             Expr::Exists { .. } => None,
             Expr::Between {
                 operand, min, max, ..
@@ -206,6 +245,26 @@ impl<'a> ReferredColumnsMut<'a> {
             Max(arg) => self.visit_expr(arg),
             Min(arg) => self.visit_expr(arg),
             GroupConcat { expr, .. } => self.visit_expr(expr),
+            Stddev { expr, .. } | Variance { expr, .. } => self.visit_expr(expr),
+            BoolAnd(arg) | BoolOr(arg) | BitAnd(arg) | BitOr(arg) => self.visit_expr(arg),
+            ArrayAgg { expr, order_by }
+            | StringAgg { expr, order_by, .. }
+            | JsonAgg { expr, order_by, .. } => {
+                self.exprs_to_visit
+                    .extend(order_by.iter_mut().flat_map(|oc| oc.order_by_exprs_mut()));
+                self.visit_expr(expr)
+            }
+            JsonObjectAgg {
+                key,
+                value,
+                order_by,
+                ..
+            } => {
+                self.exprs_to_visit.push(value);
+                self.exprs_to_visit
+                    .extend(order_by.iter_mut().flat_map(|oc| oc.order_by_exprs_mut()));
+                self.visit_expr(key)
+            }
             Call { arguments, .. } => arguments.split_first_mut().and_then(|(first_arg, args)| {
                 self.exprs_to_visit.extend(args);
                 self.visit_expr(first_arg)
@@ -217,6 +276,22 @@ impl<'a> ReferredColumnsMut<'a> {
                     .extend(len.iter_mut().map(|e| e.as_mut()));
                 self.visit_expr(string)
             }
+            Extract { expr, .. } => self.visit_expr(expr),
+            Over {
+                function,
+                partition_by,
+                order_by,
+            } => {
+                self.exprs_to_visit.extend(partition_by);
+                self.exprs_to_visit
+                    .extend(order_by.iter_mut().flat_map(|oc| {
+                        oc.order_by.iter_mut().filter_map(|(f, _)| match f {
+                            FieldReference::Expr(expr) => Some(expr),
+                            _ => None,
+                        })
+                    }));
+                self.visit_function_expression(function)
+            }
         }
     }
 
@@ -338,8 +413,21 @@ pub fn is_aggregate(function: &FunctionExpr) -> bool {
         | FunctionExpr::Sum { .. }
         | FunctionExpr::Max(_)
         | FunctionExpr::Min(_)
-        | FunctionExpr::GroupConcat { .. } => true,
+        | FunctionExpr::GroupConcat { .. }
+        | FunctionExpr::Stddev { .. }
+        | FunctionExpr::Variance { .. }
+        | FunctionExpr::BoolAnd(_)
+        | FunctionExpr::BoolOr(_)
+        | FunctionExpr::BitAnd(_)
+        | FunctionExpr::BitOr(_)
+        | FunctionExpr::ArrayAgg { .. }
+        | FunctionExpr::StringAgg { .. }
+        | FunctionExpr::JsonAgg { .. }
+        | FunctionExpr::JsonObjectAgg { .. } => true,
         FunctionExpr::Substring { .. }
+        | FunctionExpr::Extract { .. }
+        // Window functions are computed per-row, rather than collapsing their input
+        | FunctionExpr::Over { .. }
         // For now, assume all "generic" function calls are not aggregates
         | FunctionExpr::Call { .. } => false,
     }
@@ -367,7 +455,9 @@ pub fn contains_aggregate(expr: &Expr) -> bool {
         | Expr::OpAny { lhs, rhs, .. }
         | Expr::OpSome { lhs, rhs, .. }
         | Expr::OpAll { lhs, rhs, .. } => contains_aggregate(lhs) || contains_aggregate(rhs),
-        Expr::UnaryOp { rhs: expr, .. } | Expr::Cast { expr, .. } => contains_aggregate(expr),
+        Expr::UnaryOp { rhs: expr, .. }
+        | Expr::Cast { expr, .. }
+        | Expr::Interval { value: expr, .. } => contains_aggregate(expr),
         Expr::Exists(_) => false,
         Expr::Between {
             operand, min, max, ..
@@ -441,9 +531,9 @@ impl Expr {
             | Expr::OpAll { lhs, rhs, .. } => {
                 Box::new(vec![lhs, rhs].into_iter().map(AsRef::as_ref)) as _
             }
-            Expr::UnaryOp { rhs: expr, .. } | Expr::Cast { expr, .. } => {
-                Box::new(iter::once(expr.as_ref())) as _
-            }
+            Expr::UnaryOp { rhs: expr, .. }
+            | Expr::Cast { expr, .. }
+            | Expr::Interval { value: expr, .. } => Box::new(iter::once(expr.as_ref())) as _,
             Expr::CaseWhen {
                 branches,
                 else_expr,
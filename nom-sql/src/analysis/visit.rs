@@ -459,6 +459,7 @@ pub fn walk_expr<'ast, V: Visitor<'ast>>(
             }
             Ok(())
         }
+        Expr::Interval { value, .. } => visitor.visit_expr(value.as_ref()),
         Expr::Variable(var) => visitor.visit_variable(var),
     }
 }
@@ -483,6 +484,35 @@ pub fn walk_function_expr<'ast, V: Visitor<'ast>>(
         FunctionExpr::Max(expr) => visitor.visit_expr(expr.as_ref()),
         FunctionExpr::Min(expr) => visitor.visit_expr(expr.as_ref()),
         FunctionExpr::GroupConcat { expr, .. } => visitor.visit_expr(expr.as_ref()),
+        FunctionExpr::Stddev { expr, .. } | FunctionExpr::Variance { expr, .. } => {
+            visitor.visit_expr(expr.as_ref())
+        }
+        FunctionExpr::BoolAnd(expr)
+        | FunctionExpr::BoolOr(expr)
+        | FunctionExpr::BitAnd(expr)
+        | FunctionExpr::BitOr(expr) => visitor.visit_expr(expr.as_ref()),
+        FunctionExpr::ArrayAgg { expr, order_by }
+        | FunctionExpr::StringAgg { expr, order_by, .. }
+        | FunctionExpr::JsonAgg { expr, order_by, .. } => {
+            visitor.visit_expr(expr.as_ref())?;
+            if let Some(order_by) = order_by {
+                visitor.visit_order_clause(order_by)?;
+            }
+            Ok(())
+        }
+        FunctionExpr::JsonObjectAgg {
+            key,
+            value,
+            order_by,
+            ..
+        } => {
+            visitor.visit_expr(key.as_ref())?;
+            visitor.visit_expr(value.as_ref())?;
+            if let Some(order_by) = order_by {
+                visitor.visit_order_clause(order_by)?;
+            }
+            Ok(())
+        }
         FunctionExpr::Call { arguments, .. } => {
             for arg in arguments {
                 visitor.visit_expr(arg)?;
@@ -499,6 +529,21 @@ pub fn walk_function_expr<'ast, V: Visitor<'ast>>(
             }
             Ok(())
         }
+        FunctionExpr::Extract { expr, .. } => visitor.visit_expr(expr.as_ref()),
+        FunctionExpr::Over {
+            function,
+            partition_by,
+            order_by,
+        } => {
+            visitor.visit_function_expr(function.as_ref())?;
+            for expr in partition_by {
+                visitor.visit_expr(expr)?;
+            }
+            if let Some(order_by) = order_by {
+                visitor.visit_order_clause(order_by)?;
+            }
+            Ok(())
+        }
     }
 }
 
@@ -522,7 +567,11 @@ pub fn walk_common_table_expr<'ast, V: Visitor<'ast>>(
     cte: &'ast CommonTableExpr,
 ) -> Result<(), V::Error> {
     visitor.visit_sql_identifier(&cte.name)?;
-    visitor.visit_select_statement(&cte.statement)
+    visitor.visit_select_statement(&cte.statement)?;
+    if let Some(recursive_term) = &cte.recursive_term {
+        visitor.visit_select_statement(&recursive_term.statement)?;
+    }
+    Ok(())
 }
 
 pub fn walk_field_definition_expr<'ast, V: Visitor<'ast>>(
@@ -670,7 +719,10 @@ pub fn walk_table_expr<'ast, V: Visitor<'ast>>(
 ) -> Result<(), V::Error> {
     match &table_expr.inner {
         TableExprInner::Table(table) => visitor.visit_table(table)?,
-        TableExprInner::Subquery(sq) => visitor.visit_select_statement(sq)?,
+        TableExprInner::Subquery(sq) | TableExprInner::LateralSubquery(sq) => {
+            visitor.visit_select_statement(sq)?
+        }
+        TableExprInner::Function(func) => visitor.visit_function_expr(func)?,
     }
     if let Some(ref alias) = table_expr.alias {
         visitor.visit_sql_identifier(alias)?;
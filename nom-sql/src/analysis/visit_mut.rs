@@ -474,6 +474,7 @@ pub fn walk_expr<'ast, V: VisitorMut<'ast>>(
             }
             Ok(())
         }
+        Expr::Interval { value, .. } => visitor.visit_expr(value.as_mut()),
         Expr::Variable(var) => visitor.visit_variable(var),
     }
 }
@@ -498,6 +499,35 @@ pub fn walk_function_expr<'ast, V: VisitorMut<'ast>>(
         FunctionExpr::Max(expr) => visitor.visit_expr(expr.as_mut()),
         FunctionExpr::Min(expr) => visitor.visit_expr(expr.as_mut()),
         FunctionExpr::GroupConcat { expr, .. } => visitor.visit_expr(expr.as_mut()),
+        FunctionExpr::Stddev { expr, .. } | FunctionExpr::Variance { expr, .. } => {
+            visitor.visit_expr(expr.as_mut())
+        }
+        FunctionExpr::BoolAnd(expr)
+        | FunctionExpr::BoolOr(expr)
+        | FunctionExpr::BitAnd(expr)
+        | FunctionExpr::BitOr(expr) => visitor.visit_expr(expr.as_mut()),
+        FunctionExpr::ArrayAgg { expr, order_by }
+        | FunctionExpr::StringAgg { expr, order_by, .. }
+        | FunctionExpr::JsonAgg { expr, order_by, .. } => {
+            visitor.visit_expr(expr.as_mut())?;
+            if let Some(order_by) = order_by {
+                visitor.visit_order_clause(order_by)?;
+            }
+            Ok(())
+        }
+        FunctionExpr::JsonObjectAgg {
+            key,
+            value,
+            order_by,
+            ..
+        } => {
+            visitor.visit_expr(key.as_mut())?;
+            visitor.visit_expr(value.as_mut())?;
+            if let Some(order_by) = order_by {
+                visitor.visit_order_clause(order_by)?;
+            }
+            Ok(())
+        }
         FunctionExpr::Call { arguments, .. } => {
             for arg in arguments {
                 visitor.visit_expr(arg)?;
@@ -514,6 +544,21 @@ pub fn walk_function_expr<'ast, V: VisitorMut<'ast>>(
             }
             Ok(())
         }
+        FunctionExpr::Extract { expr, .. } => visitor.visit_expr(expr.as_mut()),
+        FunctionExpr::Over {
+            function,
+            partition_by,
+            order_by,
+        } => {
+            visitor.visit_function_expr(function.as_mut())?;
+            for expr in partition_by {
+                visitor.visit_expr(expr)?;
+            }
+            if let Some(order_by) = order_by {
+                visitor.visit_order_clause(order_by)?;
+            }
+            Ok(())
+        }
     }
 }
 
@@ -537,7 +582,11 @@ pub fn walk_common_table_expr<'ast, V: VisitorMut<'ast>>(
     cte: &'ast mut CommonTableExpr,
 ) -> Result<(), V::Error> {
     visitor.visit_sql_identifier(&mut cte.name)?;
-    visitor.visit_select_statement(&mut cte.statement)
+    visitor.visit_select_statement(&mut cte.statement)?;
+    if let Some(recursive_term) = &mut cte.recursive_term {
+        visitor.visit_select_statement(&mut recursive_term.statement)?;
+    }
+    Ok(())
 }
 
 pub fn walk_field_definition_expr<'ast, V: VisitorMut<'ast>>(
@@ -687,7 +736,10 @@ pub fn walk_table_expr<'ast, V: VisitorMut<'ast>>(
 ) -> Result<(), V::Error> {
     match &mut table_expr.inner {
         TableExprInner::Table(table) => visitor.visit_table(table)?,
-        TableExprInner::Subquery(sq) => visitor.visit_select_statement(sq)?,
+        TableExprInner::Subquery(sq) | TableExprInner::LateralSubquery(sq) => {
+            visitor.visit_select_statement(sq)?
+        }
+        TableExprInner::Function(func) => visitor.visit_function_expr(func)?,
     }
     if let Some(ref mut alias) = table_expr.alias {
         visitor.visit_sql_identifier(alias)?;
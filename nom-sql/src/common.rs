@@ -8,7 +8,7 @@ use itertools::Itertools;
 use nom::branch::alt;
 use nom::bytes::complete::{tag, tag_no_case, take_until};
 use nom::character::complete::{digit1, line_ending};
-use nom::combinator::{map, map_res, opt};
+use nom::combinator::{map, map_res, opt, verify};
 use nom::error::{ErrorKind, ParseError};
 use nom::multi::{separated_list0, separated_list1};
 use nom::sequence::{delimited, pair, preceded, separated_pair, terminated, tuple};
@@ -20,6 +20,8 @@ use serde::{Deserialize, Serialize};
 use crate::column::Column;
 use crate::dialect::Dialect;
 use crate::expression::expression;
+use crate::interval::timestamp_field;
+use crate::order::{order_clause, OrderClause};
 use crate::table::Relation;
 use crate::whitespace::{whitespace0, whitespace1};
 use crate::{Expr, FunctionExpr, Literal, NomSqlResult, SqlIdentifier};
@@ -430,6 +432,18 @@ fn agg_fx_args(
     }
 }
 
+/// Parse the arguments to an aggregate function which doesn't support `DISTINCT`
+fn non_distinct_agg_fx_args(
+    dialect: Dialect,
+) -> impl Fn(LocatedSpan<&[u8]>) -> NomSqlResult<&[u8], Box<Expr>> {
+    move |i| {
+        map(
+            verify(agg_fx_args(dialect), |(_, distinct)| !*distinct),
+            |(expr, _)| Box::new(expr),
+        )(i)
+    }
+}
+
 fn delim_fx_args(
     dialect: Dialect,
 ) -> impl Fn(LocatedSpan<&[u8]>) -> NomSqlResult<&[u8], Vec<Expr>> {
@@ -500,11 +514,41 @@ fn substring(dialect: Dialect) -> impl Fn(LocatedSpan<&[u8]>) -> NomSqlResult<&[
     }
 }
 
-fn function_call(
+fn extract(dialect: Dialect) -> impl Fn(LocatedSpan<&[u8]>) -> NomSqlResult<&[u8], FunctionExpr> {
+    move |i| {
+        let (i, _) = tag_no_case("extract")(i)?;
+        let (i, _) = whitespace0(i)?;
+        let (i, _) = tag("(")(i)?;
+        let (i, _) = whitespace0(i)?;
+        let (i, field) = timestamp_field(i)?;
+        let (i, _) = whitespace1(i)?;
+        let (i, _) = tag_no_case("from")(i)?;
+        let (i, _) = whitespace1(i)?;
+        let (i, expr) = expression(dialect)(i)?;
+        let (i, _) = whitespace0(i)?;
+        let (i, _) = tag(")")(i)?;
+
+        Ok((
+            i,
+            FunctionExpr::Extract {
+                field,
+                expr: Box::new(expr),
+            },
+        ))
+    }
+}
+
+pub(crate) fn function_call(
     dialect: Dialect,
 ) -> impl Fn(LocatedSpan<&[u8]>) -> NomSqlResult<&[u8], FunctionExpr> {
     move |i| {
-        let (i, name) = dialect.function_identifier()(i)?;
+        let (i, name) = alt((
+            dialect.function_identifier(),
+            // Functions whose names are also reserved keywords
+            map(tag_no_case("left"), |_| "left"),
+            map(tag_no_case("right"), |_| "right"),
+            map(tag_no_case("replace"), |_| "replace"),
+        ))(i)?;
         let (i, _) = whitespace0(i)?;
         let (i, arguments) = delim_fx_args(dialect)(i)?;
         Ok((
@@ -517,7 +561,151 @@ fn function_call(
     }
 }
 
-pub fn function_expr(
+/// Parse one of the statistical or boolean/bitwise aggregate functions.
+///
+/// `STDDEV` and `VARIANCE` compute the population statistic in MySQL, but the sample statistic in
+/// PostgreSQL.
+fn statistical_aggregate(
+    dialect: Dialect,
+) -> impl Fn(LocatedSpan<&[u8]>) -> NomSqlResult<&[u8], FunctionExpr> {
+    move |i| {
+        let sample_by_default = dialect == Dialect::PostgreSQL;
+        let args = || non_distinct_agg_fx_args(dialect);
+        alt((
+            map(preceded(tag_no_case("stddev_samp"), args()), |expr| {
+                FunctionExpr::Stddev { expr, sample: true }
+            }),
+            map(preceded(tag_no_case("stddev_pop"), args()), |expr| {
+                FunctionExpr::Stddev {
+                    expr,
+                    sample: false,
+                }
+            }),
+            map(preceded(tag_no_case("stddev"), args()), |expr| {
+                FunctionExpr::Stddev {
+                    expr,
+                    sample: sample_by_default,
+                }
+            }),
+            map(preceded(tag_no_case("std"), args()), |expr| {
+                FunctionExpr::Stddev {
+                    expr,
+                    sample: false,
+                }
+            }),
+            map(preceded(tag_no_case("var_samp"), args()), |expr| {
+                FunctionExpr::Variance { expr, sample: true }
+            }),
+            map(preceded(tag_no_case("var_pop"), args()), |expr| {
+                FunctionExpr::Variance {
+                    expr,
+                    sample: false,
+                }
+            }),
+            map(preceded(tag_no_case("variance"), args()), |expr| {
+                FunctionExpr::Variance {
+                    expr,
+                    sample: sample_by_default,
+                }
+            }),
+            map(
+                preceded(alt((tag_no_case("bool_and"), tag_no_case("every"))), args()),
+                FunctionExpr::BoolAnd,
+            ),
+            map(
+                preceded(tag_no_case("bool_or"), args()),
+                FunctionExpr::BoolOr,
+            ),
+            map(
+                preceded(tag_no_case("bit_and"), args()),
+                FunctionExpr::BitAnd,
+            ),
+            map(preceded(tag_no_case("bit_or"), args()), FunctionExpr::BitOr),
+        ))(i)
+    }
+}
+
+/// Parse the arguments to an aggregate function which collects its input values in order, eg
+/// `(x, ',' ORDER BY y DESC)`, returning the argument expressions and the `ORDER BY` clause
+fn ordered_agg_fx_args(
+    dialect: Dialect,
+) -> impl Fn(LocatedSpan<&[u8]>) -> NomSqlResult<&[u8], (Vec<Expr>, Option<OrderClause>)> {
+    move |i| {
+        let (i, _) = whitespace0(i)?;
+        delimited(
+            terminated(tag("("), whitespace0),
+            pair(
+                separated_list1(ws_sep_comma, expression(dialect)),
+                opt(order_clause(dialect)),
+            ),
+            preceded(whitespace0, tag(")")),
+        )(i)
+    }
+}
+
+/// Parse one of the aggregate functions which collect their input values into an array, a string,
+/// or a JSON array or object.
+fn collection_aggregate(
+    dialect: Dialect,
+) -> impl Fn(LocatedSpan<&[u8]>) -> NomSqlResult<&[u8], FunctionExpr> {
+    move |i| {
+        let (i, name) = alt((
+            tag_no_case("array_agg"),
+            tag_no_case("string_agg"),
+            tag_no_case("json_agg"),
+            tag_no_case("jsonb_agg"),
+            tag_no_case("json_arrayagg"),
+            tag_no_case("json_object_agg"),
+            tag_no_case("jsonb_object_agg"),
+            tag_no_case("json_objectagg"),
+        ))(i)?;
+        let name = name.to_ascii_lowercase();
+        let (i, (mut args, order_by)) = ordered_agg_fx_args(dialect)(i)?;
+
+        let fail = || {
+            Err(nom::Err::Error(ParseError::from_error_kind(
+                i,
+                ErrorKind::Verify,
+            )))
+        };
+        let res = match (name.as_slice(), args.len()) {
+            (b"array_agg", 1) => FunctionExpr::ArrayAgg {
+                expr: Box::new(args.remove(0)),
+                order_by,
+            },
+            (b"string_agg", 2) => {
+                let separator = match args.pop() {
+                    Some(Expr::Literal(Literal::String(separator))) => separator,
+                    _ => return fail(),
+                };
+                FunctionExpr::StringAgg {
+                    expr: Box::new(args.remove(0)),
+                    separator,
+                    order_by,
+                }
+            }
+            (b"json_agg" | b"jsonb_agg" | b"json_arrayagg", 1) => FunctionExpr::JsonAgg {
+                expr: Box::new(args.remove(0)),
+                order_by,
+                jsonb: name == b"jsonb_agg",
+            },
+            (b"json_object_agg" | b"jsonb_object_agg" | b"json_objectagg", 2) => {
+                let value = Box::new(args.remove(1));
+                FunctionExpr::JsonObjectAgg {
+                    key: Box::new(args.remove(0)),
+                    value,
+                    order_by,
+                    jsonb: name == b"jsonb_object_agg",
+                }
+            }
+            _ => return fail(),
+        };
+
+        Ok((i, res))
+    }
+}
+
+fn function_expr_no_window(
     dialect: Dialect,
 ) -> impl Fn(LocatedSpan<&[u8]>) -> NomSqlResult<&[u8], FunctionExpr> {
     move |i| {
@@ -576,13 +764,63 @@ pub fn function_expr(
                     separator,
                 },
             ),
+            statistical_aggregate(dialect),
+            collection_aggregate(dialect),
             substring(dialect),
+            extract(dialect),
             function_call(dialect),
             function_call_without_parens,
         ))(i)
     }
 }
 
+/// Parse the body of an `OVER` clause, eg `(PARTITION BY x, y ORDER BY z DESC)`
+fn window_spec(
+    dialect: Dialect,
+) -> impl Fn(LocatedSpan<&[u8]>) -> NomSqlResult<&[u8], (Vec<Expr>, Option<OrderClause>)> {
+    move |i| {
+        let (i, _) = tag_no_case("over")(i)?;
+        let (i, _) = whitespace0(i)?;
+        let (i, _) = tag("(")(i)?;
+        let (i, _) = whitespace0(i)?;
+        let (i, partition_by) = opt(preceded(
+            tuple((
+                tag_no_case("partition"),
+                whitespace1,
+                tag_no_case("by"),
+                whitespace1,
+            )),
+            separated_list1(ws_sep_comma, expression(dialect)),
+        ))(i)?;
+        let (i, order_by) = opt(order_clause(dialect))(i)?;
+        let (i, _) = whitespace0(i)?;
+        let (i, _) = tag(")")(i)?;
+
+        Ok((i, (partition_by.unwrap_or_default(), order_by)))
+    }
+}
+
+pub fn function_expr(
+    dialect: Dialect,
+) -> impl Fn(LocatedSpan<&[u8]>) -> NomSqlResult<&[u8], FunctionExpr> {
+    move |i| {
+        let (i, function) = function_expr_no_window(dialect)(i)?;
+        let (i, window) = opt(preceded(whitespace0, window_spec(dialect)))(i)?;
+
+        Ok((
+            i,
+            match window {
+                Some((partition_by, order_by)) => FunctionExpr::Over {
+                    function: Box::new(function),
+                    partition_by,
+                    order_by,
+                },
+                None => function,
+            },
+        ))
+    }
+}
+
 // Parses a SQL column identifier in the db/schema.table.column format
 pub fn column_identifier_no_alias(
     dialect: Dialect,
@@ -843,7 +1081,7 @@ pub fn field_reference_list(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{to_nom_result, SqlType};
+    use crate::{to_nom_result, OrderType, SqlType, TimestampField};
 
     fn test_opt_delimited_fn_call(i: &str) -> IResult<&[u8], &[u8]> {
         opt_delimited(tag("("), tag("abc"), tag(")"))(i.as_bytes())
@@ -913,6 +1151,177 @@ mod tests {
         );
     }
 
+    #[test]
+    fn statistical_aggregates() {
+        let x = || Box::new(Expr::Column("x".into()));
+        assert_eq!(
+            test_parse!(function_expr(Dialect::MySQL), b"stddev(x)"),
+            FunctionExpr::Stddev {
+                expr: x(),
+                sample: false
+            }
+        );
+        assert_eq!(
+            test_parse!(function_expr(Dialect::PostgreSQL), b"stddev(x)"),
+            FunctionExpr::Stddev {
+                expr: x(),
+                sample: true
+            }
+        );
+        assert_eq!(
+            test_parse!(function_expr(Dialect::MySQL), b"STD(x)"),
+            FunctionExpr::Stddev {
+                expr: x(),
+                sample: false
+            }
+        );
+        assert_eq!(
+            test_parse!(function_expr(Dialect::MySQL), b"stddev_samp(x)"),
+            FunctionExpr::Stddev {
+                expr: x(),
+                sample: true
+            }
+        );
+        assert_eq!(
+            test_parse!(function_expr(Dialect::PostgreSQL), b"variance(x)"),
+            FunctionExpr::Variance {
+                expr: x(),
+                sample: true
+            }
+        );
+        assert_eq!(
+            test_parse!(function_expr(Dialect::MySQL), b"var_pop ( x )"),
+            FunctionExpr::Variance {
+                expr: x(),
+                sample: false
+            }
+        );
+        assert_eq!(
+            test_parse!(function_expr(Dialect::PostgreSQL), b"every(x)"),
+            FunctionExpr::BoolAnd(x())
+        );
+        assert_eq!(
+            test_parse!(function_expr(Dialect::PostgreSQL), b"bool_or(x)"),
+            FunctionExpr::BoolOr(x())
+        );
+        assert_eq!(
+            test_parse!(function_expr(Dialect::MySQL), b"bit_and(x)"),
+            FunctionExpr::BitAnd(x())
+        );
+        assert_eq!(
+            test_parse!(function_expr(Dialect::MySQL), b"bit_or(x)"),
+            FunctionExpr::BitOr(x())
+        );
+    }
+
+    #[test]
+    fn statistical_aggregate_display_round_trip() {
+        for dialect in [Dialect::MySQL, Dialect::PostgreSQL] {
+            for q in [
+                "stddev_samp(x)",
+                "stddev_pop(x)",
+                "var_samp(x)",
+                "var_pop(x)",
+                "bool_and(x)",
+                "bool_or(x)",
+                "bit_and(x)",
+                "bit_or(x)",
+            ] {
+                let res = test_parse!(function_expr(dialect), q.as_bytes());
+                let displayed = res.display(dialect).to_string();
+                assert_eq!(
+                    test_parse!(function_expr(dialect), displayed.as_bytes()),
+                    res
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn collection_aggregates() {
+        let x = || Box::new(Expr::Column("x".into()));
+        let order_by_y_desc = || {
+            Some(OrderClause {
+                order_by: vec![(
+                    FieldReference::Expr(Expr::Column("y".into())),
+                    Some(OrderType::OrderDescending),
+                )],
+            })
+        };
+
+        assert_eq!(
+            test_parse!(function_expr(Dialect::PostgreSQL), b"array_agg(x)"),
+            FunctionExpr::ArrayAgg {
+                expr: x(),
+                order_by: None
+            }
+        );
+        assert_eq!(
+            test_parse!(
+                function_expr(Dialect::PostgreSQL),
+                b"string_agg(x, ',' ORDER BY y DESC)"
+            ),
+            FunctionExpr::StringAgg {
+                expr: x(),
+                separator: ",".to_owned(),
+                order_by: order_by_y_desc()
+            }
+        );
+        assert_eq!(
+            test_parse!(
+                function_expr(Dialect::PostgreSQL),
+                b"jsonb_agg ( x order by y desc )"
+            ),
+            FunctionExpr::JsonAgg {
+                expr: x(),
+                order_by: order_by_y_desc(),
+                jsonb: true
+            }
+        );
+        assert_eq!(
+            test_parse!(function_expr(Dialect::MySQL), b"JSON_ARRAYAGG(x)"),
+            FunctionExpr::JsonAgg {
+                expr: x(),
+                order_by: None,
+                jsonb: false
+            }
+        );
+        assert_eq!(
+            test_parse!(function_expr(Dialect::MySQL), b"json_objectagg(y, x)"),
+            FunctionExpr::JsonObjectAgg {
+                key: Box::new(Expr::Column("y".into())),
+                value: x(),
+                order_by: None,
+                jsonb: false
+            }
+        );
+
+        // A non-literal separator isn't supported
+        assert!(matches!(
+            test_parse!(function_expr(Dialect::PostgreSQL), b"string_agg(x, y)"),
+            FunctionExpr::Call { .. }
+        ));
+    }
+
+    #[test]
+    fn collection_aggregate_display_round_trip() {
+        for (dialect, q) in [
+            (Dialect::PostgreSQL, "array_agg(x ORDER BY y DESC)"),
+            (Dialect::PostgreSQL, "string_agg(x, ', ' ORDER BY y)"),
+            (Dialect::PostgreSQL, "json_agg(x)"),
+            (Dialect::PostgreSQL, "jsonb_object_agg(k, v ORDER BY y)"),
+            (Dialect::MySQL, "json_arrayagg(x)"),
+            (Dialect::MySQL, "json_objectagg(k, v)"),
+        ] {
+            let res = test_parse!(function_expr(dialect), q.as_bytes());
+            let displayed = res.display(dialect).to_string();
+            assert_eq!(
+                test_parse!(function_expr(dialect), displayed.as_bytes()),
+                res
+            );
+        }
+    }
+
     #[test]
     fn simple_generic_function() {
         let qlist = [
@@ -946,6 +1355,58 @@ mod tests {
         )
     }
 
+    #[test]
+    fn keyword_named_functions() {
+        for dialect in [Dialect::MySQL, Dialect::PostgreSQL] {
+            for name in ["left", "right", "replace"] {
+                let res = test_parse!(
+                    function_expr(dialect),
+                    format!("{}(foo, 2)", name.to_uppercase()).as_bytes()
+                );
+                assert_eq!(
+                    res,
+                    FunctionExpr::Call {
+                        name: name.into(),
+                        arguments: vec![Expr::Column("foo".into()), Expr::Literal(2u32.into())],
+                    }
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn extract_field_from_expr() {
+        for dialect in [Dialect::MySQL, Dialect::PostgreSQL] {
+            let res = test_parse!(function_expr(dialect), b"EXTRACT(year FROM created_at)");
+            assert_eq!(
+                res,
+                FunctionExpr::Extract {
+                    field: TimestampField::Year,
+                    expr: Box::new(Expr::Column("created_at".into())),
+                }
+            );
+            assert_eq!(
+                res.display(dialect).to_string(),
+                format!(
+                    "extract(YEAR from {})",
+                    Expr::Column("created_at".into()).display(dialect)
+                )
+            );
+        }
+
+        let res = test_parse!(
+            function_expr(Dialect::PostgreSQL),
+            b"extract('epoch' from x)"
+        );
+        assert_eq!(
+            res,
+            FunctionExpr::Extract {
+                field: TimestampField::Epoch,
+                expr: Box::new(Expr::Column("x".into())),
+            }
+        );
+    }
+
     #[test]
     fn nested_cast() {
         let res = test_parse!(function_expr(Dialect::MySQL), b"max(cast(foo as int))");
@@ -1069,6 +1530,68 @@ mod tests {
         );
     }
 
+    #[test]
+    fn row_number_over() {
+        let res = test_parse!(
+            function_expr(Dialect::MySQL),
+            b"row_number() over (partition by a, b order by c desc)"
+        );
+        assert_eq!(
+            res,
+            FunctionExpr::Over {
+                function: Box::new(FunctionExpr::Call {
+                    name: "row_number".into(),
+                    arguments: vec![],
+                }),
+                partition_by: vec![Expr::Column("a".into()), Expr::Column("b".into())],
+                order_by: Some(OrderClause {
+                    order_by: vec![(
+                        FieldReference::Expr(Expr::Column("c".into())),
+                        Some(OrderType::OrderDescending)
+                    )]
+                }),
+            }
+        );
+        assert_eq!(
+            res.display(Dialect::MySQL).to_string(),
+            "row_number() OVER (PARTITION BY `a`, `b` ORDER BY `c` DESC)"
+        );
+    }
+
+    #[test]
+    fn running_sum_over() {
+        let res = test_parse!(
+            function_expr(Dialect::PostgreSQL),
+            b"sum(x) OVER(ORDER BY y)"
+        );
+        assert_eq!(
+            res,
+            FunctionExpr::Over {
+                function: Box::new(FunctionExpr::Sum {
+                    expr: Box::new(Expr::Column("x".into())),
+                    distinct: false,
+                }),
+                partition_by: vec![],
+                order_by: Some(OrderClause {
+                    order_by: vec![(FieldReference::Expr(Expr::Column("y".into())), None)]
+                }),
+            }
+        );
+    }
+
+    #[test]
+    fn over_empty_window() {
+        let res = test_parse!(function_expr(Dialect::MySQL), b"count(*) over ()");
+        assert_eq!(
+            res,
+            FunctionExpr::Over {
+                function: Box::new(FunctionExpr::CountStar),
+                partition_by: vec![],
+                order_by: None,
+            }
+        );
+    }
+
     #[test]
     fn count_star() {
         assert_eq!(
@@ -20,7 +20,9 @@ pub enum CompoundSelectOperator {
     Union,
     DistinctUnion,
     Intersect,
+    IntersectAll,
     Except,
+    ExceptAll,
 }
 
 impl fmt::Display for CompoundSelectOperator {
@@ -29,7 +31,9 @@ impl fmt::Display for CompoundSelectOperator {
             CompoundSelectOperator::Union => write!(f, "UNION"),
             CompoundSelectOperator::DistinctUnion => write!(f, "UNION DISTINCT"),
             CompoundSelectOperator::Intersect => write!(f, "INTERSECT"),
+            CompoundSelectOperator::IntersectAll => write!(f, "INTERSECT ALL"),
             CompoundSelectOperator::Except => write!(f, "EXCEPT"),
+            CompoundSelectOperator::ExceptAll => write!(f, "EXCEPT ALL"),
         }
     }
 }
@@ -90,10 +94,41 @@ fn compound_op(i: LocatedSpan<&[u8]>) -> NomSqlResult<&[u8], CompoundSelectOpera
                 }
             },
         ),
-        map(tag_no_case("intersect"), |_| {
-            CompoundSelectOperator::Intersect
-        }),
-        map(tag_no_case("except"), |_| CompoundSelectOperator::Except),
+        map(
+            preceded(
+                tag_no_case("intersect"),
+                opt(preceded(whitespace1, all_or_distinct)),
+            ),
+            |all| {
+                // Like UNION, DISTINCT is the default
+                if all == Some(true) {
+                    CompoundSelectOperator::IntersectAll
+                } else {
+                    CompoundSelectOperator::Intersect
+                }
+            },
+        ),
+        map(
+            preceded(
+                tag_no_case("except"),
+                opt(preceded(whitespace1, all_or_distinct)),
+            ),
+            |all| {
+                if all == Some(true) {
+                    CompoundSelectOperator::ExceptAll
+                } else {
+                    CompoundSelectOperator::Except
+                }
+            },
+        ),
+    ))(i)
+}
+
+// Parse the optional `ALL` or `DISTINCT` following INTERSECT or EXCEPT, returning true for `ALL`
+fn all_or_distinct(i: LocatedSpan<&[u8]>) -> NomSqlResult<&[u8], bool> {
+    alt((
+        map(tag_no_case("all"), |_| true),
+        map(tag_no_case("distinct"), |_| false),
     ))(i)
 }
 
@@ -319,6 +354,32 @@ mod tests {
         assert_eq!(res.unwrap().1, expected);
     }
 
+    #[test]
+    fn intersect_and_except() {
+        let qstr = "SELECT id FROM Vote \
+                    INTERSECT SELECT id FROM Rating \
+                    EXCEPT ALL SELECT id FROM Vote \
+                    INTERSECT ALL SELECT id FROM Rating \
+                    EXCEPT DISTINCT SELECT id FROM Rating;";
+        let res = nested_compound_selection(Dialect::MySQL)(LocatedSpan::new(qstr.as_bytes()))
+            .unwrap()
+            .1;
+
+        assert_eq!(
+            res.selects
+                .iter()
+                .map(|(op, _)| op.clone())
+                .collect::<Vec<_>>(),
+            vec![
+                None,
+                Some(CompoundSelectOperator::Intersect),
+                Some(CompoundSelectOperator::ExceptAll),
+                Some(CompoundSelectOperator::IntersectAll),
+                Some(CompoundSelectOperator::Except),
+            ]
+        );
+    }
+
     #[test]
     #[ignore]
     fn union_flarum_1() {
@@ -1,15 +1,17 @@
-use std::fmt::{self, Display};
+use std::fmt;
 
 use nom::branch::alt;
 use nom::bytes::complete::tag_no_case;
 use nom::combinator::{opt, value};
 use nom::sequence::{terminated, tuple};
 use nom_locate::LocatedSpan;
+use readyset_util::fmt::fmt_with;
 use serde::{Deserialize, Serialize};
 
-use crate::common::statement_terminator;
+use crate::common::{parse_fallible, statement_terminator, until_statement_terminator};
+use crate::create::{cached_query_inner, CacheInner};
 use crate::whitespace::whitespace1;
-use crate::NomSqlResult;
+use crate::{Dialect, NomSqlResult};
 
 /// EXPLAIN statements
 ///
@@ -22,21 +24,40 @@ pub enum ExplainStatement {
     LastStatement,
     /// List domain shard replicas and what worker they're running on
     Domains,
+    /// Describe whether a query can be cached, and the dataflow that would be created for it by
+    /// `CREATE CACHE`, without actually creating the cache
+    CreateCache {
+        /// The result of parsing the inner statement or query ID.
+        ///
+        /// If parsing succeeded, then this will be an `Ok` result with the definition of the
+        /// statement. If it failed to parse, this will be an `Err` with the remainder [`String`]
+        /// that could not be parsed.
+        inner: Result<CacheInner, String>,
+    },
 }
 
-impl Display for ExplainStatement {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "EXPLAIN ")?;
-        match self {
-            ExplainStatement::Graphviz { simplified } => {
-                if *simplified {
-                    write!(f, "SIMPLIFIED ")?;
+impl ExplainStatement {
+    pub fn display(&self, dialect: Dialect) -> impl fmt::Display + Copy + '_ {
+        fmt_with(move |f| {
+            write!(f, "EXPLAIN ")?;
+            match self {
+                ExplainStatement::Graphviz { simplified } => {
+                    if *simplified {
+                        write!(f, "SIMPLIFIED ")?;
+                    }
+                    write!(f, "GRAPHVIZ;")
+                }
+                ExplainStatement::LastStatement => write!(f, "LAST STATEMENT;"),
+                ExplainStatement::Domains => write!(f, "DOMAINS;"),
+                ExplainStatement::CreateCache { inner } => {
+                    write!(f, "CREATE CACHE FROM ")?;
+                    match inner {
+                        Ok(inner) => write!(f, "{};", inner.display(dialect)),
+                        Err(unparsed) => write!(f, "{unparsed};"),
+                    }
                 }
-                write!(f, "GRAPHVIZ;")
             }
-            ExplainStatement::LastStatement => write!(f, "LAST STATEMENT;"),
-            ExplainStatement::Domains => write!(f, "DOMAINS;"),
-        }
+        })
     }
 }
 
@@ -51,19 +72,40 @@ fn explain_graphviz(i: LocatedSpan<&[u8]>) -> NomSqlResult<&[u8], ExplainStateme
     ))
 }
 
-pub(crate) fn explain_statement(i: LocatedSpan<&[u8]>) -> NomSqlResult<&[u8], ExplainStatement> {
-    let (i, _) = tag_no_case("explain")(i)?;
-    let (i, _) = whitespace1(i)?;
-    let (i, stmt) = alt((
-        explain_graphviz,
-        value(
-            ExplainStatement::LastStatement,
-            tuple((tag_no_case("last"), whitespace1, tag_no_case("statement"))),
-        ),
-        value(ExplainStatement::Domains, tag_no_case("domains")),
-    ))(i)?;
-    let (i, _) = statement_terminator(i)?;
-    Ok((i, stmt))
+fn explain_create_cache(
+    dialect: Dialect,
+) -> impl Fn(LocatedSpan<&[u8]>) -> NomSqlResult<&[u8], ExplainStatement> {
+    move |i| {
+        let (i, _) = tag_no_case("create")(i)?;
+        let (i, _) = whitespace1(i)?;
+        let (i, _) = tag_no_case("cache")(i)?;
+        let (i, _) = whitespace1(i)?;
+        let (i, _) = tag_no_case("from")(i)?;
+        let (i, _) = whitespace1(i)?;
+        let (i, inner) =
+            parse_fallible(cached_query_inner(dialect), until_statement_terminator)(i)?;
+        Ok((i, ExplainStatement::CreateCache { inner }))
+    }
+}
+
+pub(crate) fn explain_statement(
+    dialect: Dialect,
+) -> impl Fn(LocatedSpan<&[u8]>) -> NomSqlResult<&[u8], ExplainStatement> {
+    move |i| {
+        let (i, _) = tag_no_case("explain")(i)?;
+        let (i, _) = whitespace1(i)?;
+        let (i, stmt) = alt((
+            explain_graphviz,
+            value(
+                ExplainStatement::LastStatement,
+                tuple((tag_no_case("last"), whitespace1, tag_no_case("statement"))),
+            ),
+            value(ExplainStatement::Domains, tag_no_case("domains")),
+            explain_create_cache(dialect),
+        ))(i)?;
+        let (i, _) = statement_terminator(i)?;
+        Ok((i, stmt))
+    }
 }
 
 #[cfg(test)]
@@ -73,7 +115,7 @@ mod tests {
     #[test]
     fn explain_graphviz() {
         assert_eq!(
-            explain_statement(LocatedSpan::new(b"explain graphviz;"))
+            explain_statement(Dialect::MySQL)(LocatedSpan::new(b"explain graphviz;"))
                 .unwrap()
                 .1,
             ExplainStatement::Graphviz { simplified: false }
@@ -83,7 +125,7 @@ mod tests {
     #[test]
     fn explain_last_statement() {
         assert_eq!(
-            explain_statement(LocatedSpan::new(b"explain last statement;"))
+            explain_statement(Dialect::MySQL)(LocatedSpan::new(b"explain last statement;"))
                 .unwrap()
                 .1,
             ExplainStatement::LastStatement
@@ -93,8 +135,43 @@ mod tests {
     #[test]
     fn explain_domains() {
         assert_eq!(
-            test_parse!(explain_statement, b"explain domains;"),
+            test_parse!(explain_statement(Dialect::MySQL), b"explain domains;"),
             ExplainStatement::Domains
         );
     }
+
+    #[test]
+    fn explain_create_cache() {
+        let res = test_parse!(
+            explain_statement(Dialect::MySQL),
+            b"EXPLAIN CREATE CACHE FROM SELECT id FROM users WHERE name = ?;"
+        );
+        let statement = match &res {
+            ExplainStatement::CreateCache {
+                inner: Ok(CacheInner::Statement(s)),
+            } => s,
+            _ => panic!("Unexpected explain statement: {res:?}"),
+        };
+        assert_eq!(
+            statement.tables,
+            vec![crate::TableExpr::from(crate::Relation::from("users"))]
+        );
+        assert_eq!(
+            res.display(Dialect::MySQL).to_string(),
+            "EXPLAIN CREATE CACHE FROM SELECT `id` FROM `users` WHERE (`name` = ?);"
+        );
+    }
+
+    #[test]
+    fn explain_create_cache_from_id() {
+        assert_eq!(
+            test_parse!(
+                explain_statement(Dialect::MySQL),
+                b"explain create cache from q_000000000000000a"
+            ),
+            ExplainStatement::CreateCache {
+                inner: Ok(CacheInner::Id("q_000000000000000a".into()))
+            }
+        );
+    }
 }
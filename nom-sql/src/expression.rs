@@ -8,6 +8,7 @@ use nom::branch::alt;
 use nom::bytes::complete::{tag, tag_no_case};
 use nom::character::complete::char;
 use nom::combinator::{complete, map, opt, value};
+use nom::error::{ErrorKind, ParseError};
 use nom::multi::{many0, many1, separated_list0};
 use nom::sequence::{delimited, pair, preceded, terminated, tuple};
 use nom::Parser;
@@ -20,12 +21,17 @@ use serde::{Deserialize, Serialize};
 use test_strategy::Arbitrary;
 
 use crate::common::{column_identifier_no_alias, function_expr, ws_sep_comma};
+use crate::interval::interval_unit;
 use crate::literal::literal;
+use crate::order::OrderClause;
 use crate::select::nested_selection;
 use crate::set::{variable_scope_prefix, Variable};
 use crate::sql_type::{mysql_int_cast_targets, type_identifier};
 use crate::whitespace::{whitespace0, whitespace1};
-use crate::{Column, Dialect, Literal, NomSqlResult, SelectStatement, SqlIdentifier, SqlType};
+use crate::{
+    Column, Dialect, IntervalUnit, Literal, NomSqlResult, SelectStatement, SqlIdentifier, SqlType,
+    TimestampField,
+};
 
 /// Function call expressions
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Hash, Serialize, Deserialize)]
@@ -54,6 +60,60 @@ pub enum FunctionExpr {
         separator: Option<String>,
     },
 
+    /// `STDDEV_POP` aggregation, or `STDDEV_SAMP` if `sample` is `true`.
+    ///
+    /// The dialect-specific aliases `STD`, `STDDEV` (population in MySQL, sample in PostgreSQL)
+    /// are parsed into this variant as well.
+    Stddev { expr: Box<Expr>, sample: bool },
+
+    /// `VAR_POP` aggregation, or `VAR_SAMP` if `sample` is `true`.
+    ///
+    /// The dialect-specific alias `VARIANCE` (population in MySQL, sample in PostgreSQL) is parsed
+    /// into this variant as well.
+    Variance { expr: Box<Expr>, sample: bool },
+
+    /// `BOOL_AND` (or `EVERY`) aggregation
+    BoolAnd(Box<Expr>),
+
+    /// `BOOL_OR` aggregation
+    BoolOr(Box<Expr>),
+
+    /// `BIT_AND` aggregation
+    BitAnd(Box<Expr>),
+
+    /// `BIT_OR` aggregation
+    BitOr(Box<Expr>),
+
+    /// `ARRAY_AGG` aggregation, with an optional `ORDER BY` clause for the aggregated values
+    ArrayAgg {
+        expr: Box<Expr>,
+        order_by: Option<OrderClause>,
+    },
+
+    /// `STRING_AGG` aggregation, with an optional `ORDER BY` clause for the aggregated values
+    StringAgg {
+        expr: Box<Expr>,
+        separator: String,
+        order_by: Option<OrderClause>,
+    },
+
+    /// `JSON_AGG` (or `JSONB_AGG` if `jsonb` is `true`) aggregation in PostgreSQL, or
+    /// `JSON_ARRAYAGG` in MySQL, with an optional `ORDER BY` clause for the aggregated values
+    JsonAgg {
+        expr: Box<Expr>,
+        order_by: Option<OrderClause>,
+        jsonb: bool,
+    },
+
+    /// `JSON_OBJECT_AGG` (or `JSONB_OBJECT_AGG` if `jsonb` is `true`) aggregation in PostgreSQL,
+    /// or `JSON_OBJECTAGG` in MySQL, with an optional `ORDER BY` clause for the aggregated values
+    JsonObjectAgg {
+        key: Box<Expr>,
+        value: Box<Expr>,
+        order_by: Option<OrderClause>,
+        jsonb: bool,
+    },
+
     /// The SQL `SUBSTRING`/`SUBSTR` function.
     ///
     /// The supported syntax is one of:
@@ -69,11 +129,28 @@ pub enum FunctionExpr {
         len: Option<Box<Expr>>,
     },
 
+    /// The SQL `EXTRACT(field FROM expr)` function
+    Extract {
+        field: TimestampField,
+        expr: Box<Expr>,
+    },
+
     /// Generic function call expression
     Call {
         name: SqlIdentifier,
         arguments: Vec<Expr>,
     },
+
+    /// A function call evaluated over a window, such as `ROW_NUMBER() OVER (PARTITION BY x ORDER
+    /// BY y)` or `SUM(x) OVER (ORDER BY y)`.
+    ///
+    /// Window-only functions such as `ROW_NUMBER`, `RANK`, and `DENSE_RANK` are represented as a
+    /// generic [`FunctionExpr::Call`] in `function`.
+    Over {
+        function: Box<FunctionExpr>,
+        partition_by: Vec<Expr>,
+        order_by: Option<OrderClause>,
+    },
 }
 
 impl FunctionExpr {
@@ -87,16 +164,34 @@ impl FunctionExpr {
             | FunctionExpr::Sum { expr: arg, .. }
             | FunctionExpr::Max(arg)
             | FunctionExpr::Min(arg)
-            | FunctionExpr::GroupConcat { expr: arg, .. } => {
+            | FunctionExpr::GroupConcat { expr: arg, .. }
+            | FunctionExpr::Stddev { expr: arg, .. }
+            | FunctionExpr::Variance { expr: arg, .. }
+            | FunctionExpr::BoolAnd(arg)
+            | FunctionExpr::BoolOr(arg)
+            | FunctionExpr::BitAnd(arg)
+            | FunctionExpr::BitOr(arg)
+            | FunctionExpr::ArrayAgg { expr: arg, .. }
+            | FunctionExpr::StringAgg { expr: arg, .. }
+            | FunctionExpr::JsonAgg { expr: arg, .. } => {
                 concrete_iter!(iter::once(arg.as_ref()))
             }
+            FunctionExpr::JsonObjectAgg { key, value, .. } => {
+                concrete_iter!(iter::once(key.as_ref()).chain(iter::once(value.as_ref())))
+            }
             FunctionExpr::CountStar => concrete_iter!(iter::empty()),
             FunctionExpr::Call { arguments, .. } => concrete_iter!(arguments),
+            FunctionExpr::Over {
+                function,
+                partition_by,
+                ..
+            } => concrete_iter!(function.arguments().chain(partition_by)),
             FunctionExpr::Substring { string, pos, len } => {
                 concrete_iter!(iter::once(string.as_ref())
                     .chain(pos.iter().map(|p| p.as_ref()))
                     .chain(len.iter().map(|p| p.as_ref())))
             }
+            FunctionExpr::Extract { expr, .. } => concrete_iter!(iter::once(expr.as_ref())),
         }
     }
 }
@@ -133,6 +228,84 @@ impl FunctionExpr {
                 }
                 write!(f, ")")
             }
+            FunctionExpr::Stddev { expr, sample } => write!(
+                f,
+                "{}({})",
+                if *sample { "stddev_samp" } else { "stddev_pop" },
+                expr.display(dialect)
+            ),
+            FunctionExpr::Variance { expr, sample } => write!(
+                f,
+                "{}({})",
+                if *sample { "var_samp" } else { "var_pop" },
+                expr.display(dialect)
+            ),
+            FunctionExpr::BoolAnd(expr) => write!(f, "bool_and({})", expr.display(dialect)),
+            FunctionExpr::BoolOr(expr) => write!(f, "bool_or({})", expr.display(dialect)),
+            FunctionExpr::BitAnd(expr) => write!(f, "bit_and({})", expr.display(dialect)),
+            FunctionExpr::BitOr(expr) => write!(f, "bit_or({})", expr.display(dialect)),
+            FunctionExpr::ArrayAgg { expr, order_by } => {
+                write!(f, "array_agg({}", expr.display(dialect))?;
+                if let Some(order_by) = order_by {
+                    write!(f, " {}", order_by.display(dialect))?;
+                }
+                write!(f, ")")
+            }
+            FunctionExpr::StringAgg {
+                expr,
+                separator,
+                order_by,
+            } => {
+                write!(
+                    f,
+                    "string_agg({}, {}",
+                    expr.display(dialect),
+                    Literal::String(separator.clone()).display(dialect)
+                )?;
+                if let Some(order_by) = order_by {
+                    write!(f, " {}", order_by.display(dialect))?;
+                }
+                write!(f, ")")
+            }
+            FunctionExpr::JsonAgg {
+                expr,
+                order_by,
+                jsonb,
+            } => {
+                let name = match (dialect, jsonb) {
+                    (Dialect::MySQL, _) => "json_arrayagg",
+                    (Dialect::PostgreSQL, false) => "json_agg",
+                    (Dialect::PostgreSQL, true) => "jsonb_agg",
+                };
+                write!(f, "{}({}", name, expr.display(dialect))?;
+                if let Some(order_by) = order_by {
+                    write!(f, " {}", order_by.display(dialect))?;
+                }
+                write!(f, ")")
+            }
+            FunctionExpr::JsonObjectAgg {
+                key,
+                value,
+                order_by,
+                jsonb,
+            } => {
+                let name = match (dialect, jsonb) {
+                    (Dialect::MySQL, _) => "json_objectagg",
+                    (Dialect::PostgreSQL, false) => "json_object_agg",
+                    (Dialect::PostgreSQL, true) => "jsonb_object_agg",
+                };
+                write!(
+                    f,
+                    "{}({}, {}",
+                    name,
+                    key.display(dialect),
+                    value.display(dialect)
+                )?;
+                if let Some(order_by) = order_by {
+                    write!(f, " {}", order_by.display(dialect))?;
+                }
+                write!(f, ")")
+            }
             FunctionExpr::Call { name, arguments } => {
                 write!(
                     f,
@@ -154,6 +327,30 @@ impl FunctionExpr {
 
                 write!(f, ")")
             }
+            FunctionExpr::Extract { field, expr } => {
+                write!(f, "extract({field} from {})", expr.display(dialect))
+            }
+            FunctionExpr::Over {
+                function,
+                partition_by,
+                order_by,
+            } => {
+                write!(f, "{} OVER (", function.display(dialect))?;
+                if !partition_by.is_empty() {
+                    write!(
+                        f,
+                        "PARTITION BY {}",
+                        partition_by.iter().map(|e| e.display(dialect)).join(", ")
+                    )?;
+                    if order_by.is_some() {
+                        write!(f, " ")?;
+                    }
+                }
+                if let Some(order_by) = order_by {
+                    write!(f, "{}", order_by.display(dialect))?;
+                }
+                write!(f, ")")
+            }
         })
     }
 }
@@ -180,6 +377,19 @@ pub enum BinaryOperator {
     ILike,
     /// `NOT ILIKE`
     NotILike,
+    /// MySQL `REGEXP` or `RLIKE`
+    Regexp,
+    /// MySQL `NOT REGEXP` or `NOT RLIKE`
+    NotRegexp,
+    /// PostgreSQL `~`, which matches a string against a POSIX regular expression
+    Regex,
+    /// PostgreSQL `!~`
+    NotRegex,
+    /// PostgreSQL `~*`, which matches a string against a POSIX regular expression
+    /// case-insensitively
+    IRegex,
+    /// PostgreSQL `!~*`
+    NotIRegex,
     /// `=`
     Equal,
     /// `!=` or `<>`
@@ -305,6 +515,12 @@ impl Display for BinaryOperator {
             Self::NotLike => "NOT LIKE",
             Self::ILike => "ILIKE",
             Self::NotILike => "NOT ILIKE",
+            Self::Regexp => "REGEXP",
+            Self::NotRegexp => "NOT REGEXP",
+            Self::Regex => "~",
+            Self::NotRegex => "!~",
+            Self::IRegex => "~*",
+            Self::NotIRegex => "!~*",
             Self::Equal => "=",
             Self::NotEqual => "!=",
             Self::Greater => ">",
@@ -485,6 +701,17 @@ pub enum Expr {
     /// `ARRAY[expr1, expr2, ...]`
     Array(Vec<Expr>),
 
+    /// An interval, written `INTERVAL value unit` in MySQL (eg `INTERVAL 1 DAY`) or `INTERVAL
+    /// 'value' [unit]` in PostgreSQL (eg `INTERVAL '1 day 2 hours'`).
+    ///
+    /// Intervals can only be used as an operand to date and time arithmetic - there is no INTERVAL
+    /// [`SqlType`], so `CAST(... AS INTERVAL)`, INTERVAL columns, and expressions that produce an
+    /// interval (such as subtracting two timestamps in PostgreSQL) are not supported.
+    Interval {
+        value: Box<Expr>,
+        unit: Option<IntervalUnit>,
+    },
+
     /// A variable reference
     Variable(Variable),
 }
@@ -605,6 +832,13 @@ impl Expr {
                 }
                 write!(f, "]")
             }
+            Expr::Interval { value, unit } => {
+                write!(f, "INTERVAL {}", value.display(dialect))?;
+                if let Some(unit) = unit {
+                    write!(f, " {unit}")?;
+                }
+                Ok(())
+            }
             Expr::Variable(var) => write!(f, "{}", var),
         })
     }
@@ -655,33 +889,82 @@ impl Arbitrary for Expr {
         ]
         .prop_recursive(4, 8, 4, |element| {
             let box_expr = element.clone().prop_map(Box::new);
+            let function_expr = prop_oneof![
+                (box_expr.clone(), any::<bool>())
+                    .prop_map(|(expr, distinct)| FunctionExpr::Avg { expr, distinct }),
+                (box_expr.clone(), any::<bool>())
+                    .prop_map(|(expr, distinct)| FunctionExpr::Count { expr, distinct }),
+                Just(FunctionExpr::CountStar),
+                (box_expr.clone(), any::<bool>())
+                    .prop_map(|(expr, distinct)| FunctionExpr::Sum { expr, distinct }),
+                box_expr.clone().prop_map(FunctionExpr::Max),
+                box_expr.clone().prop_map(FunctionExpr::Min),
+                (box_expr.clone(), any::<Option<String>>()).prop_map(|(expr, separator)| {
+                    FunctionExpr::GroupConcat { expr, separator }
+                }),
+                (box_expr.clone(), any::<bool>())
+                    .prop_map(|(expr, sample)| FunctionExpr::Stddev { expr, sample }),
+                (box_expr.clone(), any::<bool>())
+                    .prop_map(|(expr, sample)| FunctionExpr::Variance { expr, sample }),
+                box_expr.clone().prop_map(FunctionExpr::BoolAnd),
+                box_expr.clone().prop_map(FunctionExpr::BoolOr),
+                box_expr.clone().prop_map(FunctionExpr::BitAnd),
+                box_expr.clone().prop_map(FunctionExpr::BitOr),
+                box_expr.clone().prop_map(|expr| FunctionExpr::ArrayAgg {
+                    expr,
+                    order_by: None
+                }),
+                (box_expr.clone(), any::<String>()).prop_map(|(expr, separator)| {
+                    FunctionExpr::StringAgg {
+                        expr,
+                        separator,
+                        order_by: None,
+                    }
+                }),
+                (box_expr.clone(), any::<bool>()).prop_map(|(expr, jsonb)| FunctionExpr::JsonAgg {
+                    expr,
+                    order_by: None,
+                    jsonb
+                }),
+                (box_expr.clone(), box_expr.clone(), any::<bool>()).prop_map(
+                    |(key, value, jsonb)| FunctionExpr::JsonObjectAgg {
+                        key,
+                        value,
+                        order_by: None,
+                        jsonb
+                    }
+                ),
+                (
+                    box_expr.clone(),
+                    option::of(box_expr.clone()),
+                    option::of(box_expr.clone())
+                )
+                    .prop_map(|(string, pos, len)| {
+                        FunctionExpr::Substring { string, pos, len }
+                    }),
+                (any::<TimestampField>(), box_expr.clone())
+                    .prop_map(|(field, expr)| FunctionExpr::Extract { field, expr }),
+                (
+                    any::<SqlIdentifier>(),
+                    proptest::collection::vec(element.clone(), 0..24)
+                )
+                    .prop_map(|(name, arguments)| FunctionExpr::Call { name, arguments })
+            ]
+            .boxed();
             prop_oneof![
                 prop_oneof![
-                    (box_expr.clone(), any::<bool>())
-                        .prop_map(|(expr, distinct)| FunctionExpr::Avg { expr, distinct }),
-                    (box_expr.clone(), any::<bool>())
-                        .prop_map(|(expr, distinct)| FunctionExpr::Count { expr, distinct }),
-                    Just(FunctionExpr::CountStar),
-                    (box_expr.clone(), any::<bool>())
-                        .prop_map(|(expr, distinct)| FunctionExpr::Sum { expr, distinct }),
-                    box_expr.clone().prop_map(FunctionExpr::Max),
-                    box_expr.clone().prop_map(FunctionExpr::Min),
-                    (box_expr.clone(), any::<Option<String>>()).prop_map(|(expr, separator)| {
-                        FunctionExpr::GroupConcat { expr, separator }
-                    }),
+                    function_expr.clone(),
                     (
-                        box_expr.clone(),
-                        option::of(box_expr.clone()),
-                        option::of(box_expr.clone())
+                        function_expr.prop_map(Box::new),
+                        proptest::collection::vec(element.clone(), 0..4)
                     )
-                        .prop_map(|(string, pos, len)| {
-                            FunctionExpr::Substring { string, pos, len }
-                        }),
-                    (
-                        any::<SqlIdentifier>(),
-                        proptest::collection::vec(element.clone(), 0..24)
-                    )
-                        .prop_map(|(name, arguments)| FunctionExpr::Call { name, arguments })
+                        .prop_map(|(function, partition_by)| {
+                            FunctionExpr::Over {
+                                function,
+                                partition_by,
+                                order_by: None,
+                            }
+                        })
                 ]
                 .prop_map(Expr::Call),
                 (box_expr.clone(), any::<BinaryOperator>(), box_expr.clone(),)
@@ -817,6 +1100,21 @@ fn binary_operator_no_and_or(i: LocatedSpan<&[u8]>) -> NomSqlResult<&[u8], Binar
 
             Ok((i, BinaryOperator::NotLike))
         },
+        map(
+            terminated(
+                alt((tag_no_case("regexp"), tag_no_case("rlike"))),
+                whitespace1,
+            ),
+            |_| BinaryOperator::Regexp,
+        ),
+        move |i| {
+            let (i, _) = tag_no_case("not")(i)?;
+            let (i, _) = whitespace1(i)?;
+            let (i, _) = alt((tag_no_case("regexp"), tag_no_case("rlike")))(i)?;
+            let (i, _) = whitespace1(i)?;
+
+            Ok((i, BinaryOperator::NotRegexp))
+        },
         move |i| {
             let (i, _) = tag_no_case("is")(i)?;
             let (i, _) = whitespace1(i)?;
@@ -830,6 +1128,12 @@ fn binary_operator_no_and_or(i: LocatedSpan<&[u8]>) -> NomSqlResult<&[u8], Binar
         //
         // NOTE: The order here matters or else some of these will be incorrectly partially parsed,
         // such as `?` after `?|`.
+        alt((
+            map(tag("!~*"), |_| BinaryOperator::NotIRegex),
+            map(tag("!~"), |_| BinaryOperator::NotRegex),
+            map(tag("~*"), |_| BinaryOperator::IRegex),
+            map(char('~'), |_| BinaryOperator::Regex),
+        )),
         alt((
             map(tag("@>"), |_| BinaryOperator::AtArrowRight),
             map(tag("<@"), |_| BinaryOperator::AtArrowLeft),
@@ -1059,6 +1363,8 @@ where
             Infix(NotLike) => Affix::Infix(Precedence(7), Associativity::Right),
             Infix(ILike) => Affix::Infix(Precedence(7), Associativity::Right),
             Infix(NotILike) => Affix::Infix(Precedence(7), Associativity::Right),
+            Infix(Regexp) => Affix::Infix(Precedence(7), Associativity::Right),
+            Infix(NotRegexp) => Affix::Infix(Precedence(7), Associativity::Right),
             Infix(Equal) => Affix::Infix(Precedence(7), Associativity::Right),
             Infix(NotEqual) => Affix::Infix(Precedence(7), Associativity::Right),
             Infix(Greater) => Affix::Infix(Precedence(7), Associativity::Right),
@@ -1094,6 +1400,13 @@ where
             Infix(AtArrowRight) => Affix::Infix(Precedence(8), Associativity::Left),
             Infix(AtArrowLeft) => Affix::Infix(Precedence(8), Associativity::Left),
             Infix(HashSubtract) => Affix::Infix(Precedence(8), Associativity::Left),
+
+            // PostgreSQL gives the regular expression match operators the same precedence as all
+            // other non-builtin operators, which we approximate with the JSON operators' precedence
+            Infix(Regex) => Affix::Infix(Precedence(8), Associativity::Left),
+            Infix(NotRegex) => Affix::Infix(Precedence(8), Associativity::Left),
+            Infix(IRegex) => Affix::Infix(Precedence(8), Associativity::Left),
+            Infix(NotIRegex) => Affix::Infix(Precedence(8), Associativity::Left),
         })
     }
 
@@ -1360,6 +1673,42 @@ fn cast(dialect: Dialect) -> impl Fn(LocatedSpan<&[u8]>) -> NomSqlResult<&[u8],
     }
 }
 
+fn interval_expr(dialect: Dialect) -> impl Fn(LocatedSpan<&[u8]>) -> NomSqlResult<&[u8], Expr> {
+    move |i| {
+        let (i, _) = tag_no_case("interval")(i)?;
+        let (i, _) = whitespace1(i)?;
+        let (i, negated) = opt(terminated(char('-'), whitespace0))(i)?;
+        let (i, value) = simple_expr(dialect)(i)?;
+        let (i, unit) = opt(preceded(whitespace1, interval_unit))(i)?;
+
+        // MySQL always requires a unit, but PostgreSQL allows omitting it to parse the value as a
+        // string like '1 day 2 hours'
+        if unit.is_none() && dialect == Dialect::MySQL {
+            return Err(nom::Err::Error(ParseError::from_error_kind(
+                i,
+                ErrorKind::Verify,
+            )));
+        }
+
+        let value = if negated.is_some() {
+            Expr::UnaryOp {
+                op: UnaryOperator::Neg,
+                rhs: Box::new(value),
+            }
+        } else {
+            value
+        };
+
+        Ok((
+            i,
+            Expr::Interval {
+                value: Box::new(value),
+                unit,
+            },
+        ))
+    }
+}
+
 fn nested_select(dialect: Dialect) -> impl Fn(LocatedSpan<&[u8]>) -> NomSqlResult<&[u8], Expr> {
     move |i| {
         let (i, _) = char('(')(i)?;
@@ -1441,6 +1790,7 @@ pub(crate) fn simple_expr(
             map(literal(dialect), Expr::Literal),
             case_when_expr(dialect),
             array_expr(dialect),
+            interval_expr(dialect),
             map(column_identifier_no_alias(dialect), Expr::Column),
             cast(dialect),
             map(scoped_var(dialect), Expr::Variable),
@@ -2235,6 +2585,56 @@ mod tests {
     mod mysql {
         use super::*;
 
+        #[test]
+        fn interval() {
+            let res = test_parse!(expression(Dialect::MySQL), b"created_at - INTERVAL 1 DAY");
+            assert_eq!(
+                res,
+                Expr::BinaryOp {
+                    lhs: Box::new(Expr::Column("created_at".into())),
+                    op: BinaryOperator::Subtract,
+                    rhs: Box::new(Expr::Interval {
+                        value: Box::new(Expr::Literal(1_u32.into())),
+                        unit: Some(IntervalUnit::Day),
+                    }),
+                }
+            );
+            assert_eq!(
+                res.display(Dialect::MySQL).to_string(),
+                "(`created_at` - INTERVAL 1 DAY)"
+            );
+
+            let res = test_parse!(
+                expression(Dialect::MySQL),
+                b"date_add(x, interval '1:30' hour_minute)"
+            );
+            assert_eq!(
+                res,
+                Expr::Call(FunctionExpr::Call {
+                    name: "date_add".into(),
+                    arguments: vec![
+                        Expr::Column("x".into()),
+                        Expr::Interval {
+                            value: Box::new(Expr::Literal("1:30".into())),
+                            unit: Some(IntervalUnit::HourMinute),
+                        }
+                    ],
+                })
+            );
+
+            let res = test_parse!(expression(Dialect::MySQL), b"INTERVAL -n MINUTE");
+            assert_eq!(
+                res,
+                Expr::Interval {
+                    value: Box::new(Expr::UnaryOp {
+                        op: UnaryOperator::Neg,
+                        rhs: Box::new(Expr::Column("n".into())),
+                    }),
+                    unit: Some(IntervalUnit::Minute),
+                }
+            );
+        }
+
         #[test]
         fn column_beginning_with_null() {
             let res = test_parse!(expression(Dialect::MySQL), b"nullable");
@@ -2513,12 +2913,71 @@ mod tests {
                     }
                 );
             }
+
+            #[test]
+            fn regexp() {
+                for (cond, op) in [
+                    ("title REGEXP '^a'", BinaryOperator::Regexp),
+                    ("title rlike '^a'", BinaryOperator::Regexp),
+                    ("title NOT REGEXP '^a'", BinaryOperator::NotRegexp),
+                    ("title not rlike '^a'", BinaryOperator::NotRegexp),
+                ] {
+                    let res = test_parse!(expression(Dialect::MySQL), cond.as_bytes());
+                    assert_eq!(
+                        res,
+                        Expr::BinaryOp {
+                            lhs: Box::new(Expr::Column("title".into())),
+                            op,
+                            rhs: Box::new(Expr::Literal("^a".into())),
+                        }
+                    );
+                }
+            }
         }
     }
 
     mod postgres {
         use super::*;
 
+        #[test]
+        fn interval() {
+            let res = test_parse!(
+                expression(Dialect::PostgreSQL),
+                b"created_at > now() - interval '1 day 2 hours'"
+            );
+            assert_eq!(
+                res,
+                Expr::BinaryOp {
+                    lhs: Box::new(Expr::Column("created_at".into())),
+                    op: BinaryOperator::Greater,
+                    rhs: Box::new(Expr::BinaryOp {
+                        lhs: Box::new(Expr::Call(FunctionExpr::Call {
+                            name: "now".into(),
+                            arguments: vec![],
+                        })),
+                        op: BinaryOperator::Subtract,
+                        rhs: Box::new(Expr::Interval {
+                            value: Box::new(Expr::Literal("1 day 2 hours".into())),
+                            unit: None,
+                        }),
+                    }),
+                }
+            );
+            assert_eq!(
+                res.display(Dialect::PostgreSQL).to_string(),
+                "(\"created_at\" > (now() - INTERVAL '1 day 2 hours'))"
+            );
+
+            let res = test_parse!(expression(Dialect::PostgreSQL), b"interval '2' hour");
+            assert_eq!(
+                res,
+                Expr::Interval {
+                    value: Box::new(Expr::Literal("2".into())),
+                    unit: Some(IntervalUnit::Hour),
+                }
+            );
+        }
+
         #[test]
         fn column_beginning_with_null() {
             let res = test_parse!(expression(Dialect::PostgreSQL), b"nullable");
@@ -2767,6 +3226,16 @@ mod tests {
                     "('[1, 2, 3]'::json ->> 0) like ('[3, 2, 1]'::json ->> 2)",
                 )
             }
+
+            /// `~` vs `=` and `||`.
+            #[test]
+            fn regex_match() {
+                parses_same(
+                    Dialect::PostgreSQL,
+                    "a || b ~ c = true and d !~* e",
+                    "(((a || b) ~ c) = true) and (d !~* e)",
+                )
+            }
         }
 
         mod conditions {
@@ -3009,6 +3478,31 @@ mod tests {
                     }
                 );
             }
+
+            #[test]
+            fn regex_match_operators() {
+                for (op_str, op) in [
+                    ("~", BinaryOperator::Regex),
+                    ("!~", BinaryOperator::NotRegex),
+                    ("~*", BinaryOperator::IRegex),
+                    ("!~*", BinaryOperator::NotIRegex),
+                ] {
+                    let cond = format!("title {op_str} '^a.*b$'");
+                    let res = test_parse!(expression(Dialect::PostgreSQL), cond.as_bytes());
+                    assert_eq!(
+                        res,
+                        Expr::BinaryOp {
+                            lhs: Box::new(Expr::Column("title".into())),
+                            op,
+                            rhs: Box::new(Expr::Literal("^a.*b$".into())),
+                        }
+                    );
+                    assert_eq!(
+                        res.display(Dialect::PostgreSQL).to_string(),
+                        format!("(\"title\" {op_str} '^a.*b$')")
+                    );
+                }
+            }
         }
 
         mod json {
@@ -0,0 +1,280 @@
+use std::{fmt, str};
+
+use nom::branch::alt;
+use nom::bytes::complete::take_while1;
+use nom::character::complete::char;
+use nom::combinator::{map_opt, map_res};
+use nom::sequence::delimited;
+use nom_locate::LocatedSpan;
+use serde::{Deserialize, Serialize};
+use test_strategy::Arbitrary;
+
+use crate::dialect::is_sql_identifier;
+use crate::NomSqlResult;
+
+/// The unit of an `INTERVAL` expression, such as the `DAY` in `INTERVAL 1 DAY`
+///
+/// The compound units (eg `DAY_HOUR`) are only supported in MySQL, where their values are strings
+/// containing each of the parts of the interval, such as `INTERVAL '1 12' DAY_HOUR`.
+#[derive(
+    Clone, Copy, Debug, Eq, Hash, PartialEq, PartialOrd, Serialize, Deserialize, Arbitrary,
+)]
+pub enum IntervalUnit {
+    Microsecond,
+    Second,
+    Minute,
+    Hour,
+    Day,
+    Week,
+    Month,
+    Quarter,
+    Year,
+    SecondMicrosecond,
+    MinuteMicrosecond,
+    MinuteSecond,
+    HourMicrosecond,
+    HourSecond,
+    HourMinute,
+    DayMicrosecond,
+    DaySecond,
+    DayMinute,
+    DayHour,
+    YearMonth,
+}
+
+impl IntervalUnit {
+    fn from_name(name: &str) -> Option<Self> {
+        use IntervalUnit::*;
+        Some(match name.to_ascii_uppercase().as_str() {
+            "MICROSECOND" => Microsecond,
+            "SECOND" => Second,
+            "MINUTE" => Minute,
+            "HOUR" => Hour,
+            "DAY" => Day,
+            "WEEK" => Week,
+            "MONTH" => Month,
+            "QUARTER" => Quarter,
+            "YEAR" => Year,
+            "SECOND_MICROSECOND" => SecondMicrosecond,
+            "MINUTE_MICROSECOND" => MinuteMicrosecond,
+            "MINUTE_SECOND" => MinuteSecond,
+            "HOUR_MICROSECOND" => HourMicrosecond,
+            "HOUR_SECOND" => HourSecond,
+            "HOUR_MINUTE" => HourMinute,
+            "DAY_MICROSECOND" => DayMicrosecond,
+            "DAY_SECOND" => DaySecond,
+            "DAY_MINUTE" => DayMinute,
+            "DAY_HOUR" => DayHour,
+            "YEAR_MONTH" => YearMonth,
+            _ => return None,
+        })
+    }
+}
+
+impl fmt::Display for IntervalUnit {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use IntervalUnit::*;
+        f.write_str(match self {
+            Microsecond => "MICROSECOND",
+            Second => "SECOND",
+            Minute => "MINUTE",
+            Hour => "HOUR",
+            Day => "DAY",
+            Week => "WEEK",
+            Month => "MONTH",
+            Quarter => "QUARTER",
+            Year => "YEAR",
+            SecondMicrosecond => "SECOND_MICROSECOND",
+            MinuteMicrosecond => "MINUTE_MICROSECOND",
+            MinuteSecond => "MINUTE_SECOND",
+            HourMicrosecond => "HOUR_MICROSECOND",
+            HourSecond => "HOUR_SECOND",
+            HourMinute => "HOUR_MINUTE",
+            DayMicrosecond => "DAY_MICROSECOND",
+            DaySecond => "DAY_SECOND",
+            DayMinute => "DAY_MINUTE",
+            DayHour => "DAY_HOUR",
+            YearMonth => "YEAR_MONTH",
+        })
+    }
+}
+
+/// The field to extract from a date or time value in an `EXTRACT(field FROM expr)` expression.
+///
+/// This covers the fields supported by both MySQL and PostgreSQL, which are accepted in either
+/// dialect; the PostgreSQL plural spellings (eg `HOURS`) are parsed as their singular variants.
+/// Some fields have different semantics between the two dialects - for example, `SECOND` includes
+/// fractional seconds in PostgreSQL but not MySQL.
+#[derive(
+    Clone, Copy, Debug, Eq, Hash, PartialEq, PartialOrd, Serialize, Deserialize, Arbitrary,
+)]
+pub enum TimestampField {
+    Century,
+    Decade,
+    Millennium,
+    Year,
+    Isoyear,
+    Quarter,
+    Month,
+    Week,
+    Day,
+    Dow,
+    Isodow,
+    Doy,
+    Hour,
+    Minute,
+    Second,
+    Millisecond,
+    Microsecond,
+    Epoch,
+    YearMonth,
+    DayHour,
+    DayMinute,
+    DaySecond,
+    DayMicrosecond,
+    HourMinute,
+    HourSecond,
+    HourMicrosecond,
+    MinuteSecond,
+    MinuteMicrosecond,
+    SecondMicrosecond,
+}
+
+impl TimestampField {
+    /// Look up a field by its (case-insensitive) name, as written in an `EXTRACT` expression or
+    /// passed as the first argument to PostgreSQL's `date_part`
+    pub fn from_name(name: &str) -> Option<Self> {
+        use TimestampField::*;
+        Some(match name.to_ascii_uppercase().as_str() {
+            "CENTURY" | "CENTURIES" => Century,
+            "DECADE" | "DECADES" => Decade,
+            "MILLENNIUM" | "MILLENNIA" | "MILLENNIUMS" => Millennium,
+            "YEAR" | "YEARS" => Year,
+            "ISOYEAR" => Isoyear,
+            "QUARTER" => Quarter,
+            "MONTH" | "MONTHS" => Month,
+            "WEEK" | "WEEKS" => Week,
+            "DAY" | "DAYS" => Day,
+            "DOW" => Dow,
+            "ISODOW" => Isodow,
+            "DOY" => Doy,
+            "HOUR" | "HOURS" => Hour,
+            "MINUTE" | "MINUTES" => Minute,
+            "SECOND" | "SECONDS" => Second,
+            "MILLISECOND" | "MILLISECONDS" => Millisecond,
+            "MICROSECOND" | "MICROSECONDS" => Microsecond,
+            "EPOCH" => Epoch,
+            "YEAR_MONTH" => YearMonth,
+            "DAY_HOUR" => DayHour,
+            "DAY_MINUTE" => DayMinute,
+            "DAY_SECOND" => DaySecond,
+            "DAY_MICROSECOND" => DayMicrosecond,
+            "HOUR_MINUTE" => HourMinute,
+            "HOUR_SECOND" => HourSecond,
+            "HOUR_MICROSECOND" => HourMicrosecond,
+            "MINUTE_SECOND" => MinuteSecond,
+            "MINUTE_MICROSECOND" => MinuteMicrosecond,
+            "SECOND_MICROSECOND" => SecondMicrosecond,
+            _ => return None,
+        })
+    }
+}
+
+impl fmt::Display for TimestampField {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use TimestampField::*;
+        f.write_str(match self {
+            Century => "CENTURY",
+            Decade => "DECADE",
+            Millennium => "MILLENNIUM",
+            Year => "YEAR",
+            Isoyear => "ISOYEAR",
+            Quarter => "QUARTER",
+            Month => "MONTH",
+            Week => "WEEK",
+            Day => "DAY",
+            Dow => "DOW",
+            Isodow => "ISODOW",
+            Doy => "DOY",
+            Hour => "HOUR",
+            Minute => "MINUTE",
+            Second => "SECOND",
+            Millisecond => "MILLISECOND",
+            Microsecond => "MICROSECOND",
+            Epoch => "EPOCH",
+            YearMonth => "YEAR_MONTH",
+            DayHour => "DAY_HOUR",
+            DayMinute => "DAY_MINUTE",
+            DaySecond => "DAY_SECOND",
+            DayMicrosecond => "DAY_MICROSECOND",
+            HourMinute => "HOUR_MINUTE",
+            HourSecond => "HOUR_SECOND",
+            HourMicrosecond => "HOUR_MICROSECOND",
+            MinuteSecond => "MINUTE_SECOND",
+            MinuteMicrosecond => "MINUTE_MICROSECOND",
+            SecondMicrosecond => "SECOND_MICROSECOND",
+        })
+    }
+}
+
+fn identifier_str(i: LocatedSpan<&[u8]>) -> NomSqlResult<&[u8], &str> {
+    map_res(take_while1(is_sql_identifier), |s: LocatedSpan<&[u8]>| {
+        str::from_utf8(&s)
+    })(i)
+}
+
+/// Parse the unit of an `INTERVAL` expression
+pub(crate) fn interval_unit(i: LocatedSpan<&[u8]>) -> NomSqlResult<&[u8], IntervalUnit> {
+    map_opt(identifier_str, IntervalUnit::from_name)(i)
+}
+
+/// Parse the field of an `EXTRACT` expression, which PostgreSQL also allows to be written as a
+/// string literal
+pub(crate) fn timestamp_field(i: LocatedSpan<&[u8]>) -> NomSqlResult<&[u8], TimestampField> {
+    map_opt(
+        alt((
+            identifier_str,
+            delimited(char('\''), identifier_str, char('\'')),
+        )),
+        TimestampField::from_name,
+    )(i)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_interval_unit() {
+        assert_eq!(test_parse!(interval_unit, b"day"), IntervalUnit::Day);
+        assert_eq!(
+            test_parse!(interval_unit, b"DAY_MICROSECOND"),
+            IntervalUnit::DayMicrosecond
+        );
+        assert!(interval_unit(LocatedSpan::new(b"days")).is_err());
+    }
+
+    #[test]
+    fn parse_timestamp_field() {
+        assert_eq!(test_parse!(timestamp_field, b"Year"), TimestampField::Year);
+        assert_eq!(
+            test_parse!(timestamp_field, b"'microseconds'"),
+            TimestampField::Microsecond
+        );
+        assert_eq!(
+            test_parse!(timestamp_field, b"hour_minute"),
+            TimestampField::HourMinute
+        );
+        assert!(timestamp_field(LocatedSpan::new(b"fortnight")).is_err());
+    }
+
+    #[test]
+    fn display_round_trips() {
+        for unit in [IntervalUnit::Second, IntervalUnit::YearMonth] {
+            assert_eq!(IntervalUnit::from_name(&unit.to_string()), Some(unit));
+        }
+        for field in [TimestampField::Epoch, TimestampField::MinuteMicrosecond] {
+            assert_eq!(TimestampField::from_name(&field.to_string()), Some(field));
+        }
+    }
+}
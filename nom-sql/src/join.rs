@@ -49,6 +49,12 @@ pub enum JoinOperator {
     LeftOuterJoin,
     #[weight(0)]
     RightJoin,
+    #[weight(0)]
+    RightOuterJoin,
+    #[weight(0)]
+    FullJoin,
+    #[weight(0)]
+    FullOuterJoin,
     InnerJoin,
     #[weight(0)]
     CrossJoin,
@@ -58,7 +64,10 @@ pub enum JoinOperator {
 
 impl JoinOperator {
     pub fn is_inner_join(&self) -> bool {
-        matches!(self, JoinOperator::Join | JoinOperator::InnerJoin)
+        matches!(
+            self,
+            JoinOperator::Join | JoinOperator::InnerJoin | JoinOperator::CrossJoin
+        )
     }
 }
 
@@ -69,6 +78,9 @@ impl fmt::Display for JoinOperator {
             JoinOperator::LeftJoin => write!(f, "LEFT JOIN")?,
             JoinOperator::LeftOuterJoin => write!(f, "LEFT OUTER JOIN")?,
             JoinOperator::RightJoin => write!(f, "RIGHT JOIN")?,
+            JoinOperator::RightOuterJoin => write!(f, "RIGHT OUTER JOIN")?,
+            JoinOperator::FullJoin => write!(f, "FULL JOIN")?,
+            JoinOperator::FullOuterJoin => write!(f, "FULL OUTER JOIN")?,
             JoinOperator::InnerJoin => write!(f, "INNER JOIN")?,
             JoinOperator::CrossJoin => write!(f, "CROSS JOIN")?,
             JoinOperator::StraightJoin => write!(f, "STRAIGHT JOIN")?,
@@ -107,6 +119,13 @@ pub fn join_operator(i: LocatedSpan<&[u8]>) -> NomSqlResult<&[u8], JoinOperator>
             JoinOperator::LeftOuterJoin
         }),
         map(tag_no_case("right join"), |_| JoinOperator::RightJoin),
+        map(tag_no_case("right outer join"), |_| {
+            JoinOperator::RightOuterJoin
+        }),
+        map(tag_no_case("full join"), |_| JoinOperator::FullJoin),
+        map(tag_no_case("full outer join"), |_| {
+            JoinOperator::FullOuterJoin
+        }),
         map(tag_no_case("inner join"), |_| JoinOperator::InnerJoin),
         map(tag_no_case("cross join"), |_| JoinOperator::CrossJoin),
         map(tag_no_case("straight_join"), |_| JoinOperator::StraightJoin),
@@ -152,6 +171,38 @@ mod tests {
             assert_eq!(q, expected_stmt);
             assert_eq!(expected, q.display(Dialect::MySQL).to_string());
         }
+
+        #[test]
+        fn outer_joins() {
+            for (qstring, operator) in [
+                (
+                    "SELECT * FROM a RIGHT JOIN b ON (a.x = b.x)",
+                    JoinOperator::RightJoin,
+                ),
+                (
+                    "SELECT * FROM a RIGHT OUTER JOIN b ON (a.x = b.x)",
+                    JoinOperator::RightOuterJoin,
+                ),
+                (
+                    "SELECT * FROM a FULL JOIN b ON (a.x = b.x)",
+                    JoinOperator::FullJoin,
+                ),
+                (
+                    "SELECT * FROM a full outer join b ON (a.x = b.x)",
+                    JoinOperator::FullOuterJoin,
+                ),
+            ] {
+                let res = selection(Dialect::MySQL)(LocatedSpan::new(qstring.as_bytes()));
+                let q = res.unwrap().1;
+                assert_eq!(q.tables, vec![TableExpr::from(Relation::from("a"))]);
+                assert_eq!(q.join.len(), 1);
+                assert_eq!(q.join[0].operator, operator);
+                assert_eq!(
+                    q.display(Dialect::MySQL).to_string(),
+                    format!("SELECT * FROM `a` {} `b` ON (`a`.`x` = `b`.`x`)", operator)
+                );
+            }
+        }
     }
 
     mod postgres {
@@ -34,6 +34,7 @@ pub use self::expression::{
     BinaryOperator, CaseWhenBranch, Expr, FunctionExpr, InValue, UnaryOperator,
 };
 pub use self::insert::InsertStatement;
+pub use self::interval::{IntervalUnit, TimestampField};
 pub use self::join::{JoinConstraint, JoinOperator, JoinRightSide};
 pub use self::literal::{
     embedded_literal, literal, raw_string_literal, utf8_string_literal, Double, Float,
@@ -41,7 +42,9 @@ pub use self::literal::{
 };
 pub use self::order::{OrderClause, OrderType};
 pub use self::parser::*;
-pub use self::select::{CommonTableExpr, GroupByClause, JoinClause, LimitClause, SelectStatement};
+pub use self::select::{
+    CommonTableExpr, GroupByClause, JoinClause, LimitClause, RecursiveTerm, SelectStatement,
+};
 pub use self::set::{
     PostgresParameterScope, PostgresParameterValue, PostgresParameterValueInner, SetNames,
     SetPostgresParameter, SetPostgresParameterValue, SetStatement, SetVariables, Variable,
@@ -74,6 +77,7 @@ mod drop;
 mod explain;
 mod expression;
 mod insert;
+mod interval;
 mod join;
 mod keywords;
 mod literal;
@@ -14,7 +14,7 @@ use test_strategy::Arbitrary;
 
 use crate::common::{field_reference, ws_sep_comma};
 use crate::whitespace::{whitespace0, whitespace1};
-use crate::{Dialect, FieldReference, NomSqlResult};
+use crate::{Dialect, Expr, FieldReference, NomSqlResult};
 
 #[derive(
     Clone, Copy, Debug, Eq, Hash, PartialEq, PartialOrd, Serialize, Deserialize, Arbitrary,
@@ -51,6 +51,26 @@ pub struct OrderClause {
 }
 
 impl OrderClause {
+    /// Returns an iterator over all the expressions in this `ORDER BY` clause, skipping numeric
+    /// field references
+    pub fn order_by_exprs(&self) -> impl Iterator<Item = &Expr> {
+        self.order_by.iter().filter_map(|(field, _)| match field {
+            FieldReference::Expr(expr) => Some(expr),
+            FieldReference::Numeric(_) => None,
+        })
+    }
+
+    /// Returns an iterator over mutable references to all the expressions in this `ORDER BY`
+    /// clause, skipping numeric field references
+    pub fn order_by_exprs_mut(&mut self) -> impl Iterator<Item = &mut Expr> {
+        self.order_by
+            .iter_mut()
+            .filter_map(|(field, _)| match field {
+                FieldReference::Expr(expr) => Some(expr),
+                FieldReference::Numeric(_) => None,
+            })
+    }
+
     pub fn display(&self, dialect: Dialect) -> impl fmt::Display + Copy + '_ {
         fmt_with(move |f| {
             write!(
@@ -110,7 +130,6 @@ pub fn order_clause(
 mod tests {
     use super::*;
     use crate::select::selection;
-    use crate::Expr;
 
     #[test]
     fn order_clause() {
@@ -70,7 +70,11 @@ impl JoinClause {
 #[derive(Clone, Debug, Default, Eq, Hash, PartialEq, PartialOrd, Serialize, Deserialize)]
 pub struct CommonTableExpr {
     pub name: SqlIdentifier,
+    /// The statement defining the CTE. For a recursive CTE, this is the non-recursive (base) term
     pub statement: SelectStatement,
+    /// The recursive term of a CTE defined with `WITH RECURSIVE`, which is `UNION`ed with
+    /// `statement` and may refer to the CTE itself
+    pub recursive_term: Option<RecursiveTerm>,
 }
 
 impl CommonTableExpr {
@@ -78,10 +82,41 @@ impl CommonTableExpr {
         fmt_with(move |f| {
             write!(
                 f,
-                "{} AS ({})",
+                "{} AS ({}",
                 dialect.quote_identifier(&self.name),
                 self.statement.display(dialect)
-            )
+            )?;
+            if let Some(recursive_term) = &self.recursive_term {
+                write!(f, " {}", recursive_term.display(dialect))?;
+            }
+            write!(f, ")")
+        })
+    }
+}
+
+/// The recursive term of a common table expression defined with `WITH RECURSIVE`, eg the
+/// `UNION ALL SELECT ...` in:
+///
+/// ```sql
+/// WITH RECURSIVE r AS (SELECT 1 AS n UNION ALL SELECT n + 1 FROM r WHERE n < 10)
+/// SELECT n FROM r
+/// ```
+#[derive(Clone, Debug, Eq, Hash, PartialEq, PartialOrd, Serialize, Deserialize)]
+pub struct RecursiveTerm {
+    /// Was the term combined with the base term using `UNION ALL` (rather than `UNION` or `UNION
+    /// DISTINCT`)?
+    pub all: bool,
+    pub statement: SelectStatement,
+}
+
+impl RecursiveTerm {
+    pub fn display(&self, dialect: Dialect) -> impl fmt::Display + Copy + '_ {
+        fmt_with(move |f| {
+            write!(f, "UNION ")?;
+            if self.all {
+                write!(f, "ALL ")?;
+            }
+            write!(f, "{}", self.statement.display(dialect))
         })
     }
 }
@@ -201,6 +236,16 @@ impl SelectStatement {
                         | FunctionExpr::Max(_)
                         | FunctionExpr::Min(_)
                         | FunctionExpr::GroupConcat { .. }
+                        | FunctionExpr::Stddev { .. }
+                        | FunctionExpr::Variance { .. }
+                        | FunctionExpr::BoolAnd(_)
+                        | FunctionExpr::BoolOr(_)
+                        | FunctionExpr::BitAnd(_)
+                        | FunctionExpr::BitOr(_)
+                        | FunctionExpr::ArrayAgg { .. }
+                        | FunctionExpr::StringAgg { .. }
+                        | FunctionExpr::JsonAgg { .. }
+                        | FunctionExpr::JsonObjectAgg { .. }
                 ),
                 Expr::NestedSelect(select) => select.contains_aggregate_select(),
                 _ => false,
@@ -214,9 +259,13 @@ impl SelectStatement {
     pub fn display(&self, dialect: Dialect) -> impl fmt::Display + Copy + '_ {
         fmt_with(move |f| {
             if !self.ctes.is_empty() {
+                write!(f, "WITH ")?;
+                if self.ctes.iter().any(|cte| cte.recursive_term.is_some()) {
+                    write!(f, "RECURSIVE ")?;
+                }
                 write!(
                     f,
-                    "WITH {} ",
+                    "{} ",
                     self.ctes.iter().map(|cte| cte.display(dialect)).join(", ")
                 )?;
             }
@@ -535,7 +584,35 @@ fn from_clause(dialect: Dialect) -> impl Fn(LocatedSpan<&[u8]>) -> NomSqlResult<
     }
 }
 
-fn cte(dialect: Dialect) -> impl Fn(LocatedSpan<&[u8]>) -> NomSqlResult<&[u8], CommonTableExpr> {
+fn recursive_term(
+    dialect: Dialect,
+) -> impl Fn(LocatedSpan<&[u8]>) -> NomSqlResult<&[u8], RecursiveTerm> {
+    move |i| {
+        let (i, _) = tag_no_case("union")(i)?;
+        let (i, all) = opt(preceded(
+            whitespace1,
+            alt((
+                map(tag_no_case("all"), |_| true),
+                map(tag_no_case("distinct"), |_| false),
+            )),
+        ))(i)?;
+        let (i, _) = whitespace1(i)?;
+        let (i, statement) = nested_selection(dialect)(i)?;
+
+        Ok((
+            i,
+            RecursiveTerm {
+                all: all.unwrap_or(false),
+                statement,
+            },
+        ))
+    }
+}
+
+fn cte(
+    dialect: Dialect,
+    recursive: bool,
+) -> impl Fn(LocatedSpan<&[u8]>) -> NomSqlResult<&[u8], CommonTableExpr> {
     move |i| {
         let (i, name) = dialect.identifier()(i)?;
         let (i, _) = whitespace1(i)?;
@@ -546,9 +623,21 @@ fn cte(dialect: Dialect) -> impl Fn(LocatedSpan<&[u8]>) -> NomSqlResult<&[u8], C
         let (i, _) = whitespace0(i)?;
         let (i, statement) = nested_selection(dialect)(i)?;
         let (i, _) = whitespace0(i)?;
+        let (i, recursive_term) = if recursive {
+            opt(terminated(recursive_term(dialect), whitespace0))(i)?
+        } else {
+            (i, None)
+        };
         let (i, _) = tag(")")(i)?;
 
-        Ok((i, CommonTableExpr { name, statement }))
+        Ok((
+            i,
+            CommonTableExpr {
+                name,
+                statement,
+                recursive_term,
+            },
+        ))
     }
 }
 
@@ -558,7 +647,8 @@ fn ctes(
     move |i| {
         let (i, _) = tag_no_case("with")(i)?;
         let (i, _) = whitespace1(i)?;
-        let (i, ctes) = separated_list1(ws_sep_comma, cte(dialect))(i)?;
+        let (i, recursive) = opt(terminated(tag_no_case("recursive"), whitespace1))(i)?;
+        let (i, ctes) = separated_list1(ws_sep_comma, cte(dialect, recursive.is_some()))(i)?;
         let (i, _) = whitespace0(i)?;
 
         Ok((i, ctes))
@@ -1778,6 +1868,50 @@ mod tests {
         assert_eq!(query.ctes[1].name, "min_val");
     }
 
+    #[test]
+    fn recursive_cte() {
+        let qstr = b"WITH RECURSIVE reports AS (
+              SELECT id, manager_id FROM employees WHERE manager_id = 1
+              UNION ALL
+              SELECT e.id, e.manager_id FROM employees e JOIN reports ON e.manager_id = reports.id
+            )
+            SELECT id FROM reports";
+        let res = selection(Dialect::MySQL)(LocatedSpan::new(qstr));
+        assert!(res.is_ok(), "error parsing query: {}", res.err().unwrap());
+        let (rem, query) = res.unwrap();
+        assert!(rem.is_empty());
+        assert_eq!(query.ctes.len(), 1);
+        let cte = query.ctes.first().unwrap();
+        assert_eq!(cte.name, "reports");
+        assert_eq!(
+            cte.statement.tables,
+            vec![TableExpr::from(Relation::from("employees"))]
+        );
+        let recursive_term = cte.recursive_term.as_ref().unwrap();
+        assert!(recursive_term.all);
+        assert_eq!(recursive_term.statement.join.len(), 1);
+    }
+
+    #[test]
+    fn recursive_cte_union_distinct() {
+        let qstr = b"WITH RECURSIVE r AS (SELECT 1 AS n UNION SELECT n + 1 FROM r WHERE n < 5), \
+            s AS (SELECT n FROM r) SELECT n FROM s";
+        let res = selection(Dialect::MySQL)(LocatedSpan::new(qstr));
+        assert!(res.is_ok(), "error parsing query: {}", res.err().unwrap());
+        let (rem, query) = res.unwrap();
+        assert!(rem.is_empty());
+        assert_eq!(query.ctes.len(), 2);
+        assert!(!query.ctes[0].recursive_term.as_ref().unwrap().all);
+        assert!(query.ctes[1].recursive_term.is_none());
+    }
+
+    #[test]
+    fn union_in_non_recursive_cte() {
+        let qstr = b"WITH r AS (SELECT 1 AS n UNION ALL SELECT n + 1 FROM r) SELECT n FROM r";
+        let res = selection(Dialect::MySQL)(LocatedSpan::new(qstr));
+        assert!(res.is_err());
+    }
+
     mod mysql {
         use super::*;
         use crate::column::Column;
@@ -1922,6 +2056,7 @@ mod tests {
                         tables: vec![TableExpr::from(Relation::from("t"))],
                         ..Default::default()
                     },
+                    recursive_term: None,
                 }],
                 fields: vec![FieldDefinitionExpr::Expr {
                     expr: Expr::Column("x".into()),
@@ -1937,6 +2072,19 @@ mod tests {
             );
         }
 
+        #[test]
+        fn format_recursive_cte() {
+            let qstr =
+                "WITH RECURSIVE r AS (SELECT 1 AS n UNION ALL SELECT n + 1 FROM r WHERE n < 5) \
+                        SELECT n FROM r";
+            let res = test_parse!(selection(Dialect::MySQL), qstr.as_bytes());
+            assert_eq!(
+                res.display(Dialect::MySQL).to_string(),
+                "WITH RECURSIVE `r` AS (SELECT 1 AS `n` UNION ALL SELECT (`n` + 1) FROM `r` WHERE \
+                 (`n` < 5)) SELECT `n` FROM `r`"
+            );
+        }
+
         #[test]
         fn display_query_with_limit_and_offset() {
             let query = SelectStatement {
@@ -2096,6 +2244,7 @@ mod tests {
                         tables: vec![TableExpr::from(Relation::from("t"))],
                         ..Default::default()
                     },
+                    recursive_term: None,
                 }],
                 fields: vec![FieldDefinitionExpr::Expr {
                     expr: Expr::Column("x".into()),
@@ -2111,6 +2260,82 @@ mod tests {
             );
         }
 
+        #[test]
+        fn cross_join_lateral_subquery() {
+            let res = test_parse!(
+                selection(Dialect::PostgreSQL),
+                b"SELECT t.id, s.x FROM t CROSS JOIN LATERAL \
+                  (SELECT u.x FROM u WHERE u.t_id = t.id LIMIT 3) AS s"
+            );
+            let join = res.join.first().unwrap();
+            assert_eq!(join.operator, JoinOperator::CrossJoin);
+            assert_eq!(join.constraint, JoinConstraint::Empty);
+            match &join.right {
+                JoinRightSide::Table(TableExpr {
+                    inner: TableExprInner::LateralSubquery(sq),
+                    alias,
+                }) => {
+                    assert_eq!(alias.as_deref(), Some("s"));
+                    assert_eq!(sq.tables, vec![TableExpr::from(Relation::from("u"))]);
+                }
+                right => panic!("Expected a lateral subquery, got {right:?}"),
+            }
+        }
+
+        #[test]
+        fn set_returning_functions_in_from() {
+            let res = test_parse!(
+                selection(Dialect::PostgreSQL),
+                b"SELECT e.value, tag FROM t, jsonb_array_elements(t.data) AS e \
+                  CROSS JOIN LATERAL unnest(t.tags) AS tag"
+            );
+            assert_eq!(
+                res.tables,
+                vec![
+                    TableExpr::from(Relation::from("t")),
+                    TableExpr {
+                        inner: TableExprInner::Function(FunctionExpr::Call {
+                            name: "jsonb_array_elements".into(),
+                            arguments: vec![Expr::Column("t.data".into())],
+                        }),
+                        alias: Some("e".into()),
+                    }
+                ]
+            );
+            assert_eq!(
+                res.join.first().unwrap().right,
+                JoinRightSide::Table(TableExpr {
+                    inner: TableExprInner::Function(FunctionExpr::Call {
+                        name: "unnest".into(),
+                        arguments: vec![Expr::Column("t.tags".into())],
+                    }),
+                    alias: Some("tag".into()),
+                })
+            );
+            assert_eq!(
+                res.tables[1].function_column().map(|c| c.as_str()),
+                Some("value")
+            );
+        }
+
+        #[test]
+        fn format_lateral_and_functions() {
+            let query = "SELECT * FROM t CROSS JOIN LATERAL (SELECT x FROM u WHERE u.y = t.y) \
+                         AS s CROSS JOIN unnest(t.tags) AS tag";
+            let res = test_parse!(selection(Dialect::PostgreSQL), query.as_bytes());
+            let formatted = res.display(Dialect::PostgreSQL).to_string();
+            assert!(
+                formatted.contains("CROSS JOIN LATERAL (SELECT"),
+                "{formatted}"
+            );
+            assert!(
+                formatted.contains("CROSS JOIN unnest(\"t\".\"tags\") AS \"tag\""),
+                "{formatted}"
+            );
+            let reparsed = test_parse!(selection(Dialect::PostgreSQL), formatted.as_bytes());
+            assert_eq!(reparsed, res);
+        }
+
         #[test]
         fn bare_having() {
             let res = test_parse!(
@@ -6,7 +6,7 @@ use nom::branch::alt;
 use nom::bytes::complete::{tag, tag_no_case};
 use nom::combinator::{map, opt};
 use nom::multi::separated_list1;
-use nom::sequence::{terminated, tuple};
+use nom::sequence::{preceded, terminated, tuple};
 use nom::Parser;
 use nom_locate::LocatedSpan;
 use readyset_util::fmt::fmt_with;
@@ -418,9 +418,23 @@ fn set_names(dialect: Dialect) -> impl Fn(LocatedSpan<&[u8]>) -> NomSqlResult<&[
     }
 }
 
+/// Parse the name of a postgres parameter, which may be a custom parameter qualified with a class
+/// name, such as `app.tenant`
+fn postgres_parameter_name(i: LocatedSpan<&[u8]>) -> NomSqlResult<&[u8], SqlIdentifier> {
+    let (i, class) = Dialect::PostgreSQL.identifier()(i)?;
+    let (i, name) = opt(preceded(tag("."), Dialect::PostgreSQL.identifier()))(i)?;
+    Ok((
+        i,
+        match name {
+            Some(name) => format!("{class}.{name}").into(),
+            None => class,
+        },
+    ))
+}
+
 fn set_postgres_parameter(i: LocatedSpan<&[u8]>) -> NomSqlResult<&[u8], SetPostgresParameter> {
     let (i, scope) = opt(terminated(postgres_parameter_scope, whitespace1))(i)?;
-    let (i, name) = Dialect::PostgreSQL.identifier()(i)?;
+    let (i, name) = postgres_parameter_name(i)?;
     let (i, _) = whitespace0(i)?;
     let (i, _) = alt((terminated(tag_no_case("to"), whitespace1), tag("=")))(i)?;
     let (i, _) = whitespace0(i)?;
@@ -660,6 +674,24 @@ mod tests {
             );
         }
 
+        #[test]
+        fn set_custom_parameter() {
+            let res = test_parse!(set(Dialect::PostgreSQL), b"SET app.tenant = 'acme'");
+            let roundtripped = res.display(Dialect::PostgreSQL).to_string();
+            assert_eq!(roundtripped, "SET app.tenant = 'acme'");
+
+            assert_eq!(
+                res,
+                SetStatement::PostgresParameter(SetPostgresParameter {
+                    scope: None,
+                    name: "app.tenant".into(),
+                    value: SetPostgresParameterValue::Value(PostgresParameterValue::Single(
+                        PostgresParameterValueInner::Literal("acme".into())
+                    ))
+                })
+            );
+        }
+
         #[test]
         fn set_names() {
             let res = test_parse!(set(Dialect::PostgreSQL), b"SET NAMES 'UTF8'");
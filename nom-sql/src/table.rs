@@ -2,20 +2,26 @@ use std::fmt::Display;
 use std::hash::Hash;
 use std::str;
 
+use lazy_static::lazy_static;
 use nom::branch::alt;
-use nom::bytes::complete::tag;
+use nom::bytes::complete::{tag, tag_no_case};
 use nom::combinator::{map, opt};
 use nom::multi::separated_list1;
-use nom::sequence::terminated;
+use nom::sequence::{preceded, terminated};
 use nom_locate::LocatedSpan;
 use readyset_util::fmt::fmt_with;
 use serde::{Deserialize, Serialize};
 use test_strategy::Arbitrary;
 
-use crate::common::{as_alias, ws_sep_comma};
+use crate::common::{as_alias, function_call, ws_sep_comma};
 use crate::select::nested_selection;
-use crate::whitespace::whitespace0;
-use crate::{Dialect, NomSqlResult, SelectStatement, SqlIdentifier};
+use crate::whitespace::{whitespace0, whitespace1};
+use crate::{Dialect, FunctionExpr, NomSqlResult, SelectStatement, SqlIdentifier};
+
+lazy_static! {
+    /// The name of the column produced by the `json[b]_array_elements[_text]` functions
+    static ref JSON_ELEMENTS_COLUMN: SqlIdentifier = "value".into();
+}
 
 /// A (potentially schema-qualified) name for a relation
 ///
@@ -100,6 +106,12 @@ impl Relation {
 pub enum TableExprInner {
     Table(Relation),
     Subquery(Box<SelectStatement>),
+    /// A `LATERAL` subquery, which can reference columns from tables that appear before it in the
+    /// `FROM` clause
+    LateralSubquery(Box<SelectStatement>),
+    /// A call to a set-returning function such as `unnest` or `jsonb_array_elements`, which
+    /// produces one row for each element of its argument
+    Function(FunctionExpr),
 }
 
 impl TableExprInner {
@@ -123,6 +135,8 @@ impl TableExprInner {
         fmt_with(move |f| match self {
             TableExprInner::Table(t) => write!(f, "{}", t.display(dialect)),
             TableExprInner::Subquery(sq) => write!(f, "({})", sq.display(dialect)),
+            TableExprInner::LateralSubquery(sq) => write!(f, "LATERAL ({})", sq.display(dialect)),
+            TableExprInner::Function(func) => write!(f, "{}", func.display(dialect)),
         })
     }
 }
@@ -145,6 +159,29 @@ impl From<Relation> for TableExpr {
 }
 
 impl TableExpr {
+    /// If this table expression is a call to a set-returning function, returns the name of the
+    /// single column produced by that function.
+    ///
+    /// The `json[b]_array_elements[_text]` functions produce a column named `value`, and all other
+    /// functions (such as `unnest`) produce a column named after the alias of the table expression.
+    pub fn function_column(&self) -> Option<&SqlIdentifier> {
+        match &self.inner {
+            TableExprInner::Function(FunctionExpr::Call { name, .. })
+                if matches!(
+                    name.to_lowercase().as_str(),
+                    "json_array_elements"
+                        | "jsonb_array_elements"
+                        | "json_array_elements_text"
+                        | "jsonb_array_elements_text"
+                ) =>
+            {
+                Some(&*JSON_ELEMENTS_COLUMN)
+            }
+            TableExprInner::Function(_) => self.alias.as_ref(),
+            _ => None,
+        }
+    }
+
     pub fn display(&self, dialect: Dialect) -> impl Display + Copy + '_ {
         fmt_with(move |f| {
             write!(f, "{}", self.inner.display(dialect))?;
@@ -193,6 +230,21 @@ fn table_expr_inner(
 ) -> impl Fn(LocatedSpan<&[u8]>) -> NomSqlResult<&[u8], TableExprInner> {
     move |i| {
         alt((
+            map(
+                preceded(
+                    terminated(tag_no_case("lateral"), whitespace0),
+                    subquery(dialect),
+                ),
+                |sq| TableExprInner::LateralSubquery(Box::new(sq)),
+            ),
+            // `LATERAL` is optional (and has no effect) for function calls
+            map(
+                preceded(
+                    opt(terminated(tag_no_case("lateral"), whitespace1)),
+                    function_call(dialect),
+                ),
+                TableExprInner::Function,
+            ),
             map(relation(dialect), TableExprInner::Table),
             map(subquery(dialect), |sq| {
                 TableExprInner::Subquery(Box::new(sq))
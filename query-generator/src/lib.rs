@@ -2404,6 +2404,7 @@ impl Subquery {
                 query.ctes.push(CommonTableExpr {
                     name: subquery_name.clone(),
                     statement: subquery,
+                    recursive_term: None,
                 });
                 (
                     JoinRightSide::Table(TableExpr::from(Relation {
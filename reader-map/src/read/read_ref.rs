@@ -316,6 +316,18 @@ where
     }
 }
 
+impl<'rg, K, V> DoubleEndedIterator for RangeIter<'rg, K, V>
+where
+    K: Ord + Clone,
+    V: Eq + Hash,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let next = self.iter.next_back()?;
+        self.eviction_strategy.on_read(next.1.eviction_meta());
+        Some(next)
+    }
+}
+
 /// An [`Iterator`] over value sets.
 pub struct ValuesIter<'rg, K, V>
 where
@@ -0,0 +1,292 @@
+//! Automatic creation and removal of caches, based on how often and how slowly queries run against
+//! the upstream database, and on how recently their caches have been read.
+//!
+//! This is a middle ground between the `async` and `explicit` query caching modes: queries are
+//! still dry-run migrated as in `explicit` mode, but the [`MigrationHandler`] creates caches for
+//! the supported queries that an [`AutoCachePolicy`] considers worth caching without waiting for a
+//! `CREATE CACHE` statement, and drops those caches again once they go unread for long enough.
+//!
+//! Query usage is measured from the same [`QueryExecutionEvent`]s that the query logger records
+//! in its histograms, so only queries that those events carry a query id for are considered.
+//!
+//! [`MigrationHandler`]: crate::migration_handler::MigrationHandler
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use readyset_client::query::QueryId;
+use readyset_client_metrics::QueryExecutionEvent;
+
+/// Thresholds controlling which queries have caches created and dropped automatically
+#[derive(Debug, Clone)]
+pub struct AutoCachePolicy {
+    /// The length of the window over which upstream executions of each query are counted
+    pub window: Duration,
+    /// The minimum number of times a query must be executed against the upstream database within
+    /// a single window for a cache to be created for it
+    pub min_upstream_executions: u64,
+    /// The minimum mean latency of a query's upstream executions within a window for a cache to be
+    /// created for it
+    pub min_upstream_latency: Duration,
+    /// How long an automatically created cache can go without being read before it is dropped
+    pub idle_timeout: Duration,
+    /// The maximum total number of caches, including those created with `CREATE CACHE`. No caches
+    /// are created automatically once this many exist
+    pub max_caches: usize,
+}
+
+#[derive(Debug, Default)]
+struct QueryUsage {
+    /// The number of times the query was executed against the upstream database during the
+    /// current window
+    upstream_executions: u64,
+    /// The total time spent executing the query against the upstream database during the current
+    /// window
+    upstream_time: Duration,
+    /// The last time the query was executed against ReadySet
+    last_readyset_execution: Option<Instant>,
+}
+
+/// Usage statistics for queries, shared between the query logger, which records them, and the
+/// [`AutoCacher`], which acts on them
+#[derive(Debug, Clone, Default)]
+pub struct QueryUsageTracker {
+    usage: Arc<Mutex<HashMap<QueryId, QueryUsage>>>,
+}
+
+impl QueryUsageTracker {
+    /// Create a new, empty [`QueryUsageTracker`]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record the execution of a query described by the given event
+    pub fn record(&self, event: &QueryExecutionEvent) {
+        let Some(id) = event.query_id else {
+            return;
+        };
+        let mut usage = self.usage.lock().unwrap_or_else(|e| e.into_inner());
+        let usage = usage.entry(id).or_default();
+        if let Some(duration) = event.upstream_duration {
+            usage.upstream_executions += 1;
+            usage.upstream_time += duration;
+        }
+        if event.readyset_duration.is_some() {
+            usage.last_readyset_execution = Some(Instant::now());
+        }
+    }
+}
+
+/// The caches to create and drop at the end of a window, as decided by [`AutoCacher::evaluate`]
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct AutoCacheDecision {
+    /// Queries to create caches for, most expensive upstream first
+    pub create: Vec<QueryId>,
+    /// Automatically created caches which have gone unread for longer than the idle timeout
+    pub drop: Vec<QueryId>,
+}
+
+/// Decides which caches to create and drop according to an [`AutoCachePolicy`], and keeps track
+/// of the caches created automatically so that only those are ever dropped automatically.
+///
+/// Automatically created caches are only tracked in memory, so caches created by a previous run of
+/// the adapter are not dropped automatically.
+#[derive(Debug)]
+pub struct AutoCacher {
+    policy: AutoCachePolicy,
+    usage: QueryUsageTracker,
+    /// The queries with automatically created caches, and the time each cache was created
+    caches: HashMap<QueryId, Instant>,
+    /// The time the current window started
+    window_start: Instant,
+}
+
+impl AutoCacher {
+    /// Create a new [`AutoCacher`] acting on the usage recorded in the given tracker
+    pub fn new(policy: AutoCachePolicy, usage: QueryUsageTracker) -> Self {
+        Self {
+            policy,
+            usage,
+            caches: HashMap::new(),
+            window_start: Instant::now(),
+        }
+    }
+
+    /// Returns true if the current window has ended, and [`Self::evaluate`] should be called
+    pub fn is_due(&self, now: Instant) -> bool {
+        now.duration_since(self.window_start) >= self.policy.window
+    }
+
+    /// End the current window, and decide which caches to create and drop based on the usage
+    /// recorded during it.
+    ///
+    /// `num_caches` is the total number of caches that currently exist, and `is_cacheable` should
+    /// return true for queries which are supported by ReadySet but not yet cached.
+    pub fn evaluate<F>(
+        &mut self,
+        now: Instant,
+        num_caches: usize,
+        is_cacheable: F,
+    ) -> AutoCacheDecision
+    where
+        F: Fn(&QueryId) -> bool,
+    {
+        self.window_start = now;
+        let mut usage = self.usage.usage.lock().unwrap_or_else(|e| e.into_inner());
+
+        let drop = self
+            .caches
+            .iter()
+            .filter(|(id, created)| {
+                let last_read = usage
+                    .get(id)
+                    .and_then(|u| u.last_readyset_execution)
+                    .map_or(**created, |t| t.max(**created));
+                now.duration_since(last_read) >= self.policy.idle_timeout
+            })
+            .map(|(id, _)| *id)
+            .collect::<Vec<_>>();
+
+        let mut candidates = usage
+            .iter()
+            .filter(|(id, u)| {
+                u.upstream_executions > 0
+                    && u.upstream_executions >= self.policy.min_upstream_executions
+                    && u.upstream_time.as_secs_f64() / u.upstream_executions as f64
+                        >= self.policy.min_upstream_latency.as_secs_f64()
+                    && !self.caches.contains_key(id)
+                    && is_cacheable(id)
+            })
+            .map(|(id, u)| (*id, u.upstream_time))
+            .collect::<Vec<_>>();
+        candidates.sort_by(|(_, t1), (_, t2)| t2.cmp(t1));
+        let budget = self
+            .policy
+            .max_caches
+            .saturating_sub(num_caches.saturating_sub(drop.len()));
+        let create = candidates
+            .into_iter()
+            .take(budget)
+            .map(|(id, _)| id)
+            .collect();
+
+        // Start counting upstream executions afresh for the next window, only holding on to the
+        // read times of the caches we might later need to drop
+        usage.retain(|id, _| self.caches.contains_key(id));
+        for u in usage.values_mut() {
+            u.upstream_executions = 0;
+            u.upstream_time = Duration::ZERO;
+        }
+
+        AutoCacheDecision { create, drop }
+    }
+
+    /// Record that a cache was successfully created for the given query
+    pub fn created(&mut self, id: QueryId, now: Instant) {
+        self.caches.insert(id, now);
+    }
+
+    /// Stop tracking the cache for the given query, either because it was dropped or because it
+    /// no longer exists
+    pub fn forget(&mut self, id: &QueryId) {
+        self.caches.remove(id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use readyset_client_metrics::EventType;
+
+    use super::*;
+
+    fn policy() -> AutoCachePolicy {
+        AutoCachePolicy {
+            window: Duration::from_secs(60),
+            min_upstream_executions: 2,
+            min_upstream_latency: Duration::from_millis(10),
+            idle_timeout: Duration::from_secs(600),
+            max_caches: 2,
+        }
+    }
+
+    fn execute(
+        usage: &QueryUsageTracker,
+        id: u64,
+        upstream_duration: Option<Duration>,
+        readyset_duration: Option<Duration>,
+    ) {
+        usage.record(&QueryExecutionEvent {
+            query_id: Some(QueryId::new(id)),
+            upstream_duration,
+            readyset_duration,
+            ..QueryExecutionEvent::new(EventType::Execute)
+        });
+    }
+
+    #[test]
+    fn creates_caches_for_frequent_slow_queries() {
+        let usage = QueryUsageTracker::new();
+        let mut auto_cacher = AutoCacher::new(policy(), usage.clone());
+        let now = Instant::now();
+
+        for _ in 0..3 {
+            // Frequent and slow
+            execute(&usage, 1, Some(Duration::from_millis(20)), None);
+            // Frequent and slower, but unsupported
+            execute(&usage, 2, Some(Duration::from_millis(50)), None);
+            // Frequent but fast
+            execute(&usage, 3, Some(Duration::from_millis(1)), None);
+        }
+        // Slow but infrequent
+        execute(&usage, 4, Some(Duration::from_secs(1)), None);
+
+        assert_eq!(
+            auto_cacher.evaluate(now, 0, |id| *id != QueryId::new(2)),
+            AutoCacheDecision {
+                create: vec![QueryId::new(1)],
+                drop: vec![]
+            }
+        );
+
+        // Executions are only counted within a single window
+        execute(&usage, 1, Some(Duration::from_millis(20)), None);
+        assert_eq!(
+            auto_cacher.evaluate(now, 0, |_| true),
+            AutoCacheDecision::default()
+        );
+    }
+
+    #[test]
+    fn respects_max_caches() {
+        let usage = QueryUsageTracker::new();
+        let mut auto_cacher = AutoCacher::new(policy(), usage.clone());
+
+        for _ in 0..2 {
+            execute(&usage, 1, Some(Duration::from_millis(10)), None);
+            execute(&usage, 2, Some(Duration::from_millis(30)), None);
+            execute(&usage, 3, Some(Duration::from_millis(20)), None);
+        }
+
+        let decision = auto_cacher.evaluate(Instant::now(), 1, |_| true);
+        assert_eq!(decision.create, vec![QueryId::new(2)]);
+    }
+
+    #[test]
+    fn drops_idle_caches() {
+        let usage = QueryUsageTracker::new();
+        let mut auto_cacher = AutoCacher::new(policy(), usage.clone());
+        let start = Instant::now();
+        auto_cacher.created(QueryId::new(1), start);
+        auto_cacher.created(QueryId::new(2), start);
+
+        std::thread::sleep(Duration::from_millis(10));
+        execute(&usage, 2, None, Some(Duration::from_millis(1)));
+
+        let decision = auto_cacher.evaluate(
+            start + Duration::from_secs(600) + Duration::from_millis(5),
+            2,
+            |_| true,
+        );
+        assert_eq!(decision.drop, vec![QueryId::new(1)]);
+    }
+}
@@ -70,7 +70,7 @@
 
 use std::borrow::Cow;
 use std::collections::hash_map::Entry;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::convert::{TryFrom, TryInto};
 use std::fmt::{self, Debug};
 use std::marker::PhantomData;
@@ -85,6 +85,7 @@ use nom_sql::{
     SqlQuery, UpdateStatement, UseStatement,
 };
 use readyset_client::consistency::Timestamp;
+use readyset_client::internal::LocalNodeIndex;
 use readyset_client::query::*;
 use readyset_client::results::Results;
 use readyset_client::{ColumnSchema, PlaceholderIdx, ViewCreateRequest};
@@ -105,7 +106,9 @@ use crate::backend::noria_connector::ExecuteSelectContext;
 use crate::query_handler::SetBehavior;
 use crate::query_status_cache::QueryStatusCache;
 pub use crate::upstream_database::UpstreamPrepare;
-use crate::{rewrite, QueryHandler, UpstreamDatabase, UpstreamDestination};
+use crate::{
+    rewrite, session_parameters, utils, QueryHandler, UpstreamDatabase, UpstreamDestination,
+};
 
 pub mod noria_connector;
 
@@ -319,6 +322,8 @@ impl BackendBuilder {
                 query_status_cache,
                 ticket: self.ticket,
                 timestamp_client: self.timestamp_client,
+                ryw_tables: HashMap::new(),
+                ryw_pending_writes: HashSet::new(),
             },
             settings: BackendSettings {
                 slowlog: self.slowlog,
@@ -438,6 +443,9 @@ where
     /// If statement was successfully rewritten, will store all information necessary to install
     /// the view in readyset
     view_request: Option<ViewCreateRequest>,
+    /// Whether executing the statement might change the values of session parameters in a way we
+    /// can't determine, in which case executing it invalidates them
+    may_change_session_values: bool,
 }
 
 impl<DB> CachedPreparedStatement<DB>
@@ -526,6 +534,82 @@ where
     /// is responsible for creating accurate RYW timestamps/tickets based on writes made by the
     /// Backend client.
     timestamp_client: Option<TimestampClient>,
+    /// The base tables this session has written to while RYW is enabled, by name. Used to restrict
+    /// the `ticket` to the tables a read actually depends on, since readers only have timestamps
+    /// for their own ancestors.
+    ryw_tables: HashMap<SqlIdentifier, LocalNodeIndex>,
+    /// Tables written to by this session whose writes haven't been added to the `ticket` yet,
+    /// because the position of the write in the upstream's replication log wasn't known when it
+    /// was made. These are added to the `ticket` before the next read from ReadySet.
+    ryw_pending_writes: HashSet<LocalNodeIndex>,
+}
+
+impl<DB> BackendState<DB>
+where
+    DB: UpstreamDatabase,
+{
+    /// Record a write to the table named `table`, whose base node is `index`, for read-your-write
+    /// consistency. If `write_id` is `None`, the position of the write will be fetched from the
+    /// upstream database the next time this session reads from ReadySet.
+    ///
+    /// Does nothing if RYW is not enabled.
+    fn record_ryw_write(
+        &mut self,
+        table: SqlIdentifier,
+        index: LocalNodeIndex,
+        write_id: Option<WriteId>,
+    ) -> ReadySetResult<()> {
+        if self.timestamp_client.is_none() {
+            return Ok(());
+        }
+        self.ryw_tables.insert(table, index);
+        match write_id {
+            Some(write_id) => self.append_ryw_write(write_id, vec![WriteKey::TableIndex(index)]),
+            None => {
+                self.ryw_pending_writes.insert(index);
+                Ok(())
+            }
+        }
+    }
+
+    /// Add a write identified by `write_id` to `keys` to the current RYW ticket
+    fn append_ryw_write(&mut self, write_id: WriteId, keys: Vec<WriteKey>) -> ReadySetResult<()> {
+        // TODO(andrew, justin): solidify error handling in client
+        // https://app.clubhouse.io/readysettech/story/366
+        let (Some(timestamp_client), Some(current_ticket)) = (&self.timestamp_client, &self.ticket)
+        else {
+            internal!("RYW enabled backends must have a current ticket")
+        };
+        let new_timestamp = timestamp_client
+            .append_write(write_id, keys)
+            .map_err(|e| internal_err!("{e}"))?;
+        self.ticket = Some(Timestamp::join(current_ticket, &new_timestamp));
+        Ok(())
+    }
+}
+
+/// Returns the RYW ticket to pass along with a read of `query` from ReadySet, restricted to the
+/// tables `query` reads from, or `None` if `query` doesn't read from any table in
+/// `written_tables`.
+///
+/// Readers only ever receive timestamps for their own ancestor tables, so passing the full ticket
+/// would cause reads of queries over unrelated tables to never be considered consistent.
+fn ryw_ticket_for(
+    ticket: Option<&Timestamp>,
+    written_tables: &HashMap<SqlIdentifier, LocalNodeIndex>,
+    query: &SelectStatement,
+) -> Option<Timestamp> {
+    let ticket = ticket?;
+    let map: HashMap<_, _> = utils::referred_table_names(query)
+        .into_iter()
+        .filter_map(|name| written_tables.get(name))
+        .filter_map(|index| Some((*index, *ticket.map.get(index)?)))
+        .collect();
+    if map.is_empty() {
+        None
+    } else {
+        Some(Timestamp { map })
+    }
 }
 
 /// Settings that have no state and are constant for a given [`Backend`]
@@ -1141,6 +1225,11 @@ where
         }
         query_event.query_id = id;
 
+        let may_change_session_values = match &parsed_query {
+            Some(parsed) => session_parameters::may_change_session_values(parsed),
+            None => session_parameters::unparsed_may_change_session_values(query),
+        };
+
         let cache_entry = CachedPreparedStatement {
             query_id: id,
             prep: res,
@@ -1149,6 +1238,7 @@ where
             parsed_query,
             view_request,
             always,
+            may_change_session_values,
         };
 
         self.state.prepared_statements.push(cache_entry);
@@ -1238,6 +1328,14 @@ where
         ticket: Option<Timestamp>,
         event: &mut QueryExecutionEvent,
     ) -> Result<QueryResult<'a, DB>, DB::Error> {
+        if upstream.is_some()
+            && matches!(noria_prep, noria_connector::PrepareResult::Select(_))
+            && !noria.within_staleness_budget().await
+        {
+            metrics::increment_counter!(recorded::STALENESS_BUDGET_EXCEEDED);
+            return Self::execute_upstream(upstream, upstream_prep, params, event, false).await;
+        }
+
         let noria_res = Self::execute_noria(noria, noria_prep, params, ticket, event).await;
         match noria_res {
             Ok(noria_ok) => {
@@ -1259,7 +1357,10 @@ where
                 }
                 if !matches!(
                     noria_err,
-                    ReadySetError::ReaderMissingKey | ReadySetError::NoCacheForQuery
+                    ReadySetError::ReaderMissingKey
+                        | ReadySetError::NoCacheForQuery
+                        | ReadySetError::PageOutOfBounds { .. }
+                        | ReadySetError::UnknownSessionValue { .. }
                 ) {
                     warn!(error = %noria_err,
                           "Error received from noria, sending query to fallback");
@@ -1362,11 +1463,62 @@ where
             .for_each(|ps| ps.make_upstream_only());
     }
 
+    /// Add the writes in `ryw_pending_writes` to the RYW ticket, at the upstream database's current
+    /// write position.
+    ///
+    /// This is skipped while statements are being proxied upstream, such as within an explicit
+    /// transaction, since the pending writes may not have been committed yet.
+    async fn flush_pending_ryw_writes(
+        upstream: Option<&mut DB>,
+        state: &mut BackendState<DB>,
+    ) -> Result<(), DB::Error> {
+        if state.ryw_pending_writes.is_empty() || state.proxy_state.should_proxy() {
+            return Ok(());
+        }
+        let Some(upstream) = upstream else {
+            return Ok(());
+        };
+        let write_id = upstream.write_position().await?;
+        let keys = state
+            .ryw_pending_writes
+            .drain()
+            .map(WriteKey::TableIndex)
+            .collect();
+        state.append_ryw_write(write_id, keys)?;
+        Ok(())
+    }
+
+    /// If RYW is enabled and the prepared statement identified by `id` writes to a table, record
+    /// that write. The position of a prepared write isn't known until it's been committed, so it's
+    /// fetched from upstream before the next read from ReadySet.
+    async fn record_prepared_ryw_write(&mut self, id: u32) -> ReadySetResult<()> {
+        if self.state.timestamp_client.is_none() || self.upstream.is_none() {
+            return Ok(());
+        }
+        let table = match self
+            .state
+            .prepared_statements
+            .get(id as usize)
+            .and_then(|stmt| stmt.parsed_query.as_deref())
+        {
+            Some(
+                SqlQuery::Insert(InsertStatement { table, .. })
+                | SqlQuery::Update(UpdateStatement { table, .. })
+                | SqlQuery::Delete(DeleteStatement { table, .. }),
+            ) => table.name.clone(),
+            _ => return Ok(()),
+        };
+        // Writes to tables ReadySet doesn't know about can't be read from any cache
+        let Ok(index) = self.noria.node_index_of(table.as_str()).await else {
+            return Ok(());
+        };
+        self.state.record_ryw_write(table, index, None)
+    }
+
     /// Executes a prepared statement identified by `id` with parameters specified by the client
     /// `params`.
     /// A [`QueryExecutionEvent`], is used to track metrics and behavior scoped to the
     /// execute operation.
-    // TODO(andrew, justin): add RYW support for executing prepared queries
     #[instrument(skip_all)]
     #[inline]
     pub async fn execute(
@@ -1375,6 +1527,8 @@ where
         params: &[DfValue],
     ) -> Result<QueryResult<'_, DB>, DB::Error> {
         self.last_query = None;
+        Self::flush_pending_ryw_writes(self.upstream.as_mut(), &mut self.state).await?;
+        self.record_prepared_ryw_write(id).await?;
         let cached_statement = self
             .state
             .prepared_statements
@@ -1387,7 +1541,15 @@ where
 
         let upstream = &mut self.upstream;
         let noria = &mut self.noria;
-        let ticket = self.state.ticket.clone();
+        if cached_statement.may_change_session_values {
+            noria.invalidate_session_values();
+        }
+        let ticket = match cached_statement.parsed_query.as_deref() {
+            Some(SqlQuery::Select(stmt)) => {
+                ryw_ticket_for(self.state.ticket.as_ref(), &self.state.ryw_tables, stmt)
+            }
+            _ => None,
+        };
 
         // If the query is pending, check the query status cache to see if it is now successful.
         //
@@ -1604,6 +1766,87 @@ where
         ]))
     }
 
+    /// Resolves the query referenced by a `CREATE CACHE` (or `EXPLAIN CREATE CACHE`) statement to
+    /// the statement to cache, along with the schema search path to use for it if it was
+    /// referenced by query id
+    fn resolve_cache_inner(
+        &self,
+        inner: &Result<CacheInner, String>,
+    ) -> ReadySetResult<(SelectStatement, Option<Vec<SqlIdentifier>>)> {
+        match inner {
+            Ok(CacheInner::Statement(st)) => Ok((*st.clone(), None)),
+            Ok(CacheInner::Id(id)) => match self.state.query_status_cache.query(id.as_str()) {
+                Some(Query::Parsed(view_request)) => Ok((
+                    view_request.statement.clone(),
+                    Some(view_request.schema_search_path.clone()),
+                )),
+                Some(Query::ParseFailed(q)) => Err(ReadySetError::UnparseableQuery {
+                    query: (*q).clone(),
+                }),
+                None => Err(ReadySetError::NoQueryForId { id: id.to_string() }),
+            },
+            Err(query) => Err(ReadySetError::UnparseableQuery {
+                query: query.clone(),
+            }),
+        }
+    }
+
+    /// Forwards an `EXPLAIN CREATE CACHE` request to noria, reporting whether the query is
+    /// supported and, if so, the dataflow that caching it would create
+    #[instrument(skip(self))]
+    async fn explain_create_cache(
+        &mut self,
+        mut stmt: SelectStatement,
+        override_schema_search_path: Option<Vec<SqlIdentifier>>,
+    ) -> ReadySetResult<noria_connector::QueryResult<'static>> {
+        let res = match rewrite::process_query(&mut stmt, self.noria.server_supports_pagination()) {
+            Ok(_) => {
+                self.noria
+                    .explain_create_cache(&stmt, override_schema_search_path)
+                    .await
+            }
+            Err(e) => Err(e),
+        };
+
+        let explanation = match res {
+            Ok(explanation) => explanation,
+            Err(e) if let Some(reason) = e.unsupported_cause() => {
+                return Ok(noria_connector::QueryResult::Meta(vec![
+                    ("Supported", "no").into(),
+                    ("Unsupported_reason", reason).into(),
+                ]))
+            }
+            Err(e) => return Err(e),
+        };
+
+        let join = |items: Vec<String>, sep: &str| {
+            if items.is_empty() {
+                "none".to_string()
+            } else {
+                items.join(sep)
+            }
+        };
+        let tables = explanation
+            .tables
+            .iter()
+            .map(|t| t.display_unquoted().to_string())
+            .collect();
+        let materialization = if explanation.partial {
+            "partial"
+        } else {
+            "full"
+        };
+
+        Ok(noria_connector::QueryResult::Meta(vec![
+            ("Supported", "yes").into(),
+            ("New_nodes", join(explanation.new_nodes, "; ")).into(),
+            ("Reused_nodes", join(explanation.reused_nodes, "; ")).into(),
+            ("Key_columns", join(explanation.key_columns, ", ")).into(),
+            ("Materialization", materialization).into(),
+            ("Upstream_tables", join(tables, ", ")).into(),
+        ]))
+    }
+
     /// Forwards a `CREATE CACHE` request to noria
     #[instrument(skip(self))]
     async fn create_cached_query(
@@ -1778,38 +2021,21 @@ where
             SqlQuery::Explain(nom_sql::ExplainStatement::Domains) => {
                 self.noria.explain_domains().await
             }
+            SqlQuery::Explain(nom_sql::ExplainStatement::CreateCache { inner }) => {
+                let (stmt, search_path) = match self.resolve_cache_inner(inner) {
+                    Ok(resolved) => resolved,
+                    Err(e) => return Some(Err(e)),
+                };
+                self.explain_create_cache(stmt, search_path).await
+            }
             SqlQuery::CreateCache(CreateCacheStatement {
                 name,
                 inner,
                 always,
             }) => {
-                let (stmt, search_path) = match inner {
-                    Ok(CacheInner::Statement(st)) => (*st.clone(), None),
-                    Ok(CacheInner::Id(id)) => {
-                        match self.state.query_status_cache.query(id.as_str()) {
-                            Some(q) => match q {
-                                Query::Parsed(view_request) => (
-                                    view_request.statement.clone(),
-                                    Some(view_request.schema_search_path.clone()),
-                                ),
-                                Query::ParseFailed(q) => {
-                                    return Some(Err(ReadySetError::UnparseableQuery {
-                                        query: (*q).clone(),
-                                    }))
-                                }
-                            },
-                            None => {
-                                return Some(Err(ReadySetError::NoQueryForId {
-                                    id: id.to_string(),
-                                }))
-                            }
-                        }
-                    }
-                    Err(query) => {
-                        return Some(Err(ReadySetError::UnparseableQuery {
-                            query: query.clone(),
-                        }))
-                    }
+                let (stmt, search_path) = match self.resolve_cache_inner(inner) {
+                    Ok(resolved) => resolved,
+                    Err(e) => return Some(Err(e)),
                 };
 
                 // Log a telemetry event
@@ -1868,7 +2094,7 @@ where
     #[allow(clippy::too_many_arguments)]
     async fn query_adhoc_select<'a>(
         noria: &'a mut NoriaConnector,
-        upstream: Option<&'a mut DB>,
+        mut upstream: Option<&'a mut DB>,
         settings: &BackendSettings,
         state: &mut BackendState<DB>,
         original_query: &'a str,
@@ -1920,6 +2146,14 @@ where
             return Self::query_fallback(upstream, original_query, event).await;
         }
 
+        if upstream_exists && !noria.within_staleness_budget().await {
+            metrics::increment_counter!(recorded::STALENESS_BUDGET_EXCEEDED);
+            return Self::query_fallback(upstream, original_query, event).await;
+        }
+
+        Self::flush_pending_ryw_writes(upstream.as_deref_mut(), state).await?;
+        let ticket = ryw_ticket_for(state.ticket.as_ref(), &state.ryw_tables, &original_stmt);
+
         let noria_res = {
             event.destination = Some(QueryDestination::Readyset);
             let start = Instant::now();
@@ -1927,7 +2161,7 @@ where
                 statement: original_stmt,
                 create_if_missing: settings.migration_mode == MigrationMode::InRequestPath,
             };
-            let res = noria.execute_select(ctx, state.ticket.clone(), event).await;
+            let res = noria.execute_select(ctx, ticket, event).await;
             event.readyset_duration = Some(start.elapsed());
             res
         };
@@ -2091,6 +2325,30 @@ where
                 trace!(?search_path, "Setting search_path");
                 noria.set_schema_search_path(search_path);
             }
+            // A SET made within a transaction might be rolled back later, so we can't rely on
+            // its value
+            SetBehavior::SetSessionValues(values) if state.proxy_state.should_proxy() => {
+                for (parameter, _) in values {
+                    trace!(
+                        ?parameter,
+                        "Invalidating session value set within a transaction"
+                    );
+                    noria.invalidate_session_value(parameter);
+                }
+            }
+            SetBehavior::SetSessionValues(values) => {
+                for (parameter, value) in values {
+                    trace!(?parameter, ?value, "Setting session value");
+                    match value {
+                        Some(value) => noria.set_session_value(parameter, value),
+                        None => noria.invalidate_session_value(parameter),
+                    }
+                }
+            }
+            SetBehavior::SetMaxStaleness(max_staleness) => {
+                trace!(?max_staleness, "Setting staleness budget");
+                noria.set_max_staleness(max_staleness);
+            }
         }
 
         Ok(())
@@ -2134,35 +2392,29 @@ where
                         event.sql_type = SqlQueryType::Write;
                         let _t = event.start_upstream_timer();
 
-                        // Update ticket if RYW enabled
-                        let query_result = if cfg!(feature = "ryw") {
-                            if let Some(timestamp_service) = &mut state.timestamp_client {
-                                let (query_result, identifier) =
+                        // Update ticket if RYW enabled. Writes to tables ReadySet doesn't know
+                        // about can't be read from any cache, so those are proxied as usual.
+                        let ryw_index = if state.timestamp_client.is_some() {
+                            // TODO(andrew): Move table name to table index conversion to
+                            // timestamp service https://app.clubhouse.io/readysettech/story/331
+                            noria.node_index_of(t.name.as_str()).await.ok()
+                        } else {
+                            None
+                        };
+                        let query_result = match ryw_index {
+                            Some(index) if state.proxy_state.is_fallback() => {
+                                let (query_result, write_id) =
                                     upstream.handle_ryw_write(raw_query).await?;
-
-                                // TODO(andrew): Move table name to table index conversion to
-                                // timestamp service https://app.clubhouse.io/readysettech/story/331
-                                let index = noria.node_index_of(t.name.as_str()).await?;
-                                let affected_tables = vec![WriteKey::TableIndex(index)];
-
-                                let new_timestamp = timestamp_service
-                                    .append_write(WriteId::MySqlGtid(identifier), affected_tables)
-                                    .map_err(|e| internal_err!("{e}"))?;
-
-                                // TODO(andrew, justin): solidify error handling in client
-                                // https://app.clubhouse.io/readysettech/story/366
-                                let current_ticket = state.ticket.as_ref().ok_or_else(|| {
-                                    internal_err!("RYW enabled backends must have a current ticket")
-                                })?;
-
-                                state.ticket =
-                                    Some(Timestamp::join(current_ticket, &new_timestamp));
+                                state.record_ryw_write(t.name.clone(), index, Some(write_id))?;
                                 Ok(query_result)
-                            } else {
+                            }
+                            Some(index) => {
+                                // We're in an explicit transaction (or autocommit is off), so the
+                                // write won't be committed until later
+                                state.record_ryw_write(t.name.clone(), index, None)?;
                                 upstream.query(raw_query).await
                             }
-                        } else {
-                            upstream.query(raw_query).await
+                            None => upstream.query(raw_query).await,
                         };
 
                         query_result.map(QueryResult::Upstream)
@@ -2260,6 +2512,17 @@ where
             self.parse_query(query)
         };
 
+        // Statements which might change session values in ways we can't follow mean we can no
+        // longer rely on the values we know of
+        let may_change_session_values = match &parse_result {
+            Ok(SqlQuery::Set(s)) => Handler::handle_set_statement(s) == SetBehavior::Unsupported,
+            Ok(parsed_query) => session_parameters::may_change_session_values(parsed_query),
+            Err(_) => session_parameters::unparsed_may_change_session_values(query),
+        };
+        if may_change_session_values {
+            self.noria.invalidate_session_values();
+        }
+
         let result = match parse_result {
             // Parse error, but no fallback exists
             Err(e) if !self.has_fallback() => {
@@ -2311,10 +2574,23 @@ where
             Ok(ref parsed_query) if let Some(noria_extension) = self.query_noria_extensions(parsed_query, &mut event).await => {
                 noria_extension.map(Into::into).map_err(Into::into)
             }
+            // The staleness budget is also a ReadySet extension, so setting it is never proxied
+            Ok(SqlQuery::Set(ref s))
+                if let SetBehavior::SetMaxStaleness(max_staleness) =
+                    Handler::handle_set_statement(s) =>
+            {
+                trace!(?max_staleness, "Setting staleness budget");
+                self.noria.set_max_staleness(max_staleness);
+                Ok(noria_connector::QueryResult::Empty.into())
+            }
             // SET autocommit=1 needs to be handled explicitly or it will end up getting proxied in
-            // most cases.
+            // most cases. The same goes for SETs of session values, since we need to know their
+            // values even if they're set while proxying (eg inside a transaction).
             Ok(SqlQuery::Set(s))
-                if Handler::handle_set_statement(&s) == SetBehavior::SetAutocommit(true) =>
+                if matches!(
+                    Handler::handle_set_statement(&s),
+                    SetBehavior::SetAutocommit(true) | SetBehavior::SetSessionValues(_)
+                ) =>
             {
                 Self::query_adhoc_non_select(
                     &mut self.noria,
@@ -3,6 +3,7 @@ use std::collections::{BTreeMap, HashMap, HashSet};
 use std::convert::{TryFrom, TryInto};
 use std::fmt;
 use std::sync::{atomic, Arc};
+use std::time::{Duration, Instant};
 
 use itertools::Itertools;
 use nom_sql::analysis::visit::Visitor;
@@ -13,6 +14,7 @@ use nom_sql::{
 use readyset_client::consistency::Timestamp;
 use readyset_client::internal::LocalNodeIndex;
 use readyset_client::recipe::changelist::{Change, ChangeList, IntoChanges};
+use readyset_client::recipe::CacheExplanation;
 use readyset_client::results::{ResultIterator, Results};
 use readyset_client::{
     ColumnSchema, ReadQuery, ReaderAddress, ReaderHandle, ReadySetHandle, SchemaType, Table,
@@ -32,6 +34,7 @@ use tracing::{error, info, instrument, trace, warn};
 
 use crate::backend::SelectSchema;
 use crate::rewrite::{self, ProcessedQueryParams};
+use crate::session_parameters::{SessionParameter, SessionValues};
 use crate::utils;
 
 type StatementID = u32;
@@ -403,6 +406,18 @@ pub struct NoriaConnector {
     /// supports a multi-element schema search path, the concept of "currently connected database"
     /// in MySQL can be thought of as a schema search path that only has one element.
     schema_search_path: Vec<SqlIdentifier>,
+
+    /// Values of session-scoped parameters (such as user variables) set in this session, which
+    /// are passed to ReadySet as extra parameters for queries that reference them.
+    session_values: SessionValues,
+
+    /// The maximum replication lag that reads in this session may observe, as set with `SET
+    /// readyset_max_staleness`
+    max_staleness: Option<Duration>,
+
+    /// The replication lag as of the last time we asked the controller for it, along with when
+    /// we asked
+    last_replication_lag: Option<(Instant, Option<Duration>)>,
 }
 
 mod request_handler {
@@ -517,6 +532,9 @@ impl NoriaConnector {
             dialect,
             parse_dialect,
             schema_search_path,
+            session_values: SessionValues::default(),
+            max_staleness: None,
+            last_replication_lag: None,
         }
     }
 
@@ -1057,6 +1075,65 @@ impl NoriaConnector {
     pub fn schema_search_path(&self) -> &[SqlIdentifier] {
         self.schema_search_path.as_ref()
     }
+
+    /// Set the value of a session-scoped parameter, to be used by subsequent reads of queries
+    /// which reference it
+    pub fn set_session_value(&mut self, parameter: SessionParameter, value: DfValue) {
+        self.session_values.set(parameter, value);
+    }
+
+    /// Record that a session-scoped parameter may have been changed to a value we don't know, so
+    /// that queries which reference it are proxied until it's set again
+    pub fn invalidate_session_value(&mut self, parameter: SessionParameter) {
+        self.session_values.invalidate(parameter);
+    }
+
+    /// Record that any session-scoped parameter may have been changed to a value we don't know
+    pub fn invalidate_session_values(&mut self) {
+        self.session_values.invalidate_all();
+    }
+
+    async fn replication_lag(&mut self) -> ReadySetResult<Option<Duration>> {
+        noria_await!(
+            self.inner.get_mut()?,
+            self.inner.get_mut()?.noria.replication_lag()
+        )
+    }
+
+    /// Set (or, if `None`, remove) the maximum replication lag that reads in this session may
+    /// observe
+    pub fn set_max_staleness(&mut self, max_staleness: Option<Duration>) {
+        self.max_staleness = max_staleness;
+    }
+
+    /// Returns whether the replication lag is known to be within this session's staleness
+    /// budget, and so reads may be served from ReadySet. If the lag is unknown, or it can't be
+    /// determined, this returns false.
+    pub async fn within_staleness_budget(&mut self) -> bool {
+        let Some(max_staleness) = self.max_staleness else {
+            return true;
+        };
+
+        // The lag can't have grown by more than the time elapsed since we last measured it, so
+        // we only need to ask the controller again if that bound exceeds the budget
+        if let Some((measured_at, Some(lag))) = self.last_replication_lag {
+            if lag + measured_at.elapsed() <= max_staleness {
+                return true;
+            }
+        }
+
+        let measured_at = Instant::now();
+        match self.replication_lag().await {
+            Ok(lag) => {
+                self.last_replication_lag = Some((measured_at, lag));
+                lag.map_or(false, |lag| lag <= max_staleness)
+            }
+            Err(error) => {
+                warn!(%error, "Could not determine replication lag");
+                false
+            }
+        }
+    }
 }
 
 impl NoriaConnector {
@@ -1098,6 +1175,28 @@ impl NoriaConnector {
         Ok(())
     }
 
+    /// Describes the dataflow that would be created for the given query by a `CREATE CACHE`,
+    /// without creating a cache for it
+    pub(crate) async fn explain_create_cache(
+        &mut self,
+        statement: &nom_sql::SelectStatement,
+        override_schema_search_path: Option<Vec<SqlIdentifier>>,
+    ) -> ReadySetResult<CacheExplanation> {
+        let schema_search_path =
+            override_schema_search_path.unwrap_or_else(|| self.schema_search_path.clone());
+        let name = utils::generate_query_name(statement, &schema_search_path).into();
+        let changelist = ChangeList::from_change(
+            Change::create_cache(name, statement.clone(), false),
+            self.dialect,
+        )
+        .with_schema_search_path(schema_search_path);
+
+        noria_await!(
+            self.inner.get_mut()?,
+            self.inner.get_mut()?.noria.explain_create_cache(changelist)
+        )
+    }
+
     async fn get_view(
         &mut self,
         q: &nom_sql::SelectStatement,
@@ -1595,6 +1694,7 @@ impl NoriaConnector {
             getter,
             processed_query_params.as_ref(),
             params,
+            &self.session_values,
             ticket,
             self.read_behavior,
             self.read_request_handler.as_mut(),
@@ -1682,12 +1782,13 @@ fn build_view_query<'a>(
     getter: &'a mut View,
     processed_query_params: &ProcessedQueryParams,
     params: &[DfValue],
+    session_values: &SessionValues,
     ticket: Option<Timestamp>,
     read_behavior: ReadBehavior,
     dialect: Dialect,
 ) -> ReadySetResult<Option<(&'a mut ReaderHandle, ViewQuery)>> {
     let (limit, offset) = processed_query_params.limit_offset_params(params)?;
-    let raw_keys = processed_query_params.make_keys(params)?;
+    let raw_keys = processed_query_params.make_keys(params, session_values)?;
 
     getter.build_view_query(
         raw_keys,
@@ -1707,6 +1808,7 @@ async fn do_read<'a>(
     getter: &'a mut View,
     processed_query_params: &ProcessedQueryParams,
     params: &[DfValue],
+    session_values: &SessionValues,
     ticket: Option<Timestamp>,
     read_behavior: ReadBehavior,
     read_request_handler: Option<&'a mut ReadRequestHandler>,
@@ -1717,6 +1819,7 @@ async fn do_read<'a>(
         getter,
         processed_query_params,
         params,
+        session_values,
         ticket,
         read_behavior,
         dialect,
@@ -8,6 +8,7 @@
 #![feature(arc_unwrap_or_clone)]
 #![deny(unreachable_pub)]
 
+pub mod auto_cache;
 pub mod backend;
 pub mod fallback_cache;
 pub mod http_router;
@@ -15,13 +16,17 @@ pub mod migration_handler;
 pub mod proxied_queries_reporter;
 mod query_handler;
 pub mod query_status_cache;
+pub mod query_status_persister;
 pub mod rewrite;
+pub mod session_parameters;
 pub mod upstream_database;
 mod utils;
 pub mod views_synchronizer;
 
 pub use crate::backend::{Backend, BackendBuilder};
-pub use crate::query_handler::{QueryHandler, SetBehavior};
+pub use crate::query_handler::{QueryHandler, SetBehavior, MAX_STALENESS_VARIABLE};
+pub use crate::query_status_persister::QueryStatusPersister;
+pub use crate::session_parameters::SessionParameter;
 pub use crate::upstream_database::{
     UpstreamConfig, UpstreamDatabase, UpstreamDestination, UpstreamPrepare,
 };
@@ -5,13 +5,17 @@
 //!
 //! The migration handler may change a queries state based on the
 //! response from ReadySet.
+//!
+//! If configured with an [`AutoCacher`], the migration handler also
+//! creates and drops caches automatically based on how queries are used.
 use std::collections::HashMap;
+use std::sync::Arc;
 use std::time::Instant;
 
 use dataflow_expression::Dialect;
 use metrics::{counter, register_counter, Counter};
 use nom_sql::Literal;
-use readyset_client::query::{MigrationState, Query};
+use readyset_client::query::{MigrationState, Query, QueryId};
 use readyset_client::recipe::changelist::{Change, ChangeList};
 use readyset_client::{PlaceholderIdx, ReadySetHandle, ViewCreateRequest};
 use readyset_client_metrics::recorded;
@@ -21,8 +25,9 @@ use readyset_sql_passes::InlineLiterals;
 use readyset_util::redacted::Sensitive;
 use readyset_util::shutdown::ShutdownReceiver;
 use tokio::select;
-use tracing::{debug, error, info, instrument};
+use tracing::{debug, error, info, instrument, warn};
 
+use crate::auto_cache::AutoCacher;
 use crate::backend::NoriaConnector;
 use crate::query_status_cache::QueryStatusCache;
 use crate::utils;
@@ -57,6 +62,9 @@ pub struct MigrationHandler {
     /// Queries are removed when a migration yields success or unsupported
     /// and re-added when they are found in the pending migration list.
     start_time: HashMap<ViewCreateRequest, Instant>,
+
+    /// If set, used to decide which caches to create and drop automatically
+    auto_cacher: Option<AutoCacher>,
 }
 
 impl MigrationHandler {
@@ -78,9 +86,16 @@ impl MigrationHandler {
             max_retry,
             shutdown_recv,
             start_time: HashMap::new(),
+            auto_cacher: None,
         }
     }
 
+    /// Sets the [`AutoCacher`] used to create and drop caches automatically
+    pub fn auto_cacher(mut self, auto_cacher: Option<AutoCacher>) -> Self {
+        self.auto_cacher = auto_cacher;
+        self
+    }
+
     /// Migrate (or attempt a dry run migration) for each query marked as pending in the
     /// `QueryStatusCache`.
     async fn process_pending_migrations(
@@ -183,6 +198,103 @@ impl MigrationHandler {
         }
     }
 
+    /// If the current auto caching window has ended, create caches for the supported queries that
+    /// have been run often and slowly enough against the upstream database, and drop the
+    /// automatically created caches that have gone unread for too long.
+    async fn process_auto_caching(&mut self) {
+        let query_status_cache = self.query_status_cache;
+        let now = Instant::now();
+        let decision = match &mut self.auto_cacher {
+            Some(auto_cacher) if auto_cacher.is_due(now) => {
+                auto_cacher.evaluate(now, query_status_cache.allow_list().len(), |id| {
+                    query_status_cache
+                        .query(&id.to_string())
+                        .map_or(false, |q| {
+                            query_status_cache.query_status(&q).is_dry_run_succeeded()
+                        })
+                })
+            }
+            _ => return,
+        };
+
+        for id in decision.drop {
+            let Some(view_request) = self.auto_cached_view_request(&id) else {
+                continue;
+            };
+            let qname = utils::generate_query_name(
+                &view_request.statement,
+                &view_request.schema_search_path,
+            );
+            match self.noria.drop_view(&qname.into()).await {
+                Ok(()) => {
+                    info!(query_id = %id, "Dropped unused automatically created cache");
+                    counter!(recorded::AUTO_CACHE_DROPPED, 1);
+                    // We know the query is supported, so there's no need for another dry run
+                    query_status_cache.update_query_migration_state(
+                        &*view_request,
+                        MigrationState::DryRunSucceeded,
+                    );
+                    self.forget_auto_cache(&id);
+                }
+                Err(error) => {
+                    warn!(%error, query_id = %id, "Failed to drop automatically created cache")
+                }
+            }
+        }
+
+        for id in decision.create {
+            let Some(view_request) = query_status_cache
+                .query(&id.to_string())
+                .and_then(Query::into_parsed)
+            else {
+                continue;
+            };
+            let qname = utils::generate_query_name(
+                &view_request.statement,
+                &view_request.schema_search_path,
+            );
+            match self
+                .noria
+                .handle_create_cached_query(
+                    Some(&qname.into()),
+                    &view_request.statement,
+                    Some(view_request.schema_search_path.clone()),
+                    false,
+                )
+                .await
+            {
+                Ok(()) => {
+                    info!(query_id = %id, "Automatically created cache");
+                    counter!(recorded::AUTO_CACHE_CREATED, 1);
+                    query_status_cache
+                        .update_query_migration_state(&*view_request, MigrationState::Successful);
+                    if let Some(auto_cacher) = &mut self.auto_cacher {
+                        auto_cacher.created(id, Instant::now());
+                    }
+                }
+                Err(error) => warn!(%error, query_id = %id, "Failed to automatically create cache"),
+            }
+        }
+    }
+
+    /// Returns the query for an automatically created cache, if that cache still exists. If it has
+    /// since been dropped with `DROP CACHE`, stops tracking it.
+    fn auto_cached_view_request(&mut self, id: &QueryId) -> Option<Arc<ViewCreateRequest>> {
+        match self.query_status_cache.query(&id.to_string()) {
+            Some(q) if self.query_status_cache.query_status(&q).is_successful() => q.into_parsed(),
+            _ => {
+                self.forget_auto_cache(id);
+                None
+            }
+        }
+    }
+
+    fn forget_auto_cache(&mut self, id: &QueryId) {
+        if let Some(auto_cacher) = &mut self.auto_cacher {
+            auto_cacher.forget(id);
+        }
+    }
+
     #[instrument(level = "warn", name = "migration_handler", skip(self))]
     pub async fn run(&mut self) -> ReadySetResult<()> {
         let mut interval = tokio::time::interval(self.min_poll_interval);
@@ -204,6 +316,7 @@ impl MigrationHandler {
                 _ = interval.tick() => {
                     self.process_pending_migrations(&success_counter, &failure_counter).await;
                     self.process_inlined_migrations().await;
+                    self.process_auto_caching().await;
                 }
             }
         }
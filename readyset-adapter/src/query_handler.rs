@@ -1,7 +1,11 @@
-use nom_sql::{SqlIdentifier, SqlQuery};
+use std::time::Duration;
+
+use nom_sql::{Literal, SqlIdentifier, SqlQuery};
+use readyset_data::DfValue;
 use readyset_errors::ReadySetResult;
 
 use crate::backend::noria_connector;
+use crate::session_parameters::SessionParameter;
 
 /// Classification for how we should be handling a SQL `SET` statement.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -14,8 +18,22 @@ pub enum SetBehavior {
     SetAutocommit(bool),
     /// This `SET` statement represents the current schema search path being changed
     SetSearchPath(Vec<SqlIdentifier>),
+    /// This `SET` statement assigns values to session-scoped parameters which may be referenced by
+    /// cached queries, so the values should be recorded, and the statement proxied upstream. A
+    /// value of `None` means the parameter is set to a value we can't determine (such as its
+    /// default, or the result of a subquery).
+    SetSessionValues(Vec<(SessionParameter, Option<DfValue>)>),
+    /// This `SET` statement sets (or, if `None`, removes) the session's staleness budget: the
+    /// maximum replication lag that reads from ReadySet may observe. This is a ReadySet
+    /// extension, so the statement should not be proxied upstream.
+    SetMaxStaleness(Option<Duration>),
 }
 
+/// The name of the session variable which sets the session's staleness budget, as a (possibly
+/// fractional) number of seconds. Reads which would observe more replication lag than that are
+/// proxied to the upstream database instead.
+pub const MAX_STALENESS_VARIABLE: &str = "readyset_max_staleness";
+
 impl SetBehavior {
     /// Return a [`SetBehavior`] specifying that a statement should be proxied if the argument is
     /// `true`, or unsupported if the argument is `false`
@@ -26,6 +44,27 @@ impl SetBehavior {
             Self::Unsupported
         }
     }
+
+    /// Return a [`SetBehavior`] which sets the session's staleness budget to the given value for
+    /// [`MAX_STALENESS_VARIABLE`]. A value of `None` (for `DEFAULT`) or `NULL` removes the budget.
+    pub fn set_max_staleness(value: Option<&Literal>) -> Self {
+        let seconds = match value {
+            None | Some(Literal::Null) => return Self::SetMaxStaleness(None),
+            Some(Literal::UnsignedInteger(i)) => *i as f64,
+            Some(Literal::Integer(i)) => *i as f64,
+            Some(Literal::Float(f)) => f.value as f64,
+            Some(Literal::Double(d)) => d.value,
+            Some(Literal::Numeric(mantissa, scale)) => *mantissa as f64 / 10f64.powi(*scale as i32),
+            Some(Literal::String(s)) => match s.parse() {
+                Ok(seconds) => seconds,
+                Err(_) => return Self::Unsupported,
+            },
+            Some(_) => return Self::Unsupported,
+        };
+
+        Duration::try_from_secs_f64(seconds)
+            .map_or(Self::Unsupported, |d| Self::SetMaxStaleness(Some(d)))
+    }
 }
 
 /// A trait describing the behavior of how specific queries should be handled by a noria-client
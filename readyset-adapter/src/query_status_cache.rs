@@ -658,6 +658,44 @@ impl QueryStatusCache {
         }
     }
 
+    /// Returns the status of every query in the cache, for persisting across adapter restarts.
+    ///
+    /// Execution info only describes this adapter process, so it is not included.
+    pub fn persistent_statuses(&self) -> Vec<(Query, QueryStatus)> {
+        self.statuses
+            .iter()
+            .map(|r| ((*r.key()).clone().into(), r.value().clone()))
+            .chain(
+                self.failed_parses
+                    .iter()
+                    .map(|r| ((*r.key()).clone().into(), r.value().clone())),
+            )
+            .map(|(q, status)| {
+                (
+                    q,
+                    QueryStatus {
+                        execution_info: None,
+                        ..status
+                    },
+                )
+            })
+            .collect()
+    }
+
+    /// Restores query statuses that were previously returned by [`Self::persistent_statuses`].
+    ///
+    /// Queries that are already in the cache keep their current status.
+    pub fn restore_statuses<I>(&self, statuses: I)
+    where
+        I: IntoIterator<Item = (Query, QueryStatus)>,
+    {
+        for (q, status) in statuses {
+            if !q.with_status(self, |s| s.is_some()) {
+                self.insert_with_status(q, status);
+            }
+        }
+    }
+
     /// Returns a query given a query hash
     pub fn query(&self, id: &str) -> Option<Query> {
         let id = QueryId::new(u64::from_str_radix(id.strip_prefix("q_")?, 16).ok()?);
@@ -855,6 +893,36 @@ mod tests {
         assert_eq!(cache.allow_list().len(), 0);
     }
 
+    #[test]
+    fn restore_persistent_statuses() {
+        let cache = QueryStatusCache::new().style(MigrationStyle::Async);
+        let dropped = ViewCreateRequest::new(select_statement("SELECT * FROM t1").unwrap(), vec![]);
+        let always = ViewCreateRequest::new(
+            select_statement("SELECT * FROM t1 WHERE id = ?").unwrap(),
+            vec![],
+        );
+        let failed = "this isn't valid SQL".to_owned();
+
+        cache.insert(dropped.clone());
+        cache.drop_query(&dropped);
+        cache.update_query_migration_state(&always, MigrationState::Successful);
+        cache.always_attempt_readyset(&always, true);
+        cache.insert(failed.clone());
+        cache.execute_succeeded(&always.clone().into());
+
+        let statuses = cache.persistent_statuses();
+        assert!(statuses.iter().all(|(_, s)| s.execution_info.is_none()));
+
+        let restored = QueryStatusCache::new().style(MigrationStyle::Async);
+        restored.insert(failed);
+        restored.restore_statuses(statuses);
+
+        assert_eq!(restored.deny_list().len(), 2);
+        assert_eq!(restored.allow_list().len(), 1);
+        assert!(restored.query_status(&always).always);
+        assert!(restored.query_status(&dropped).is_dropped());
+    }
+
     #[test]
     fn view_not_found_for_query() {
         let cache = QueryStatusCache::new().style(MigrationStyle::Explicit);
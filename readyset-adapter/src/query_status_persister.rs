@@ -0,0 +1,113 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use readyset_client::consensus::{Authority, AuthorityControl};
+use readyset_client::query::{Query, QueryStatus};
+use readyset_util::shutdown::ShutdownReceiver;
+use tokio::select;
+use tracing::{debug, info, instrument, warn};
+
+use crate::query_status_cache::QueryStatusCache;
+
+/// Persists the contents of the [`QueryStatusCache`] in the authority, so that the migration state
+/// of queries, the deny list, and which queries are always routed to ReadySet all survive adapter
+/// restarts.
+pub struct QueryStatusPersister {
+    /// The authority to persist query statuses in
+    authority: Arc<Authority>,
+    /// The query status cache to persist
+    query_status_cache: &'static QueryStatusCache,
+    /// The interval between subsequent writes of changed query statuses to the authority
+    persist_interval: std::time::Duration,
+    /// The query statuses as of the last time they were restored from or written to the
+    /// authority, so that only statuses which have since changed need to be written
+    persisted: HashMap<Query, QueryStatus>,
+    /// Receiver to return the shutdown signal on
+    shutdown_recv: ShutdownReceiver,
+}
+
+impl QueryStatusPersister {
+    pub fn new(
+        authority: Arc<Authority>,
+        query_status_cache: &'static QueryStatusCache,
+        persist_interval: std::time::Duration,
+        shutdown_recv: ShutdownReceiver,
+    ) -> Self {
+        QueryStatusPersister {
+            authority,
+            query_status_cache,
+            persist_interval,
+            persisted: HashMap::new(),
+            shutdown_recv,
+        }
+    }
+
+    /// Load any query statuses previously persisted in the authority into the query status cache.
+    ///
+    /// This should be called before the adapter starts accepting connections.
+    pub async fn restore(&mut self) {
+        match self.authority.query_statuses().await {
+            Ok(statuses) => {
+                info!(
+                    num_queries = statuses.len(),
+                    "Restoring persisted query statuses"
+                );
+                self.query_status_cache
+                    .restore_statuses(statuses.iter().cloned());
+                self.persisted.extend(statuses);
+            }
+            Err(error) => warn!(%error, "Could not load persisted query statuses"),
+        }
+    }
+
+    #[instrument(level = "info", name = "query_status_persister", skip(self))]
+    pub async fn run(&mut self) {
+        let mut interval = tokio::time::interval(self.persist_interval);
+        loop {
+            select! {
+                // We use `biased` here to ensure that our shutdown signal will be received and
+                // acted upon even if the other branches in this `select!` are constantly in a
+                // ready state (e.g. a stream that has many messages where very little time passes
+                // between receipt of these messages). More information about this situation can
+                // be found in the docs for `tokio::select`.
+                biased;
+                _ = self.shutdown_recv.recv() => {
+                    info!("Query status persister shutting down after shut down signal received");
+                    // Make sure we don't lose anything that changed since the last write
+                    self.persist().await;
+                    break;
+                }
+                _ = interval.tick() => self.persist().await,
+            }
+        }
+    }
+
+    async fn persist(&mut self) {
+        let changed = self
+            .query_status_cache
+            .persistent_statuses()
+            .into_iter()
+            .filter(|(q, status)| match self.persisted.get(q) {
+                Some(persisted) => persisted != status,
+                None => status.is_persistent(),
+            })
+            .collect::<Vec<_>>();
+        if changed.is_empty() {
+            return;
+        }
+
+        debug!(num_queries = changed.len(), "Persisting query statuses");
+        match self.authority.persist_query_statuses(changed.clone()).await {
+            Ok(()) => {
+                for (q, status) in changed {
+                    if status.is_persistent() {
+                        self.persisted.insert(q, status);
+                    } else {
+                        self.persisted.remove(&q);
+                    }
+                }
+            }
+            Err(error) => warn!(%error, "Could not persist query statuses"),
+        }
+    }
+}
@@ -7,12 +7,15 @@ use std::{iter, mem};
 use itertools::{Either, Itertools};
 use nom_sql::analysis::visit_mut::{self, VisitorMut};
 use nom_sql::{
-    BinaryOperator, Expr, InValue, ItemPlaceholder, LimitClause, Literal, SelectStatement,
+    BinaryOperator, Expr, FieldDefinitionExpr, FunctionExpr, InValue, ItemPlaceholder, LimitClause,
+    Literal, SelectStatement,
 };
 use readyset_data::{DfType, DfValue};
 use readyset_errors::{internal_err, invalid_err, unsupported, ReadySetError, ReadySetResult};
 use tracing::trace;
 
+use crate::session_parameters::{SessionParameter, SessionValues};
+
 /// Struct storing information about parameters processed from a raw user supplied query, which
 /// provides support for converting a user-supplied parameter list into a set of lookup keys to pass
 /// to ReadySet.
@@ -24,6 +27,9 @@ use tracing::trace;
 pub struct ProcessedQueryParams {
     reordered_placeholders: Option<Vec<usize>>,
     rewritten_in_conditions: Vec<RewrittenIn>,
+    /// Session-scoped values which have been replaced with placeholders, alongside the index in
+    /// the parameter list where they appear, before auto-parametrization
+    session_parameters: Vec<(usize, SessionParameter)>,
     auto_parameters: Vec<(usize, Literal)>,
     pagination_parameters: AdapterPaginationParams,
 }
@@ -33,18 +39,17 @@ struct AdapterPaginationParams {
     /// The values of `LIMIT` and `OFFSET` in the original query
     limit_clause: LimitClause,
     force_paginate_in_adapter: bool,
+    /// Whether the query compares columns against placeholders with range operators. ReadySet
+    /// paginates range queries in the reader, so the value for the `OFFSET` has to be passed along
+    /// with the lookup rather than as part of the key
+    paginate_in_reader: bool,
 }
 
 /// This method checks if readyset-server is configured to handle LIMIT/OFFSET queries at the
 /// dataflow level. If not then LIMIT and OFFSET will be stripped and executed in the
 /// post-processing path.
-fn use_fallback_pagination(server_supports_pagination: bool, limit_clause: &LimitClause) -> bool {
-    if server_supports_pagination &&
-        // Can't handle parameterized LIMIT even if support is enabled
-        !matches!(limit_clause.limit(), Some(Literal::Placeholder(_))) &&
-        // Can't handle bare OFFSET
-        !(limit_clause.limit().is_none() && limit_clause.offset().is_some())
-    {
+fn use_fallback_pagination(server_supports_pagination: bool) -> bool {
+    if server_supports_pagination {
         return false;
     }
 
@@ -53,13 +58,47 @@ fn use_fallback_pagination(server_supports_pagination: bool, limit_clause: &Limi
     true
 }
 
+/// Returns true if the `WHERE` clause of the given query compares any columns against placeholders
+/// using range operators, meaning ReadySet will perform range lookups for the query
+fn has_range_placeholder(query: &SelectStatement) -> bool {
+    fn is_placeholder(expr: &Expr) -> bool {
+        matches!(expr, Expr::Literal(Literal::Placeholder(_)))
+    }
+
+    fn visit(expr: &Expr) -> bool {
+        match expr {
+            Expr::BinaryOp {
+                lhs,
+                op: BinaryOperator::And,
+                rhs,
+            } => visit(lhs) || visit(rhs),
+            Expr::BinaryOp {
+                lhs,
+                op:
+                    BinaryOperator::Less
+                    | BinaryOperator::Greater
+                    | BinaryOperator::LessOrEqual
+                    | BinaryOperator::GreaterOrEqual,
+                rhs,
+            } => is_placeholder(lhs) || is_placeholder(rhs),
+            Expr::Between { min, max, .. } => is_placeholder(min) || is_placeholder(max),
+            _ => false,
+        }
+    }
+
+    query.where_clause.as_ref().map_or(false, visit)
+}
+
 /// This rewrite pass accomplishes the following:
 /// - Remaps dollar sign placeholders so that they appear in order
+/// - Replaces session-scoped values, such as user variables, with placeholders when they're
+///   compared against columns, so that they can be resolved each time the query is executed
 /// - Replaces literals with placeholders when they can be used as lookup indices in the noria
 ///   dataflow representation of the query. Note that this pass may not replace all literals and is
 ///   therefore cannot guarantee that the rewritten query is free of user PII.
 /// - Collapses 'WHERE <expr> IN ?, ... ?' to 'WHERE <expr> = ?'
-/// - Removes `OFFSET ?` if there isn't a `LIMIT`
+/// - Removes `LIMIT` and `OFFSET` if the server doesn't support pagination, so that they can be
+///   applied in the adapter instead
 pub fn process_query(
     query: &mut SelectStatement,
     server_supports_pagination: bool,
@@ -68,24 +107,28 @@ pub fn process_query(
 
     let limit_clause = mem::take(&mut query.limit_clause);
 
-    let force_paginate_in_adapter =
-        use_fallback_pagination(server_supports_pagination, &query.limit_clause);
+    let force_paginate_in_adapter = use_fallback_pagination(server_supports_pagination);
 
     if !force_paginate_in_adapter {
         // If adapter pagination shouldn't be used reinstate the limit clause
         query.limit_clause.clone_from(&limit_clause);
     }
 
+    let paginate_in_reader = !force_paginate_in_adapter && has_range_placeholder(query);
+
+    let session_parameters = parametrize_session_values(query);
     let auto_parameters = auto_parametrize_query(query);
     let rewritten_in_conditions = collapse_where_in(query)?;
     number_placeholders(query)?;
     Ok(ProcessedQueryParams {
         reordered_placeholders,
         rewritten_in_conditions,
+        session_parameters,
         auto_parameters,
         pagination_parameters: AdapterPaginationParams {
             limit_clause,
             force_paginate_in_adapter,
+            paginate_in_reader,
         },
     })
 }
@@ -138,6 +181,7 @@ impl ProcessedQueryParams {
         let AdapterPaginationParams {
             limit_clause,
             force_paginate_in_adapter,
+            paginate_in_reader,
         } = &self.pagination_parameters;
 
         let (limit, offset) = match limit_clause {
@@ -155,18 +199,37 @@ impl ProcessedQueryParams {
         };
 
         if *force_paginate_in_adapter || limit == Some(0) {
-            Ok((limit, offset))
-        } else {
-            Ok((None, None))
-        }
+            return Ok((limit, offset));
+        }
+
+        // Only literal limits are part of the reader's post-lookup processing - for parametrized
+        // limits, the reader keeps a fixed maximum number of rows, and truncates them to the limit
+        // passed along with the lookup
+        let literal_limit = limit_clause
+            .limit()
+            .map_or(false, |limit| !matches!(limit, Literal::Placeholder(_)));
+        // If the limit is a literal, the offset is the page number in the lookup key, unless the
+        // query is paginated in the reader
+        let offset_in_reader = *paginate_in_reader || !literal_limit;
+
+        Ok((
+            limit.filter(|_| !literal_limit),
+            offset.filter(|_| offset_in_reader),
+        ))
     }
 
     pub(crate) fn make_keys<'param, T>(
         &self,
         params: &'param [T],
+        session_values: &SessionValues,
     ) -> ReadySetResult<Vec<Cow<'param, [T]>>>
     where
-        T: Clone + TryFrom<Literal, Error = ReadySetError> + Debug + Default + PartialEq,
+        T: Clone
+            + TryFrom<Literal, Error = ReadySetError>
+            + From<DfValue>
+            + Debug
+            + Default
+            + PartialEq,
     {
         let params = if let Some(order_map) = &self.reordered_placeholders {
             Cow::Owned(reorder_params(params, order_map)?)
@@ -179,6 +242,7 @@ impl ProcessedQueryParams {
         let AdapterPaginationParams {
             limit_clause,
             force_paginate_in_adapter,
+            ..
         } = &self.pagination_parameters;
 
         if *force_paginate_in_adapter {
@@ -194,10 +258,23 @@ impl ProcessedQueryParams {
             }
         }
 
-        if params.is_empty() && self.auto_parameters.is_empty() {
+        if params.is_empty()
+            && self.session_parameters.is_empty()
+            && self.auto_parameters.is_empty()
+        {
             return Ok(vec![]);
         }
 
+        let session_parameters = self
+            .session_parameters
+            .iter()
+            .map(|(i, param)| -> ReadySetResult<_> {
+                Ok((*i, session_values.resolve(param)?.into()))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let params = splice_auto_parameters(params, &session_parameters);
+
         let auto_parameters = self
             .auto_parameters
             .clone()
@@ -205,7 +282,7 @@ impl ProcessedQueryParams {
             .map(|(i, lit)| -> ReadySetResult<_> { Ok((i, lit.try_into()?)) })
             .collect::<Result<Vec<_>, _>>()?;
 
-        let params = splice_auto_parameters(params, &auto_parameters);
+        let params = splice_auto_parameters(params.as_ref(), &auto_parameters);
 
         if self.rewritten_in_conditions.is_empty() {
             return Ok(vec![Cow::Owned(params.into_owned())]);
@@ -333,27 +410,72 @@ impl<'ast> VisitorMut<'ast> for CollapseWhereInVisitor {
 ///
 /// Note that IN conditions without any placeholders will be left untouched, as these can be handled
 /// by regular filter nodes in dataflow
+/// Returns the first aggregate in the select list of the given query which the reader can't
+/// re-aggregate across the results for the multiple keys looked up for a parameterized IN, if any
+fn aggregate_unsupported_for_multiple_keys(query: &SelectStatement) -> Option<&FunctionExpr> {
+    query.fields.iter().find_map(|field| match field {
+        FieldDefinitionExpr::Expr {
+            expr: Expr::Call(func),
+            ..
+        } if matches!(
+            func,
+            FunctionExpr::Avg { distinct: true, .. }
+                | FunctionExpr::Sum { distinct: true, .. }
+                | FunctionExpr::Stddev { .. }
+                | FunctionExpr::Variance { .. }
+                | FunctionExpr::BitAnd(_)
+                | FunctionExpr::BitOr(_)
+                | FunctionExpr::ArrayAgg {
+                    order_by: Some(_),
+                    ..
+                }
+                | FunctionExpr::StringAgg { .. }
+                | FunctionExpr::JsonAgg { .. }
+                | FunctionExpr::JsonObjectAgg { .. }
+        ) =>
+        {
+            Some(func)
+        }
+        _ => None,
+    })
+}
+
 fn collapse_where_in(query: &mut SelectStatement) -> ReadySetResult<Vec<RewrittenIn>> {
     let mut res = vec![];
     let distinct = query.distinct;
-    let has_aggregates = query.contains_aggregate_select();
 
     if let Some(ref mut w) = query.where_clause {
         let mut visitor = CollapseWhereInVisitor::default();
         visitor.visit_expr(w)?;
         res = visitor.out;
 
-        // When a `SELECT` statement contains aggregates, such as `SUM` or `COUNT` (or `DISTINCT`,
-        // which is implemented via COUNT),  we can't use placeholders, as those will aggregate key
-        // lookups into a multi row response, as opposed to a single row response required by
-        // aggregates. We could support this pretty easily, but for now it's not in-scope
+        // When a `SELECT` statement contains aggregates, such as `SUM` or `COUNT`, the lookup for
+        // each key in the IN list returns its own aggregated rows, which the reader then has to
+        // re-aggregate into the single row per group required by the query. That's only possible
+        // for some aggregates
         if !res.is_empty() {
-            if has_aggregates {
-                unsupported!("Aggregates with parameterized IN are not supported");
+            if let Some(func) = aggregate_unsupported_for_multiple_keys(query) {
+                unsupported!(
+                    "{} with parameterized IN is not supported",
+                    // FIXME(ENG-2499): Use correct dialect.
+                    func.display(nom_sql::Dialect::MySQL)
+                );
             }
             if distinct {
                 unsupported!("DISTINCT with parameterized IN is not supported");
             }
+            // Window functions are computed separately for the rows returned for each key
+            if query.fields.iter().any(|field| {
+                matches!(
+                    field,
+                    FieldDefinitionExpr::Expr {
+                        expr: Expr::Call(FunctionExpr::Over { .. }),
+                        ..
+                    }
+                )
+            }) {
+                unsupported!("Window functions with parameterized IN are not supported");
+            }
         }
     }
     Ok(res)
@@ -515,6 +637,103 @@ pub fn number_placeholders(query: &mut SelectStatement) -> ReadySetResult<()> {
     Ok(())
 }
 
+#[derive(Default)]
+struct SessionParametersVisitor {
+    out: Vec<(usize, SessionParameter)>,
+    in_supported_position: bool,
+    param_index: usize,
+    query_depth: u8,
+}
+
+impl<'ast> VisitorMut<'ast> for SessionParametersVisitor {
+    type Error = !;
+
+    fn visit_literal(&mut self, literal: &'ast mut Literal) -> Result<(), Self::Error> {
+        if matches!(literal, Literal::Placeholder(_)) {
+            self.param_index += 1;
+        }
+        Ok(())
+    }
+
+    fn visit_select_statement(
+        &mut self,
+        select_statement: &'ast mut SelectStatement,
+    ) -> Result<(), Self::Error> {
+        self.query_depth = self.query_depth.saturating_add(1);
+        visit_mut::walk_select_statement(self, select_statement)?;
+        self.query_depth = self.query_depth.saturating_sub(1);
+        Ok(())
+    }
+
+    fn visit_where_clause(&mut self, expression: &'ast mut Expr) -> Result<(), Self::Error> {
+        self.in_supported_position = self.query_depth <= 1;
+        self.visit_expr(expression)?;
+        self.in_supported_position = false;
+        Ok(())
+    }
+
+    fn visit_expr(&mut self, expression: &'ast mut Expr) -> Result<(), Self::Error> {
+        let was_supported = self.in_supported_position;
+        if was_supported {
+            match expression {
+                Expr::BinaryOp {
+                    lhs: box Expr::Column(_),
+                    op: BinaryOperator::Equal,
+                    rhs,
+                } if let Some(param) = SessionParameter::from_expr(rhs.as_ref()) => {
+                    **rhs = Expr::Literal(Literal::Placeholder(ItemPlaceholder::QuestionMark));
+                    self.out.push((self.param_index, param));
+                    self.param_index += 1;
+                    return Ok(());
+                }
+                Expr::BinaryOp {
+                    lhs,
+                    op: BinaryOperator::Equal,
+                    rhs: rhs @ box Expr::Column(_),
+                } if SessionParameter::from_expr(lhs.as_ref()).is_some() => {
+                    // for value = col, swap the equality first then revisit
+                    mem::swap(lhs, rhs);
+                    return self.visit_expr(expression);
+                }
+                Expr::BinaryOp {
+                    lhs,
+                    op: BinaryOperator::And,
+                    rhs,
+                } => {
+                    self.visit_expr(lhs.as_mut())?;
+                    self.in_supported_position = true;
+                    self.visit_expr(rhs.as_mut())?;
+                    self.in_supported_position = true;
+                    return Ok(());
+                }
+                _ => self.in_supported_position = false,
+            }
+        }
+
+        visit_mut::walk_expr(self, expression)?;
+        self.in_supported_position = was_supported;
+        Ok(())
+    }
+}
+
+/// Replace all session-scoped values (see [`SessionParameter`]) that are compared for equality
+/// against columns in the top-level `WHERE` clause of the given query with parameters, and return
+/// the replaced values alongside the index in the parameter list where they appear as a tuple of
+/// (placeholder position, value).
+///
+/// Session values in any other position are left in the query, which means it can't be cached.
+pub fn parametrize_session_values(query: &mut SelectStatement) -> Vec<(usize, SessionParameter)> {
+    // Like auto-parametrization, we can't mix range and equal parameters in the same query
+    if has_range_placeholder(query) {
+        return vec![];
+    }
+
+    let mut visitor = SessionParametersVisitor::default();
+    #[allow(clippy::unwrap_used)] // error is !, which can never be returned
+    visitor.visit_select_statement(query).unwrap();
+    visitor.out
+}
+
 #[derive(Default)]
 struct AutoParametrizeVisitor {
     out: Vec<(usize, Literal)>,
@@ -815,6 +1034,30 @@ mod tests {
             );
         }
 
+        #[test]
+        fn collapsed_where_in_with_aggregates() {
+            let mut q = parse_select_statement(
+                "SELECT avg(y), count(distinct z) FROM x WHERE x.y IN (?, ?, ?)",
+            );
+            let rewritten = collapse_where_in(&mut q).unwrap();
+            assert_eq!(rewritten.len(), 1);
+            assert_eq!(
+                q,
+                parse_select_statement("SELECT avg(y), count(distinct z) FROM x WHERE x.y = ?")
+            );
+
+            let mut q = parse_select_statement("SELECT stddev(y) FROM x WHERE x.y IN (?, ?)");
+            assert!(collapse_where_in(&mut q).unwrap_err().is_unsupported());
+        }
+
+        #[test]
+        fn collapsed_where_in_with_window_function() {
+            let mut q = parse_select_statement(
+                "SELECT z, row_number() OVER (ORDER BY z) FROM x WHERE x.y IN (?, ?)",
+            );
+            assert!(collapse_where_in(&mut q).unwrap_err().is_unsupported());
+        }
+
         #[test]
         fn collapsed_where_literals() {
             let mut q = parse_select_statement("SELECT * FROM x WHERE x.y IN (1, 2, 3)");
@@ -1242,12 +1485,20 @@ mod tests {
         fn process_and_make_keys(
             query: &str,
             params: Vec<DfValue>,
+        ) -> (Vec<Vec<DfValue>>, SelectStatement) {
+            process_and_make_keys_in_session(query, params, &SessionValues::default())
+        }
+
+        fn process_and_make_keys_in_session(
+            query: &str,
+            params: Vec<DfValue>,
+            session_values: &SessionValues,
         ) -> (Vec<Vec<DfValue>>, SelectStatement) {
             let mut query = parse_select_statement(query);
             let processed = process_query(&mut query, false).unwrap();
             (
                 processed
-                    .make_keys(&params)
+                    .make_keys(&params, session_values)
                     .unwrap()
                     .into_iter()
                     .map(|c| c.to_vec())
@@ -1601,6 +1852,93 @@ mod tests {
             );
         }
 
+        #[test]
+        fn range_offset_passed_to_reader() {
+            let get_lim_off = |q: &str, p: &[DfValue]| -> (Option<usize>, Option<usize>) {
+                let proc = process_query(&mut parse_select_statement(q), true).unwrap();
+                proc.limit_offset_params(p).unwrap()
+            };
+
+            assert_eq!(
+                get_lim_off(
+                    "SELECT * FROM t WHERE x > ? ORDER BY x LIMIT 4 OFFSET ?",
+                    &[1.into(), 8.into()]
+                ),
+                (None, Some(8))
+            );
+
+            assert_eq!(
+                get_lim_off(
+                    "SELECT * FROM t WHERE y = ? AND x BETWEEN ? AND ? ORDER BY x LIMIT 4 OFFSET ?",
+                    &[1.into(), 2.into(), 3.into(), 4.into()]
+                ),
+                (None, Some(4))
+            );
+
+            assert_eq!(
+                get_lim_off(
+                    "SELECT * FROM t WHERE x = ? ORDER BY x LIMIT 4 OFFSET ?",
+                    &[1.into(), 8.into()]
+                ),
+                (None, None)
+            );
+        }
+
+        #[test]
+        fn parametrized_limit_passed_to_reader() {
+            let get_lim_off = |q: &str, p: &[DfValue]| -> (Option<usize>, Option<usize>) {
+                let mut query = parse_select_statement(q);
+                let proc = process_query(&mut query, true).unwrap();
+                assert!(
+                    !query.limit_clause.is_empty(),
+                    "{}",
+                    query.display(nom_sql::Dialect::MySQL)
+                );
+                proc.limit_offset_params(p).unwrap()
+            };
+
+            assert_eq!(
+                get_lim_off(
+                    "SELECT * FROM t WHERE x = ? ORDER BY y LIMIT ? OFFSET ?",
+                    &[1.into(), 2.into(), 3.into()]
+                ),
+                (Some(2), Some(3))
+            );
+
+            assert_eq!(
+                get_lim_off(
+                    "SELECT * FROM t WHERE x = ? ORDER BY y LIMIT ?",
+                    &[1.into(), 2.into()]
+                ),
+                (Some(2), None)
+            );
+
+            assert_eq!(
+                get_lim_off(
+                    "SELECT * FROM t WHERE x = ? ORDER BY y OFFSET ?",
+                    &[1.into(), 2.into()]
+                ),
+                (None, Some(2))
+            );
+
+            assert_eq!(
+                get_lim_off(
+                    "SELECT * FROM t WHERE x = ? ORDER BY y LIMIT ? OFFSET 4",
+                    &[1.into(), 2.into()]
+                ),
+                (Some(2), Some(4))
+            );
+
+            // Literal limits are paginated by page number instead
+            assert_eq!(
+                get_lim_off(
+                    "SELECT * FROM t WHERE x = ? ORDER BY y LIMIT 4 OFFSET ?",
+                    &[1.into(), 2.into()]
+                ),
+                (None, None)
+            );
+        }
+
         #[test]
         fn reuses_params_basic() {
             let (keys, query) =
@@ -1649,5 +1987,76 @@ mod tests {
                 vec![vec![1.into(), 1.into()], vec![1.into(), 2.into()]]
             );
         }
+
+        #[test]
+        fn session_parameters_with_params_and_auto_params() {
+            let mut session_values = SessionValues::default();
+            session_values.set(SessionParameter::UserVariable("user_id".into()), 7.into());
+            let (keys, query) = process_and_make_keys_in_session(
+                "SELECT * FROM t WHERE a = ? AND owner = @user_id AND b = 3 AND c = ?",
+                vec![1.into(), 2.into()],
+                &session_values,
+            );
+
+            assert_eq!(
+                query,
+                parse_select_statement(
+                    "SELECT * FROM t WHERE a = $1 AND owner = $2 AND b = $3 AND c = $4"
+                ),
+                "{}",
+                query.display(nom_sql::Dialect::MySQL)
+            );
+            assert_eq!(keys, vec![vec![1.into(), 7.into(), 3.into(), 2.into()]]);
+        }
+
+        #[test]
+        fn session_parameter_on_lhs() {
+            let mut session_values = SessionValues::default();
+            session_values.set(
+                SessionParameter::Setting("app.tenant".into()),
+                "acme".into(),
+            );
+            let (keys, query) = process_and_make_keys_in_session(
+                "SELECT * FROM t WHERE current_setting('app.tenant') = tenant",
+                vec![],
+                &session_values,
+            );
+
+            assert_eq!(
+                query,
+                parse_select_statement("SELECT * FROM t WHERE tenant = $1"),
+                "{}",
+                query.display(nom_sql::Dialect::MySQL)
+            );
+            assert_eq!(keys, vec![vec!["acme".into()]]);
+        }
+
+        #[test]
+        fn session_parameter_in_unsupported_position() {
+            let (keys, query) = process_and_make_keys_in_session(
+                "SELECT * FROM t WHERE owner = @user_id OR is_public = 1",
+                vec![],
+                &SessionValues::default(),
+            );
+
+            assert_eq!(
+                query,
+                parse_select_statement("SELECT * FROM t WHERE owner = @user_id OR is_public = 1"),
+                "{}",
+                query.display(nom_sql::Dialect::MySQL)
+            );
+            assert!(keys.is_empty(), "keys = {:?}", keys);
+        }
+
+        #[test]
+        fn unset_setting() {
+            let mut query = parse_select_statement(
+                "SELECT * FROM t WHERE tenant = current_setting('app.tenant')",
+            );
+            let processed = process_query(&mut query, false).unwrap();
+            processed
+                .make_keys::<DfValue>(&[], &SessionValues::default())
+                .unwrap_err();
+        }
     }
 }
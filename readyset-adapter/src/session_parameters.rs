@@ -0,0 +1,289 @@
+//! Support for values in queries which depend on the state of the client's session.
+//!
+//! Queries which compare columns against session-scoped values, such as `WHERE owner = @user_id`
+//! in MySQL or `WHERE tenant = current_setting('app.tenant')` in PostgreSQL, can't be cached with
+//! those values inlined, since they differ between (and within) sessions. Instead,
+//! [`rewrite::process_query`] replaces them with placeholders, and the adapter resolves them
+//! against the [`SessionValues`] of the session executing the query, passing the results to
+//! ReadySet as extra implicit parameters. This means every session shares a single cache for
+//! each query shape.
+//!
+//! The adapter only knows the value of a session-scoped value if it saw the statement which set
+//! it. Session values can also be changed by statements which the adapter can't interpret (such as
+//! `SELECT set_config(...)`, `RESET`, or rolling back a transaction), or have defaults the adapter
+//! can't see (such as per-role configuration parameters). Once that might have happened, the value
+//! is treated as unknown, and queries referencing it are proxied upstream until it's set again.
+//!
+//! [`rewrite::process_query`]: crate::rewrite::process_query
+
+use std::collections::HashMap;
+use std::fmt::{self, Display};
+
+use chrono::Utc;
+use nom_sql::analysis::visit::{self, Visitor};
+use nom_sql::{Expr, FunctionExpr, Literal, SqlIdentifier, SqlQuery, Variable, VariableScope};
+use readyset_data::DfValue;
+use readyset_errors::{ReadySetError, ReadySetResult};
+
+/// A value in a query whose value depends on the session executing the query
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum SessionParameter {
+    /// A MySQL user-defined variable, such as `@user_id`
+    UserVariable(SqlIdentifier),
+    /// A PostgreSQL configuration parameter, as read by `current_setting('app.tenant')`. The name
+    /// is always lowercase, since configuration parameter names are case-insensitive
+    Setting(String),
+    /// The current date, as returned by `CURRENT_DATE` or `CURDATE()`
+    CurrentDate,
+    /// The session's time zone, which [`CurrentDate`] is evaluated in. This is never referenced
+    /// directly by queries.
+    ///
+    /// [`CurrentDate`]: SessionParameter::CurrentDate
+    TimeZone,
+}
+
+impl Display for SessionParameter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UserVariable(name) => write!(f, "user variable @{name}"),
+            Self::Setting(name) => write!(f, "configuration parameter \"{name}\""),
+            Self::CurrentDate => f.write_str("the current date"),
+            Self::TimeZone => f.write_str("the time zone"),
+        }
+    }
+}
+
+impl SessionParameter {
+    /// If the given expression reads a session-scoped value, return the [`SessionParameter`] for
+    /// that value
+    pub fn from_expr(expr: &Expr) -> Option<Self> {
+        match expr {
+            Expr::Variable(Variable {
+                scope: VariableScope::User,
+                name,
+            }) => Some(Self::UserVariable(name.clone())),
+            Expr::Call(FunctionExpr::Call { name, arguments }) => {
+                match (name.to_ascii_lowercase().as_str(), arguments.as_slice()) {
+                    ("current_setting", [Expr::Literal(Literal::String(setting))]) => {
+                        Some(Self::Setting(setting.to_ascii_lowercase()))
+                    }
+                    ("current_date" | "curdate", []) => Some(Self::CurrentDate),
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+}
+
+/// The values of the [`SessionParameter`]s set within a single client session
+#[derive(Debug, Clone, Default)]
+pub struct SessionValues {
+    /// The values of parameters set by statements the adapter has seen. A value of `None` means
+    /// the parameter may since have been changed to a value the adapter doesn't know.
+    values: HashMap<SessionParameter, Option<DfValue>>,
+    /// Set once any parameter may have been changed to a value the adapter doesn't know, after
+    /// which only the parameters which have been set again since can be resolved
+    all_unknown: bool,
+}
+
+impl SessionValues {
+    /// Record the value of the given parameter for this session, as set by a `SET` statement
+    pub fn set(&mut self, parameter: SessionParameter, value: DfValue) {
+        self.values.insert(parameter, Some(value));
+    }
+
+    /// Record that the given parameter may have been changed to a value we don't know
+    pub fn invalidate(&mut self, parameter: SessionParameter) {
+        self.values.insert(parameter, None);
+    }
+
+    /// Record that any parameter may have been changed to a value we don't know
+    pub fn invalidate_all(&mut self) {
+        self.values.clear();
+        self.all_unknown = true;
+    }
+
+    /// Resolve the current value of the given parameter in this session, returning
+    /// [`ReadySetError::UnknownSessionValue`] if we can't be sure of its value.
+    ///
+    /// User variables which have never been set are `NULL`, as in MySQL. Configuration parameters
+    /// which have never been set are unknown, since they may have per-role or per-connection
+    /// defaults, and the current date is unknown until the session's time zone is set to UTC.
+    pub fn resolve(&self, parameter: &SessionParameter) -> ReadySetResult<DfValue> {
+        let unknown = || ReadySetError::UnknownSessionValue {
+            parameter: parameter.to_string(),
+        };
+
+        match parameter {
+            SessionParameter::CurrentDate => match self.values.get(&SessionParameter::TimeZone) {
+                Some(Some(time_zone)) if time_zone.as_str().map_or(false, is_utc) => {
+                    Ok(Utc::now().naive_utc().date().into())
+                }
+                _ => Err(unknown()),
+            },
+            _ => match self.values.get(parameter) {
+                Some(Some(value)) => Ok(value.clone()),
+                None if matches!(parameter, SessionParameter::UserVariable(_))
+                    && !self.all_unknown =>
+                {
+                    Ok(DfValue::None)
+                }
+                _ => Err(unknown()),
+            },
+        }
+    }
+}
+
+/// Returns true if the given time zone name or offset is equivalent to UTC
+fn is_utc(time_zone: &str) -> bool {
+    ["utc", "etc/utc", "+00:00", "z"]
+        .iter()
+        .any(|utc| time_zone.eq_ignore_ascii_case(utc))
+}
+
+/// Returns true if the given statement might change the value of a [`SessionParameter`] in a way
+/// that isn't described by a `SET` statement, which means the session's values should be
+/// [invalidated][SessionValues::invalidate_all] when it's executed.
+///
+/// This covers calls to PostgreSQL's `set_config` anywhere in the statement, and rolling back a
+/// transaction, which reverts any `SET`s made within it. Functions which set configuration
+/// parameters internally can't be detected.
+pub fn may_change_session_values(query: &SqlQuery) -> bool {
+    struct SetConfigVisitor;
+
+    impl<'ast> Visitor<'ast> for SetConfigVisitor {
+        type Error = ();
+
+        fn visit_function_expr(
+            &mut self,
+            function_expr: &'ast FunctionExpr,
+        ) -> Result<(), Self::Error> {
+            match function_expr {
+                FunctionExpr::Call { name, .. } if name.eq_ignore_ascii_case("set_config") => {
+                    Err(())
+                }
+                _ => visit::walk_function_expr(self, function_expr),
+            }
+        }
+    }
+
+    matches!(query, SqlQuery::Rollback(_)) || SetConfigVisitor.visit_sql_query(query).is_err()
+}
+
+/// Like [`may_change_session_values`], but for statements which we couldn't parse.
+///
+/// Since we don't know what these statements do, any statement other than a query or write is
+/// assumed to change session values (this covers `RESET`, `DISCARD` and `CALL`, for example), as
+/// are queries which call `set_config` or assign to MySQL user variables with `:=`.
+pub fn unparsed_may_change_session_values(query: &str) -> bool {
+    let query = query.to_ascii_lowercase();
+    query.contains("set_config")
+        || query.contains(":=")
+        || !matches!(
+            query.split_whitespace().next(),
+            Some("select" | "with" | "insert" | "update" | "delete" | "show" | "explain")
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use nom_sql::{parse_expr, Dialect};
+
+    use super::*;
+
+    fn parse(dialect: Dialect, expr: &str) -> Option<SessionParameter> {
+        SessionParameter::from_expr(&parse_expr(dialect, expr).unwrap())
+    }
+
+    #[test]
+    fn from_expr() {
+        assert_eq!(
+            parse(Dialect::MySQL, "@user_id"),
+            Some(SessionParameter::UserVariable("user_id".into()))
+        );
+        assert_eq!(parse(Dialect::MySQL, "@@sql_mode"), None);
+        assert_eq!(
+            parse(Dialect::PostgreSQL, "current_setting('App.Tenant')"),
+            Some(SessionParameter::Setting("app.tenant".into()))
+        );
+        assert_eq!(
+            parse(Dialect::PostgreSQL, "CURRENT_DATE"),
+            Some(SessionParameter::CurrentDate)
+        );
+        assert_eq!(
+            parse(Dialect::MySQL, "curdate()"),
+            Some(SessionParameter::CurrentDate)
+        );
+        assert_eq!(parse(Dialect::PostgreSQL, "now()"), None);
+    }
+
+    #[test]
+    fn resolve() {
+        let mut values = SessionValues::default();
+        let user_id = SessionParameter::UserVariable("user_id".into());
+        let tenant = SessionParameter::Setting("app.tenant".into());
+
+        assert_eq!(values.resolve(&user_id).unwrap(), DfValue::None);
+        values.resolve(&tenant).unwrap_err();
+
+        values.set(user_id.clone(), 4.into());
+        values.set(tenant.clone(), "acme".into());
+        assert_eq!(values.resolve(&user_id).unwrap(), DfValue::from(4));
+        assert_eq!(values.resolve(&tenant).unwrap(), DfValue::from("acme"));
+
+        values.invalidate(tenant.clone());
+        assert!(matches!(
+            values.resolve(&tenant),
+            Err(ReadySetError::UnknownSessionValue { .. })
+        ));
+        assert_eq!(values.resolve(&user_id).unwrap(), DfValue::from(4));
+    }
+
+    #[test]
+    fn resolve_after_invalidate_all() {
+        let mut values = SessionValues::default();
+        let user_id = SessionParameter::UserVariable("user_id".into());
+        let other = SessionParameter::UserVariable("other".into());
+        values.set(user_id.clone(), 4.into());
+
+        values.invalidate_all();
+        values.resolve(&user_id).unwrap_err();
+        // Even user variables we've never seen set might have been set since
+        values.resolve(&other).unwrap_err();
+
+        values.set(user_id.clone(), 5.into());
+        assert_eq!(values.resolve(&user_id).unwrap(), DfValue::from(5));
+    }
+
+    #[test]
+    fn current_date_requires_utc() {
+        let mut values = SessionValues::default();
+        values.resolve(&SessionParameter::CurrentDate).unwrap_err();
+
+        values.set(SessionParameter::TimeZone, "+00:00".into());
+        values.resolve(&SessionParameter::CurrentDate).unwrap();
+
+        values.set(SessionParameter::TimeZone, "America/New_York".into());
+        values.resolve(&SessionParameter::CurrentDate).unwrap_err();
+    }
+
+    #[test]
+    fn statements_changing_session_values() {
+        let parse = |q| nom_sql::parse_query(Dialect::PostgreSQL, q).unwrap();
+        assert!(may_change_session_values(&parse(
+            "SELECT set_config('app.tenant', 'acme', false)"
+        )));
+        assert!(may_change_session_values(&parse("ROLLBACK")));
+        assert!(!may_change_session_values(&parse(
+            "SELECT * FROM t WHERE tenant = current_setting('app.tenant')"
+        )));
+
+        assert!(unparsed_may_change_session_values("RESET app.tenant"));
+        assert!(unparsed_may_change_session_values("discard all"));
+        assert!(unparsed_may_change_session_values("SELECT @user_id := 4"));
+        assert!(!unparsed_may_change_session_values(
+            "SELECT some_unparseable_syntax FROM t"
+        ));
+    }
+}
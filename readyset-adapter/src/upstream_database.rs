@@ -7,6 +7,7 @@ use nom_sql::{SqlIdentifier, StartTransactionStatement};
 use readyset_client_metrics::QueryDestination;
 use readyset_data::DfValue;
 use readyset_errors::ReadySetError;
+pub use timestamp_service::client::WriteId;
 
 use crate::fallback_cache::FallbackCache;
 
@@ -147,16 +148,23 @@ pub trait UpstreamDatabase: Sized + Send {
     /// Execute a raw, un-prepared query
     async fn query<'a>(&'a mut self, query: &'a str) -> Result<Self::QueryResult<'a>, Self::Error>;
 
-    /// Execute a raw, un-prepared write query, constructing and returning a RYW ticket for the
-    /// write
-    // TODO: newtype RYW ticket, not just String
+    /// Execute a raw, un-prepared write query in its own transaction, returning the position of
+    /// that transaction in the upstream database's replication log, for use as a RYW ticket.
+    ///
+    /// Must not be called while a transaction is open on this connection.
     async fn handle_ryw_write<'a, S>(
         &'a mut self,
         query: S,
-    ) -> Result<(Self::QueryResult<'a>, String), Self::Error>
+    ) -> Result<(Self::QueryResult<'a>, WriteId), Self::Error>
     where
         S: AsRef<str> + Send + Sync + 'a;
 
+    /// Return a position in the upstream database's replication log which is at or after the
+    /// commit of every transaction this connection has committed so far, for use as a RYW ticket
+    /// for writes whose exact position isn't known, such as those made by prepared statements or
+    /// within explicit transactions.
+    async fn write_position(&mut self) -> Result<WriteId, Self::Error>;
+
     /// Handle starting a transaction with the upstream database.
     async fn start_tx<'a>(
         &'a mut self,
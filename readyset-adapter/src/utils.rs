@@ -6,7 +6,8 @@ use itertools::Itertools;
 use nom_sql::analysis::visit::{self, Visitor};
 use nom_sql::{
     BinaryOperator, Column, ColumnConstraint, CreateTableBody, DeleteStatement, Expr,
-    InsertStatement, Literal, SelectStatement, SqlIdentifier, SqlQuery, TableKey, UpdateStatement,
+    InsertStatement, Literal, Relation, SelectStatement, SqlIdentifier, SqlQuery, TableKey,
+    UpdateStatement,
 };
 use readyset_client::{Modification, Operation};
 use readyset_data::{DfType, DfValue, Dialect};
@@ -301,6 +302,31 @@ pub(crate) fn get_limit_parameters(query: &SelectStatement) -> Vec<Column> {
     limit_params
 }
 
+struct ReferredTableNamesVisitor<'ast> {
+    names: HashSet<&'ast SqlIdentifier>,
+}
+
+impl<'ast> Visitor<'ast> for ReferredTableNamesVisitor<'ast> {
+    type Error = !;
+
+    fn visit_table(&mut self, table: &'ast Relation) -> Result<(), Self::Error> {
+        self.names.insert(&table.name);
+        Ok(())
+    }
+}
+
+/// Returns the names of all the tables referenced anywhere in `query`, including in subqueries.
+///
+/// Since this only looks at the names of tables, the result may also include the names of CTEs
+/// and the tables qualifying column references.
+pub(crate) fn referred_table_names(query: &SelectStatement) -> HashSet<&SqlIdentifier> {
+    let mut visitor = ReferredTableNamesVisitor {
+        names: HashSet::new(),
+    };
+    let Ok(_) = visitor.visit_select_statement(query);
+    visitor.names
+}
+
 pub(crate) fn insert_statement_parameter_columns(query: &InsertStatement) -> Vec<&Column> {
     // need to find for which fields we *actually* have a parameter
     query
@@ -915,4 +941,19 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn test_referred_table_names() {
+        let query = "SELECT t1.a, s.b FROM t1 JOIN (SELECT b FROM t2) s ON t1.a = s.b \
+                     WHERE t1.c IN (SELECT c FROM public.t3)";
+        let SqlQuery::Select(query) = nom_sql::parse_query(Dialect::MySQL, query).unwrap() else {
+            unreachable!()
+        };
+
+        let names = referred_table_names(&query);
+        assert!(names.contains(&SqlIdentifier::from("t1")));
+        assert!(names.contains(&SqlIdentifier::from("t2")));
+        assert!(names.contains(&SqlIdentifier::from("t3")));
+        assert!(!names.contains(&SqlIdentifier::from("t4")));
+    }
 }
@@ -57,8 +57,21 @@ pub const MIGRATION_HANDLER_FAILURES: &str = "readyset_migration_handler_failure
 /// status in the query status cache. Requires optimization of locking.
 pub const MIGRATION_HANDLER_ALLOWED: &str = "readyset_migration_handler_allowed";
 
+/// Counter: The number of caches the migration handler has created automatically, because the
+/// query was executed often and slowly enough against the upstream database.
+pub const AUTO_CACHE_CREATED: &str = "readyset_auto_cache_created";
+
+/// Counter: The number of automatically created caches the migration handler has dropped because
+/// they went unread for too long.
+pub const AUTO_CACHE_DROPPED: &str = "readyset_auto_cache_dropped";
+
 /// Counter: The number of HTTP requests received at the noria-client.
 pub const ADAPTER_EXTERNAL_REQUESTS: &str = "readyset_noria_client_external_requests";
 
 /// Gauge: The number of currently connected SQL clients
 pub const CONNECTED_CLIENTS: &str = "readyset_noria_client_connected_clients";
+
+/// Counter: The number of reads which were proxied to the upstream database because ReadySet's
+/// replication lag exceeded the staleness budget of the session executing them, as set with
+/// `SET readyset_max_staleness`.
+pub const STALENESS_BUDGET_EXCEEDED: &str = "readyset_noria_client_staleness_budget_exceeded";
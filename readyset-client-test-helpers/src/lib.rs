@@ -79,6 +79,7 @@ pub struct TestBuilder {
     migration_mode: MigrationMode,
     recreate_database: bool,
     query_status_cache: Option<&'static QueryStatusCache>,
+    read_your_writes: bool,
 }
 
 impl Default for TestBuilder {
@@ -98,6 +99,7 @@ impl TestBuilder {
             migration_mode: MigrationMode::InRequestPath,
             recreate_database: true,
             query_status_cache: None,
+            read_your_writes: false,
         }
     }
 
@@ -145,6 +147,13 @@ impl TestBuilder {
         self
     }
 
+    /// Enable read-your-write consistency in both the adapter and the replicator
+    pub fn read_your_writes(mut self, read_your_writes: bool) -> Self {
+        self.backend_builder = self.backend_builder.enable_ryw(read_your_writes);
+        self.read_your_writes = read_your_writes;
+        self
+    }
+
     pub async fn build<A>(self) -> (A::ConnectionOpts, Handle, ShutdownSender)
     where
         A: Adapter + 'static,
@@ -192,6 +201,7 @@ impl TestBuilder {
         if !self.partial {
             builder.disable_partial();
         }
+        builder.set_read_your_writes(self.read_your_writes);
 
         if let Some((f, _)) = &fallback_url_and_db_name {
             builder.set_replication_url(f.clone());
@@ -23,6 +23,7 @@ mod standalone;
 pub use self::consul::ConsulAuthority;
 pub use self::local::{LocalAuthority, LocalAuthorityStore};
 pub use self::standalone::StandaloneAuthority;
+use crate::query::{Query, QueryStatus};
 use crate::ControllerDescriptor;
 
 // This should be an associated type on Authority but since Authority will only have one possible
@@ -35,6 +36,10 @@ pub type VolumeId = String;
 pub type WorkerId = String;
 
 const CREATE_CACHE_STATEMENTS_PATH: &str = "create_cache_statements";
+const QUERY_STATUSES_PATH: &str = "query_statuses";
+/// The maximum size of the serialized query statuses stored at [`QUERY_STATUSES_PATH`]. Consul
+/// limits values to 512 KB (after base64 encoding), so we leave plenty of room under that.
+const MAX_QUERY_STATUSES_BYTES: usize = 256 * 1024;
 
 /// A response to a `worker_heartbeat`, to inform the worker of its
 /// status within the system.
@@ -249,6 +254,61 @@ pub trait AuthorityControl: Send + Sync {
         })
         .await
     }
+
+    /// Return the statuses of all the queries that adapters in this ReadySet deployment have
+    /// persisted with [`persist_query_statuses`][Self::persist_query_statuses]
+    async fn query_statuses(&self) -> ReadySetResult<Vec<(Query, QueryStatus)>> {
+        Ok(self
+            .try_read(QUERY_STATUSES_PATH)
+            .await?
+            .unwrap_or_default())
+    }
+
+    /// Persist the given query statuses, replacing any previously persisted statuses for the same
+    /// queries and leaving the statuses of all other queries unchanged.
+    ///
+    /// Statuses which aren't [persistent][QueryStatus::is_persistent] remove any previously
+    /// persisted status for their query. Persisted statuses are kept in the order they were last
+    /// written, and the least recently written statuses are evicted once their total serialized
+    /// size exceeds [`MAX_QUERY_STATUSES_BYTES`].
+    async fn persist_query_statuses<I>(&self, statuses: I) -> ReadySetResult<()>
+    where
+        I: IntoIterator<Item = (Query, QueryStatus)> + Clone + Send,
+    {
+        self.read_modify_write::<_, Vec<(Query, QueryStatus)>, ReadySetError>(
+            QUERY_STATUSES_PATH,
+            move |persisted| {
+                let statuses = statuses.clone().into_iter().collect::<Vec<_>>();
+                let updated = statuses.iter().map(|(q, _)| q).collect::<HashSet<_>>();
+                let mut merged = persisted.unwrap_or_default();
+                merged.retain(|(q, _)| !updated.contains(q));
+                merged.extend(
+                    statuses
+                        .iter()
+                        .filter(|(_, status)| status.is_persistent())
+                        .cloned(),
+                );
+
+                // Each entry is followed by a comma or the closing bracket, plus the opening
+                // bracket
+                let mut size = 1;
+                let mut num_evicted = 0;
+                for (i, entry) in merged.iter().enumerate().rev() {
+                    size += serde_json::to_vec(entry)?.len() + 1;
+                    if size > MAX_QUERY_STATUSES_BYTES {
+                        num_evicted = i + 1;
+                        break;
+                    }
+                }
+                merged.drain(..num_evicted);
+
+                Ok(merged)
+            },
+        )
+        .await??;
+
+        Ok(())
+    }
 }
 
 async fn modify_create_cache_statements<A, F>(authority: &A, mut f: F) -> ReadySetResult<()>
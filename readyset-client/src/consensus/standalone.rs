@@ -287,6 +287,8 @@ mod tests {
     use tempfile::tempdir;
 
     use super::*;
+    use crate::consensus::MAX_QUERY_STATUSES_BYTES;
+    use crate::query::{MigrationState, Query, QueryStatus};
 
     #[tokio::test]
     async fn it_works() {
@@ -473,4 +475,74 @@ mod tests {
         stmts.sort();
         assert_eq!(stmts, STMTS);
     }
+
+    #[tokio::test]
+    async fn query_statuses() {
+        let dir = tempdir().unwrap();
+        let authority =
+            StandaloneAuthority::new(dir.path().to_str().unwrap(), "query_statuses").unwrap();
+
+        assert!(authority.query_statuses().await.unwrap().is_empty());
+
+        let status = |migration_state| QueryStatus {
+            migration_state,
+            execution_info: None,
+            always: false,
+        };
+        authority
+            .persist_query_statuses([
+                (Query::from("a"), status(MigrationState::Pending)),
+                (Query::from("b"), status(MigrationState::Unsupported)),
+            ])
+            .await
+            .unwrap();
+        authority
+            .persist_query_statuses([(Query::from("a"), status(MigrationState::Dropped))])
+            .await
+            .unwrap();
+
+        let mut statuses = authority.query_statuses().await.unwrap();
+        statuses.sort_by_key(|(q, _)| q.display(nom_sql::Dialect::MySQL).to_string());
+        assert_eq!(
+            statuses,
+            vec![
+                (Query::from("a"), status(MigrationState::Dropped)),
+                (Query::from("b"), status(MigrationState::Unsupported)),
+            ]
+        );
+
+        // Statuses that don't need persisting remove the previously persisted status
+        authority
+            .persist_query_statuses([(Query::from("b"), status(MigrationState::Pending))])
+            .await
+            .unwrap();
+        assert_eq!(
+            authority.query_statuses().await.unwrap(),
+            vec![(Query::from("a"), status(MigrationState::Dropped))]
+        );
+    }
+
+    #[tokio::test]
+    async fn query_statuses_evict_least_recently_persisted() {
+        let dir = tempdir().unwrap();
+        let authority = StandaloneAuthority::new(
+            dir.path().to_str().unwrap(),
+            "query_statuses_evict_least_recently_persisted",
+        )
+        .unwrap();
+
+        let status = QueryStatus::with_migration_state(MigrationState::Unsupported);
+        let query = |i: usize| Query::from(format!("{i:01024}"));
+        for i in 0..512 {
+            authority
+                .persist_query_statuses([(query(i), status.clone())])
+                .await
+                .unwrap();
+        }
+
+        let statuses = authority.query_statuses().await.unwrap();
+        assert!(serde_json::to_vec(&statuses).unwrap().len() <= MAX_QUERY_STATUSES_BYTES);
+        assert!(statuses.len() < 512);
+        assert_eq!(statuses.last(), Some(&(query(511), status)));
+    }
 }
@@ -28,7 +28,7 @@ use crate::debug::stats;
 use crate::internal::DomainIndex;
 use crate::metrics::MetricsDump;
 use crate::recipe::changelist::ChangeList;
-use crate::recipe::{ExtendRecipeResult, ExtendRecipeSpec, MigrationStatus};
+use crate::recipe::{CacheExplanation, ExtendRecipeResult, ExtendRecipeSpec, MigrationStatus};
 use crate::replication::ReplicationOffsets;
 use crate::status::ReadySetStatus;
 use crate::table::{Table, TableBuilder, TableRpc};
@@ -549,6 +549,19 @@ impl ReadySetHandle {
         self.rpc("dry_run", request, self.migration_timeout)
     }
 
+    /// Describes the dataflow that would be created for the cached query in the given set of
+    /// changes, by performing a dry-run migration with them.
+    ///
+    /// `Self::poll_ready` must have returned `Async::Ready` before you call this method.
+    pub fn explain_create_cache(
+        &mut self,
+        changes: ChangeList,
+    ) -> impl Future<Output = ReadySetResult<CacheExplanation>> + '_ {
+        let request = ExtendRecipeSpec::from(changes);
+
+        self.rpc("explain_create_cache", request, self.migration_timeout)
+    }
+
     /// Extend the existing recipe with the given set of queries.
     ///
     /// `Self::poll_ready` must have returned `Async::Ready` before you call this method.
@@ -734,6 +747,19 @@ impl ReadySetHandle {
         self.rpc("replication_offsets", (), self.request_timeout)
     }
 
+    /// Get how far the data replicated into ReadySet lags behind the upstream database, or `None`
+    /// if that isn't currently known (for example, because ReadySet is snapshotting, or isn't
+    /// replicating from an upstream database at all).
+    ///
+    /// The lag never increases by more than the time elapsed since it was last measured, so a
+    /// previously returned value plus the time since it was returned is an upper bound on the
+    /// current lag.
+    pub fn replication_lag(
+        &mut self,
+    ) -> impl Future<Output = ReadySetResult<Option<Duration>>> + '_ {
+        self.rpc("replication_lag", (), self.request_timeout)
+    }
+
     /// Get a list of all current tables node indexes that are involved in snapshotting.
     pub fn snapshotting_tables(
         &mut self,
@@ -175,11 +175,15 @@ impl Serialize for DeniedQuery {
 }
 
 /// The status of the query, which we use to determine if the query should be cached or not.
-#[derive(Debug, Clone, PartialEq, Eq)]
+///
+/// Query statuses are persisted in the authority so that they survive adapter restarts, but the
+/// execution info is only meaningful within a single adapter process, so it is not serialized.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct QueryStatus {
     /// The migration state of the query
     pub migration_state: MigrationState,
     /// The execution info of the query, if any
+    #[serde(skip)]
     pub execution_info: Option<ExecutionInfo>,
     /// If we should always cache the query (never proxy to upstream)
     pub always: bool,
@@ -245,6 +249,21 @@ impl QueryStatus {
         self.migration_state == MigrationState::DryRunSucceeded
     }
 
+    /// Returns true if this query status carries information that should be persisted across
+    /// adapter restarts.
+    ///
+    /// Pending queries are indistinguishable from queries we've never seen, and the state of
+    /// inlined queries is rebuilt as they're executed, so neither is persisted unless the query
+    /// should always be routed to ReadySet.
+    #[must_use]
+    pub fn is_persistent(&self) -> bool {
+        self.always
+            || !matches!(
+                self.migration_state,
+                MigrationState::Pending | MigrationState::Inlined(_)
+            )
+    }
+
     /// Returns true if the query should be considered "denied"
     #[must_use]
     pub fn is_denied(&self) -> bool {
@@ -4,6 +4,7 @@ pub mod changelist;
 
 use std::borrow::Cow;
 
+use nom_sql::Relation;
 use serde::{Deserialize, Serialize};
 
 pub use crate::recipe::changelist::ChangeList;
@@ -42,6 +43,25 @@ pub enum ExtendRecipeResult {
     Pending(u64),
 }
 
+/// A description of the dataflow that would be created for a query by `CREATE CACHE`, as
+/// returned by [`ReadySetHandle::explain_create_cache`]
+///
+/// [`ReadySetHandle::explain_create_cache`]: crate::ReadySetHandle::explain_create_cache
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct CacheExplanation {
+    /// Descriptions of the dataflow nodes that would be added to the graph for the query
+    pub new_nodes: Vec<String>,
+    /// Descriptions of the dataflow nodes already in the graph that the query would reuse
+    pub reused_nodes: Vec<String>,
+    /// The names of the columns that the cache would be keyed on
+    pub key_columns: Vec<String>,
+    /// Whether the cache would be partially materialized, filling in keys on demand, rather than
+    /// fully materialized
+    pub partial: bool,
+    /// The base tables that the query reads from
+    pub tables: Vec<Relation>,
+}
+
 /// The status of an actively running migration
 #[derive(Clone, Copy, Debug, Deserialize, Serialize)]
 pub enum MigrationStatus {
@@ -245,7 +245,8 @@ impl ResultIterator {
                         aggregate: aggregates.clone(),
                         filter: filter.take(),
                     }),
-                    limit,
+                    // The limit and offset can only be applied once the rows are sorted
+                    limit: None,
                     offset: None,
                     default_row: default_row.clone(),
                     non_empty: false,
@@ -262,11 +263,10 @@ impl ResultIterator {
                 });
 
                 if let Some(offset) = offset {
-                    if offset >= results.len() {
-                        results.clear();
-                    } else {
-                        results.drain(offset..);
-                    }
+                    results.drain(..offset.min(results.len()));
+                }
+                if let Some(limit) = limit {
+                    results.truncate(limit);
                 }
 
                 return ResultIterator::owned(vec![Results {
@@ -500,6 +500,7 @@ impl StreamingIterator for AggregateIterator {
         };
 
         self.advance_filtered();
+        let mut merged = false;
         while let Some(row) = self.inner.get() {
             if self
                 .aggregate
@@ -510,7 +511,12 @@ impl StreamingIterator for AggregateIterator {
                 break;
             }
 
-            for agg in &self.aggregate.aggregates {
+            for agg in self
+                .aggregate
+                .aggregates
+                .iter()
+                .filter(|agg| !agg.function.is_derived())
+            {
                 let col = agg.column;
                 aggregate_row[col] = agg
                     .function
@@ -518,9 +524,19 @@ impl StreamingIterator for AggregateIterator {
                     .expect("no fail");
             }
 
+            merged = true;
             self.advance_filtered();
         }
 
+        // If the group only had one row, the values for any derived aggregates are already correct
+        if merged {
+            for agg in &self.aggregate.aggregates {
+                if let Some(val) = agg.function.derive(&aggregate_row).expect("no fail") {
+                    aggregate_row[agg.column] = val;
+                }
+            }
+        }
+
         self.out_row = Some(aggregate_row)
     }
 
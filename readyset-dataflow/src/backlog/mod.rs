@@ -5,14 +5,15 @@ use std::sync::Arc;
 
 use ahash::RandomState;
 use common::SizeOf;
-use dataflow_expression::{PostLookup, ReaderProcessing};
+use dataflow_expression::{Expr, PostLookup, ReaderProcessing};
+use nom_sql::OrderType;
 use reader_map::{EvictionQuantity, EvictionStrategy};
 use readyset_client::consistency::Timestamp;
 use readyset_client::results::SharedResults;
 use readyset_client::KeyComparison;
 use vec1::Vec1;
 
-pub use self::multir::LookupError;
+pub use self::multir::{LookupError, RangeRowLimit};
 use crate::prelude::*;
 
 /// The kind of reader update notification, currently the eviction epoch of the writer
@@ -272,7 +273,7 @@ impl WriteHandle {
     }
 
     pub(crate) fn interval_difference(&self, key: KeyComparison) -> Option<Vec<KeyComparison>> {
-        match self.handle.read().get_multi(&[key]) {
+        match self.handle.read().get_multi(&[key], None) {
             Err(LookupError::Miss((misses, _))) => {
                 Some(misses.into_iter().map(|c| c.into_owned()).collect())
             }
@@ -365,7 +366,7 @@ impl WriteHandle {
                 let size = self
                     .handle
                     .read()
-                    .get_multi(std::slice::from_ref(&range_key))
+                    .get_multi(std::slice::from_ref(&range_key), None)
                     .map(|rs| {
                         rs.iter()
                             .flat_map(|rs| rs.iter().map(SizeOf::deep_size_of))
@@ -514,12 +515,77 @@ impl SingleReadHandle {
         }
     }
 
-    /// Lookup a list of keys under the same reader guard
+    /// Expand the given list of keys requested by a read into the list of keys to look up in the
+    /// reader, by replacing each equality key with one key for each set of
+    /// [`null_key_columns`](PostLookup::null_key_columns) configured for this reader
+    pub fn expand_keys(&self, keys: Vec<KeyComparison>) -> Vec<KeyComparison> {
+        let null_key_columns = &self.post_lookup.null_key_columns;
+        if null_key_columns.is_empty() {
+            return keys;
+        }
+
+        keys.into_iter()
+            .flat_map(|key| match key {
+                KeyComparison::Equal(key) => null_key_columns
+                    .iter()
+                    .map(|cols| {
+                        let mut key = key.clone();
+                        for &col in cols {
+                            if let Some(val) = key.get_mut(col) {
+                                *val = DfValue::None;
+                            }
+                        }
+                        KeyComparison::Equal(key)
+                    })
+                    .collect(),
+                range => vec![range],
+            })
+            .collect()
+    }
+
+    /// Returns the most rows that each range lookup into this reader needs to return to answer a
+    /// read with the given `limit` and `offset` (passed along with the read), or `None` if every
+    /// row in the range is needed.
+    ///
+    /// The rows returned by a range lookup are ordered and paginated after the lookup, so if
+    /// they're ordered by the key columns of the reader, only the first keys in that order
+    /// (rather than every key in the range) can end up in the results.
+    pub fn range_row_limit(
+        &self,
+        limit: Option<usize>,
+        offset: Option<usize>,
+        filter: Option<&Expr>,
+    ) -> Option<RangeRowLimit> {
+        // Filters and aggregates can both change how many rows we'd need to look at
+        if filter.is_some() || self.post_lookup.aggregates.is_some() {
+            return None;
+        }
+        let limit = limit.or(self.post_lookup.limit)?;
+        let order_by = self.post_lookup.order_by.as_ref()?;
+        let &(_, order_type) = order_by.first()?;
+        let ordered_by_key = order_by.len() >= self.index.len()
+            && order_by
+                .iter()
+                .zip(&self.index.columns)
+                .all(|(&(col, ot), &key_col)| col == key_col && ot == order_type);
+        if !ordered_by_key {
+            return None;
+        }
+
+        Some(RangeRowLimit {
+            rows: limit.saturating_add(offset.unwrap_or(0)),
+            reverse: order_type == OrderType::OrderDescending,
+        })
+    }
+
+    /// Lookup a list of keys under the same reader guard. If `row_limit` is given, range lookups
+    /// stop once they've found enough rows (see [`Self::range_row_limit`]).
     pub fn get_multi<'a>(
         &self,
         keys: &'a [KeyComparison],
+        row_limit: Option<RangeRowLimit>,
     ) -> Result<SharedResults, LookupError<'a>> {
-        match self.handle.get_multi(keys) {
+        match self.handle.get_multi(keys, row_limit) {
             Err(e) if e.is_miss() && self.trigger.is_none() => Ok(SharedResults::default()),
             r => r,
         }
@@ -530,10 +596,11 @@ impl SingleReadHandle {
     pub fn get_multi_with_notifier<'a>(
         &self,
         keys: &'a [KeyComparison],
+        row_limit: Option<RangeRowLimit>,
     ) -> Result<SharedResults, LookupError<'a, ReaderUpdatedNotifier>> {
         match self
             .handle
-            .get_multi_and_map_error(keys, || self.receiver.resubscribe())
+            .get_multi_and_map_error(keys, row_limit, || self.receiver.resubscribe())
         {
             Err(e) if e.is_miss() && self.trigger.is_none() => Ok(SharedResults::default()),
             r => r,
@@ -578,6 +645,7 @@ mod tests {
     use std::ops::Bound;
 
     use readyset_client::results::SharedRows;
+    use readyset_data::DfType;
 
     use super::*;
 
@@ -590,6 +658,50 @@ mod tests {
         }
     }
 
+    #[test]
+    fn range_row_limit() {
+        let ordered_by =
+            |order_by| ReaderProcessing::new(Some(order_by), None, None, None, None).unwrap();
+
+        let (r, _w) = new(
+            2,
+            Index::btree_map(vec![0]),
+            ordered_by(vec![
+                (0, OrderType::OrderDescending),
+                (1, OrderType::OrderAscending),
+            ]),
+        );
+        assert_eq!(
+            r.range_row_limit(Some(3), Some(2), None),
+            Some(RangeRowLimit {
+                rows: 5,
+                reverse: true
+            })
+        );
+        // Without a limit, every row could be part of the results
+        assert_eq!(r.range_row_limit(None, Some(2), None), None);
+        // Rows that don't match the filter don't count towards the limit
+        assert_eq!(
+            r.range_row_limit(
+                Some(3),
+                None,
+                Some(&Expr::Column {
+                    index: 1,
+                    ty: DfType::Bool
+                })
+            ),
+            None
+        );
+
+        // Rows ordered by anything other than the key could come from any key in the range
+        let (r, _w) = new(
+            2,
+            Index::btree_map(vec![0]),
+            ordered_by(vec![(1, OrderType::OrderAscending)]),
+        );
+        assert_eq!(r.range_row_limit(Some(3), None, None), None);
+    }
+
     #[test]
     fn store_works() {
         let a = vec![1i32.into(), "a".into()].into_boxed_slice();
@@ -704,6 +816,43 @@ mod tests {
         assert_eq!(r.get(&a[0..1]).unwrap()[0], b);
     }
 
+    #[test]
+    fn expand_null_key_columns() {
+        let a = vec![1i32.into(), 2i32.into(), "a".into()].into_boxed_slice();
+        let b = vec![DfValue::None, 2i32.into(), "b".into()].into_boxed_slice();
+        let c = vec![1i32.into(), DfValue::None, "c".into()].into_boxed_slice();
+
+        let (r, mut w) = new(
+            3,
+            Index::hash_map(vec![0, 1]),
+            ReaderProcessing::default().with_null_key_columns(vec![vec![0], vec![1]]),
+        );
+        w.add(vec![
+            Record::Positive(a.to_vec()),
+            Record::Positive(b.to_vec()),
+            Record::Positive(c.to_vec()),
+        ]);
+        w.swap();
+
+        let keys = r.expand_keys(vec![KeyComparison::Equal(vec1![1.into(), 2.into()])]);
+        assert_eq!(
+            keys,
+            vec![
+                KeyComparison::Equal(vec1![DfValue::None, 2.into()]),
+                KeyComparison::Equal(vec1![1.into(), DfValue::None]),
+            ]
+        );
+
+        let mut rows = r
+            .get_multi(&keys, None)
+            .unwrap()
+            .into_iter()
+            .flat_map(|rows| rows.iter().cloned().collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        rows.sort();
+        assert_eq!(rows, vec![b, c]);
+    }
+
     #[test]
     fn absorb_multi() {
         let a = vec![1i32.into(), "a".into()].into_boxed_slice();
@@ -792,14 +941,14 @@ mod tests {
                 Bound::Excluded(vec1![DfValue::from(10)]),
             ))];
 
-            assert!(r.get_multi(range_key).err().unwrap().is_miss());
+            assert!(r.get_multi(range_key, None).err().unwrap().is_miss());
 
             w.mark_filled(KeyComparison::from_range(
                 &(vec1![DfValue::from(0)]..vec1![DfValue::from(10)]),
             ))
             .unwrap();
             w.swap();
-            r.get_multi(range_key).unwrap();
+            r.get_multi(range_key, None).unwrap();
         }
     }
 
@@ -848,14 +997,14 @@ mod tests {
             ))
             .unwrap();
             w.swap();
-            r.get_multi(range_key).unwrap();
+            r.get_multi(range_key, None).unwrap();
 
             w.mark_hole(&KeyComparison::from_range(
                 &(vec1![DfValue::from(0)]..vec1![DfValue::from(10)]),
             ))
             .unwrap();
             w.swap();
-            assert!(r.get_multi(range_key).err().unwrap().is_miss());
+            assert!(r.get_multi(range_key, None).err().unwrap().is_miss());
         }
     }
 }
@@ -87,6 +87,54 @@ impl<'a, T> LookupError<'a, T> {
     }
 }
 
+/// A bound on the number of rows that need to be returned by each range lookup into a reader,
+/// used when the results of the lookup will be ordered by the key columns of the reader and
+/// limited after the lookup. Since every row for a key has the same key, only the first keys in
+/// the range (in the order of the results) can contribute rows to the results.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RangeRowLimit {
+    /// The number of rows to return from each range, after which no further keys are looked up.
+    /// Since all of the rows for a key are always returned together, more rows than this may be
+    /// returned.
+    pub rows: usize,
+    /// Whether the results are ordered by the key columns in descending rather than ascending
+    /// order
+    pub reverse: bool,
+}
+
+/// Add the results for each key in `hit`, the result of a range lookup, to `hits`, stopping once
+/// enough rows have been added to satisfy `row_limit` if given
+fn extend_range_hits<'a, I>(hits: &mut SharedResults, hit: I, row_limit: Option<RangeRowLimit>)
+where
+    I: DoubleEndedIterator<Item = &'a SharedRows>,
+{
+    fn take_rows<'a>(
+        hits: &mut SharedResults,
+        hit: impl Iterator<Item = &'a SharedRows>,
+        mut remaining: usize,
+    ) {
+        for rows in hit {
+            if remaining == 0 {
+                break;
+            }
+            remaining = remaining.saturating_sub(rows.len());
+            hits.push(rows.clone());
+        }
+    }
+
+    match row_limit {
+        None => hits.extend(hit.cloned()),
+        Some(RangeRowLimit {
+            rows,
+            reverse: false,
+        }) => take_rows(hits, hit, rows),
+        Some(RangeRowLimit {
+            rows,
+            reverse: true,
+        }) => take_rows(hits, hit.rev(), rows),
+    }
+}
+
 impl Handle {
     pub(super) fn timestamp(&self) -> Option<Timestamp> {
         match *self {
@@ -112,6 +160,7 @@ impl Handle {
     fn get_multi_single_handle<'a, T, F: Fn() -> T>(
         handle: &HandleSingle,
         keys: &'a [KeyComparison],
+        row_limit: Option<RangeRowLimit>,
         miss_meta: F,
     ) -> Result<SharedResults, LookupError<'a, T>> {
         let mut prev_keys = HashSet::new();
@@ -144,7 +193,9 @@ impl Handle {
                     let start_bound = start.as_ref().map(|v| &v[0]);
                     let end_bound = end.as_ref().map(|v| &v[0]);
                     match map.range(&(start_bound, end_bound)) {
-                        Ok(hit) => hits.extend(hit.map(|(_, v)| v.as_ref().clone())),
+                        Ok(hit) => {
+                            extend_range_hits(&mut hits, hit.map(|(_, v)| v.as_ref()), row_limit)
+                        }
                         Err(Miss(miss)) => misses.extend(miss.into_iter().map(|(start, end)| {
                             Cow::Owned(KeyComparison::Range((
                                 start.map(|s| vec1![s]),
@@ -166,6 +217,7 @@ impl Handle {
     fn get_multi_many_handle<'a, T, F: Fn() -> T>(
         handle: &HandleMany,
         keys: &'a [KeyComparison],
+        row_limit: Option<RangeRowLimit>,
         miss_meta: F,
     ) -> Result<SharedResults, LookupError<'a, T>> {
         let mut prev_keys = HashSet::new();
@@ -199,7 +251,9 @@ impl Handle {
                         start.as_ref().map(|v| v.as_slice()),
                         end.as_ref().map(|v| v.as_slice()),
                     )) {
-                        Ok(hit) => hits.extend(hit.map(|(_, v)| v.as_ref().clone())),
+                        Ok(hit) => {
+                            extend_range_hits(&mut hits, hit.map(|(_, v)| v.as_ref()), row_limit)
+                        }
                         Err(Miss(miss)) => misses.extend(miss.into_iter().map(|(start, end)| {
                             Cow::Owned(KeyComparison::Range((
                                 start.map(|s| Vec1::try_from_vec(s).unwrap()),
@@ -219,14 +273,16 @@ impl Handle {
     }
 
     /// Retrieve results for multiple keys from the map under the same read guard, assuring that all
-    /// of the values refer to the same state map.
+    /// of the values refer to the same state map. If `row_limit` is given, range lookups stop once
+    /// enough rows have been found.
     pub(super) fn get_multi<'a>(
         &self,
         keys: &'a [KeyComparison],
+        row_limit: Option<RangeRowLimit>,
     ) -> Result<SharedResults, LookupError<'a>> {
         match self {
-            Handle::Single(h) => Self::get_multi_single_handle(h, keys, || {}),
-            Handle::Many(h) => Self::get_multi_many_handle(h, keys, || {}),
+            Handle::Single(h) => Self::get_multi_single_handle(h, keys, row_limit, || {}),
+            Handle::Many(h) => Self::get_multi_many_handle(h, keys, row_limit, || {}),
         }
     }
 
@@ -237,11 +293,12 @@ impl Handle {
     pub(super) fn get_multi_and_map_error<'a, T, F: Fn() -> T>(
         &self,
         keys: &'a [KeyComparison],
+        row_limit: Option<RangeRowLimit>,
         miss_meta: F,
     ) -> Result<SharedResults, LookupError<'a, T>> {
         match self {
-            Handle::Single(h) => Self::get_multi_single_handle(h, keys, miss_meta),
-            Handle::Many(h) => Self::get_multi_many_handle(h, keys, miss_meta),
+            Handle::Single(h) => Self::get_multi_single_handle(h, keys, row_limit, miss_meta),
+            Handle::Many(h) => Self::get_multi_many_handle(h, keys, row_limit, miss_meta),
         }
     }
 
@@ -416,7 +473,7 @@ mod tests {
             Bound::Included(vec1![3i32.into()]),
         ));
 
-        let res = handle.get_multi(&[key]).unwrap();
+        let res = handle.get_multi(&[key], None).unwrap();
         assert_eq!(
             res.iter()
                 .flat_map(|rs| rs.iter())
@@ -428,6 +485,62 @@ mod tests {
         );
     }
 
+    #[test]
+    fn get_single_range_with_row_limit() {
+        let (mut w, handle) = make_single();
+
+        (0i32..10)
+            .flat_map(|n| {
+                [
+                    (n.into(), vec![n.into(), 0i32.into()].into_boxed_slice()),
+                    (n.into(), vec![n.into(), 1i32.into()].into_boxed_slice()),
+                ]
+            })
+            .for_each(|(k, v)| {
+                w.insert(k, v);
+            });
+
+        w.insert_range((DfValue::from(0i32))..(DfValue::from(10i32)));
+        w.publish();
+
+        let key = KeyComparison::Range((
+            Bound::Included(vec1![2i32.into()]),
+            Bound::Excluded(vec1![8i32.into()]),
+        ));
+        let keys_returned = |row_limit| {
+            handle
+                .get_multi(std::slice::from_ref(&key), Some(row_limit))
+                .unwrap()
+                .iter()
+                .map(|rs| rs[0][0].clone())
+                .collect::<Vec<_>>()
+        };
+
+        // All the rows for the last key are returned, even if that's more rows than needed
+        assert_eq!(
+            keys_returned(RangeRowLimit {
+                rows: 3,
+                reverse: false
+            }),
+            vec![DfValue::from(2i32), DfValue::from(3i32)]
+        );
+        assert_eq!(
+            keys_returned(RangeRowLimit {
+                rows: 4,
+                reverse: true
+            }),
+            vec![DfValue::from(7i32), DfValue::from(6i32)]
+        );
+        assert_eq!(
+            keys_returned(RangeRowLimit {
+                rows: 100,
+                reverse: false
+            })
+            .len(),
+            6
+        );
+    }
+
     #[test]
     fn contains_key_single() {
         let (mut w, handle) = make_single();
@@ -465,7 +578,7 @@ mod tests {
             Bound::Included(vec1![3i32.into(), 3i32.into()]),
         ));
 
-        let res = handle.get_multi(&[key]).unwrap();
+        let res = handle.get_multi(&[key], None).unwrap();
         assert_eq!(
             res.iter()
                 .flat_map(|rs| rs.iter())
@@ -497,7 +610,7 @@ mod tests {
         ];
 
         // Ensure that get_multi() deduplicates equal keys
-        let res = handle.get_multi(&keys).unwrap();
+        let res = handle.get_multi(&keys, None).unwrap();
         assert_eq!(
             res.iter()
                 .flat_map(|rs| rs.iter())
@@ -533,7 +646,7 @@ mod tests {
         ];
 
         // Ensure that get_multi() deduplicates equal keys
-        let res = handle.get_multi(&keys).unwrap();
+        let res = handle.get_multi(&keys, None).unwrap();
         assert_eq!(
             res.iter()
                 .flat_map(|rs| rs.iter())
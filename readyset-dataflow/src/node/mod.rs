@@ -6,6 +6,7 @@ use readyset_data::{DfType, Dialect};
 use serde::{Deserialize, Serialize};
 
 use crate::ops::grouped::aggregate::AggregatorState;
+use crate::ops::grouped::collect::CollectState;
 use crate::ops::grouped::concat::GroupConcatState;
 use crate::ops::{self};
 use crate::prelude::*;
@@ -191,6 +192,7 @@ impl DanglingDomainNode {
 pub enum AuxiliaryNodeState {
     Aggregation(AggregatorState),
     Concat(GroupConcatState),
+    Collect(CollectState),
 }
 
 // external parts of Ingredient
@@ -354,6 +356,9 @@ impl Node {
     ///    ⋈    |  Join
     ///    ⋉    |  Left join
     ///    ⋃    |  Union
+    ///    ∩    |  Intersect
+    ///    ∖    |  Except
+    ///    μ    |  Unnest
     ///    →|   |  Ingress
     ///    |→   |  Egress
     ///    ÷    |  Dropped
@@ -370,14 +375,18 @@ impl Node {
                     Some(AuxiliaryNodeState::Aggregation(Default::default()))
                 }
                 NodeOperator::Concat(_) => Some(AuxiliaryNodeState::Concat(Default::default())),
+                NodeOperator::Collect(_) => Some(AuxiliaryNodeState::Collect(Default::default())),
                 NodeOperator::Extremum(_)
                 | NodeOperator::Join(_)
                 | NodeOperator::Paginate(_)
                 | NodeOperator::Project(_)
                 | NodeOperator::Union(_)
+                | NodeOperator::SetOperation(_)
                 | NodeOperator::Identity(_)
                 | NodeOperator::Filter(_)
-                | NodeOperator::TopK(_) => None,
+                | NodeOperator::TopK(_)
+                | NodeOperator::Window(_)
+                | NodeOperator::Unnest(_) => None,
             },
             NodeType::Ingress
             | NodeType::Base(_)
@@ -48,6 +48,9 @@ impl NodeType {
     ///    ⋈    |  Join
     ///    ⋉    |  Left join
     ///    ⋃    |  Union
+    ///    ∩    |  Intersect
+    ///    ∖    |  Except
+    ///    μ    |  Unnest
     ///    →|   |  Ingress
     ///    |→   |  Egress
     ///    ÷    |  Sharder
@@ -3,11 +3,13 @@ use std::collections::HashMap;
 use std::hash::{Hash, Hasher};
 
 pub use nom_sql::{BinaryOperator, Literal, SqlType};
-use readyset_data::{Collation, DfType};
+use readyset_data::dialect::SqlEngine;
+use readyset_data::{Collation, DfType, Dialect};
 use readyset_errors::{invariant, ReadySetResult};
 use serde::{Deserialize, Serialize};
 
 use crate::node::AuxiliaryNodeState;
+use crate::ops::grouped::collect::Collection;
 use crate::ops::grouped::{GroupedOperation, GroupedOperator};
 use crate::prelude::*;
 
@@ -16,12 +18,39 @@ use crate::prelude::*;
 pub enum Aggregation {
     /// Count the number of non-null values.
     Count,
+    /// Count the number of distinct non-null values. Maintains the multiplicity of each value in
+    /// HashMap
+    CountDistinct,
     /// Sum the value of the `over` column for all records of each group.
     Sum,
+    /// Sum the distinct values of the `over` column for all records of each group. Maintains the
+    /// multiplicity of each value in HashMap
+    SumDistinct,
     /// Average the value of the `over` column. Maintains count and sum in HashMap
     Avg,
     /// Concatenates using the given separator between values.
     GroupConcat { separator: String },
+    /// Standard deviation of the `over` column, either of the sample or of the population.
+    /// Maintains count, mean and sum of squared differences from the mean in HashMap
+    Stddev { sample: bool },
+    /// Variance of the `over` column, either of the sample or of the population. Maintains
+    /// count, mean and sum of squared differences from the mean in HashMap
+    Variance { sample: bool },
+    /// True if the `over` column is true for all records of each group. Maintains counts of true
+    /// and false values in HashMap
+    BoolAnd,
+    /// True if the `over` column is true for any record of each group. Maintains counts of true
+    /// and false values in HashMap
+    BoolOr,
+    /// Bitwise AND of the `over` column. Maintains counts of set bits in HashMap
+    BitAnd,
+    /// Bitwise OR of the `over` column. Maintains counts of set bits in HashMap
+    BitOr,
+    /// Collects the values of the `over` column into an array, string, or JSON value. Like
+    /// `GroupConcat`, these are implemented by a separate [`Collect`] operator in the dataflow.
+    ///
+    /// [`Collect`]: crate::ops::grouped::collect::Collect
+    Collect(Collection),
 }
 
 impl Aggregation {
@@ -29,20 +58,22 @@ impl Aggregation {
     ///
     /// The aggregation will aggregate the value in column number `over` from its inputs (i.e.,
     /// from the `src` node in the graph), and use the columns in the `group_by` array as a group
-    /// identifier.
+    /// identifier. The `dialect` determines the result type of the aggregation, for those
+    /// aggregations whose result types differ between MySQL and PostgreSQL.
     pub fn over(
         self,
         src: NodeIndex,
         over: usize,
         group_by: &[usize],
         over_col_ty: &DfType,
+        dialect: Dialect,
     ) -> ReadySetResult<GroupedOperator<Aggregator>> {
         let out_ty = match &self {
-            Aggregation::Count { .. } => DfType::BigInt,
+            Aggregation::Count | Aggregation::CountDistinct => DfType::BigInt,
             // The SUM() and AVG() functions return a DECIMAL value for exact-value arguments
             // (integer or DECIMAL), and a DOUBLE value for approximate-value arguments (FLOAT or
             // DOUBLE).
-            Aggregation::Sum | Aggregation::Avg => {
+            Aggregation::Sum | Aggregation::SumDistinct | Aggregation::Avg => {
                 if over_col_ty.is_any_float() {
                     DfType::Double
                 } else {
@@ -50,6 +81,22 @@ impl Aggregation {
                 }
             }
             Aggregation::GroupConcat { .. } => DfType::Text(/* TODO */ Collation::default()),
+            // MySQL always returns a DOUBLE for the statistical aggregates, whereas PostgreSQL
+            // returns a NUMERIC for exact-value arguments (like it does for AVG)
+            Aggregation::Stddev { .. } | Aggregation::Variance { .. } => match dialect.engine() {
+                SqlEngine::PostgreSQL if !over_col_ty.is_any_float() => DfType::DEFAULT_NUMERIC,
+                _ => DfType::Double,
+            },
+            Aggregation::BoolAnd | Aggregation::BoolOr => DfType::Bool,
+            // MySQL's BIT_AND() and BIT_OR() return an unsigned 64-bit integer, whereas
+            // PostgreSQL's return the same type as their argument
+            Aggregation::BitAnd | Aggregation::BitOr => match dialect.engine() {
+                SqlEngine::MySQL => DfType::UnsignedBigInt,
+                SqlEngine::PostgreSQL => over_col_ty.clone(),
+            },
+            Aggregation::Collect(_) => {
+                internal!("Collections are separate from the other aggregations in the dataflow.")
+            }
         };
 
         Ok(GroupedOperator::new(
@@ -125,10 +172,176 @@ impl AverageDataPair {
     }
 }
 
+/// For storing (Count, Mean, Sum of squared differences from the mean) in additional state for
+/// Stddev and Variance.
+///
+/// These are maintained using Welford's algorithm, which (unlike keeping a sum of squares) doesn't
+/// suffer from catastrophic cancellation when the variance is small relative to the mean. Removing
+/// a value applies the inverse of the update for adding it.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct VarianceData {
+    count: i64,
+    mean: f64,
+    m2: f64,
+}
+
+impl VarianceData {
+    fn apply_diff(&mut self, d: NumericalDiff) -> ReadySetResult<()> {
+        let value = f64::try_from(&d.value)?;
+        if d.positive {
+            self.count += 1;
+            let delta = value - self.mean;
+            self.mean += delta / self.count as f64;
+            self.m2 += delta * (value - self.mean);
+        } else if self.count <= 1 {
+            // Start over from exactly zero rather than accumulating error from the inverse update
+            *self = Self::default();
+        } else {
+            self.count -= 1;
+            let delta = value - self.mean;
+            self.mean -= delta / self.count as f64;
+            self.m2 -= delta * (value - self.mean);
+            if self.count == 1 {
+                // The variance of a single value is exactly zero
+                self.m2 = 0.0;
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the variance of the values seen so far, or `None` if there are too few values for
+    /// the variance to be defined
+    fn variance(&self, sample: bool) -> Option<f64> {
+        let denominator = if sample { self.count - 1 } else { self.count };
+        if denominator <= 0 {
+            return None;
+        }
+
+        Some(self.m2.max(0.0) / denominator as f64)
+    }
+}
+
+/// For storing the number of true and false values in additional state for BoolAnd and BoolOr.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct BoolCounts {
+    true_count: i64,
+    false_count: i64,
+}
+
+impl BoolCounts {
+    fn apply_diff(&mut self, d: NumericalDiff) {
+        let count = if d.value.is_truthy() {
+            &mut self.true_count
+        } else {
+            &mut self.false_count
+        };
+        if d.positive {
+            *count += 1;
+        } else {
+            *count -= 1;
+        }
+    }
+}
+
+/// For storing the number of values, and the number of values with each bit set, in additional
+/// state for BitAnd and BitOr.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct BitCounts {
+    count: i64,
+    /// The number of values with each bit set, indexed by bit position
+    bits: Vec<i64>,
+}
+
+impl Default for BitCounts {
+    fn default() -> Self {
+        Self {
+            count: 0,
+            bits: vec![0; u64::BITS as usize],
+        }
+    }
+}
+
+impl BitCounts {
+    fn apply_diff(&mut self, d: NumericalDiff) -> ReadySetResult<()> {
+        // Negative values are treated as their two's complement representation
+        let value =
+            d.value.as_int().ok_or_else(|| {
+                invalid_err!("Cannot compute a bitwise aggregate of a non-integer")
+            })? as u64;
+        let delta = if d.positive { 1 } else { -1 };
+        self.count += delta;
+        for (bit, count) in self.bits.iter_mut().enumerate() {
+            if value & (1 << bit) != 0 {
+                *count += delta;
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the bitwise AND (if `and` is true) or OR of the values seen so far, or `None` if
+    /// there are no values
+    fn value(&self, and: bool) -> Option<u64> {
+        if self.count <= 0 {
+            return None;
+        }
+
+        Some(
+            self.bits
+                .iter()
+                .enumerate()
+                .filter(|(_, count)| {
+                    if and {
+                        **count == self.count
+                    } else {
+                        **count > 0
+                    }
+                })
+                .fold(0, |acc, (bit, _)| acc | (1 << bit)),
+        )
+    }
+}
+
+/// For storing the multiplicity of each distinct value in additional state for CountDistinct and
+/// SumDistinct.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct DistinctValues {
+    multiplicities: HashMap<DfValue, usize>,
+}
+
+impl DistinctValues {
+    /// Applies the diff to the multiplicities, returning true if that changed the set of distinct
+    /// values (because the value was either seen for the first time, or removed for the last
+    /// time)
+    fn apply_diff(&mut self, d: &NumericalDiff) -> bool {
+        if d.positive {
+            let multiplicity = self.multiplicities.entry(d.value.clone()).or_default();
+            *multiplicity += 1;
+            *multiplicity == 1
+        } else {
+            match self.multiplicities.get_mut(&d.value) {
+                Some(multiplicity) if *multiplicity > 1 => {
+                    *multiplicity -= 1;
+                    false
+                }
+                Some(_) => {
+                    self.multiplicities.remove(&d.value);
+                    true
+                }
+                // We never saw this value, so there's nothing to remove
+                None => false,
+            }
+        }
+    }
+}
+
 #[derive(Debug, Default)]
 /// Auxiliary State for an Aggregator node, which is owned by a Domain
 pub struct AggregatorState {
     count_sum_map: HashMap<GroupHash, AverageDataPair>,
+    distinct_map: HashMap<GroupHash, DistinctValues>,
+    variance_map: HashMap<GroupHash, VarianceData>,
+    bool_map: HashMap<GroupHash, BoolCounts>,
+    bit_map: HashMap<GroupHash, BitCounts>,
 }
 
 impl Aggregator {
@@ -144,7 +357,33 @@ impl Aggregator {
         hasher.finish()
     }
 
+    /// Returns the value of a BitAnd or BitOr over no non-null values. MySQL returns the identity
+    /// of the operation (all bits set for `BIT_AND()`, and no bits set for `BIT_OR()`), whereas
+    /// PostgreSQL returns NULL.
+    fn empty_bit_value(&self) -> DfValue {
+        match (&self.op, &self.out_ty) {
+            (Aggregation::BitAnd, DfType::UnsignedBigInt) => DfValue::from(u64::MAX),
+            (Aggregation::BitOr, DfType::UnsignedBigInt) => DfValue::from(0u64),
+            _ => DfValue::None,
+        }
+    }
+
     fn new_data(&self) -> ReadySetResult<DfValue> {
+        if matches!(self.op, Aggregation::BitAnd | Aggregation::BitOr) {
+            return Ok(self.empty_bit_value());
+        }
+
+        // These aggregations are NULL until they've seen a non-null value
+        if matches!(
+            self.op,
+            Aggregation::Stddev { .. }
+                | Aggregation::Variance { .. }
+                | Aggregation::BoolAnd
+                | Aggregation::BoolOr
+        ) {
+            return Ok(DfValue::None);
+        }
+
         match &self.out_ty {
             DfType::BigInt => Ok(DfValue::Int(Default::default())),
             DfType::Double => Ok(DfValue::Double(Default::default())),
@@ -206,10 +445,14 @@ impl GroupedOperation for Aggregator {
             }
         };
 
-        let count_sum_map = match auxiliary_node_state {
-            Some(AuxiliaryNodeState::Aggregation(ref mut aggregator_state)) => {
-                &mut aggregator_state.count_sum_map
-            }
+        let AggregatorState {
+            count_sum_map,
+            distinct_map,
+            variance_map,
+            bool_map,
+            bit_map,
+        } = match auxiliary_node_state {
+            Some(AuxiliaryNodeState::Aggregation(ref mut aggregator_state)) => aggregator_state,
             Some(_) => internal!("Incorrect auxiliary state for Aggregation node"),
             None => internal!("Missing auxiliary state for Aggregation node"),
         };
@@ -224,6 +467,58 @@ impl GroupedOperation for Aggregator {
                 .apply_diff(diff)
         };
 
+        let mut apply_distinct = |curr: DfValue, diff: Self::Diff| -> ReadySetResult<DfValue> {
+            let changed = distinct_map
+                .entry(diff.group_hash)
+                .or_default()
+                .apply_diff(&diff);
+            if !changed {
+                return Ok(curr);
+            }
+
+            match self.op {
+                Aggregation::CountDistinct => apply_count(curr, diff),
+                _ => apply_sum(curr, diff),
+            }
+        };
+
+        let mut apply_variance = |diff: Self::Diff, sample: bool| -> ReadySetResult<Option<f64>> {
+            let data = variance_map.entry(diff.group_hash).or_default();
+            data.apply_diff(diff)?;
+            Ok(data.variance(sample))
+        };
+
+        let stat_value = |variance: Option<f64>| -> ReadySetResult<DfValue> {
+            match variance {
+                Some(variance) => {
+                    DfValue::Double(variance).coerce_to(&self.out_ty, &DfType::Double)
+                }
+                None => Ok(DfValue::None),
+            }
+        };
+
+        let mut apply_bool = |diff: Self::Diff, and: bool| -> ReadySetResult<DfValue> {
+            let counts = bool_map.entry(diff.group_hash).or_default();
+            counts.apply_diff(diff);
+            Ok(if counts.true_count + counts.false_count <= 0 {
+                DfValue::None
+            } else if and {
+                (counts.false_count == 0).into()
+            } else {
+                (counts.true_count > 0).into()
+            })
+        };
+
+        let mut apply_bit = |diff: Self::Diff, and: bool| -> ReadySetResult<DfValue> {
+            let counts = bit_map.entry(diff.group_hash).or_default();
+            counts.apply_diff(diff)?;
+            Ok(match counts.value(and) {
+                None => self.empty_bit_value(),
+                Some(value) if self.out_ty == DfType::UnsignedBigInt => DfValue::from(value),
+                Some(value) => DfValue::from(value as i64),
+            })
+        };
+
         let apply_diff =
             |curr: ReadySetResult<DfValue>, diff: Self::Diff| -> ReadySetResult<DfValue> {
                 if diff.value.is_none() {
@@ -232,11 +527,25 @@ impl GroupedOperation for Aggregator {
 
                 match self.op {
                     Aggregation::Count { .. } => apply_count(curr?, diff),
+                    Aggregation::CountDistinct | Aggregation::SumDistinct => {
+                        apply_distinct(curr?, diff)
+                    }
                     Aggregation::Sum => apply_sum(curr?, diff),
                     Aggregation::Avg => apply_avg(curr?, diff),
                     Aggregation::GroupConcat { separator: _ } => internal!(
                         "GroupConcats are separate from the other aggregations in the dataflow."
                     ),
+                    Aggregation::Collect(_) => internal!(
+                        "Collections are separate from the other aggregations in the dataflow."
+                    ),
+                    Aggregation::Stddev { sample } => {
+                        stat_value(apply_variance(diff, sample)?.map(f64::sqrt))
+                    }
+                    Aggregation::Variance { sample } => stat_value(apply_variance(diff, sample)?),
+                    Aggregation::BoolAnd => apply_bool(diff, true),
+                    Aggregation::BoolOr => apply_bool(diff, false),
+                    Aggregation::BitAnd => apply_bit(diff, true),
+                    Aggregation::BitOr => apply_bit(diff, false),
                 }
             };
 
@@ -249,19 +558,41 @@ impl GroupedOperation for Aggregator {
         if !detailed {
             return match self.op {
                 Aggregation::Count { .. } => "+".to_owned(),
+                Aggregation::CountDistinct => "+ DISTINCT".to_owned(),
                 Aggregation::Sum => "𝛴".to_owned(),
+                Aggregation::SumDistinct => "𝛴 DISTINCT".to_owned(),
                 Aggregation::Avg => "Avg".to_owned(),
                 Aggregation::GroupConcat { separator: ref s } => {
                     format!("||({})", s)
                 }
+                Aggregation::Stddev { .. } => "σ".to_owned(),
+                Aggregation::Variance { .. } => "σ²".to_owned(),
+                Aggregation::BoolAnd => "∀".to_owned(),
+                Aggregation::BoolOr => "∃".to_owned(),
+                Aggregation::BitAnd => "&".to_owned(),
+                Aggregation::BitOr => "|".to_owned(),
+                Aggregation::Collect(ref kind) => kind.to_string(),
             };
         }
 
         let op_string = match self.op {
             Aggregation::Count { .. } => "|*|".to_owned(),
+            Aggregation::CountDistinct => format!("|DISTINCT {}|", self.over),
             Aggregation::Sum => format!("𝛴({})", self.over),
+            Aggregation::SumDistinct => format!("𝛴(DISTINCT {})", self.over),
             Aggregation::Avg => format!("Avg({})", self.over),
             Aggregation::GroupConcat { separator: ref s } => format!("||({}, {})", s, self.over),
+            Aggregation::Stddev { sample } => {
+                format!("σ{}({})", if sample { "_samp" } else { "_pop" }, self.over)
+            }
+            Aggregation::Variance { sample } => {
+                format!("σ²{}({})", if sample { "_samp" } else { "_pop" }, self.over)
+            }
+            Aggregation::BoolAnd => format!("∀({})", self.over),
+            Aggregation::BoolOr => format!("∃({})", self.over),
+            Aggregation::BitAnd => format!("&({})", self.over),
+            Aggregation::BitOr => format!("|({})", self.over),
+            Aggregation::Collect(ref kind) => format!("{}({})", kind, self.over),
         };
         let group_cols = self
             .group
@@ -282,14 +613,14 @@ impl GroupedOperation for Aggregator {
 
     fn empty_value(&self) -> Option<DfValue> {
         match self.op {
-            Aggregation::Count { .. } => Some(0.into()),
+            Aggregation::Count | Aggregation::CountDistinct => Some(0.into()),
             _ => None,
         }
     }
 
     fn emit_empty(&self) -> bool {
         match self.op {
-            Aggregation::Count { .. } | Aggregation::GroupConcat { .. } => {
+            Aggregation::Count | Aggregation::CountDistinct | Aggregation::GroupConcat { .. } => {
                 self.group_by().is_empty()
             }
             _ => false,
@@ -318,7 +649,13 @@ mod tests {
             "identity",
             &["x", "ys"],
             aggregation
-                .over(s.as_global(), 1, &[0], &DfType::Double)
+                .over(
+                    s.as_global(),
+                    1,
+                    &[0],
+                    &DfType::Double,
+                    Dialect::DEFAULT_MYSQL,
+                )
                 .unwrap(),
             mat,
         );
@@ -332,7 +669,13 @@ mod tests {
             "identity",
             &["x", "z", "ys"],
             aggregation
-                .over(s.as_global(), 1, &[0, 2], &DfType::Double)
+                .over(
+                    s.as_global(),
+                    1,
+                    &[0, 2],
+                    &DfType::Double,
+                    Dialect::DEFAULT_MYSQL,
+                )
                 .unwrap(),
             mat,
         );
@@ -344,19 +687,24 @@ mod tests {
         let src = 0.into();
 
         let c = Aggregation::Count
-            .over(src, 1, &[0, 2], &DfType::Unknown)
+            .over(src, 1, &[0, 2], &DfType::Unknown, Dialect::DEFAULT_MYSQL)
             .unwrap();
         assert_eq!(c.description(true), "|*| γ[0, 2]");
 
         let s = Aggregation::Sum
-            .over(src, 1, &[2, 0], &DfType::Unknown)
+            .over(src, 1, &[2, 0], &DfType::Unknown, Dialect::DEFAULT_MYSQL)
             .unwrap();
         assert_eq!(s.description(true), "𝛴(1) γ[2, 0]");
 
         let a = Aggregation::Avg
-            .over(src, 1, &[2, 0], &DfType::Unknown)
+            .over(src, 1, &[2, 0], &DfType::Unknown, Dialect::DEFAULT_MYSQL)
             .unwrap();
         assert_eq!(a.description(true), "Avg(1) γ[2, 0]");
+
+        let c = Aggregation::CountDistinct
+            .over(src, 1, &[0, 2], &DfType::Unknown, Dialect::DEFAULT_MYSQL)
+            .unwrap();
+        assert_eq!(c.description(true), "|DISTINCT 1| γ[0, 2]");
     }
 
     /// Testing count emits correct records with single column group and single over column
@@ -1129,4 +1477,227 @@ mod tests {
             .into()
         );
     }
+
+    /// Returns the aggregated value of the (single) positive record in `rs`
+    fn positive_value(rs: Records) -> DfValue {
+        let mut positives = rs.into_iter().filter_map(|r| match r {
+            Record::Positive(r) => Some(r),
+            Record::Negative(_) => None,
+        });
+        let r = positives.next().unwrap();
+        assert!(positives.next().is_none());
+        r[1].clone()
+    }
+
+    #[test]
+    fn count_distinct_forwards() {
+        let mut c = setup(Aggregation::CountDistinct, true);
+
+        let rs = c.narrow_one_row(vec![1.into(), 1.into()], true);
+        assert_eq!(positive_value(rs), 1.into());
+
+        // A duplicate value doesn't change the count
+        let rs = c.narrow_one_row(vec![1.into(), 1.into()], true);
+        assert_eq!(positive_value(rs), 1.into());
+
+        let rs = c.narrow_one_row(vec![1.into(), 2.into()], true);
+        assert_eq!(positive_value(rs), 2.into());
+
+        // Removing one of the duplicates doesn't change the count, but removing the last one does
+        let rs = c.narrow_one_row((vec![1.into(), 1.into()], false), true);
+        assert_eq!(positive_value(rs), 2.into());
+        let rs = c.narrow_one_row((vec![1.into(), 1.into()], false), true);
+        assert_eq!(positive_value(rs), 1.into());
+
+        // Other groups are counted independently
+        let rs = c.narrow_one_row(vec![2.into(), 2.into()], true);
+        assert_eq!(positive_value(rs), 1.into());
+
+        // Nulls aren't counted
+        let rs = c.narrow_one_row(vec![2.into(), DfValue::None], true);
+        assert_eq!(positive_value(rs), 1.into());
+    }
+
+    #[test]
+    fn sum_distinct_forwards() {
+        let mut c = setup(Aggregation::SumDistinct, true);
+
+        let u = vec![
+            (vec![1.into(), 2.into()], true),
+            (vec![1.into(), 2.into()], true),
+            (vec![1.into(), 3.into()], true),
+        ];
+        let rs = c.narrow_one(u, true);
+        assert_eq!(positive_value(rs), DfValue::Double(5.0));
+
+        let rs = c.narrow_one_row((vec![1.into(), 2.into()], false), true);
+        assert_eq!(positive_value(rs), DfValue::Double(5.0));
+        let rs = c.narrow_one_row((vec![1.into(), 2.into()], false), true);
+        assert_eq!(positive_value(rs), DfValue::Double(3.0));
+        let rs = c.narrow_one_row(vec![1.into(), 3.into()], true);
+        assert_eq!(positive_value(rs), DfValue::Double(3.0));
+    }
+
+    #[test]
+    fn stddev_and_variance_forwards() {
+        let mut pop = setup(Aggregation::Stddev { sample: false }, true);
+        let mut samp = setup(Aggregation::Variance { sample: true }, true);
+
+        // The sample variance of a single value isn't defined, but its population stddev is 0
+        let rs = pop.narrow_one_row(vec![1.into(), 2.into()], true);
+        assert_eq!(positive_value(rs), DfValue::Double(0.0));
+        let rs = samp.narrow_one_row(vec![1.into(), 2.into()], true);
+        assert_eq!(positive_value(rs), DfValue::None);
+
+        for v in [4, 4, 4, 5, 5, 7] {
+            pop.narrow_one_row(vec![1.into(), v.into()], true);
+            samp.narrow_one_row(vec![1.into(), v.into()], true);
+        }
+
+        // [2, 4, 4, 4, 5, 5, 7, 9] has a mean of 5, and a sum of squared deviations of 32
+        let rs = pop.narrow_one_row(vec![1.into(), 9.into()], true);
+        assert_eq!(positive_value(rs), DfValue::Double(2.0));
+        let rs = samp.narrow_one_row(vec![1.into(), 9.into()], true);
+        match positive_value(rs) {
+            DfValue::Double(v) => assert!((v - 32.0 / 7.0).abs() < 1e-9),
+            v => unreachable!("unexpected value {v:?}"),
+        }
+
+        // Other groups are aggregated independently
+        let rs = pop.narrow_one_row(vec![2.into(), 1.into()], true);
+        assert_eq!(positive_value(rs), DfValue::Double(0.0));
+
+        // [2, 4, 4, 4, 5, 5, 7] has a mean of 31 / 7, and a sum of squared deviations of 96 / 7
+        let rs = samp.narrow_one_row((vec![1.into(), 9.into()], false), true);
+        match positive_value(rs) {
+            DfValue::Double(v) => assert!((v - 16.0 / 7.0).abs() < 1e-9),
+            v => unreachable!("unexpected value {v:?}"),
+        }
+    }
+
+    #[test]
+    fn variance_of_large_values() {
+        let mut g = setup(Aggregation::Variance { sample: false }, true);
+
+        // Squaring these values loses all precision of their deviations from the mean
+        for v in [1e9 + 4.0, 1e9 + 7.0, 1e9 + 13.0] {
+            g.narrow_one_row(vec![1.into(), DfValue::Double(v)], true);
+        }
+        let rs = g.narrow_one_row(vec![1.into(), DfValue::Double(1e9 + 16.0)], true);
+        match positive_value(rs) {
+            DfValue::Double(v) => assert!((v - 22.5).abs() < 1e-6),
+            v => unreachable!("unexpected value {v:?}"),
+        }
+
+        let rs = g.narrow_one_row((vec![1.into(), DfValue::Double(1e9 + 16.0)], false), true);
+        match positive_value(rs) {
+            DfValue::Double(v) => assert!((v - 14.0).abs() < 1e-6),
+            v => unreachable!("unexpected value {v:?}"),
+        }
+    }
+
+    #[test]
+    fn stddev_result_types() {
+        let src = 0.into();
+        let ty = |agg: Aggregation, over_ty: &DfType, dialect| {
+            agg.over(src, 1, &[0], over_ty, dialect)
+                .unwrap()
+                .output_col_type()
+        };
+
+        assert_eq!(
+            ty(
+                Aggregation::Stddev { sample: true },
+                &DfType::Int,
+                Dialect::DEFAULT_MYSQL
+            ),
+            DfType::Double
+        );
+        assert_eq!(
+            ty(
+                Aggregation::Stddev { sample: true },
+                &DfType::Int,
+                Dialect::DEFAULT_POSTGRESQL
+            ),
+            DfType::DEFAULT_NUMERIC
+        );
+        assert_eq!(
+            ty(
+                Aggregation::Variance { sample: false },
+                &DfType::Double,
+                Dialect::DEFAULT_POSTGRESQL
+            ),
+            DfType::Double
+        );
+        assert_eq!(
+            ty(
+                Aggregation::BoolOr,
+                &DfType::Bool,
+                Dialect::DEFAULT_POSTGRESQL
+            ),
+            DfType::Bool
+        );
+        assert_eq!(
+            ty(Aggregation::BitOr, &DfType::Int, Dialect::DEFAULT_MYSQL),
+            DfType::UnsignedBigInt
+        );
+        assert_eq!(
+            ty(
+                Aggregation::BitAnd,
+                &DfType::Int,
+                Dialect::DEFAULT_POSTGRESQL
+            ),
+            DfType::Int
+        );
+    }
+
+    #[test]
+    fn bool_and_or_forwards() {
+        let mut and = setup(Aggregation::BoolAnd, true);
+        let mut or = setup(Aggregation::BoolOr, true);
+
+        for g in [&mut and, &mut or] {
+            g.narrow_one_row(vec![1.into(), true.into()], true);
+        }
+        let rs = and.narrow_one_row(vec![1.into(), false.into()], true);
+        assert_eq!(positive_value(rs), false.into());
+        let rs = or.narrow_one_row(vec![1.into(), false.into()], true);
+        assert_eq!(positive_value(rs), true.into());
+
+        // Removing the only true value makes BOOL_OR false, and removing the only false value
+        // makes BOOL_AND true
+        let rs = or.narrow_one_row((vec![1.into(), true.into()], false), true);
+        assert_eq!(positive_value(rs), false.into());
+        let rs = and.narrow_one_row((vec![1.into(), false.into()], false), true);
+        assert_eq!(positive_value(rs), true.into());
+    }
+
+    #[test]
+    fn bit_and_or_forwards() {
+        let mut and = setup(Aggregation::BitAnd, true);
+        let mut or = setup(Aggregation::BitOr, true);
+
+        for g in [&mut and, &mut or] {
+            g.narrow_one_row(vec![1.into(), 0b1100.into()], true);
+        }
+        let rs = and.narrow_one_row(vec![1.into(), 0b0110.into()], true);
+        assert_eq!(positive_value(rs), DfValue::from(0b0100u64));
+        let rs = or.narrow_one_row(vec![1.into(), 0b0110.into()], true);
+        assert_eq!(positive_value(rs), DfValue::from(0b1110u64));
+
+        let rs = and.narrow_one_row((vec![1.into(), 0b0110.into()], false), true);
+        assert_eq!(positive_value(rs), DfValue::from(0b1100u64));
+        let rs = or.narrow_one_row((vec![1.into(), 0b1100.into()], false), true);
+        assert_eq!(positive_value(rs), DfValue::from(0b0110u64));
+
+        // Negative values are aggregated as their two's complement representation
+        let rs = and.narrow_one_row(vec![1.into(), (-1).into()], true);
+        assert_eq!(positive_value(rs), DfValue::from(0b1100u64));
+
+        // In MySQL, groups with only NULL values have the identity of the operation
+        let rs = and.narrow_one_row(vec![2.into(), DfValue::None], true);
+        assert_eq!(positive_value(rs), DfValue::from(u64::MAX));
+        let rs = or.narrow_one_row(vec![2.into(), DfValue::None], true);
+        assert_eq!(positive_value(rs), DfValue::from(0u64));
+    }
 }
@@ -0,0 +1,474 @@
+//! Aggregates which collect all the values in a group into a single array, string, or JSON value,
+//! such as `ARRAY_AGG`, `STRING_AGG` and `JSON_AGG`.
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::fmt;
+
+use common::DfValue;
+use nom_sql::OrderType;
+use readyset_data::{Collation, DfType};
+use readyset_errors::invariant_eq;
+use readyset_util::Indices;
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+
+use crate::node::{AuxiliaryNodeState, Node};
+use crate::ops::grouped::{GroupedOperation, GroupedOperator};
+use crate::prelude::*;
+
+/// The kinds of value a [`Collect`] operator can build out of the values in a group
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Collection {
+    /// Collect the values into an array (`ARRAY_AGG`)
+    ArrayAgg,
+    /// Concatenate the non-null values into a string, separated by the given separator
+    /// (`STRING_AGG`)
+    StringAgg { separator: String },
+    /// Collect the values into a JSON array (`JSON_AGG`, `JSONB_AGG`, `JSON_ARRAYAGG`)
+    JsonAgg { jsonb: bool },
+    /// Collect pairs of keys and values into a JSON object (`JSON_OBJECT_AGG`,
+    /// `JSONB_OBJECT_AGG`, `JSON_OBJECTAGG`)
+    JsonObjectAgg { jsonb: bool },
+}
+
+impl fmt::Display for Collection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Collection::ArrayAgg => write!(f, "ARRAY_AGG"),
+            Collection::StringAgg { .. } => write!(f, "STRING_AGG"),
+            Collection::JsonAgg { jsonb: false } => write!(f, "JSON_AGG"),
+            Collection::JsonAgg { jsonb: true } => write!(f, "JSONB_AGG"),
+            Collection::JsonObjectAgg { jsonb: false } => write!(f, "JSON_OBJECT_AGG"),
+            Collection::JsonObjectAgg { jsonb: true } => write!(f, "JSONB_OBJECT_AGG"),
+        }
+    }
+}
+
+/// A single row's contribution to a group
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+struct Entry {
+    /// The values of the columns we're ordering by
+    order_key: Vec<DfValue>,
+    /// The value of the key column, for [`Collection::JsonObjectAgg`]
+    key: DfValue,
+    /// The value being collected
+    value: DfValue,
+}
+
+/// The last stored state for a given group.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+struct LastState {
+    /// The value we last emitted for this group.
+    output: DfValue,
+    /// All the entries in the group, sorted by their `order_key`
+    entries: Vec<Entry>,
+}
+
+/// `Collect` implements the aggregate functions which build a single (array, string, or JSON)
+/// value out of all the values in a group, optionally ordered by a set of columns.
+///
+/// Like [`GroupConcat`](super::concat::GroupConcat), this keeps every value in each group in its
+/// auxiliary state, so that it can recompute the output when values are removed.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Collect {
+    /// What kind of value to build
+    kind: Collection,
+    /// Which column to aggregate.
+    source_col: usize,
+    /// The column containing the keys, for [`Collection::JsonObjectAgg`]
+    key_col: Option<usize>,
+    /// The columns to order the values by, and in which direction
+    order: Vec<(usize, OrderType)>,
+    /// The columns to group by.
+    group_by: Vec<usize>,
+    /// The type of the column we're aggregating
+    value_ty: DfType,
+}
+
+impl Collect {
+    /// Construct a new `Collect`, building a value of the given `kind` out of the values in
+    /// `source_col` (of type `value_ty`), ordered by the columns in `order`.
+    pub fn new(
+        src: NodeIndex,
+        kind: Collection,
+        source_col: usize,
+        key_col: Option<usize>,
+        order: Vec<(usize, OrderType)>,
+        group_by: Vec<usize>,
+        value_ty: DfType,
+    ) -> ReadySetResult<GroupedOperator<Collect>> {
+        invariant_eq!(
+            key_col.is_some(),
+            matches!(kind, Collection::JsonObjectAgg { .. })
+        );
+
+        Ok(GroupedOperator::new(
+            src,
+            Collect {
+                kind,
+                source_col,
+                key_col,
+                order,
+                group_by,
+                value_ty,
+            },
+        ))
+    }
+
+    fn cmp_order_keys(&self, a: &[DfValue], b: &[DfValue]) -> Ordering {
+        for ((a, b), (_, order_type)) in a.iter().zip(b).zip(&self.order) {
+            let result = order_type.apply(a.cmp(b));
+            if result != Ordering::Equal {
+                return result;
+            }
+        }
+        Ordering::Equal
+    }
+
+    /// Convert a value from the source column to JSON. Values of JSON columns are embedded as
+    /// JSON, rather than as strings.
+    fn to_json_value(&self, value: &DfValue) -> ReadySetResult<JsonValue> {
+        Ok(match value {
+            DfValue::None => JsonValue::Null,
+            _ if matches!(self.value_ty, DfType::Json | DfType::Jsonb) => value.to_json()?,
+            _ if self.value_ty == DfType::Bool => JsonValue::Bool(value.is_truthy()),
+            DfValue::Int(i) => (*i).into(),
+            DfValue::UnsignedInt(i) => (*i).into(),
+            DfValue::Float(f) => f64::from(*f).into(),
+            DfValue::Double(f) => (*f).into(),
+            DfValue::Numeric(d) => serde_json::from_str(&d.to_string())?,
+            _ => JsonValue::String(to_text(value)?),
+        })
+    }
+
+    /// Build the output value for a group out of its (sorted) entries
+    fn output(&self, entries: &[Entry]) -> ReadySetResult<DfValue> {
+        if entries.is_empty() {
+            return Ok(DfValue::None);
+        }
+
+        match &self.kind {
+            Collection::ArrayAgg => Ok(DfValue::from(
+                entries.iter().map(|e| e.value.clone()).collect::<Vec<_>>(),
+            )),
+            Collection::StringAgg { separator } => {
+                let mut out: Option<String> = None;
+                for entry in entries.iter().filter(|e| !e.value.is_none()) {
+                    let text = to_text(&entry.value)?;
+                    match &mut out {
+                        Some(out) => {
+                            out.push_str(separator);
+                            out.push_str(&text);
+                        }
+                        None => out = Some(text),
+                    }
+                }
+                Ok(out.map(DfValue::from).unwrap_or_default())
+            }
+            Collection::JsonAgg { .. } => Ok(JsonValue::Array(
+                entries
+                    .iter()
+                    .map(|e| self.to_json_value(&e.value))
+                    .collect::<ReadySetResult<_>>()?,
+            )
+            .into()),
+            Collection::JsonObjectAgg { .. } => {
+                let mut object = serde_json::Map::new();
+                // Both MySQL and PostgreSQL error on NULL keys, but we can't return an error from
+                // the middle of the dataflow, so just skip them
+                for entry in entries.iter().filter(|e| !e.key.is_none()) {
+                    object.insert(to_text(&entry.key)?, self.to_json_value(&entry.value)?);
+                }
+                Ok(JsonValue::Object(object).into())
+            }
+        }
+    }
+}
+
+fn to_text(value: &DfValue) -> ReadySetResult<String> {
+    match value {
+        DfValue::Text(..) | DfValue::TinyText(..) => Ok(<&str>::try_from(value)?.to_owned()),
+        x => Ok(x.to_string()),
+    }
+}
+
+pub struct CollectDiff {
+    entry: Entry,
+    is_positive: bool,
+    group_by: Vec<DfValue>,
+}
+
+impl GroupedOperation for Collect {
+    type Diff = CollectDiff;
+
+    fn setup(&mut self, _: &Node) -> ReadySetResult<()> {
+        Ok(())
+    }
+
+    fn group_by(&self) -> &[usize] {
+        &self.group_by
+    }
+
+    fn to_diff(&self, record: &[DfValue], is_positive: bool) -> ReadySetResult<Self::Diff> {
+        let get = |col: usize| -> ReadySetResult<DfValue> {
+            record
+                .get(col)
+                .cloned()
+                .ok_or(ReadySetError::InvalidRecordLength)
+        };
+
+        let entry = Entry {
+            order_key: self
+                .order
+                .iter()
+                .map(|(col, _)| get(*col))
+                .collect::<ReadySetResult<_>>()?,
+            key: self.key_col.map(get).transpose()?.unwrap_or_default(),
+            value: get(self.source_col)?,
+        };
+        // We need this to figure out which state to use.
+        let group_by = record
+            .cloned_indices(self.group_by.iter().cloned())
+            .map_err(|_| ReadySetError::InvalidRecordLength)?;
+        Ok(CollectDiff {
+            entry,
+            is_positive,
+            group_by,
+        })
+    }
+
+    fn apply(
+        &self,
+        current: Option<&DfValue>,
+        diffs: &mut dyn Iterator<Item = Self::Diff>,
+        auxiliary_node_state: Option<&mut AuxiliaryNodeState>,
+    ) -> ReadySetResult<Option<DfValue>> {
+        let mut diffs = diffs.peekable();
+
+        let first_diff = diffs
+            .peek()
+            .ok_or_else(|| internal_err!("{} got no diffs", self.kind))?;
+        let group = first_diff.group_by.clone();
+
+        let last_state = match auxiliary_node_state {
+            Some(AuxiliaryNodeState::Collect(ref mut cs)) => &mut cs.last_state,
+            Some(_) => internal!("Incorrect auxiliary state for Collect node"),
+            None => internal!("Missing auxiliary state for Collect node"),
+        };
+
+        let mut state = match (current, last_state.remove(&group)) {
+            // if state matches, use it
+            (Some(current), Some(ls)) if *current == ls.output => ls,
+            // if state doesn't match, need to recreate it
+            (Some(_), _) => return Ok(None),
+            // if we're recreating or this is the first record for the group, make a new state
+            (None, _) => LastState::default(),
+        };
+
+        for CollectDiff {
+            entry,
+            is_positive,
+            group_by,
+        } in diffs
+        {
+            invariant_eq!(group_by, group);
+            if is_positive {
+                // Insert after any entries that compare equal, so that values without an explicit
+                // order stay in the order they arrived in
+                let pos = state.entries.partition_point(|e| {
+                    self.cmp_order_keys(&e.order_key, &entry.order_key) != Ordering::Greater
+                });
+                state.entries.insert(pos, entry);
+            } else {
+                let pos = state
+                    .entries
+                    .iter()
+                    .rposition(|e| e == &entry)
+                    .ok_or_else(|| internal_err!("{} couldn't remove value", self.kind))?;
+                state.entries.remove(pos);
+            }
+        }
+
+        let output = self.output(&state.entries)?;
+        state.output = output.clone();
+        last_state.insert(group, state);
+        Ok(Some(output))
+    }
+
+    fn description(&self, detailed: bool) -> String {
+        if !detailed {
+            return self.kind.to_string();
+        }
+
+        let mut args = match self.key_col {
+            Some(key_col) => format!("{}, {}", key_col, self.source_col),
+            None => self.source_col.to_string(),
+        };
+        if let Collection::StringAgg { separator } = &self.kind {
+            args.push_str(&format!(", {:?}", separator));
+        }
+        if !self.order.is_empty() {
+            args.push_str(" ORDER BY ");
+            args.push_str(
+                &self
+                    .order
+                    .iter()
+                    .map(|(col, order_type)| format!("{} {}", col, order_type))
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            );
+        }
+
+        format!("{}({}) γ{:?}", self.kind, args, self.group_by)
+    }
+
+    fn over_column(&self) -> usize {
+        self.source_col
+    }
+
+    fn output_col_type(&self) -> DfType {
+        match self.kind {
+            Collection::ArrayAgg => DfType::Array(Box::new(self.value_ty.clone())),
+            Collection::StringAgg { .. } => DfType::Text(Collation::default()),
+            Collection::JsonAgg { jsonb } | Collection::JsonObjectAgg { jsonb } => {
+                if jsonb {
+                    DfType::Jsonb
+                } else {
+                    DfType::Json
+                }
+            }
+        }
+    }
+
+    fn can_lose_state(&self) -> bool {
+        false
+    }
+}
+
+#[derive(Debug, Default)]
+/// Auxiliary State for a single Collect Node, which is owned by a Domain.
+pub struct CollectState {
+    last_state: HashMap<Vec<DfValue>, LastState>,
+}
+
+#[cfg(test)]
+#[allow(clippy::unreachable)]
+mod tests {
+    use super::*;
+    use crate::ops;
+
+    fn setup(kind: Collection, order: Vec<(usize, OrderType)>) -> ops::test::MockGraph {
+        let mut g = ops::test::MockGraph::new();
+        let s = g.add_base("source", &["x", "y", "z"]);
+
+        let key_col = matches!(kind, Collection::JsonObjectAgg { .. }).then_some(2);
+        let c = Collect::new(
+            s.as_global(),
+            kind,
+            1,
+            key_col,
+            order,
+            vec![0],
+            DfType::BigInt,
+        )
+        .unwrap();
+
+        g.set_op("collect", &["x", "ys"], c, true);
+        g
+    }
+
+    fn positive_value(rs: Records) -> DfValue {
+        let mut positives = rs.into_iter().filter_map(|r| match r {
+            Record::Positive(r) => Some(r),
+            Record::Negative(_) => None,
+        });
+        let r = positives.next().unwrap();
+        assert!(positives.next().is_none());
+        r[1].clone()
+    }
+
+    #[test]
+    fn it_describes() {
+        let c = setup(
+            Collection::StringAgg {
+                separator: ",".into(),
+            },
+            vec![(2, OrderType::OrderDescending)],
+        );
+        assert_eq!(
+            c.node().description(true),
+            "STRING_AGG(1, \",\" ORDER BY 2 DESC) γ[0]"
+        );
+    }
+
+    #[test]
+    fn array_agg_forwards_in_order() {
+        let mut c = setup(Collection::ArrayAgg, vec![(2, OrderType::OrderAscending)]);
+
+        let rs = c.narrow_one_row(vec![1.into(), 10.into(), 3.into()], true);
+        assert_eq!(positive_value(rs), DfValue::from(vec![DfValue::from(10)]));
+
+        let rs = c.narrow_one_row(vec![1.into(), 20.into(), 1.into()], true);
+        assert_eq!(
+            positive_value(rs),
+            DfValue::from(vec![DfValue::from(20), DfValue::from(10)])
+        );
+
+        let rs = c.narrow_one_row(vec![1.into(), 30.into(), 2.into()], true);
+        assert_eq!(
+            positive_value(rs),
+            DfValue::from(vec![
+                DfValue::from(20),
+                DfValue::from(30),
+                DfValue::from(10)
+            ])
+        );
+
+        let rs = c.narrow_one_row((vec![1.into(), 20.into(), 1.into()], false), true);
+        assert_eq!(
+            positive_value(rs),
+            DfValue::from(vec![DfValue::from(30), DfValue::from(10)])
+        );
+    }
+
+    #[test]
+    fn string_agg_skips_nulls() {
+        let mut c = setup(
+            Collection::StringAgg {
+                separator: ", ".into(),
+            },
+            vec![],
+        );
+
+        let rs = c.narrow_one_row(vec![1.into(), DfValue::None, 1.into()], true);
+        assert_eq!(positive_value(rs), DfValue::None);
+
+        let rs = c.narrow_one_row(vec![1.into(), 1.into(), 2.into()], true);
+        assert_eq!(positive_value(rs), DfValue::from("1"));
+
+        let rs = c.narrow_one_row(vec![1.into(), 2.into(), 3.into()], true);
+        assert_eq!(positive_value(rs), DfValue::from("1, 2"));
+    }
+
+    #[test]
+    fn json_aggs_forward() {
+        let mut c = setup(
+            Collection::JsonAgg { jsonb: false },
+            vec![(2, OrderType::OrderDescending)],
+        );
+        c.narrow_one_row(vec![1.into(), 1.into(), 1.into()], true);
+        c.narrow_one_row(vec![1.into(), DfValue::None, 2.into()], true);
+        let rs = c.narrow_one_row(vec![1.into(), 3.into(), 3.into()], true);
+        assert_eq!(positive_value(rs), DfValue::from("[3,null,1]"));
+
+        let mut c = setup(Collection::JsonObjectAgg { jsonb: true }, vec![]);
+        c.narrow_one_row(vec![1.into(), 1.into(), "a".into()], true);
+        let rs = c.narrow_one_row(vec![1.into(), 2.into(), "b".into()], true);
+        assert_eq!(positive_value(rs), DfValue::from(r#"{"a":1,"b":2}"#));
+
+        let rs = c.narrow_one_row((vec![1.into(), 1.into(), "a".into()], false), true);
+        assert_eq!(positive_value(rs), DfValue::from(r#"{"b":2}"#));
+    }
+}
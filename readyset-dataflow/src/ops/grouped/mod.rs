@@ -14,6 +14,7 @@ use crate::processing::{ColumnSource, IngredientLookupResult, LookupIndex, Looku
 
 // pub mod latest;
 pub mod aggregate;
+pub mod collect;
 pub mod concat;
 pub mod extremum;
 
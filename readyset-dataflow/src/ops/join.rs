@@ -1,6 +1,7 @@
 use std::collections::{HashMap, HashSet};
 use std::convert::{TryFrom, TryInto};
 
+use dataflow_expression::Expr;
 use dataflow_state::PointKey;
 use itertools::Itertools;
 use readyset_client::KeyComparison;
@@ -8,6 +9,7 @@ use readyset_errors::{internal_err, ReadySetResult};
 use readyset_util::intervals::into_bound_endpoint;
 use readyset_util::Indices;
 use serde::{Deserialize, Serialize};
+use tracing::error;
 use vec1::{vec1, Vec1};
 
 use super::Side;
@@ -23,6 +25,8 @@ pub enum JoinType {
     Left,
     /// Inner join between two views
     Inner,
+    /// Full outer join between two views
+    Full,
 }
 
 /// Join rows between two nodes based on a (compound) equal join key
@@ -49,6 +53,11 @@ pub struct Join {
     generated_column_buffer: HashMap<(Vec<usize>, Side), Records>,
 
     kind: JoinType,
+
+    /// An optional extra predicate which has to hold for a pair of rows with equal join keys to
+    /// match, evaluated over the columns of the row from the left parent followed by the columns
+    /// of the row from the right parent
+    residual: Option<Expr>,
 }
 
 impl Join {
@@ -112,9 +121,22 @@ impl Join {
             in_place_right_emit,
             generated_column_buffer: Default::default(),
             kind,
+            residual: None,
         }
     }
 
+    /// Only match pairs of rows from the left and right parents for which the given predicate
+    /// holds, in addition to having equal join keys.
+    ///
+    /// The predicate is evaluated over the columns of the row from the left parent followed by the
+    /// columns of the row from the right parent. Rows with no matches for which the predicate
+    /// holds are treated the same as rows with no matches at all, so for left and full joins
+    /// they're emitted padded with NULLs.
+    pub fn with_residual(mut self, residual: Expr) -> Self {
+        self.residual = Some(residual);
+        self
+    }
+
     fn on_left(&self) -> Vec<usize> {
         self.on.iter().map(|(l, _)| *l).collect()
     }
@@ -133,6 +155,25 @@ impl Join {
             .collect()
     }
 
+    /// Returns true if the residual predicate for this join (if any) holds for the given pair of
+    /// rows from the left and right parents
+    fn residual_matches(&self, left: &[DfValue], right: &[DfValue]) -> bool {
+        let Some(residual) = &self.residual else {
+            return true;
+        };
+
+        let row = left.iter().chain(right).cloned().collect::<Vec<_>>();
+        match residual.eval(&row) {
+            Ok(v) => v.is_truthy(),
+            // Like in filters, if evaluating the predicate fails we treat the rows as not
+            // matching
+            Err(e) => {
+                error!(%e, "Error evaluating join residual predicate");
+                false
+            }
+        }
+    }
+
     fn handle_replay_for_generated(
         &self,
         left: Records,
@@ -149,10 +190,10 @@ impl Join {
             let (rec, positive) = rec.extract();
             invariant!(positive, "replays should only include positive records");
 
-            for other_rec in right
-                .iter()
-                .filter(|r| rec.indices(from_key.clone()) == r.indices(other_key.clone()))
-            {
+            for other_rec in right.iter().filter(|r| {
+                rec.indices(from_key.clone()) == r.indices(other_key.clone())
+                    && self.residual_matches(&rec, r.row())
+            }) {
                 ret.push(Record::Positive(self.generate_row(&rec, other_rec.row())))
             }
         }
@@ -173,6 +214,21 @@ impl Join {
             .collect()
     }
 
+    /// Generate a row for a record from the right parent which has no matching rows in the left,
+    /// filling in all columns from the left with NULL
+    fn generate_null_from_right(&self, right: &[DfValue]) -> Vec<DfValue> {
+        self.emit
+            .iter()
+            .map(|&(side, col)| {
+                if side == Side::Right {
+                    right[col].clone()
+                } else {
+                    DfValue::None
+                }
+            })
+            .collect()
+    }
+
     fn resolve_col(&self, col: usize) -> (Option<usize>, Option<usize>) {
         let (side, pcol) = self.emit[col];
 
@@ -214,7 +270,14 @@ impl Ingredient for Join {
     }
 
     fn must_replay_among(&self) -> Option<HashSet<NodeIndex>> {
-        Some(Some(self.left.as_global()).into_iter().collect())
+        match self.kind {
+            // Rows from the right with no match in the left only show up when replaying through
+            // the right, so full joins need to replay through both parents
+            JoinType::Full => None,
+            JoinType::Left | JoinType::Inner => {
+                Some(Some(self.left.as_global()).into_iter().collect())
+            }
+        }
     }
 
     fn on_connected(&mut self, _g: &Graph) {}
@@ -339,6 +402,7 @@ impl Ingredient for Join {
             .group_by(|rec| from_key.iter().map(|i| rec[*i].clone()).collect::<Vec<_>>());
 
         let is_replay = replay_key_cols.is_some();
+        let is_full_replay = matches!(replay, ReplayContext::Full { .. });
 
         // Only do a lookup into a weak index if we're processing regular updates,
         // not if we're processing a replay, since regular updates should represent
@@ -362,14 +426,24 @@ impl Ingredient for Join {
             let nulls = join_key.iter().any(|v| v.is_none());
 
             // The difference between a left join and an inner join, is that for the former we must
-            // emit rows with nulls even if we later get no match in the other side.
-
-            let mut new_right_count = None;
+            // emit rows with nulls even if we later get no match in the other side. A full join
+            // does the same for rows from both sides.
+
+            let mut from_rows = None;
+
+            // Track the rows with this key on our side, so that we know when the NULL rows for the
+            // other side have to be emitted or retracted. Full replays don't need this, since they
+            // only ever add rows that weren't there before.
+            let tracks_null_rows = match self.kind {
+                JoinType::Left => !from_left,
+                JoinType::Full => !is_full_replay,
+                JoinType::Inner => false,
+            };
 
-            if self.kind == JoinType::Left && !from_left {
+            if tracks_null_rows {
                 let rc = self.lookup(
-                    *self.right,
-                    &self.on_right(),
+                    from,
+                    &from_key,
                     &PointKey::from(join_key.iter().cloned()),
                     nodes,
                     state,
@@ -380,8 +454,8 @@ impl Ingredient for Join {
                     IngredientLookupResult::Records(rc) => {
                         if replay_key_cols.is_some() && !nulls {
                             lookups.push(Lookup {
-                                on: *self.right,
-                                cols: self.on_right(),
+                                on: from,
+                                cols: from_key.clone(),
                                 key: join_key
                                     .clone()
                                     .try_into()
@@ -389,11 +463,10 @@ impl Ingredient for Join {
                             });
                         }
 
-                        let rc = rc.count();
-                        new_right_count = Some(rc);
+                        from_rows = Some(rc.collect::<Result<Vec<_>, _>>()?);
                     }
                     IngredientLookupResult::Miss => {
-                        // we got something from right, but that row's key is not in right??
+                        // we got something from one side, but that row's key is not in that side??
                         //
                         // this *can* happen! imagine if you have two partial indices on right,
                         // one on column a and one on column b. imagine that a is the join key.
@@ -447,41 +520,91 @@ impl Ingredient for Join {
             }
 
             let other_rows = other_records.collect::<Result<Vec<_>, _>>()?;
+            let group = group.map(|r| r.extract()).collect::<Vec<_>>();
 
-            let mut rc_diff = 0isize;
-            for r in group {
-                let (row, positive) = r.extract();
+            // Does the residual predicate hold for a row from our side and a row from the other
+            // side?
+            let rows_match = |row: &[DfValue], other: &[DfValue]| {
+                if from_left {
+                    self.residual_matches(row, other)
+                } else {
+                    self.residual_matches(other, row)
+                }
+            };
 
+            let mut rc_diff = 0isize;
+            for (row, positive) in &group {
+                let positive = *positive;
                 rc_diff += if positive { 1 } else { -1 };
 
-                if other_rows.is_empty() {
-                    if self.kind == JoinType::Left && from_left {
-                        // left join, got a thing from left, no rows in right == NULL
-                        ret.push((self.generate_null(&row), positive).into());
+                let matching_rows = other_rows
+                    .iter()
+                    .filter(|other| rows_match(row, other))
+                    .collect::<Vec<_>>();
+
+                if matching_rows.is_empty() {
+                    match self.kind {
+                        JoinType::Left | JoinType::Full if from_left => {
+                            // left or full join, got a thing from left, no rows in right == NULL
+                            ret.push((self.generate_null(&row), positive).into());
+                        }
+                        JoinType::Full => {
+                            // full join, got a thing from right, no rows in left == NULL
+                            ret.push((self.generate_null_from_right(&row), positive).into());
+                        }
+                        JoinType::Left | JoinType::Inner => {}
                     }
-                } else {
-                    for other in other_rows.iter() {
+                } else if !(self.kind == JoinType::Full && is_full_replay && !from_left) {
+                    // When fully replaying a full join, the rows that match on both sides are
+                    // produced by the replay through the left, so the replay through the right
+                    // only needs to produce the rows that have no match in the left
+                    for other in matching_rows {
                         if from == *self.left {
-                            ret.push((self.generate_row(&row, other), positive).into());
+                            ret.push((self.generate_row(row, other), positive).into());
                         } else {
-                            ret.push((self.generate_row(other, &row), positive).into());
+                            ret.push((self.generate_row(other, row), positive).into());
                         }
                     }
                 }
             }
 
-            // For a left join with updates from the right side, we also have to emit/delete NULL
-            // rows if row count changed to/from zero
-            if let Some(new_rc) = new_right_count {
-                let old_rc = new_rc as isize - rc_diff;
-                if new_rc == 0 && old_rc != 0 {
-                    for other in other_rows.iter() {
-                        ret.push((self.generate_null(other), true).into());
-                    }
-                } else if new_rc != 0 && old_rc == 0 {
-                    for other in other_rows.iter() {
-                        ret.push((self.generate_null(other), false).into());
-                    }
+            // For a left join with updates from the right side (or a full join with updates from
+            // either side), we also have to emit/delete NULL rows for the other side if the number
+            // of rows matching them changed to/from zero
+            if let Some(from_rows) = from_rows {
+                for other in other_rows.iter() {
+                    let (new_rc, rc_diff) = if self.residual.is_none() {
+                        // Without a residual predicate, every row with the same key matches
+                        (from_rows.len(), rc_diff)
+                    } else {
+                        (
+                            from_rows
+                                .iter()
+                                .filter(|row| rows_match(row, other))
+                                .count(),
+                            group
+                                .iter()
+                                .filter(|(row, _)| rows_match(row, other))
+                                .map(|(_, positive)| if *positive { 1 } else { -1 })
+                                .sum(),
+                        )
+                    };
+
+                    let old_rc = new_rc as isize - rc_diff;
+                    let positive = if new_rc == 0 && old_rc != 0 {
+                        true
+                    } else if new_rc != 0 && old_rc == 0 {
+                        false
+                    } else {
+                        continue;
+                    };
+
+                    let row = if from_left {
+                        self.generate_null_from_right(other)
+                    } else {
+                        self.generate_null(other)
+                    };
+                    ret.push((row, positive).into());
                 }
             }
         }
@@ -515,6 +638,7 @@ impl Ingredient for Join {
             return String::from(match self.kind {
                 JoinType::Left => "⋉",
                 JoinType::Inner => "⋈",
+                JoinType::Full => "⟗",
             });
         }
 
@@ -534,10 +658,17 @@ impl Ingredient for Join {
         let op = match self.kind {
             JoinType::Left => "⋉",
             JoinType::Inner => "⋈",
+            JoinType::Full => "⟗",
         };
 
+        let residual = self
+            .residual
+            .as_ref()
+            .map(|residual| format!(" σ[{}]", residual))
+            .unwrap_or_default();
+
         format!(
-            "[{}] {}:({}) {} {}:({})",
+            "[{}] {}:({}) {} {}:({}){}",
             emit,
             self.left.as_global().index(),
             self.on_left().into_iter().map(|i| i.to_string()).join(", "),
@@ -546,7 +677,8 @@ impl Ingredient for Join {
             self.on_right()
                 .into_iter()
                 .map(|i| i.to_string())
-                .join(", ")
+                .join(", "),
+            residual
         )
     }
 
@@ -674,6 +806,16 @@ impl Ingredient for Join {
         // means "get me all rows in a not in b" (i.e. a \ b), not "get me rows in b where col
         // is NULL" (which is what this function would do).
 
+        if self.kind == JoinType::Full {
+            // Rows from either parent can be NULL-padded on the other side, so a replay through
+            // only one of the parents can't produce all the rows for a key. Until we can merge
+            // replays through both parents, full joins are always fully materialized.
+            return ColumnSource::RequiresFullReplay(vec1![
+                self.left.as_global(),
+                self.right.as_global()
+            ]);
+        }
+
         // column indices in the left parent
         let mut left_cols = vec![];
         // column indices in the right parent
@@ -1101,6 +1243,313 @@ mod tests {
         }
     }
 
+    mod full_join {
+        use super::*;
+
+        fn setup() -> (ops::test::MockGraph, IndexPair, IndexPair) {
+            let mut g = ops::test::MockGraph::new();
+            let l = g.add_base("left", &["l0", "l1"]);
+            let r = g.add_base("right", &["r0", "r1"]);
+
+            let j = Join::new(
+                l.as_global(),
+                r.as_global(),
+                JoinType::Full,
+                vec![(0, 0)],
+                vec![(Side::Left, 0), (Side::Left, 1), (Side::Right, 1)],
+            );
+
+            g.set_op("join", &["j0", "j1", "j2"], j, false);
+            (g, l, r)
+        }
+
+        #[test]
+        fn it_describes() {
+            let (j, l, r) = setup();
+            assert_eq!(
+                j.node().description(true),
+                format!("[{}:0, {}:1, {}:1] {}:(0) ⟗ {}:(0)", l, l, r, l, r)
+            );
+        }
+
+        #[test]
+        fn replays_through_both_parents() {
+            let (j, l, r) = setup();
+            assert_eq!(j.node().must_replay_among(), None);
+            assert_eq!(
+                j.node().column_source(&[0]),
+                ColumnSource::RequiresFullReplay(vec1![l.as_global(), r.as_global()])
+            );
+        }
+
+        #[test]
+        fn unmatched_rows_from_either_side() {
+            let (mut j, l, r) = setup();
+
+            let l_a1 = vec![1.into(), "a".try_into().unwrap()];
+            j.seed(l, l_a1.clone());
+            let rs = j.one_row(l, l_a1, false);
+            assert_eq!(
+                rs,
+                vec![(vec![1.into(), "a".try_into().unwrap(), DfValue::None], true)].into()
+            );
+
+            let r_x2 = vec![2.into(), "x".try_into().unwrap()];
+            j.seed(r, r_x2.clone());
+            let rs = j.one_row(r, r_x2, false);
+            assert_eq!(
+                rs,
+                vec![(
+                    vec![DfValue::None, DfValue::None, "x".try_into().unwrap()],
+                    true
+                )]
+                .into()
+            );
+        }
+
+        #[test]
+        fn null_keys_never_match() {
+            let (mut j, l, r) = setup();
+
+            let l_nulla = vec![DfValue::None, "a".try_into().unwrap()];
+            j.seed(l, l_nulla.clone());
+            j.one_row(l, l_nulla, false);
+
+            let r_nullx = vec![DfValue::None, "x".try_into().unwrap()];
+            j.seed(r, r_nullx.clone());
+            let rs = j.one_row(r, r_nullx, false);
+            assert_eq!(
+                rs,
+                vec![(
+                    vec![DfValue::None, DfValue::None, "x".try_into().unwrap()],
+                    true
+                )]
+                .into()
+            );
+        }
+
+        #[test]
+        fn match_from_left_retracts_right_nulls() {
+            let (mut j, l, r) = setup();
+
+            let r_x1 = vec![1.into(), "x".try_into().unwrap()];
+            j.seed(r, r_x1.clone());
+            j.one_row(r, r_x1, false);
+
+            let l_a1 = vec![1.into(), "a".try_into().unwrap()];
+            j.seed(l, l_a1.clone());
+            let rs = j.one_row(l, l_a1.clone(), false);
+            assert_eq!(
+                rs,
+                vec![
+                    (
+                        vec![1.into(), "a".try_into().unwrap(), "x".try_into().unwrap()],
+                        true
+                    ),
+                    (
+                        vec![DfValue::None, DfValue::None, "x".try_into().unwrap()],
+                        false
+                    ),
+                ]
+                .into()
+            );
+
+            // Deleting the only matching row in the left brings the NULLs for the right back
+            j.unseed(l);
+            let rs = j.one_row(l, (l_a1, false), false);
+            assert_eq!(
+                rs,
+                vec![
+                    (
+                        vec![1.into(), "a".try_into().unwrap(), "x".try_into().unwrap()],
+                        false
+                    ),
+                    (
+                        vec![DfValue::None, DfValue::None, "x".try_into().unwrap()],
+                        true
+                    ),
+                ]
+                .into()
+            );
+        }
+
+        #[test]
+        fn match_from_right_retracts_left_nulls() {
+            let (mut j, l, r) = setup();
+
+            let l_a1 = vec![1.into(), "a".try_into().unwrap()];
+            j.seed(l, l_a1.clone());
+            j.one_row(l, l_a1, false);
+
+            let r_x1 = vec![1.into(), "x".try_into().unwrap()];
+            j.seed(r, r_x1.clone());
+            let rs = j.one_row(r, r_x1.clone(), false);
+            assert_eq!(
+                rs,
+                vec![
+                    (
+                        vec![1.into(), "a".try_into().unwrap(), "x".try_into().unwrap()],
+                        true
+                    ),
+                    (
+                        vec![1.into(), "a".try_into().unwrap(), DfValue::None],
+                        false
+                    ),
+                ]
+                .into()
+            );
+
+            // A second match on the right shouldn't touch the NULL rows again
+            let r_y1 = vec![1.into(), "y".try_into().unwrap()];
+            j.seed(r, r_y1.clone());
+            let rs = j.one_row(r, r_y1, false);
+            assert_eq!(
+                rs,
+                vec![(
+                    vec![1.into(), "a".try_into().unwrap(), "y".try_into().unwrap()],
+                    true
+                )]
+                .into()
+            );
+        }
+
+        #[test]
+        fn full_replay_through_right_skips_matched_rows() {
+            let (mut j, l, r) = setup();
+
+            j.seed(l, vec![1.into(), "a".try_into().unwrap()]);
+            j.seed(r, vec![1.into(), "x".try_into().unwrap()]);
+            j.seed(r, vec![2.into(), "y".try_into().unwrap()]);
+
+            let res = j.input_raw(
+                r,
+                vec![
+                    vec![1.into(), "x".try_into().unwrap()],
+                    vec![2.into(), "y".try_into().unwrap()],
+                ],
+                ReplayContext::Full {
+                    last: true,
+                    tag: Tag::new(0),
+                },
+                false,
+            );
+            match res {
+                RawProcessingResult::Regular(res) => assert_eq!(
+                    res.results,
+                    vec![(
+                        vec![DfValue::None, DfValue::None, "y".try_into().unwrap()],
+                        true
+                    )]
+                    .into()
+                ),
+                _ => panic!("Expected a regular processing result"),
+            }
+        }
+    }
+
+    mod residual {
+        use dataflow_expression::utils::column_with_type;
+        use dataflow_expression::BinaryOperator;
+        use readyset_data::DfType;
+
+        use super::*;
+
+        /// Left join on `l0 = r0`, with the residual predicate `r1 > l1`
+        fn setup() -> (ops::test::MockGraph, IndexPair, IndexPair) {
+            let mut g = ops::test::MockGraph::new();
+            let l = g.add_base("left", &["l0", "l1"]);
+            let r = g.add_base("right", &["r0", "r1"]);
+
+            let j = Join::new(
+                l.as_global(),
+                r.as_global(),
+                JoinType::Left,
+                vec![(0, 0)],
+                vec![(Side::Left, 0), (Side::Left, 1), (Side::Right, 1)],
+            )
+            .with_residual(Expr::Op {
+                left: Box::new(column_with_type(3, DfType::Int)),
+                op: BinaryOperator::Greater,
+                right: Box::new(column_with_type(1, DfType::Int)),
+                ty: DfType::Bool,
+            });
+
+            g.set_op("join", &["j0", "j1", "j2"], j, false);
+            (g, l, r)
+        }
+
+        #[test]
+        fn it_describes() {
+            let (j, l, r) = setup();
+            assert_eq!(
+                j.node().description(true),
+                format!(
+                    "[{}:0, {}:1, {}:1] {}:(0) ⋉ {}:(0) σ[(3 > 1)]",
+                    l, l, r, l, r
+                )
+            );
+        }
+
+        #[test]
+        fn only_matches_rows_satisfying_residual() {
+            let (mut j, l, r) = setup();
+
+            j.seed(r, vec![1.into(), 5.into()]);
+            j.seed(r, vec![1.into(), 10.into()]);
+
+            j.seed(l, vec![1.into(), 7.into()]);
+            let rs = j.one_row(l, vec![1.into(), 7.into()], false);
+            assert_eq!(rs, vec![(vec![1.into(), 7.into(), 10.into()], true)].into());
+
+            // Same join key, but none of the rows on the right satisfy the predicate
+            j.seed(l, vec![1.into(), 20.into()]);
+            let rs = j.one_row(l, vec![1.into(), 20.into()], false);
+            assert_eq!(
+                rs,
+                vec![(vec![1.into(), 20.into(), DfValue::None], true)].into()
+            );
+        }
+
+        #[test]
+        fn flips_between_matched_and_null_rows() {
+            let (mut j, l, r) = setup();
+
+            j.seed(l, vec![1.into(), 7.into()]);
+            j.one_row(l, vec![1.into(), 7.into()], false);
+
+            // A row on the right with the same key that doesn't satisfy the predicate shouldn't
+            // retract the NULL row
+            j.seed(r, vec![1.into(), 5.into()]);
+            let rs = j.one_row(r, vec![1.into(), 5.into()], false);
+            assert!(rs.is_empty());
+
+            j.seed(r, vec![1.into(), 10.into()]);
+            let rs = j.one_row(r, vec![1.into(), 10.into()], false);
+            assert_eq!(
+                rs,
+                vec![
+                    (vec![1.into(), 7.into(), 10.into()], true),
+                    (vec![1.into(), 7.into(), DfValue::None], false),
+                ]
+                .into()
+            );
+
+            // Removing the only row that satisfies the predicate brings the NULL row back, even
+            // though there are still rows with the same key on the right
+            j.unseed(r);
+            j.seed(r, vec![1.into(), 5.into()]);
+            let rs = j.one_row(r, (vec![1.into(), 10.into()], false), false);
+            assert_eq!(
+                rs,
+                vec![
+                    (vec![1.into(), 7.into(), 10.into()], false),
+                    (vec![1.into(), 7.into(), DfValue::None], true),
+                ]
+                .into()
+            );
+        }
+    }
+
     mod compound_keys {
         use super::*;
 
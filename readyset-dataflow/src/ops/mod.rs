@@ -14,10 +14,14 @@ pub mod identity;
 pub mod join;
 pub mod paginate;
 pub mod project;
+pub mod set_operation;
 pub mod topk;
 pub mod union;
+pub mod unnest;
 pub(crate) mod utils;
+pub mod window;
 
+use crate::ops::grouped::collect::Collect;
 use crate::ops::grouped::concat::GroupConcat;
 use crate::processing::{
     ColumnMiss, ColumnSource, IngredientLookupResult, LookupIndex, LookupMode,
@@ -47,13 +51,17 @@ pub enum NodeOperator {
     Aggregation(grouped::GroupedOperator<grouped::aggregate::Aggregator>),
     Extremum(grouped::GroupedOperator<grouped::extremum::ExtremumOperator>),
     Concat(grouped::GroupedOperator<GroupConcat>),
+    Collect(grouped::GroupedOperator<Collect>),
     Join(join::Join),
     Paginate(paginate::Paginate),
     Project(project::Project),
     Union(union::Union),
+    SetOperation(set_operation::SetOperation),
     Identity(identity::Identity),
     Filter(filter::Filter),
     TopK(topk::TopK),
+    Window(window::Window),
+    Unnest(unnest::Unnest),
 }
 
 impl ToString for NodeOperator {
@@ -62,13 +70,17 @@ impl ToString for NodeOperator {
             NodeOperator::Aggregation(_) => "Aggregation",
             NodeOperator::Extremum(_) => "Extremum",
             NodeOperator::Concat(_) => "Concat",
+            NodeOperator::Collect(_) => "Collect",
             NodeOperator::Join(_) => "Join",
             NodeOperator::Paginate(_) => "Paginate",
             NodeOperator::Project(_) => "Project",
             NodeOperator::Union(_) => "Union",
+            NodeOperator::SetOperation(_) => "SetOperation",
             NodeOperator::Identity(_) => "Identity",
             NodeOperator::Filter(_) => "Filter",
             NodeOperator::TopK(_) => "TopK",
+            NodeOperator::Window(_) => "Window",
+            NodeOperator::Unnest(_) => "Unnest",
         }
         .to_string()
     }
@@ -80,13 +92,17 @@ macro_rules! impl_ingredient_fn_mut {
             NodeOperator::Aggregation(ref mut i) => i.$fn($($arg),*),
             NodeOperator::Extremum(ref mut i) => i.$fn($($arg),*),
             NodeOperator::Concat(ref mut i) => i.$fn($($arg),*),
+            NodeOperator::Collect(ref mut i) => i.$fn($($arg),*),
             NodeOperator::Join(ref mut i) => i.$fn($($arg),*),
             NodeOperator::Paginate(ref mut i) => i.$fn($($arg),*),
             NodeOperator::Project(ref mut i) => i.$fn($($arg),*),
             NodeOperator::Union(ref mut i) => i.$fn($($arg),*),
+            NodeOperator::SetOperation(ref mut i) => i.$fn($($arg),*),
             NodeOperator::Identity(ref mut i) => i.$fn($($arg),*),
             NodeOperator::Filter(ref mut i) => i.$fn($($arg),*),
             NodeOperator::TopK(ref mut i) => i.$fn($($arg),*),
+            NodeOperator::Window(ref mut i) => i.$fn($($arg),*),
+            NodeOperator::Unnest(ref mut i) => i.$fn($($arg),*),
         }
     }
 }
@@ -97,13 +113,17 @@ macro_rules! impl_ingredient_fn_ref {
             NodeOperator::Aggregation(ref i) => i.$fn($($arg),*),
             NodeOperator::Extremum(ref i) => i.$fn($($arg),*),
             NodeOperator::Concat(ref i) => i.$fn($($arg),*),
+            NodeOperator::Collect(ref i) => i.$fn($($arg),*),
             NodeOperator::Join(ref i) => i.$fn($($arg),*),
             NodeOperator::Paginate(ref i) => i.$fn($($arg),*),
             NodeOperator::Project(ref i) => i.$fn($($arg),*),
             NodeOperator::Union(ref i) => i.$fn($($arg),*),
+            NodeOperator::SetOperation(ref i) => i.$fn($($arg),*),
             NodeOperator::Identity(ref i) => i.$fn($($arg),*),
             NodeOperator::Filter(ref i) => i.$fn($($arg),*),
             NodeOperator::TopK(ref i) => i.$fn($($arg),*),
+            NodeOperator::Window(ref i) => i.$fn($($arg),*),
+            NodeOperator::Unnest(ref i) => i.$fn($($arg),*),
         }
     }
 }
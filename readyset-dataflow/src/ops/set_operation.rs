@@ -0,0 +1,431 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt::{self, Display};
+use std::iter;
+
+use dataflow_state::PointKey;
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+
+use crate::prelude::*;
+use crate::processing::{IngredientLookupResult, LookupIndex, LookupMode};
+
+/// The set operation computed by a [`SetOperation`] node
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SetOperator {
+    /// `INTERSECT`: each distinct row that exists in both parents, once
+    Intersect,
+    /// `INTERSECT ALL`: each row that exists in both parents, as many times as the smaller number
+    /// of copies of that row in either parent
+    IntersectAll,
+    /// `EXCEPT`: each distinct row in the left parent that does not exist in the right parent,
+    /// once
+    Except,
+    /// `EXCEPT ALL`: each row in the left parent, as many times as the number of copies of that
+    /// row in the left parent exceeds the number of copies in the right parent
+    ExceptAll,
+}
+
+impl SetOperator {
+    /// Returns the number of copies of a row in the output of this set operation, given the number
+    /// of copies of that row in the left and right parents
+    pub fn multiplicity(&self, left: usize, right: usize) -> usize {
+        match self {
+            SetOperator::Intersect => usize::from(left > 0 && right > 0),
+            SetOperator::IntersectAll => left.min(right),
+            SetOperator::Except => usize::from(left > 0 && right == 0),
+            SetOperator::ExceptAll => left.saturating_sub(right),
+        }
+    }
+}
+
+impl Display for SetOperator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SetOperator::Intersect => write!(f, "∩"),
+            SetOperator::IntersectAll => write!(f, "∩ ALL"),
+            SetOperator::Except => write!(f, "∖"),
+            SetOperator::ExceptAll => write!(f, "∖ ALL"),
+        }
+    }
+}
+
+/// SetOperation implements a Dataflow node that computes the `INTERSECT` or `EXCEPT` (or their
+/// `ALL` variants) of the rows in its two parents.
+///
+/// Rows are compared on the `emit_left` columns of the left parent and the `emit_right` columns of
+/// the right parent, and the node emits the `emit_left` columns. Unlike [`Union`], the number of
+/// copies of a row in the output depends on how many copies of that row exist in *both* parents,
+/// so when a record arrives the node counts the copies of its row in each parent and in its own
+/// (always fully materialized) state, and emits positive or negative records for the difference.
+///
+/// [`Union`]: crate::ops::union::Union
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SetOperation {
+    left: IndexPair,
+    right: IndexPair,
+    /// The columns to compare and emit from the left parent
+    emit_left: Vec<usize>,
+    /// The columns to compare from the right parent
+    emit_right: Vec<usize>,
+    operator: SetOperator,
+    /// The index of this node. Used to look up into our own state
+    our_index: Option<IndexPair>,
+}
+
+impl SetOperation {
+    /// Construct a new SetOperation node computing `operator` over the rows of the `left` and
+    /// `right` parents.
+    pub fn new(
+        left: NodeIndex,
+        right: NodeIndex,
+        emit_left: Vec<usize>,
+        emit_right: Vec<usize>,
+        operator: SetOperator,
+    ) -> ReadySetResult<Self> {
+        invariant_eq!(
+            emit_left.len(),
+            emit_right.len(),
+            "both parents of a set operation must emit the same number of columns"
+        );
+        invariant!(
+            !emit_left.is_empty(),
+            "set operations must emit at least one column"
+        );
+
+        Ok(SetOperation {
+            left: left.into(),
+            right: right.into(),
+            emit_left,
+            emit_right,
+            operator,
+            our_index: None,
+        })
+    }
+
+    /// Returns the indices of all the columns emitted by this node
+    fn output_columns(&self) -> Vec<usize> {
+        (0..self.emit_left.len()).collect()
+    }
+
+    /// Count the number of rows in the given parent whose `columns` are equal to `key`
+    fn count_in_parent(
+        &self,
+        parent: IndexPair,
+        columns: &[usize],
+        key: &PointKey,
+        nodes: &DomainNodes,
+        state: &StateMap,
+    ) -> ReadySetResult<usize> {
+        match self.lookup(*parent, columns, key, nodes, state, LookupMode::Strict)? {
+            IngredientLookupResult::Records(rs) => rs.fold_ok(0, |n, _| n + 1),
+            IngredientLookupResult::Miss => {
+                internal!("Parents of set operations must be fully materialized")
+            }
+        }
+    }
+}
+
+impl Ingredient for SetOperation {
+    fn take(&mut self) -> NodeOperator {
+        self.clone().into()
+    }
+
+    fn ancestors(&self) -> Vec<NodeIndex> {
+        vec![self.left.as_global(), self.right.as_global()]
+    }
+
+    fn must_replay_among(&self) -> Option<HashSet<NodeIndex>> {
+        // Every row in the output of a set operation is a row from the left parent
+        Some(HashSet::from([self.left.as_global()]))
+    }
+
+    impl_replace_sibling!(left, right);
+
+    fn on_commit(&mut self, us: NodeIndex, remap: &HashMap<NodeIndex, IndexPair>) {
+        self.left.remap(remap);
+        self.right.remap(remap);
+        self.our_index = Some(remap[&us]);
+    }
+
+    fn on_input(
+        &mut self,
+        from: LocalNodeIndex,
+        rs: Records,
+        _replay: &ReplayContext,
+        nodes: &DomainNodes,
+        state: &StateMap,
+        _auxiliary_node_states: &mut AuxiliaryNodeStateMap,
+    ) -> ReadySetResult<ProcessingResult> {
+        let from_emit = if from == *self.left {
+            &self.emit_left
+        } else {
+            invariant_eq!(from, *self.right);
+            &self.emit_right
+        };
+
+        let us = self.our_index.unwrap();
+        let db = state
+            .get(*us)
+            .ok_or_else(|| internal_err!("set operation must have its own state materialized"))?;
+        let output_columns = self.output_columns();
+
+        // Our parents' states already reflect the records we've been sent, so for each distinct row
+        // in the input we recompute how many copies of that row should be in our output and emit
+        // the difference from what's currently in our state. This also means that full replays,
+        // which may split the copies of a row across several batches, emit each row exactly once.
+        let mut out = vec![];
+        for row in rs
+            .iter()
+            .map(|r| from_emit.iter().map(|&c| r[c].clone()).collect::<Vec<_>>())
+            .unique()
+        {
+            let key = PointKey::from(row.iter().cloned());
+            let left = self.count_in_parent(self.left, &self.emit_left, &key, nodes, state)?;
+            let right = self.count_in_parent(self.right, &self.emit_right, &key, nodes, state)?;
+            let current = match db.lookup(&output_columns, &key) {
+                LookupResult::Some(rs) => rs.len(),
+                LookupResult::Missing => {
+                    internal!("set operation state must be fully materialized")
+                }
+            };
+
+            let target = self.operator.multiplicity(left, right);
+            if target > current {
+                out.extend(
+                    iter::repeat(row)
+                        .take(target - current)
+                        .map(Record::Positive),
+                );
+            } else {
+                out.extend(
+                    iter::repeat(row)
+                        .take(current - target)
+                        .map(Record::Negative),
+                );
+            }
+        }
+
+        Ok(ProcessingResult {
+            results: out.into(),
+            ..Default::default()
+        })
+    }
+
+    fn suggest_indexes(&self, this: NodeIndex) -> HashMap<NodeIndex, LookupIndex> {
+        HashMap::from([
+            (
+                this,
+                LookupIndex::Strict(Index::hash_map(self.output_columns())),
+            ),
+            (
+                self.left.as_global(),
+                LookupIndex::Strict(Index::hash_map(self.emit_left.clone())),
+            ),
+            (
+                self.right.as_global(),
+                LookupIndex::Strict(Index::hash_map(self.emit_right.clone())),
+            ),
+        ])
+    }
+
+    fn column_source(&self, _cols: &[usize]) -> ColumnSource {
+        ColumnSource::RequiresFullReplay(vec1![self.left.as_global()])
+    }
+
+    fn description(&self, detailed: bool) -> String {
+        if !detailed {
+            return self.operator.to_string();
+        }
+
+        format!(
+            "{}:[{}] {} {}:[{}]",
+            self.left.as_global().index(),
+            self.emit_left.iter().join(", "),
+            self.operator,
+            self.right.as_global().index(),
+            self.emit_right.iter().join(", "),
+        )
+    }
+
+    fn is_selective(&self) -> bool {
+        true
+    }
+
+    fn requires_full_materialization(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ops::test::MockGraph;
+
+    fn setup(operator: SetOperator) -> (MockGraph, IndexPair, IndexPair) {
+        let mut g = MockGraph::new();
+        let l = g.add_base("left", &["l0", "l1"]);
+        let r = g.add_base("right", &["r0", "r1", "r2"]);
+
+        g.set_op(
+            "set_operation",
+            &["x", "y"],
+            SetOperation::new(
+                l.as_global(),
+                r.as_global(),
+                vec![0, 1],
+                vec![2, 0],
+                operator,
+            )
+            .unwrap(),
+            true,
+        );
+        (g, l, r)
+    }
+
+    /// Apply the given records to the state of the given parent, then send them to the node under
+    /// test
+    fn input<R: Into<Records>>(g: &mut MockGraph, parent: IndexPair, records: R) -> Records {
+        let mut records = records.into();
+        g.states
+            .get_mut(*parent)
+            .unwrap()
+            .process_records(&mut records, None, None)
+            .unwrap();
+        g.one(parent, records, true)
+    }
+
+    #[test]
+    fn it_describes() {
+        let (g, l, r) = setup(SetOperator::ExceptAll);
+        assert_eq!(
+            g.node().description(true),
+            format!(
+                "{}:[0, 1] ∖ ALL {}:[2, 0]",
+                l.as_global().index(),
+                r.as_global().index()
+            )
+        );
+    }
+
+    #[test]
+    fn suggest_indexes() {
+        let (g, l, r) = setup(SetOperator::Intersect);
+        let me = g.node_index().as_global();
+        assert_eq!(
+            g.node().suggest_indexes(me),
+            HashMap::from([
+                (me, LookupIndex::Strict(Index::hash_map(vec![0, 1]))),
+                (
+                    l.as_global(),
+                    LookupIndex::Strict(Index::hash_map(vec![0, 1]))
+                ),
+                (
+                    r.as_global(),
+                    LookupIndex::Strict(Index::hash_map(vec![2, 0]))
+                ),
+            ])
+        );
+    }
+
+    #[test]
+    fn intersect() {
+        let (mut g, l, r) = setup(SetOperator::Intersect);
+        let row: Vec<DfValue> = vec![1.into(), "a".into()];
+        let right_row: Vec<DfValue> = vec!["a".into(), 2.into(), 1.into()];
+
+        assert!(input(&mut g, l, vec![row.clone()]).is_empty());
+        assert!(input(&mut g, l, vec![row.clone()]).is_empty());
+        assert_eq!(
+            input(&mut g, r, vec![right_row.clone()]),
+            vec![(row.clone(), true)].into()
+        );
+        assert!(input(&mut g, r, vec![right_row]).is_empty());
+        assert!(input(&mut g, l, vec![(row.clone(), false)]).is_empty());
+        assert_eq!(
+            input(&mut g, l, vec![(row.clone(), false)]),
+            vec![(row, false)].into()
+        );
+    }
+
+    #[test]
+    fn intersect_all() {
+        let (mut g, l, r) = setup(SetOperator::IntersectAll);
+        let row: Vec<DfValue> = vec![1.into(), "a".into()];
+        let right_row: Vec<DfValue> = vec!["a".into(), 2.into(), 1.into()];
+
+        assert!(input(&mut g, l, vec![row.clone(), row.clone()]).is_empty());
+        assert_eq!(
+            input(&mut g, r, vec![right_row.clone()]),
+            vec![(row.clone(), true)].into()
+        );
+        assert_eq!(
+            input(&mut g, r, vec![right_row.clone()]),
+            vec![(row.clone(), true)].into()
+        );
+        // A third copy on the right doesn't match anything on the left
+        assert!(input(&mut g, r, vec![right_row.clone()]).is_empty());
+        assert!(input(&mut g, r, vec![(right_row, false)]).is_empty());
+        assert_eq!(
+            input(&mut g, l, vec![(row.clone(), false)]),
+            vec![(row, false)].into()
+        );
+    }
+
+    #[test]
+    fn except() {
+        let (mut g, l, r) = setup(SetOperator::Except);
+        let row: Vec<DfValue> = vec![1.into(), "a".into()];
+        let right_row: Vec<DfValue> = vec!["a".into(), 2.into(), 1.into()];
+
+        assert_eq!(
+            input(&mut g, l, vec![row.clone()]),
+            vec![(row.clone(), true)].into()
+        );
+        assert!(input(&mut g, l, vec![row.clone()]).is_empty());
+        assert_eq!(
+            input(&mut g, r, vec![right_row.clone()]),
+            vec![(row.clone(), false)].into()
+        );
+        assert_eq!(
+            input(&mut g, r, vec![(right_row, false)]),
+            vec![(row, true)].into()
+        );
+    }
+
+    #[test]
+    fn except_all() {
+        let (mut g, l, r) = setup(SetOperator::ExceptAll);
+        let row: Vec<DfValue> = vec![1.into(), "a".into()];
+        let other_row: Vec<DfValue> = vec![2.into(), "b".into()];
+        let right_row: Vec<DfValue> = vec!["a".into(), 2.into(), 1.into()];
+
+        assert_eq!(
+            input(&mut g, l, vec![row.clone(), row.clone(), other_row.clone()]),
+            vec![(row.clone(), true), (row.clone(), true), (other_row, true)].into()
+        );
+        assert_eq!(
+            input(&mut g, r, vec![right_row.clone()]),
+            vec![(row.clone(), false)].into()
+        );
+        assert_eq!(
+            input(&mut g, l, vec![(row.clone(), false)]),
+            vec![(row.clone(), false)].into()
+        );
+        assert!(input(&mut g, r, vec![right_row.clone()]).is_empty());
+        assert!(input(&mut g, r, vec![(right_row.clone(), false)]).is_empty());
+        assert_eq!(
+            input(&mut g, r, vec![(right_row, false)]),
+            vec![(row, true)].into()
+        );
+    }
+
+    #[test]
+    fn nulls_compare_equal() {
+        let (mut g, l, r) = setup(SetOperator::Intersect);
+        let row = vec![DfValue::None, "a".into()];
+        let right_row = vec!["a".into(), 2.into(), DfValue::None];
+
+        assert!(input(&mut g, l, vec![row.clone()]).is_empty());
+        assert_eq!(input(&mut g, r, vec![right_row]), vec![(row, true)].into());
+    }
+}
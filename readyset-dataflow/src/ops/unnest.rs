@@ -0,0 +1,319 @@
+use std::collections::HashMap;
+
+use readyset_data::{Array, DfType};
+use readyset_errors::{invalid_err, ReadySetResult};
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use tracing::error;
+
+use crate::prelude::*;
+use crate::processing::{ColumnSource, LookupIndex};
+
+/// The kind of value expanded by an [`Unnest`] operator, along with the way each element is
+/// emitted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum UnnestKind {
+    /// `unnest(array)`: emit one row per element of a SQL array, iterating the innermost
+    /// dimension first
+    Array,
+    /// `json[b]_array_elements(json)`: emit one row per element of a JSON array, as JSON
+    Json,
+    /// `json[b]_array_elements_text(json)`: emit one row per element of a JSON array, as text.
+    /// JSON strings are emitted without their quotes, and JSON nulls are emitted as SQL `NULL`
+    JsonText,
+}
+
+impl UnnestKind {
+    /// Returns `true` if values of the given type can be expanded, or if the type isn't known
+    ///
+    /// The upstream database rejects queries that expand values of any other type, so those
+    /// queries shouldn't be cached.
+    pub fn accepts_input_type(&self, input_ty: &DfType) -> bool {
+        if input_ty.is_unknown() {
+            return true;
+        }
+
+        match self {
+            UnnestKind::Array => matches!(input_ty, DfType::Array(_)),
+            UnnestKind::Json | UnnestKind::JsonText => input_ty.is_any_json(),
+        }
+    }
+
+    /// Returns the type of the elements emitted when expanding a value of the given type
+    pub fn output_type(&self, input_ty: &DfType) -> DfType {
+        match (self, input_ty) {
+            (UnnestKind::Array, DfType::Array(elem_ty)) => (**elem_ty).clone(),
+            (UnnestKind::Array, _) => DfType::Unknown,
+            (UnnestKind::Json, ty) if ty.is_any_json() => ty.clone(),
+            (UnnestKind::Json, _) => DfType::Jsonb,
+            (UnnestKind::JsonText, _) => DfType::DEFAULT_TEXT,
+        }
+    }
+}
+
+/// The unnest operator
+///
+/// The unnest operator expands a single array-valued column of each incoming record into one
+/// output record per element of that array. Each output record consists of all the columns of the
+/// input record, followed by the element itself. Records whose array column is `NULL` or empty
+/// produce no output records, which matches the semantics of an inner join against a
+/// set-returning function in the `FROM` clause.
+///
+/// Queries expanding a column whose type can't be expanded are rejected when they're lowered (see
+/// [`UnnestKind::accepts_input_type`]), but a value of the right type can still fail to expand -
+/// for example, a JSON object rather than a JSON array. The upstream database fails the whole
+/// query in that case, but since a dataflow node can't fail a write, we log an error and produce
+/// no output records for that value instead.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Unnest {
+    src: IndexPair,
+    /// The index of the column in the parent to expand
+    column: usize,
+    kind: UnnestKind,
+    /// The number of columns in the parent, which is also the index of our output column
+    parent_columns: usize,
+}
+
+impl Unnest {
+    /// Construct a new unnest operator which expands the given `column` of `src`, which has
+    /// `parent_columns` columns
+    pub fn new(src: NodeIndex, column: usize, kind: UnnestKind, parent_columns: usize) -> Unnest {
+        Unnest {
+            src: src.into(),
+            column,
+            kind,
+            parent_columns,
+        }
+    }
+
+    /// Returns the elements of the given value, according to our [`UnnestKind`]
+    fn elements(&self, value: &DfValue) -> ReadySetResult<Vec<DfValue>> {
+        if value.is_none() {
+            return Ok(vec![]);
+        }
+
+        match self.kind {
+            UnnestKind::Array => {
+                let array: &Array = match value {
+                    DfValue::Array(array) => array,
+                    _ => return Err(invalid_err!("Cannot unnest non-array value")),
+                };
+                Ok(array.values().cloned().collect())
+            }
+            UnnestKind::Json | UnnestKind::JsonText => match value.to_json()? {
+                JsonValue::Array(elements) => Ok(elements
+                    .into_iter()
+                    .map(|elem| match (self.kind, elem) {
+                        (UnnestKind::JsonText, JsonValue::Null) => DfValue::None,
+                        (UnnestKind::JsonText, JsonValue::String(s)) => s.into(),
+                        (_, elem) => elem.into(),
+                    })
+                    .collect()),
+                _ => Err(invalid_err!(
+                    "Cannot extract elements from a non-array JSON value"
+                )),
+            },
+        }
+    }
+}
+
+impl Ingredient for Unnest {
+    fn take(&mut self) -> NodeOperator {
+        Clone::clone(self).into()
+    }
+
+    fn ancestors(&self) -> Vec<NodeIndex> {
+        vec![self.src.as_global()]
+    }
+
+    impl_replace_sibling!(src);
+
+    fn on_commit(&mut self, _: NodeIndex, remap: &HashMap<NodeIndex, IndexPair>) {
+        self.src.remap(remap);
+    }
+
+    fn on_input(
+        &mut self,
+        _: LocalNodeIndex,
+        rs: Records,
+        _: &ReplayContext,
+        _: &DomainNodes,
+        _: &StateMap,
+        _: &mut AuxiliaryNodeStateMap,
+    ) -> ReadySetResult<ProcessingResult> {
+        let mut results = Vec::new();
+        let mut log_error_once_flag = false;
+        for r in rs {
+            let (row, positive) = r.extract();
+            // If we can't expand the value, it produces no rows, just like a NULL would
+            let elements = match row.get(self.column) {
+                Some(value) => self.elements(value).unwrap_or_else(|e| {
+                    // only log this error once per on_input call
+                    if !log_error_once_flag {
+                        error!(%e, "Error expanding value in unnest");
+                        log_error_once_flag = true;
+                    }
+                    vec![]
+                }),
+                None => vec![],
+            };
+
+            results.extend(elements.into_iter().map(|elem| {
+                let mut out = Vec::with_capacity(row.len() + 1);
+                out.extend(row.iter().cloned());
+                out.push(elem);
+                Record::from((out, positive))
+            }));
+        }
+
+        Ok(ProcessingResult {
+            results: results.into(),
+            ..Default::default()
+        })
+    }
+
+    fn suggest_indexes(&self, _: NodeIndex) -> HashMap<NodeIndex, LookupIndex> {
+        HashMap::new()
+    }
+
+    fn column_source(&self, cols: &[usize]) -> ColumnSource {
+        if cols.iter().any(|c| *c >= self.parent_columns) {
+            // Lookups on the elements themselves can't be mapped to a lookup in our parent
+            ColumnSource::RequiresFullReplay(vec1![self.src.as_global()])
+        } else {
+            ColumnSource::exact_copy(self.src.as_global(), cols.to_vec())
+        }
+    }
+
+    fn description(&self, detailed: bool) -> String {
+        if !detailed {
+            String::from("μ")
+        } else {
+            format!("μ[{}]", self.column)
+        }
+    }
+
+    fn is_selective(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ops;
+
+    fn setup(kind: UnnestKind) -> ops::test::MockGraph {
+        let mut g = ops::test::MockGraph::new();
+        let s = g.add_base("source", &["id", "vals"]);
+        g.set_op(
+            "unnest",
+            &["id", "vals", "val"],
+            Unnest::new(s.as_global(), 1, kind, 2),
+            false,
+        );
+        g
+    }
+
+    #[test]
+    fn it_expands_arrays() {
+        let mut g = setup(UnnestKind::Array);
+        let vals = DfValue::from(vec![DfValue::from(1), DfValue::from(2)]);
+        assert_eq!(
+            g.narrow_one_row(vec![1.into(), vals.clone()], false),
+            vec![
+                vec![1.into(), vals.clone(), 1.into()],
+                vec![1.into(), vals, 2.into()],
+            ]
+            .into()
+        );
+    }
+
+    #[test]
+    fn it_retracts_all_elements() {
+        let mut g = setup(UnnestKind::Array);
+        let vals = DfValue::from(vec![DfValue::from(1), DfValue::from(2)]);
+        assert_eq!(
+            g.narrow_one_row((vec![1.into(), vals.clone()], false), false),
+            Records::from(vec![
+                Record::Negative(vec![1.into(), vals.clone(), 1.into()]),
+                Record::Negative(vec![1.into(), vals, 2.into()]),
+            ])
+        );
+    }
+
+    #[test]
+    fn it_skips_null_and_empty() {
+        let mut g = setup(UnnestKind::Array);
+        assert!(g
+            .narrow_one_row(vec![1.into(), DfValue::None], false)
+            .is_empty());
+        assert!(g
+            .narrow_one_row(vec![1.into(), DfValue::from(Vec::<DfValue>::new())], false)
+            .is_empty());
+    }
+
+    #[test]
+    fn it_expands_json_arrays() {
+        let mut g = setup(UnnestKind::Json);
+        let vals = DfValue::from(r#"[1, "a", null]"#);
+        assert_eq!(
+            g.narrow_one_row(vec![1.into(), vals.clone()], false),
+            vec![
+                vec![1.into(), vals.clone(), "1".into()],
+                vec![1.into(), vals.clone(), "\"a\"".into()],
+                vec![1.into(), vals, "null".into()],
+            ]
+            .into()
+        );
+    }
+
+    #[test]
+    fn it_expands_json_arrays_as_text() {
+        let mut g = setup(UnnestKind::JsonText);
+        let vals = DfValue::from(r#"[1, "a", null]"#);
+        assert_eq!(
+            g.narrow_one_row(vec![1.into(), vals.clone()], false),
+            vec![
+                vec![1.into(), vals.clone(), "1".into()],
+                vec![1.into(), vals.clone(), "a".into()],
+                vec![1.into(), vals, DfValue::None],
+            ]
+            .into()
+        );
+    }
+
+    #[test]
+    fn it_skips_non_array_json() {
+        let mut g = setup(UnnestKind::Json);
+        assert!(g
+            .narrow_one_row(vec![1.into(), DfValue::from(r#"{"a": 1}"#)], false)
+            .is_empty());
+    }
+
+    #[test]
+    fn accepts_input_type() {
+        let int_array = DfType::Array(Box::new(DfType::Int));
+        assert!(UnnestKind::Array.accepts_input_type(&int_array));
+        assert!(UnnestKind::Array.accepts_input_type(&DfType::Unknown));
+        assert!(!UnnestKind::Array.accepts_input_type(&DfType::Int));
+        assert!(!UnnestKind::Array.accepts_input_type(&DfType::Jsonb));
+        assert!(UnnestKind::JsonText.accepts_input_type(&DfType::Json));
+        assert!(!UnnestKind::Json.accepts_input_type(&DfType::DEFAULT_TEXT));
+        assert!(!UnnestKind::Json.accepts_input_type(&int_array));
+    }
+
+    #[test]
+    fn column_source() {
+        let g = setup(UnnestKind::Array);
+        let me = g.node();
+        assert!(matches!(
+            me.column_source(&[0, 1]),
+            ColumnSource::ExactCopy(_)
+        ));
+        assert!(matches!(
+            me.column_source(&[0, 2]),
+            ColumnSource::RequiresFullReplay(_)
+        ));
+    }
+}
@@ -0,0 +1,696 @@
+use std::borrow::Cow;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::convert::TryInto;
+use std::fmt::{self, Display};
+use std::mem;
+
+use dataflow_state::PointKey;
+use itertools::Itertools;
+use nom_sql::OrderType;
+use readyset_data::DfType;
+use readyset_util::Indices;
+use serde::{Deserialize, Serialize};
+
+use crate::ops::utils::Order;
+use crate::prelude::*;
+use crate::processing::{ColumnMiss, LookupIndex};
+
+/// The function computed by a [`Window`] operator for each row within a partition.
+///
+/// All functions are computed over the rows of the partition up to and including the current row
+/// *and all of its peers* (rows which compare equal to it according to the window's ordering),
+/// which matches the default window frame (`RANGE BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW`) in
+/// both MySQL and PostgreSQL. If the window has no ordering, every row in the partition is a peer
+/// of every other row.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WindowFunction {
+    /// `ROW_NUMBER()`: the 1-based position of the row within its partition
+    RowNumber,
+    /// `RANK()`: the position of the first row in the row's peer group, with gaps
+    Rank,
+    /// `DENSE_RANK()`: the position of the row's peer group, without gaps
+    DenseRank,
+    /// A running count of the non-null values of the `over` column, or of all rows if there is no
+    /// `over` column (`COUNT(*)`)
+    Count,
+    /// A running sum of the `over` column
+    Sum,
+    /// A running minimum of the `over` column
+    Min,
+    /// A running maximum of the `over` column
+    Max,
+}
+
+impl WindowFunction {
+    /// Returns the type of the values computed by this window function, given the type of the
+    /// column it's computed over (if any)
+    pub fn output_type(&self, over_col_ty: Option<&DfType>) -> DfType {
+        match self {
+            WindowFunction::RowNumber
+            | WindowFunction::Rank
+            | WindowFunction::DenseRank
+            | WindowFunction::Count => DfType::BigInt,
+            // Match the output type of the SUM() aggregate
+            WindowFunction::Sum => match over_col_ty {
+                Some(ty) if ty.is_any_float() => DfType::Double,
+                _ => DfType::DEFAULT_NUMERIC,
+            },
+            WindowFunction::Min | WindowFunction::Max => {
+                over_col_ty.cloned().unwrap_or(DfType::Unknown)
+            }
+        }
+    }
+
+    /// Returns true if this window function requires a column to compute over
+    pub fn requires_over_column(&self) -> bool {
+        matches!(
+            self,
+            WindowFunction::Sum | WindowFunction::Min | WindowFunction::Max
+        )
+    }
+}
+
+impl Display for WindowFunction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WindowFunction::RowNumber => write!(f, "row_number"),
+            WindowFunction::Rank => write!(f, "rank"),
+            WindowFunction::DenseRank => write!(f, "dense_rank"),
+            WindowFunction::Count => write!(f, "count"),
+            WindowFunction::Sum => write!(f, "sum"),
+            WindowFunction::Min => write!(f, "min"),
+            WindowFunction::Max => write!(f, "max"),
+        }
+    }
+}
+
+/// Window implements a Dataflow node that computes a window function (such as `ROW_NUMBER()` or a
+/// running `SUM`) over partitions of its input, ordered by a set of columns.
+///
+/// The node emits all the columns of its parent, plus one additional column (always the last
+/// column) containing the value of the window function for that row. Like [`Paginate`], the node
+/// keeps its own output materialized, indexed by the partition columns, so that when a record
+/// arrives it can load the rest of the rows in that record's partition, recompute the window
+/// function for the partition, and emit a negative and positive record for each row whose value
+/// changed.
+///
+/// [`Paginate`]: crate::ops::paginate::Paginate
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Window {
+    /// The direct Ingredient or Base ancestor of this node
+    src: IndexPair,
+    /// The index of this node. Used to look up into our own state
+    our_index: Option<IndexPair>,
+    /// The column index of the window function column emitted by this node
+    ///
+    /// This is always equal to the number of columns in the parent node (there is one more output
+    /// column than input columns)
+    ///
+    /// Set during [`Ingredient::on_connected`]
+    output_col: Option<usize>,
+    /// The list of column indices that we're partitioning by
+    group_by: Vec<usize>,
+    /// The ordering for rows within each partition
+    order: Order,
+    /// The window function to compute
+    function: WindowFunction,
+    /// The column the window function is computed over, if any
+    over: Option<usize>,
+    /// The type of the values computed by the window function
+    out_ty: DfType,
+}
+
+impl Window {
+    /// Construct a new Window operator.
+    ///
+    /// Unlike [`Paginate`](crate::ops::paginate::Paginate) and
+    /// [`TopK`](crate::ops::topk::TopK), `order` uses the same semantics as SQL - rows are
+    /// numbered starting from the first row according to `order`.
+    pub fn new(
+        src: NodeIndex,
+        function: WindowFunction,
+        over: Option<usize>,
+        group_by: Vec<usize>,
+        order: Vec<(usize, OrderType)>,
+        out_ty: DfType,
+    ) -> ReadySetResult<Self> {
+        if function.requires_over_column() && over.is_none() {
+            internal!(
+                "{} window function requires a column to compute over",
+                function
+            );
+        }
+
+        Ok(Window {
+            src: src.into(),
+            our_index: None,
+            output_col: None,
+            group_by,
+            order: order.into(),
+            function,
+            over,
+            out_ty,
+        })
+    }
+
+    /// Project the columns we are partitioning by out of the given record
+    fn project_group<'rec, R>(&self, rec: &'rec R) -> ReadySetResult<Vec<&'rec DfValue>>
+    where
+        R: Indices<'static, usize, Output = DfValue> + ?Sized,
+    {
+        rec.indices(self.group_by.clone())
+            .map_err(|_| ReadySetError::InvalidRecordLength)
+    }
+
+    /// Return the column index of the window function column output by this node (which will
+    /// always be the last column)
+    ///
+    /// # Panics
+    ///
+    /// Panics if called before [`Ingredient::on_connected`]
+    fn output_column(&self) -> usize {
+        self.output_col
+            .expect("output_column called before Ingredient::on_connected")
+    }
+
+    /// Returns the zero value for running sums computed by this node
+    fn zero(&self) -> ReadySetResult<DfValue> {
+        match &self.out_ty {
+            DfType::BigInt => Ok(DfValue::Int(Default::default())),
+            DfType::Double => Ok(DfValue::Double(Default::default())),
+            DfType::Numeric { .. } => Ok(DfValue::Numeric(Default::default())),
+            ty => internal!("Invalid output type for window sum: {}", ty),
+        }
+    }
+
+    /// Compute the value of the window function for each of the given rows, which must already be
+    /// sorted according to `self.order`
+    fn compute<R>(&self, rows: &[R]) -> ReadySetResult<Vec<DfValue>>
+    where
+        R: AsRef<[DfValue]>,
+    {
+        let mut values = Vec::with_capacity(rows.len());
+        let mut acc = match self.function {
+            WindowFunction::Count => DfValue::Int(0),
+            _ => DfValue::None,
+        };
+        let mut dense_rank = 0i64;
+        let mut peers_start = 0;
+        while peers_start < rows.len() {
+            let peers_end = rows[peers_start..]
+                .iter()
+                .position(|row| {
+                    self.order
+                        .cmp(rows[peers_start].as_ref(), row.as_ref())
+                        .is_ne()
+                })
+                .map_or(rows.len(), |len| peers_start + len);
+            dense_rank += 1;
+
+            for row in &rows[peers_start..peers_end] {
+                // Null values are ignored by all window functions that take a column
+                let value = self
+                    .over
+                    .map(|over| &row.as_ref()[over])
+                    .filter(|value| !value.is_none());
+                match (&self.function, value) {
+                    (WindowFunction::Count, Some(_)) => acc = (&acc + &DfValue::Int(1))?,
+                    (WindowFunction::Count, None) if self.over.is_none() => {
+                        acc = (&acc + &DfValue::Int(1))?
+                    }
+                    (WindowFunction::Sum, Some(value)) => {
+                        if acc.is_none() {
+                            acc = self.zero()?;
+                        }
+                        acc = (&acc + value)?
+                    }
+                    (WindowFunction::Min, Some(value)) if acc.is_none() || *value < acc => {
+                        acc = value.clone()
+                    }
+                    (WindowFunction::Max, Some(value)) if acc.is_none() || *value > acc => {
+                        acc = value.clone()
+                    }
+                    _ => {}
+                }
+            }
+
+            for i in peers_start..peers_end {
+                values.push(match self.function {
+                    WindowFunction::RowNumber => ((i + 1) as i64).into(),
+                    WindowFunction::Rank => ((peers_start + 1) as i64).into(),
+                    WindowFunction::DenseRank => dense_rank.into(),
+                    WindowFunction::Count
+                    | WindowFunction::Sum
+                    | WindowFunction::Min
+                    | WindowFunction::Max => acc.clone(),
+                });
+            }
+
+            peers_start = peers_end;
+        }
+
+        Ok(values)
+    }
+
+    fn post_group(
+        &self,
+        out: &mut Vec<Record>,
+        current_group: &mut Vec<Cow<'_, [DfValue]>>,
+    ) -> ReadySetResult<()> {
+        let mut current_group = mem::take(current_group);
+        // Break ties between peers using the rest of the row, so that functions which distinguish
+        // between peers (such as `ROW_NUMBER()`) assign values deterministically
+        current_group.sort_by(|a, b| {
+            self.order
+                .cmp(a, b)
+                .then_with(|| a[..self.output_column()].cmp(&b[..self.output_column()]))
+        });
+        let values = self.compute(&current_group)?;
+
+        for (mut row, value) in current_group.into_iter().zip(values) {
+            if let Some(current_value) = row.get(self.output_column()) {
+                // if the row already has a value, that means it started out in the group
+                if *current_value != value {
+                    // if the value is different, we need to emit a negative for the old value and
+                    // a positive for the new one
+                    out.push(Record::Negative(row.clone().into()));
+                    row.to_mut()[self.output_column()] = value;
+                    out.push(Record::Positive(row.into()));
+                }
+            } else {
+                row.to_mut().push(value);
+                out.push(row.into_owned().into());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Ingredient for Window {
+    fn take(&mut self) -> NodeOperator {
+        self.clone().into()
+    }
+
+    fn ancestors(&self) -> Vec<NodeIndex> {
+        vec![self.src.as_global()]
+    }
+
+    impl_replace_sibling!(src);
+
+    fn on_connected(&mut self, graph: &Graph) {
+        self.output_col = Some(graph[self.src.as_global()].columns().len());
+    }
+
+    fn on_commit(&mut self, us: NodeIndex, remap: &HashMap<NodeIndex, IndexPair>) {
+        self.src.remap(remap);
+        self.our_index = Some(remap[&us]);
+    }
+
+    fn on_input(
+        &mut self,
+        from: LocalNodeIndex,
+        rs: Records,
+        replay: &ReplayContext,
+        _nodes: &DomainNodes,
+        state: &StateMap,
+        _auxiliary_node_states: &mut AuxiliaryNodeStateMap,
+    ) -> ReadySetResult<ProcessingResult> {
+        debug_assert_eq!(from, *self.src);
+
+        if rs.is_empty() {
+            return Ok(ProcessingResult {
+                results: rs,
+                ..Default::default()
+            });
+        }
+
+        let mut rs = Vec::from(rs);
+        rs.sort_by(|a: &Record, b: &Record| {
+            self.project_group(&***a)
+                .unwrap_or_default()
+                .cmp(&self.project_group(&***b).unwrap_or_default())
+        });
+
+        let us = self.our_index.unwrap();
+        let db = state
+            .get(*us)
+            .ok_or_else(|| internal_err!("window must have its own state materialized"))?;
+
+        let mut current_group_key: Vec<DfValue> = vec![];
+        let mut current_group: Vec<Cow<[DfValue]>> = vec![];
+        let mut group_missed = false;
+
+        let mut out = vec![];
+        let mut lookups = vec![];
+        let mut misses = vec![];
+
+        for r in rs {
+            let record_group = self.project_group(r.rec())?;
+            if current_group_key.iter().cmp(record_group.iter().copied()) != Ordering::Equal {
+                // New group!
+                if !current_group_key.is_empty() {
+                    self.post_group(&mut out, &mut current_group)?;
+                }
+
+                // Clear and extend to reuse the allocation
+                current_group_key.clear();
+                current_group_key.extend(record_group.into_iter().cloned());
+
+                // Load the whole partition into memory
+                match db.lookup(&self.group_by, &PointKey::from(current_group_key.clone())) {
+                    LookupResult::Some(local_records) => {
+                        if replay.is_partial() {
+                            lookups.push(Lookup {
+                                on: *us,
+                                cols: self.group_by.clone(),
+                                key: current_group_key.clone().try_into().expect("Empty group"),
+                            });
+                        }
+
+                        group_missed = false;
+                        current_group.extend(local_records);
+                    }
+                    LookupResult::Missing => {
+                        group_missed = true;
+                    }
+                }
+            }
+
+            if group_missed {
+                misses.push(
+                    Miss::builder()
+                        .on(*us)
+                        .lookup_idx(self.group_by.clone())
+                        .lookup_key(self.group_by.clone())
+                        .replay(replay)
+                        .record(r.into_row())
+                        .build(),
+                );
+                continue;
+            }
+
+            match r {
+                Record::Positive(r) => current_group.push(Cow::Owned(r)),
+                Record::Negative(r) => {
+                    if let Some(pos) = current_group
+                        .iter()
+                        .position(|row| row[..self.output_column()] == *r)
+                    {
+                        let row = current_group.remove(pos);
+                        if row.len() > self.output_column() {
+                            // Only rows that were already in our state have been emitted before
+                            out.push(Record::Negative(row.into_owned()));
+                        }
+                    }
+                }
+            }
+        }
+
+        if !current_group.is_empty() {
+            self.post_group(&mut out, &mut current_group)?;
+        }
+
+        Ok(ProcessingResult {
+            results: out.into(),
+            lookups,
+            misses,
+        })
+    }
+
+    fn suggest_indexes(&self, this: NodeIndex) -> HashMap<NodeIndex, LookupIndex> {
+        HashMap::from([(
+            this,
+            LookupIndex::Strict(Index::hash_map(self.group_by.clone())),
+        )])
+    }
+
+    fn column_source(&self, cols: &[usize]) -> ColumnSource {
+        if cols.contains(&self.output_column()) {
+            if cols.len() == 1 {
+                // Lookups on only the window function value require a full replay
+                return ColumnSource::RequiresFullReplay(vec1![self.src.as_global()]);
+            }
+
+            #[allow(clippy::unwrap_used)]
+            // Once we remove the output column, we have to have at least one column left (because
+            // we just checked len > 1)
+            let columns = cols
+                .iter()
+                .copied()
+                .filter(|c| *c != self.output_column())
+                .collect::<Vec<_>>();
+            ColumnSource::GeneratedFromColumns(vec1![ColumnRef {
+                node: self.our_index.unwrap().as_global(),
+                columns,
+            }])
+        } else {
+            ColumnSource::ExactCopy(ColumnRef {
+                node: self.src.as_global(),
+                columns: cols.to_vec(),
+            })
+        }
+    }
+
+    fn handle_upquery(&mut self, miss: ColumnMiss) -> ReadySetResult<Vec<ColumnMiss>> {
+        let output_column = miss
+            .column_indices
+            .iter()
+            .position(|ci| *ci == self.output_column())
+            .expect("handle_upquery invariant");
+
+        Ok(vec![ColumnMiss {
+            node: *self.our_index.unwrap(),
+            column_indices: self.group_by.clone(),
+            missed_keys: miss.missed_keys.mapped(|k| {
+                k.map_endpoints(|mut r| {
+                    r.remove(output_column).expect("handle_upquery invariant");
+                    r
+                })
+            }),
+        }])
+    }
+
+    fn description(&self, detailed: bool) -> String {
+        if !detailed {
+            return "Window".into();
+        }
+
+        format!(
+            "Window {}({}) γ[{}] o[{}]",
+            self.function,
+            self.over.map(|c| c.to_string()).unwrap_or_default(),
+            self.group_by.iter().join(", "),
+            self.order
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ops::test::MockGraph;
+
+    fn setup(function: WindowFunction, over: Option<usize>) -> (MockGraph, IndexPair) {
+        let mut g = MockGraph::new();
+        let s = g.add_base("source", &["x", "y"]);
+
+        // function(over) OVER (PARTITION BY y ORDER BY x)
+        g.set_op(
+            "window",
+            &["x", "y", "w"],
+            Window::new(
+                s.as_global(),
+                function,
+                over,
+                vec![1],
+                vec![(0, OrderType::OrderAscending)],
+                DfType::BigInt,
+            )
+            .unwrap(),
+            true,
+        );
+        (g, s)
+    }
+
+    fn with_value<V: Into<DfValue>>(row: &[DfValue], value: V) -> Vec<DfValue> {
+        let mut res = row.to_vec();
+        res.push(value.into());
+        res
+    }
+
+    #[test]
+    fn column_source_for_window_lookup() {
+        let (g, s) = setup(WindowFunction::RowNumber, None);
+        assert_eq!(
+            g.node().column_source(&[1]),
+            ColumnSource::ExactCopy(ColumnRef {
+                node: s.as_global(),
+                columns: vec![1]
+            })
+        );
+        assert_eq!(
+            g.node().column_source(&[1, 2]),
+            ColumnSource::GeneratedFromColumns(vec1![ColumnRef {
+                node: g.node_index().as_global(),
+                columns: vec![1],
+            }])
+        );
+        assert_eq!(
+            g.node().column_source(&[2]),
+            ColumnSource::RequiresFullReplay(vec1![s.as_global()])
+        );
+    }
+
+    #[test]
+    fn suggest_indexes() {
+        let (g, _) = setup(WindowFunction::RowNumber, None);
+        let res = g.node().suggest_indexes(g.node_index().as_global());
+        assert_eq!(res.len(), 1);
+        assert_eq!(
+            res[&g.node_index().as_global()],
+            LookupIndex::Strict(Index::hash_map(vec![1]))
+        );
+    }
+
+    #[test]
+    fn row_number_multiple_groups() {
+        let (mut g, _) = setup(WindowFunction::RowNumber, None);
+
+        let r1a = vec![1.into(), "a".into()];
+        let r2a = vec![2.into(), "a".into()];
+        let r1b = vec![1.into(), "b".into()];
+
+        let res = g.narrow_one(vec![r2a.clone(), r1b.clone(), r1a.clone()], true);
+        assert_eq!(
+            res,
+            vec![
+                with_value(&r1a, 1i64),
+                with_value(&r2a, 2i64),
+                with_value(&r1b, 1i64),
+            ]
+            .into()
+        );
+        // Values must match the declared output type of `BIGINT`
+        assert!(res.iter().all(|r| matches!(r.rec()[2], DfValue::Int(_))));
+    }
+
+    #[test]
+    fn row_number_insert_shifts_later_rows() {
+        let (mut g, _) = setup(WindowFunction::RowNumber, None);
+
+        let r1a = vec![1.into(), "a".into()];
+        let r2a = vec![2.into(), "a".into()];
+        let r3a = vec![3.into(), "a".into()];
+
+        g.narrow_one(vec![r1a, r3a.clone()], true);
+
+        let res = g.narrow_one_row(r2a.clone(), true);
+        assert_eq!(
+            res,
+            vec![
+                (with_value(&r2a, 2i64), true),
+                (with_value(&r3a, 2i64), false),
+                (with_value(&r3a, 3i64), true),
+            ]
+            .into()
+        );
+    }
+
+    #[test]
+    fn row_number_delete_shifts_later_rows() {
+        let (mut g, _) = setup(WindowFunction::RowNumber, None);
+
+        let r1a = vec![1.into(), "a".into()];
+        let r2a = vec![2.into(), "a".into()];
+        let r3a = vec![3.into(), "a".into()];
+
+        g.narrow_one(vec![r1a, r2a.clone(), r3a.clone()], true);
+
+        let res = g.narrow_one_row((r2a.clone(), false), true);
+        assert_eq!(
+            res,
+            vec![
+                (with_value(&r2a, 2i64), false),
+                (with_value(&r3a, 3i64), false),
+                (with_value(&r3a, 2i64), true),
+            ]
+            .into()
+        );
+    }
+
+    #[test]
+    fn rank_and_dense_rank_with_ties() {
+        let r1a = vec![1.into(), "a".into()];
+        let r1a_2 = vec![1.into(), "a".into()];
+        let r2a = vec![2.into(), "a".into()];
+
+        let (mut g, _) = setup(WindowFunction::Rank, None);
+        let res = g.narrow_one(vec![r1a.clone(), r1a_2.clone(), r2a.clone()], true);
+        assert_eq!(
+            res,
+            vec![
+                with_value(&r1a, 1i64),
+                with_value(&r1a_2, 1i64),
+                with_value(&r2a, 3i64),
+            ]
+            .into()
+        );
+
+        let (mut g, _) = setup(WindowFunction::DenseRank, None);
+        let res = g.narrow_one(vec![r1a.clone(), r1a_2.clone(), r2a.clone()], true);
+        assert_eq!(
+            res,
+            vec![
+                with_value(&r1a, 1i64),
+                with_value(&r1a_2, 1i64),
+                with_value(&r2a, 2i64),
+            ]
+            .into()
+        );
+    }
+
+    #[test]
+    fn running_sum() {
+        let (mut g, _) = setup(WindowFunction::Sum, Some(0));
+
+        let r1a = vec![1.into(), "a".into()];
+        let r2a = vec![2.into(), "a".into()];
+        let r3a = vec![3.into(), "a".into()];
+
+        let res = g.narrow_one(vec![r1a.clone(), r3a.clone()], true);
+        assert_eq!(res, vec![with_value(&r1a, 1), with_value(&r3a, 4)].into());
+
+        let res = g.narrow_one_row(r2a.clone(), true);
+        assert_eq!(
+            res,
+            vec![
+                (with_value(&r2a, 3), true),
+                (with_value(&r3a, 4), false),
+                (with_value(&r3a, 6), true),
+            ]
+            .into()
+        );
+    }
+
+    #[test]
+    fn running_count_star_includes_peers() {
+        let (mut g, _) = setup(WindowFunction::Count, None);
+
+        let r1a = vec![1.into(), "a".into()];
+        let r2a = vec![2.into(), "a".into()];
+        let r2a_2 = vec![2.into(), "a".into()];
+
+        let res = g.narrow_one(vec![r1a.clone(), r2a.clone(), r2a_2.clone()], true);
+        assert_eq!(
+            res,
+            vec![
+                with_value(&r1a, 1),
+                with_value(&r2a, 3),
+                with_value(&r2a_2, 3),
+            ]
+            .into()
+        );
+    }
+}
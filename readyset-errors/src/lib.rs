@@ -294,6 +294,29 @@ pub enum ReadySetError {
     #[error("the queries lookup key is not found at the reader")]
     ReaderMissingKey,
 
+    /// A read requested rows past the number of rows the reader keeps for each key, which happens
+    /// when the `LIMIT` and `OFFSET` given for a query with a parametrized `LIMIT` add up to more
+    /// than the configured maximum
+    #[error("LIMIT {limit} with OFFSET {offset} exceeds the {max} rows cached for each key")]
+    PageOutOfBounds {
+        /// The requested limit
+        limit: usize,
+        /// The requested offset
+        offset: usize,
+        /// The number of rows kept in the reader for each key
+        max: usize,
+    },
+
+    /// A query references a session-scoped value (such as a user variable) which the adapter
+    /// can't be sure of the current value of, since it may have been changed by a statement the
+    /// adapter couldn't interpret. This error should not reach the client when an upstream
+    /// database is present.
+    #[error("The current value of {parameter} in this session is not known")]
+    UnknownSessionValue {
+        /// A description of the session-scoped value
+        parameter: String,
+    },
+
     /// A prepared statement is missing.
     #[error("Prepared statement with ID {statement_id} not found")]
     PreparedStatementMissing {
@@ -716,6 +739,15 @@ impl ReadySetError {
         self.any_cause(|e| e.is_unsupported())
     }
 
+    /// If `self` either *is* [`Unsupported`] or was *caused by* [`Unsupported`], returns the
+    /// reason given for the operation being unsupported. Otherwise, returns `None`
+    pub fn unsupported_cause(&self) -> Option<&str> {
+        self.find_map_cause(|e| match e {
+            Self::Unsupported(reason) => Some(reason.as_str()),
+            _ => None,
+        })
+    }
+
     /// Returns `true` if self is ['ViewNotFound'].
     pub fn is_view_not_found(&self) -> bool {
         matches!(self, Self::ViewNotFound(..))
@@ -1192,6 +1224,7 @@ mod test {
             }),
         };
         assert!(err.caused_by_unsupported());
+        assert_eq!(err.unsupported_cause(), Some("Test"));
     }
 
     #[test]
@@ -487,6 +487,7 @@ impl TestScript {
 
             let mut builder = Builder::for_tests();
             builder.set_allow_mixed_comparisons(true);
+            builder.set_max_recursion_depth(Some(16));
 
             if run_opts.enable_reuse {
                 builder.set_reuse(Some(ReuseConfigType::Finkelstein))
@@ -147,9 +147,28 @@ impl MirGraph {
     /// from its parent.
     pub fn referenced_columns(&self, node: NodeIndex) -> Vec<MirColumn> {
         match &self.graph[node].inner {
-            MirNodeInner::Aggregation { on, group_by, .. }
-            | MirNodeInner::Extremum { on, group_by, .. } => {
-                // Aggregates need the group_by columns and the "over" column
+            MirNodeInner::Aggregation {
+                on,
+                key,
+                group_by,
+                order,
+                ..
+            } => {
+                // Aggregates need the group_by columns, the "over" column, and any key or order
+                // columns
+                let mut columns = group_by.clone();
+                for c in iter::once(on)
+                    .chain(key.iter())
+                    .chain(order.iter().map(|(c, _)| c))
+                {
+                    if !columns.contains(c) {
+                        columns.push(c.clone());
+                    }
+                }
+                columns
+            }
+            MirNodeInner::Extremum { on, group_by, .. } => {
+                // Extremums need the group_by columns and the "over" column
                 let mut columns = group_by.clone();
                 if !columns.contains(on) {
                     columns.push(on.clone());
@@ -157,6 +176,37 @@ impl MirGraph {
                 columns
             }
             MirNodeInner::Distinct { group_by } => group_by.clone(),
+            MirNodeInner::Window {
+                on,
+                group_by,
+                order,
+                ..
+            } => {
+                // Window nodes pass through all of their parent's columns (everything but their
+                // output column), and also need the partition, order, and "over" columns
+                let mut columns = self.columns(node);
+                columns.pop();
+                for c in group_by
+                    .iter()
+                    .chain(order.iter().map(|(c, _)| c))
+                    .chain(on.iter())
+                {
+                    if !columns.contains(c) {
+                        columns.push(c.clone());
+                    }
+                }
+                columns
+            }
+            MirNodeInner::Unnest { on, .. } => {
+                // Unnest nodes pass through all of their parent's columns (everything but their
+                // output column), and also need the column they're expanding
+                let mut columns = self.columns(node);
+                columns.pop();
+                if !columns.contains(on) {
+                    columns.push(on.clone());
+                }
+                columns
+            }
             MirNodeInner::Project { emit } => {
                 let mut columns = vec![];
                 for expr in emit {
@@ -191,15 +241,25 @@ impl MirGraph {
                         .chain(order_by.iter().flatten().map(|(c, _)| c.clone()))
                         .chain(returned_cols.iter().flatten().cloned())
                         .chain(aggregates.iter().flat_map(|aggs| {
-                            aggs.group_by
-                                .clone()
-                                .into_iter()
-                                .chain(aggs.aggregates.iter().map(|agg| agg.column.clone()))
+                            aggs.group_by.clone().into_iter().chain(
+                                aggs.aggregates.iter().flat_map(|agg| {
+                                    iter::once(agg.column.clone())
+                                        .chain(agg.function.derived_from().into_iter().cloned())
+                                }),
+                            )
                         })),
                 );
                 columns
             }
-            MirNodeInner::Filter { conditions } => {
+            MirNodeInner::Filter { conditions }
+            | MirNodeInner::LeftJoin {
+                residual: Some(conditions),
+                ..
+            }
+            | MirNodeInner::DependentLeftJoin {
+                residual: Some(conditions),
+                ..
+            } => {
                 let mut columns = self.columns(node);
                 for c in conditions.referred_columns() {
                     if !columns.iter().any(|col| col == c) {
@@ -254,7 +314,9 @@ impl MirGraph {
                 .collect(),
             MirNodeInner::Join { project, .. }
             | MirNodeInner::LeftJoin { project, .. }
-            | MirNodeInner::DependentJoin { project, .. } => project.clone(),
+            | MirNodeInner::FullJoin { project, .. }
+            | MirNodeInner::DependentJoin { project, .. }
+            | MirNodeInner::DependentLeftJoin { project, .. } => project.clone(),
             MirNodeInner::JoinAggregates => {
                 let cols = self
                     // see note [edge-ordering]
@@ -281,10 +343,19 @@ impl MirGraph {
                 .first()
                 .cloned()
                 .expect("Union must have at least one set of emit columns"),
+            MirNodeInner::SetOperation { emit, .. } => emit
+                .first()
+                .cloned()
+                .expect("Set operations must have two sets of emit columns"),
             MirNodeInner::Paginate { .. } => parent_columns()
                 .into_iter()
                 .chain(iter::once(MirColumn::named(&*PAGE_NUMBER_COL)))
                 .collect(),
+            MirNodeInner::Window { output_column, .. }
+            | MirNodeInner::Unnest { output_column, .. } => parent_columns()
+                .into_iter()
+                .chain(iter::once(output_column.clone()))
+                .collect(),
             MirNodeInner::Distinct { group_by } => group_by
                 .iter()
                 .cloned()
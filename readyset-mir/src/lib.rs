@@ -27,9 +27,10 @@
 //!   keyword, is actually converted to a `Count` node when lowering to dataflow, since due to the
 //!   intricacies of partial stateful dataflow the best way to implement distinct is to calculate a
 //!   count grouped by all columns followed by omitting the actual count value in the result set
-//! * The [`DependentJoin`] node will throw an *error* if encountered when converting to dataflow -
-//!   this node type is created as part of compiling correlated subqueries, and the expectation is
-//!   that it will be removed entirely as part of a [rewrite pass][decorrelate]
+//! * The [`DependentJoin`] and [`DependentLeftJoin`] nodes will throw an *error* if encountered
+//!   when converting to dataflow - these node types are created as part of compiling correlated
+//!   subqueries, and the expectation is that they will be removed entirely as part of a
+//!   [rewrite pass][decorrelate]
 //!
 //! [AST]: nom_sql
 //! [node]: crate::node::MirNode
@@ -38,6 +39,7 @@
 //! [`TableAlias`]: crate::node::MirNodeInner::TableAlias
 //! [`Distinct`]: crate::node::MirNodeInner::Distinct
 //! [`DependentJoin`]: crate::node::MirNodeInner::DependentJoin
+//! [`DependentLeftJoin`]: crate::node::MirNodeInner::DependentLeftJoin
 //! [decorrelate]: crate::rewrite::decorrelate::eliminate_dependent_joins
 
 #![warn(clippy::panic)]
@@ -2,12 +2,12 @@ use std::collections::HashSet;
 use std::fmt::{Debug, Display, Error, Formatter};
 
 use dataflow::ops;
-use nom_sql::Relation;
+use nom_sql::{OrderType, Relation};
 use readyset_errors::{internal, ReadySetResult};
 use serde::{Deserialize, Serialize};
 
 pub use self::node_inner::{MirNodeInner, ProjectExpr, ViewKeyColumn};
-use crate::DfNodeIndex;
+use crate::{Column, DfNodeIndex};
 
 pub mod node_inner;
 
@@ -15,6 +15,16 @@ pub mod node_inner;
 pub enum GroupedNodeType {
     Aggregation(ops::grouped::aggregate::Aggregation),
     Extremum(ops::grouped::extremum::Extremum),
+    /// An aggregation which collects all the values in each group (see
+    /// [`Aggregation::Collect`]), with an optional key column and set of columns to order the
+    /// values by
+    ///
+    /// [`Aggregation::Collect`]: ops::grouped::aggregate::Aggregation::Collect
+    Collect {
+        kind: ops::grouped::collect::Collection,
+        key: Option<Column>,
+        order: Vec<(Column, OrderType)>,
+    },
 }
 
 /// A node in the MIR graph, that represent some abstract computation
@@ -121,7 +131,10 @@ mod tests {
         use common::IndexType;
         use dataflow::ops::grouped::aggregate::Aggregation;
         use dataflow::ops::grouped::extremum::Extremum;
+        use dataflow::ops::set_operation::SetOperator;
         use dataflow::ops::union::DuplicateMode;
+        use dataflow::ops::unnest::UnnestKind;
+        use dataflow::ops::window::WindowFunction;
         use nom_sql::{BinaryOperator, ColumnSpecification, Expr, OrderType, SqlType};
         use readyset_client::ViewPlaceholder;
 
@@ -209,7 +222,9 @@ mod tests {
             has_columns_single_parent(
                 MirNodeInner::Aggregation {
                     on: Column::new(Some("base"), "a"),
+                    key: None,
                     group_by: vec![Column::new(Some("base"), "b")],
+                    order: vec![],
                     output_column: Column::named("agg"),
                     kind: Aggregation::Sum,
                 },
@@ -344,6 +359,41 @@ mod tests {
             );
         }
 
+        #[test]
+        fn set_operation() {
+            let mut graph = MirGraph::new();
+            let base1 = base1(&mut graph);
+            let base2 = base2(&mut graph);
+
+            let set_operation = graph.add_node(MirNode::new(
+                "set_operation".into(),
+                MirNodeInner::SetOperation {
+                    emit: vec![
+                        vec![
+                            Column::new(Some("base"), "b"),
+                            Column::new(Some("base"), "a"),
+                        ],
+                        vec![
+                            Column::new(Some("base2"), "b"),
+                            Column::new(Some("base2"), "a"),
+                        ],
+                    ],
+                    operator: SetOperator::ExceptAll,
+                },
+            ));
+            graph.add_edge(base1, set_operation, 0);
+            graph.add_edge(base2, set_operation, 1);
+
+            let cols = graph.columns(set_operation);
+            assert_eq!(
+                cols,
+                vec![
+                    Column::new(Some("base"), "b"),
+                    Column::new(Some("base"), "a")
+                ]
+            );
+        }
+
         #[test]
         fn leaf() {
             same_columns_as_parent(MirNodeInner::Leaf {
@@ -358,6 +408,7 @@ mod tests {
                 returned_cols: None,
                 default_row: None,
                 aggregates: None,
+                null_key_columns: vec![],
             })
         }
 
@@ -392,6 +443,40 @@ mod tests {
             )
         }
 
+        #[test]
+        fn window() {
+            has_columns_single_parent(
+                MirNodeInner::Window {
+                    on: None,
+                    group_by: vec![Column::new(Some("base"), "b")],
+                    order: vec![(Column::new(Some("base"), "a"), OrderType::OrderAscending)],
+                    output_column: Column::named("row_number"),
+                    kind: WindowFunction::RowNumber,
+                },
+                vec![
+                    Column::new(Some("base"), "a"),
+                    Column::new(Some("base"), "b"),
+                    Column::named("row_number"),
+                ],
+            )
+        }
+
+        #[test]
+        fn unnest() {
+            has_columns_single_parent(
+                MirNodeInner::Unnest {
+                    on: Column::new(Some("base"), "b"),
+                    output_column: Column::new(Some("elems"), "elem"),
+                    kind: UnnestKind::Array,
+                },
+                vec![
+                    Column::new(Some("base"), "a"),
+                    Column::new(Some("base"), "b"),
+                    Column::new(Some("elems"), "elem"),
+                ],
+            )
+        }
+
         #[test]
         fn join_aggregates() {
             let mut graph = MirGraph::new();
@@ -400,7 +485,9 @@ mod tests {
                 "count".into(),
                 MirNodeInner::Aggregation {
                     on: Column::new(Some("base"), "a"),
+                    key: None,
                     group_by: vec![Column::new(Some("base"), "b")],
+                    order: vec![],
                     output_column: Column::named("count"),
                     kind: Aggregation::Count,
                 },
@@ -411,7 +498,9 @@ mod tests {
                 "count".into(),
                 MirNodeInner::Aggregation {
                     on: Column::new(Some("base"), "a"),
+                    key: None,
                     group_by: vec![Column::new(Some("base"), "b")],
+                    order: vec![],
                     output_column: Column::named("sum"),
                     kind: Aggregation::Sum,
                 },
@@ -443,7 +532,9 @@ mod tests {
                 "agg1".into(),
                 MirNodeInner::Aggregation {
                     on: Column::named("c"),
+                    key: None,
                     group_by: vec![Column::named("a"), Column::named("b")],
+                    order: vec![],
                     output_column: Column::named("sum(c)"),
                     kind: Aggregation::Sum,
                 },
@@ -453,7 +544,9 @@ mod tests {
                 "agg2".into(),
                 MirNodeInner::Aggregation {
                     on: Column::named("c"),
+                    key: None,
                     group_by: vec![Column::named("a"), Column::named("b")],
+                    order: vec![],
                     output_column: Column::named("sum(c)"),
                     kind: Aggregation::Sum,
                 },
@@ -529,7 +622,9 @@ mod tests {
                 "aggregate".into(),
                 MirNodeInner::Aggregation {
                     on: Column::named("on"),
+                    key: None,
                     group_by: vec![Column::named("gb_a"), Column::named("gb_b")],
+                    order: vec![],
                     output_column: Column::named("output"),
                     kind: Aggregation::Count,
                 },
@@ -3,13 +3,16 @@ use std::fmt::{self, Debug, Display};
 use common::{DfValue, IndexType};
 use dataflow::ops::grouped::aggregate::Aggregation;
 use dataflow::ops::grouped::extremum::Extremum;
+use dataflow::ops::set_operation::SetOperator;
 use dataflow::ops::union;
+use dataflow::ops::unnest::UnnestKind;
+use dataflow::ops::window::WindowFunction;
 use dataflow::PostLookupAggregates;
 use derive_more::From;
 use itertools::Itertools;
 use nom_sql::{BinaryOperator, ColumnSpecification, Expr, OrderType, Relation, SqlIdentifier};
 use readyset_client::{PlaceholderIdx, ViewPlaceholder};
-use readyset_errors::{internal, ReadySetResult};
+use readyset_errors::{internal, unsupported, ReadySetResult};
 use serde::{Deserialize, Serialize};
 use vec1::Vec1;
 
@@ -76,8 +79,14 @@ pub enum MirNodeInner {
     Aggregation {
         /// Column to compute the aggregate function over
         on: Column,
+        /// Column containing the keys, for aggregate functions which collect pairs of keys and
+        /// values (such as `JSON_OBJECTAGG`)
+        key: Option<Column>,
         /// List of columns to group by
         group_by: Vec<Column>,
+        /// Set of columns used for ordering the aggregated values within each group, for aggregate
+        /// functions with an inner `ORDER BY` clause (such as `ARRAY_AGG(x ORDER BY y)`)
+        order: Vec<(Column, OrderType)>,
         /// The column name to use for the result of the aggregate, which will always be the last
         /// column
         output_column: Column,
@@ -169,6 +178,29 @@ pub enum MirNodeInner {
         on: Vec<(Column, Column)>,
         /// Columns (from both parents) to project in the output.
         project: Vec<Column>,
+        /// An optional extra condition (from the `ON` clause of the join) which must hold for a
+        /// pair of rows to match, in addition to their join keys being equal. Rows in the left
+        /// for which no rows in the right match are emitted padded with NULLs.
+        ///
+        /// Like [`Filter`] conditions, this is still just the raw AST, and can reference columns
+        /// in either parent.
+        ///
+        /// [`Filter`]: MirNodeInner::Filter
+        residual: Option<Expr>,
+    },
+    /// Node which computes a *full outer* join on its two parents, emitting rows from either
+    /// parent with no match in the other padded with NULLs for the other parent's columns
+    ///
+    /// Converted to [`Join`] with [`JoinType::Full`] when lowering to dataflow.
+    ///
+    /// [`Join`]: dataflow::ops::join::Join
+    /// [`JoinType::Full`]: dataflow::ops::join::JoinType::Full
+    FullJoin {
+        /// Columns to use as the join keys. Each tuple corresponds to a column in the left parent
+        /// and column in the right parent.
+        on: Vec<(Column, Column)>,
+        /// Columns (from both parents) to project in the output.
+        project: Vec<Column>,
     },
     /// Join where nodes in the right-hand side depend on columns in the left-hand side
     /// (referencing tables in `dependent_tables`). These are created during compilation for
@@ -187,6 +219,28 @@ pub enum MirNodeInner {
         /// Columns (from both parents) to project in the output.
         project: Vec<Column>,
     },
+    /// Left join where nodes in the right-hand side depend on columns in the left-hand side. These
+    /// are created during compilation for correlated `NOT EXISTS` and `NOT IN` subqueries, and
+    /// like [`DependentJoin`]s must be removed entirely by rewrite passes before lowering to
+    /// dataflow.
+    ///
+    /// Unlike for [`DependentJoin`]s, dependent filters can't be pushed below a left join without
+    /// changing which rows get padded with NULLs, so instead they are absorbed into the join's
+    /// `on` and `residual` as the join is decorrelated.
+    ///
+    /// [`DependentJoin`]: MirNodeInner::DependentJoin
+    DependentLeftJoin {
+        /// Columns to use as the join keys. Each tuple corresponds to a column in the left parent
+        /// and column in the right parent.
+        on: Vec<(Column, Column)>,
+        /// Columns (from both parents) to project in the output.
+        project: Vec<Column>,
+        /// An optional extra condition which must hold for a pair of rows to match - see
+        /// [`LeftJoin`].
+        ///
+        /// [`LeftJoin`]: MirNodeInner::LeftJoin
+        residual: Option<Expr>,
+    },
     /// Represents view key placeholders in a query that have not yet been added to the [`Leaf`][]
     /// node of the query.
     ///
@@ -225,6 +279,22 @@ pub enum MirNodeInner {
         /// in all parents.
         duplicate_mode: union::DuplicateMode,
     },
+    /// Node which computes the `INTERSECT` or `EXCEPT` (or the `ALL` variant of either) of its two
+    /// parents.
+    ///
+    /// Converted to [`SetOperation`] when lowering to dataflow
+    ///
+    /// [`SetOperation`]: dataflow::ops::set_operation::SetOperation
+    SetOperation {
+        /// Columns to compare and emit from each parent
+        ///
+        /// # Invariants
+        ///
+        /// * This will always have length 2, with the left parent's columns first
+        emit: Vec<Vec<Column>>,
+        /// The set operation to compute
+        operator: SetOperator,
+    },
     /// Node which orders its input rows within a group, then emits an extra page number column
     /// (which will always have a name given by [`PAGE_NUMBER_COL`]) for the page number of the
     /// rows within that group, with page size given by `limit`.
@@ -255,6 +325,41 @@ pub enum MirNodeInner {
         /// LIMIT clause
         limit: usize,
     },
+    /// Node which computes a window function (such as `ROW_NUMBER()` or a running `SUM`) over
+    /// partitions of its input ordered by a set of columns, and emits all of its parent's columns
+    /// plus one extra column for the result of the window function.
+    ///
+    /// Converted to [`Window`] when lowering to dataflow.
+    ///
+    /// [`Window`]: dataflow::ops::window::Window
+    Window {
+        /// Column to compute the window function over, if the window function takes an argument
+        on: Option<Column>,
+        /// Set of columns used to partition the input (the `PARTITION BY` clause)
+        group_by: Vec<Column>,
+        /// Set of columns used for ordering rows within each partition
+        order: Vec<(Column, OrderType)>,
+        /// The column name to use for the result of the window function, which will always be the
+        /// last column
+        output_column: Column,
+        /// Which window function we are computing
+        kind: WindowFunction,
+    },
+    /// Node which expands an array-valued column of its parent into one row per element, and emits
+    /// all of its parent's columns plus one extra column for the element. Used to implement
+    /// set-returning functions (such as `unnest`) in the `FROM` clause.
+    ///
+    /// Converted to [`Unnest`] when lowering to dataflow.
+    ///
+    /// [`Unnest`]: dataflow::ops::unnest::Unnest
+    Unnest {
+        /// Column to expand
+        on: Column,
+        /// The column name to use for each element, which will always be the last column
+        output_column: Column,
+        /// What kind of value we are expanding
+        kind: UnnestKind,
+    },
     /// Node which emits only distinct rows per some group.
     ///
     /// Converted to [`Aggregator`] with [`Aggregation::Count`] when lowering to dataflow.
@@ -292,6 +397,13 @@ pub enum MirNodeInner {
         default_row: Option<Vec<DfValue>>,
         /// Aggregates to perform in the reader on result sets for keys after performing the lookup
         aggregates: Option<PostLookupAggregates<Column>>,
+        /// If the keys of this leaf include the parameters of a union whose parents are
+        /// parametrized by different placeholders, the key columns each of those parents projects
+        /// as `NULL`.
+        ///
+        /// Lookups into the reader for each key are expanded into one lookup per entry in this
+        /// list, with the given key columns replaced by `NULL`.
+        null_key_columns: Vec<Vec<Column>>,
     },
 }
 
@@ -308,6 +420,7 @@ impl MirNodeInner {
             returned_cols: None,
             default_row: None,
             aggregates: None,
+            null_key_columns: vec![],
         }
     }
 
@@ -333,7 +446,9 @@ impl MirNodeInner {
             }
             MirNodeInner::Join { project, .. }
             | MirNodeInner::LeftJoin { project, .. }
-            | MirNodeInner::DependentJoin { project, .. } => {
+            | MirNodeInner::FullJoin { project, .. }
+            | MirNodeInner::DependentJoin { project, .. }
+            | MirNodeInner::DependentLeftJoin { project, .. } => {
                 if !project.contains(&c) {
                     project.push(c);
                 }
@@ -349,6 +464,10 @@ impl MirNodeInner {
                 }
                 Ok(true)
             }
+            MirNodeInner::SetOperation { .. } => {
+                // Adding a column would change which rows are considered equal
+                unsupported!("Can't add columns to INTERSECT or EXCEPT")
+            }
             MirNodeInner::Distinct { group_by, .. } => {
                 group_by.push(c);
                 Ok(true)
@@ -365,11 +484,15 @@ impl MirNodeInner {
         }
     }
 
-    /// Returns `true` if self is a [`DependentJoin`].
+    /// Returns `true` if self is a [`DependentJoin`] or a [`DependentLeftJoin`].
     ///
     /// [`DependentJoin`]: MirNodeInner::DependentJoin
+    /// [`DependentLeftJoin`]: MirNodeInner::DependentLeftJoin
     pub fn is_dependent_join(&self) -> bool {
-        matches!(self, Self::DependentJoin { .. })
+        matches!(
+            self,
+            Self::DependentJoin { .. } | Self::DependentLeftJoin { .. }
+        )
     }
 
     /// Returns `true` if self is a [`ViewKey`].
@@ -383,24 +506,54 @@ impl MirNodeInner {
         match self {
             MirNodeInner::Aggregation {
                 ref on,
+                ref key,
                 ref group_by,
+                ref order,
                 ref kind,
                 ..
             } => {
                 let op_string = match *kind {
                     Aggregation::Count { .. } => format!("|*|({})", on.name.as_str()),
+                    Aggregation::CountDistinct => {
+                        format!("|DISTINCT {}|", on.name.as_str())
+                    }
                     Aggregation::Sum => format!("𝛴({})", on.name.as_str()),
+                    Aggregation::SumDistinct => format!("𝛴(DISTINCT {})", on.name.as_str()),
                     Aggregation::Avg => format!("AVG({})", on.name.as_str()),
                     Aggregation::GroupConcat { separator: ref s } => {
                         format!("||([{}], \"{}\")", on.name.as_str(), s.as_str())
                     }
+                    Aggregation::Stddev { sample } => format!(
+                        "STDDEV_{}({})",
+                        if sample { "SAMP" } else { "POP" },
+                        on.name.as_str()
+                    ),
+                    Aggregation::Variance { sample } => format!(
+                        "VAR_{}({})",
+                        if sample { "SAMP" } else { "POP" },
+                        on.name.as_str()
+                    ),
+                    Aggregation::BoolAnd => format!("BOOL_AND({})", on.name.as_str()),
+                    Aggregation::BoolOr => format!("BOOL_OR({})", on.name.as_str()),
+                    Aggregation::BitAnd => format!("BIT_AND({})", on.name.as_str()),
+                    Aggregation::BitOr => format!("BIT_OR({})", on.name.as_str()),
+                    Aggregation::Collect(ref kind) => match key {
+                        Some(key) => {
+                            format!("{}({}, {})", kind, key.name.as_str(), on.name.as_str())
+                        }
+                        None => format!("{}({})", kind, on.name.as_str()),
+                    },
                 };
                 let group_cols = group_by
                     .iter()
                     .map(|c| c.name.as_str())
                     .collect::<Vec<_>>()
                     .join(", ");
-                format!("{} γ[{}]", op_string, group_cols)
+                if order.is_empty() {
+                    format!("{} γ[{}]", op_string, group_cols)
+                } else {
+                    format!("{} γ[{}], {:?}", op_string, group_cols, order)
+                }
             }
             MirNodeInner::Base {
                 column_specs,
@@ -476,6 +629,30 @@ impl MirNodeInner {
                 format!("Leaf [⚷: {}]", key_cols)
             }
             MirNodeInner::LeftJoin {
+                ref on,
+                ref project,
+                ref residual,
+            } => {
+                let jc = on
+                    .iter()
+                    .map(|(l, r)| format!("{}:{}", l.name, r.name))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                format!(
+                    "⋉ [{} on {}{}]",
+                    project
+                        .iter()
+                        .map(|c| c.name.as_str())
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                    jc,
+                    residual
+                        .iter()
+                        .map(|r| format!(" σ {}", r.display(nom_sql::Dialect::MySQL)))
+                        .collect::<String>()
+                )
+            }
+            MirNodeInner::FullJoin {
                 ref on,
                 ref project,
                 ..
@@ -486,7 +663,7 @@ impl MirNodeInner {
                     .collect::<Vec<_>>()
                     .join(", ");
                 format!(
-                    "⋉ [{} on {}]",
+                    "⟗ [{} on {}]",
                     project
                         .iter()
                         .map(|c| c.name.as_str())
@@ -508,6 +685,23 @@ impl MirNodeInner {
                         .join(", ")
                 )
             }
+            MirNodeInner::DependentLeftJoin {
+                ref on,
+                ref project,
+                ref residual,
+            } => {
+                format!(
+                    "⧑⋉ | {} on: {}{}",
+                    project.iter().map(|c| &c.name).join(", "),
+                    on.iter()
+                        .map(|(l, r)| format!("{}:{}", l.name, r.name))
+                        .join(", "),
+                    residual
+                        .iter()
+                        .map(|r| format!(" σ {}", r.display(nom_sql::Dialect::MySQL)))
+                        .collect::<String>()
+                )
+            }
             MirNodeInner::Project { ref emit } => format!("π [{}]", emit.iter().join(", ")),
             MirNodeInner::Distinct { ref group_by } => {
                 let key_cols = group_by
@@ -531,6 +725,26 @@ impl MirNodeInner {
             } => {
                 format!("TopK [k: {}, {:?}]", limit, order)
             }
+            MirNodeInner::Window {
+                ref on,
+                ref group_by,
+                ref order,
+                ref kind,
+                ..
+            } => {
+                format!(
+                    "Window [{}({}) γ: {}, {:?}]",
+                    kind,
+                    on.as_ref().map(|c| c.name.as_str()).unwrap_or_default(),
+                    group_by.iter().map(|c| &c.name).join(", "),
+                    order
+                )
+            }
+            MirNodeInner::Unnest {
+                ref on, ref kind, ..
+            } => {
+                format!("Unnest [{:?}({})]", kind, on.name)
+            }
             MirNodeInner::Union {
                 ref emit,
                 ref duplicate_mode,
@@ -548,6 +762,13 @@ impl MirNodeInner {
                     })
                     .join(&format!(" {} ", symbol))
             }
+            MirNodeInner::SetOperation {
+                ref emit,
+                ref operator,
+            } => emit
+                .iter()
+                .map(|c| c.iter().map(|e| e.name.clone()).join(", "))
+                .join(&format!(" {} ", operator)),
             MirNodeInner::AliasTable { ref table } => {
                 format!("AliasTable [{}]", table.display_unquoted())
             }
@@ -17,6 +17,12 @@ use crate::{Column, NodeIndex};
 ///   filters, so can be swapped in position with those filters with impunity
 /// - Grouped nodes ([`Aggregation`] and [`Extremum`]) require adding any *non* dependent columns
 ///   mentioned in the filter to the group-by of the node.
+/// - [`TopK`] nodes require adding any non dependent columns mentioned in the filter to the
+///   group-by of the node, which is only equivalent to filtering the output of the TopK if the
+///   filter is a single equality between columns (so that each group matches exactly one set of
+///   values for the dependent columns)
+/// - [`DependentLeftJoin`]s aren't commutative with filters, so rather than being pushed below the
+///   join the filter is absorbed into the join's condition (see [`absorb_dependent_filter`])
 /// - All other nodes currently return an [unsupported error][] - it *is* theoretically possible to
 ///   push below any node, but currently we don't have that ability
 ///
@@ -28,8 +34,10 @@ use crate::{Column, NodeIndex};
 /// [`Join`]: MirNodeInner::Join
 /// [`LeftJoin`]: MirNodeInner::LeftJoin
 /// [`DependentJoin`]: MirNodeInner::DependentJoin
+/// [`DependentLeftJoin`]: MirNodeInner::DependentLeftJoin
 /// [`Aggregation`]: MirNodeInner::Aggregation
 /// [`Extremum`]: MirNodeInner::Extremum
+/// [`TopK`]: MirNodeInner::TopK
 /// [unsupported error]: noria_errors::ReadySetError::Unsupported
 /// [`pull_all_required_columns`]: noria_mir::rewrite::pull_columns::pull_all_required_columns
 fn push_dependent_filter(
@@ -74,6 +82,13 @@ fn push_dependent_filter(
         }
     }
 
+    if matches!(
+        query.get_node(child_idx).unwrap().inner,
+        MirNodeInner::DependentLeftJoin { .. }
+    ) {
+        return absorb_dependent_filter(query, node_idx, child_idx, dependency);
+    }
+
     trace!(
         "Lifting `{}` above `{}`",
         node_idx.index(),
@@ -92,6 +107,29 @@ fn push_dependent_filter(
             }
             true
         }
+        MirNodeInner::TopK { .. } => {
+            let is_column_equality = matches!(
+                &query.get_node(node_idx).unwrap().inner,
+                MirNodeInner::Filter {
+                    conditions: Expr::BinaryOp {
+                        lhs: box Expr::Column(_),
+                        op: BinaryOperator::Equal,
+                        rhs: box Expr::Column(_),
+                    }
+                }
+            );
+            if !is_column_equality {
+                unsupported!(
+                    "Only equality conditions between columns are supported when decorrelating \
+                     subqueries with a LIMIT"
+                );
+            }
+
+            for col in &dependency.non_dependent_cols {
+                query.graph.add_column(child_idx, col.clone())?;
+            }
+            true
+        }
         inner => unsupported!(
             "Don't know how to push filter below {} to decorrelate",
             inner.description()
@@ -107,6 +145,78 @@ fn push_dependent_filter(
     Ok(())
 }
 
+/// Absorb the given `node`, which should be a [filter][] node with the given `dependency` on
+/// columns on the left-hand side of the [dependent left join][] `join` directly below it, into the
+/// condition of that join.
+///
+/// Filtering the output of a left join isn't equivalent to filtering its right-hand side (rows in
+/// the left that no longer have any matches need to be padded with NULLs instead of dropped), so
+/// rather than pushing the filter below the join we turn it into a join key if it's an equality
+/// between a column on either side, or add it to the join's residual condition otherwise.
+///
+/// [filter]: MirNodeInner::Filter
+/// [dependent left join]: MirNodeInner::DependentLeftJoin
+fn absorb_dependent_filter(
+    query: &mut MirQuery<'_>,
+    node_idx: NodeIndex,
+    join: NodeIndex,
+    dependency: DependentCondition,
+) -> ReadySetResult<()> {
+    let conditions = match &query.get_node(node_idx).unwrap().inner {
+        MirNodeInner::Filter { conditions } => conditions.clone(),
+        _ => internal!("The node passed to absorb_dependent_filter must be a filter"),
+    };
+
+    let is_right = |col: &Column| dependency.non_dependent_cols.contains(col);
+    let key = match &conditions {
+        Expr::BinaryOp {
+            lhs: box Expr::Column(c1),
+            op: BinaryOperator::Equal,
+            rhs: box Expr::Column(c2),
+        } => {
+            let (c1, c2) = (Column::from(c1), Column::from(c2));
+            match (is_right(&c1), is_right(&c2)) {
+                (false, true) => Some((c1, c2)),
+                (true, false) => Some((c2, c1)),
+                _ => None,
+            }
+        }
+        _ => None,
+    };
+
+    trace!("Absorbing `{}` into `{}`", node_idx.index(), join.index());
+    match &mut query.get_node_mut(join).unwrap().inner {
+        MirNodeInner::DependentLeftJoin {
+            on,
+            project,
+            residual,
+        } => match key {
+            Some((left, right)) => {
+                for col in [&left, &right] {
+                    if !project.contains(col) {
+                        project.push(col.clone());
+                    }
+                }
+                on.push((left, right));
+            }
+            None => {
+                *residual = Some(match residual.take() {
+                    Some(existing) => Expr::BinaryOp {
+                        lhs: Box::new(existing),
+                        op: BinaryOperator::And,
+                        rhs: Box::new(conditions),
+                    },
+                    None => conditions,
+                })
+            }
+        },
+        _ => internal!("absorb_dependent_filter must be called with a dependent left join"),
+    }
+
+    query.remove_node(node_idx)?;
+    Ok(())
+}
+
 /// A MIR rewrite pass that attempts to eliminate all [dependent joins][] by algebraically pushing
 /// any dependent filters below the join.
 ///
@@ -124,7 +234,7 @@ fn push_dependent_filter(
 /// 2. Find a filter in the ancestors of the right hand side of that join that references columns
 ///    on the left hand side of that join
 ///    - If we don't find one, then the join no longer needs to be dependent, so convert it to a
-///      regular inner join
+///      regular (inner or left) join
 /// 3. Attempt to push that filter down the graph, using an algebraic rewrite rule (this is done
 ///    in [`push_dependent_filter`])
 ///
@@ -256,6 +366,15 @@ pub(crate) fn eliminate_dependent_joins(query: &mut MirQuery<'_>) -> ReadySetRes
                     on: on.clone(),
                     project: project.clone(),
                 },
+                MirNodeInner::DependentLeftJoin {
+                    on,
+                    project,
+                    residual,
+                } => MirNodeInner::LeftJoin {
+                    on: on.clone(),
+                    project: project.clone(),
+                    residual: residual.clone(),
+                },
                 _ => unreachable!("Already checked is_dependent_join above"),
             };
             query.get_node_mut(join).unwrap().inner = new_inner;
@@ -278,7 +397,9 @@ struct DependentCondition {
 mod tests {
     use common::IndexType;
     use dataflow::ops::grouped::aggregate::Aggregation;
-    use nom_sql::{BinaryOperator, ColumnSpecification, Expr, Literal, Relation, SqlType};
+    use nom_sql::{
+        BinaryOperator, ColumnSpecification, Expr, Literal, OrderType, Relation, SqlType,
+    };
     use petgraph::Direction;
 
     use super::*;
@@ -364,7 +485,9 @@ mod tests {
             "__exists_count".into(),
             MirNodeInner::Aggregation {
                 on: Column::named("__count_val"),
+                key: None,
                 group_by: vec![Column::named("__count_grp")],
+                order: vec![],
                 output_column: Column::named("__exists_count"),
                 kind: Aggregation::Count,
             },
@@ -492,6 +615,233 @@ mod tests {
         assert!(pull_result.is_ok(), "{}", pull_result.err().unwrap());
     }
 
+    #[test]
+    fn not_exists_ish() {
+        readyset_tracing::init_test_logging();
+        // query looks something like:
+        //     SELECT t1.a FROM t1
+        //     WHERE NOT EXISTS (SELECT * FROM t2 WHERE t2.a = t1.a AND t2.b > t1.b)
+        let mut graph = MirGraph::new();
+
+        let query_name = Relation::from("q");
+
+        let t2 = graph.add_node(MirNode::new(
+            "t2".into(),
+            MirNodeInner::Base {
+                column_specs: vec![
+                    ColumnSpecification {
+                        column: nom_sql::Column::from("t2.a"),
+                        sql_type: SqlType::Int(None),
+                        constraints: vec![],
+                        comment: None,
+                    },
+                    ColumnSpecification {
+                        column: nom_sql::Column::from("t2.b"),
+                        sql_type: SqlType::Int(None),
+                        constraints: vec![],
+                        comment: None,
+                    },
+                ],
+                primary_key: Some([Column::new(Some("t2"), "a")].into()),
+                unique_keys: Default::default(),
+            },
+        ));
+        graph[t2].add_owner(query_name.clone());
+        // t2 -> ...
+
+        // -> σ[t2.a = t1.a]
+        let t2_eq_filter = graph.add_node(MirNode::new(
+            "t2_eq_filter".into(),
+            MirNodeInner::Filter {
+                conditions: Expr::BinaryOp {
+                    lhs: Box::new(Expr::Column("t2.a".into())),
+                    op: BinaryOperator::Equal,
+                    rhs: Box::new(Expr::Column("t1.a".into())),
+                },
+            },
+        ));
+        graph[t2_eq_filter].add_owner(query_name.clone());
+        graph.add_edge(t2, t2_eq_filter, 0);
+
+        // -> σ[t2.b > t1.b]
+        let t2_gt_filter = graph.add_node(MirNode::new(
+            "t2_gt_filter".into(),
+            MirNodeInner::Filter {
+                conditions: Expr::BinaryOp {
+                    lhs: Box::new(Expr::Column("t2.b".into())),
+                    op: BinaryOperator::Greater,
+                    rhs: Box::new(Expr::Column("t1.b".into())),
+                },
+            },
+        ));
+        graph[t2_gt_filter].add_owner(query_name.clone());
+        graph.add_edge(t2_eq_filter, t2_gt_filter, 0);
+
+        // -> π[lit: 0, lit: 0]
+        let group_proj = graph.add_node(MirNode::new(
+            "q_prj_hlpr".into(),
+            MirNodeInner::Project {
+                emit: vec![
+                    ProjectExpr::Expr {
+                        alias: "__count_val".into(),
+                        expr: Expr::Literal(0u32.into()),
+                    },
+                    ProjectExpr::Expr {
+                        alias: "__count_grp".into(),
+                        expr: Expr::Literal(0u32.into()),
+                    },
+                ],
+            },
+        ));
+        graph[group_proj].add_owner(query_name.clone());
+        graph.add_edge(t2_gt_filter, group_proj, 0);
+
+        // -> |0| γ[1]
+        let exists_count = graph.add_node(MirNode::new(
+            "__exists_count".into(),
+            MirNodeInner::Aggregation {
+                on: Column::named("__count_val"),
+                key: None,
+                group_by: vec![Column::named("__count_grp")],
+                order: vec![],
+                output_column: Column::named("__exists_count"),
+                kind: Aggregation::Count,
+            },
+        ));
+        graph[exists_count].add_owner(query_name.clone());
+        graph.add_edge(group_proj, exists_count, 0);
+
+        let t1 = graph.add_node(MirNode::new(
+            "t1".into(),
+            MirNodeInner::Base {
+                column_specs: vec![
+                    ColumnSpecification {
+                        column: nom_sql::Column::from("t1.a"),
+                        sql_type: SqlType::Int(None),
+                        constraints: vec![],
+                        comment: None,
+                    },
+                    ColumnSpecification {
+                        column: nom_sql::Column::from("t1.b"),
+                        sql_type: SqlType::Int(None),
+                        constraints: vec![],
+                        comment: None,
+                    },
+                ],
+                primary_key: Some([Column::from("a")].into()),
+                unique_keys: Default::default(),
+            },
+        ));
+        graph[t1].add_owner(query_name.clone());
+        // t1 -> ...
+
+        // -> π[..., lit: 0]
+        let left_literal_join_key_proj = graph.add_node(MirNode::new(
+            "t1_join_key".into(),
+            MirNodeInner::Project {
+                emit: vec![
+                    ProjectExpr::Column(Column::new(Some("t1"), "a")),
+                    ProjectExpr::Column(Column::new(Some("t1"), "b")),
+                    ProjectExpr::Expr {
+                        alias: "__exists_join_key".into(),
+                        expr: Expr::Literal(0u32.into()),
+                    },
+                ],
+            },
+        ));
+        graph[left_literal_join_key_proj].add_owner(query_name.clone());
+        graph.add_edge(t1, left_literal_join_key_proj, 0);
+
+        // -> ⧑⋉ on: l.__exists_join_key ≡ r.__count_grp
+        let not_exists_join = graph.add_node(MirNode::new(
+            "not_exists_join".into(),
+            MirNodeInner::DependentLeftJoin {
+                on: vec![(
+                    Column::named("__exists_join_key"),
+                    Column::named("__count_grp"),
+                )],
+                project: vec![
+                    Column::new(Some("t1"), "a"),
+                    Column::new(Some("t1"), "b"),
+                    Column::named("__exists_join_key"),
+                    Column::named("__count_grp"),
+                    Column::named("__exists_count"),
+                ],
+                residual: None,
+            },
+        ));
+        graph[not_exists_join].add_owner(query_name.clone());
+        graph.add_edge(left_literal_join_key_proj, not_exists_join, 0);
+        graph.add_edge(exists_count, not_exists_join, 1);
+
+        // -> σ[__exists_count IS NULL]
+        let is_null_filter = graph.add_node(MirNode::new(
+            "count_is_null".into(),
+            MirNodeInner::Filter {
+                conditions: Expr::BinaryOp {
+                    lhs: Box::new(Expr::Column("__exists_count".into())),
+                    op: BinaryOperator::Is,
+                    rhs: Box::new(Expr::Literal(Literal::Null)),
+                },
+            },
+        ));
+        graph[is_null_filter].add_owner(query_name.clone());
+        graph.add_edge(not_exists_join, is_null_filter, 0);
+
+        let leaf = graph.add_node(MirNode::new(
+            "q".into(),
+            MirNodeInner::leaf(vec![], IndexType::HashMap),
+        ));
+        graph[leaf].add_owner(query_name.clone());
+        graph.add_edge(is_null_filter, leaf, 0);
+
+        let mut query = MirQuery::new(query_name, leaf, &mut graph);
+
+        eliminate_dependent_joins(&mut query).unwrap();
+
+        eprintln!("{}", query.to_graphviz());
+
+        match &query.graph[not_exists_join].inner {
+            MirNodeInner::LeftJoin { on, residual, .. } => {
+                assert!(
+                    on.contains(&(Column::new(Some("t1"), "a"), Column::new(Some("t2"), "a"))),
+                    "Equality filter should be absorbed as a join key (got: {:?})",
+                    on
+                );
+                assert_eq!(
+                    *residual,
+                    Some(Expr::BinaryOp {
+                        lhs: Box::new(Expr::Column("t2.b".into())),
+                        op: BinaryOperator::Greater,
+                        rhs: Box::new(Expr::Column("t1.b".into())),
+                    }),
+                    "Non-equality filter should be absorbed into the residual"
+                );
+            }
+            inner => panic!(
+                "should have rewritten dependent to non-dependent left join (got: {})",
+                inner.description()
+            ),
+        }
+
+        assert!(
+            !query.graph.contains_node(t2_eq_filter) && !query.graph.contains_node(t2_gt_filter),
+            "Dependent filters should be removed from the right-hand side of the join"
+        );
+        assert_eq!(
+            query
+                .graph
+                .neighbors_directed(not_exists_join, Direction::Outgoing)
+                .next()
+                .unwrap(),
+            is_null_filter,
+            "Nothing should be inserted below the left join"
+        );
+
+        let pull_result = pull_all_required_columns(&mut query);
+        assert!(pull_result.is_ok(), "{}", pull_result.err().unwrap());
+    }
+
     #[test]
     fn multiple_filters_after_agg() {
         readyset_tracing::init_test_logging();
@@ -548,7 +898,9 @@ mod tests {
             "q_t2_count".into(),
             MirNodeInner::Aggregation {
                 on: Column::new(Some("t2"), "b"),
+                key: None,
                 group_by: vec![Column::new(Some("t2"), "b")],
+                order: vec![],
                 output_column: Column::named("COUNT(t2.b)"),
                 kind: Aggregation::Count,
             },
@@ -615,7 +967,9 @@ mod tests {
             "__exists_count".into(),
             MirNodeInner::Aggregation {
                 on: Column::named("__count_val"),
+                key: None,
                 group_by: vec![Column::named("__count_grp")],
+                order: vec![],
                 output_column: Column::named("__exists_count"),
                 kind: Aggregation::Count,
             },
@@ -740,4 +1094,151 @@ mod tests {
 
         pull_all_required_columns(&mut query).unwrap();
     }
+
+    /// Build a graph for a query that looks something like:
+    ///
+    /// ```sql
+    /// SELECT t1.a, sq.b FROM t1
+    /// CROSS JOIN LATERAL (SELECT t2.b FROM t2 WHERE t2.a <op> t1.a ORDER BY t2.b LIMIT 3) sq
+    /// ```
+    ///
+    /// returning the graph, along with the indices of the dependent filter, the topk node, the
+    /// dependent join, and the leaf
+    fn lateral_topk(op: BinaryOperator) -> (MirGraph, [NodeIndex; 4]) {
+        let mut graph = MirGraph::new();
+        let query_name = Relation::from("q");
+
+        let t2 = graph.add_node(MirNode::new(
+            "t2".into(),
+            MirNodeInner::Base {
+                column_specs: vec![
+                    ColumnSpecification {
+                        column: nom_sql::Column::from("t2.a"),
+                        sql_type: SqlType::Int(None),
+                        constraints: vec![],
+                        comment: None,
+                    },
+                    ColumnSpecification {
+                        column: nom_sql::Column::from("t2.b"),
+                        sql_type: SqlType::Int(None),
+                        constraints: vec![],
+                        comment: None,
+                    },
+                ],
+                primary_key: Some([Column::new(Some("t2"), "b")].into()),
+                unique_keys: Default::default(),
+            },
+        ));
+        graph[t2].add_owner(query_name.clone());
+
+        // -> σ[t2.a <op> t1.a]
+        let t2_filter = graph.add_node(MirNode::new(
+            "t2_filter".into(),
+            MirNodeInner::Filter {
+                conditions: Expr::BinaryOp {
+                    lhs: Box::new(Expr::Column("t2.a".into())),
+                    op,
+                    rhs: Box::new(Expr::Column("t1.a".into())),
+                },
+            },
+        ));
+        graph[t2_filter].add_owner(query_name.clone());
+        graph.add_edge(t2, t2_filter, 0);
+
+        // -> topk[3; t2.b]
+        let topk = graph.add_node(MirNode::new(
+            "t2_topk".into(),
+            MirNodeInner::TopK {
+                order: Some(vec![(
+                    Column::new(Some("t2"), "b"),
+                    OrderType::OrderAscending,
+                )]),
+                group_by: vec![],
+                limit: 3,
+            },
+        ));
+        graph[topk].add_owner(query_name.clone());
+        graph.add_edge(t2_filter, topk, 0);
+
+        let t1 = graph.add_node(MirNode::new(
+            "t1".into(),
+            MirNodeInner::Base {
+                column_specs: vec![ColumnSpecification {
+                    column: nom_sql::Column::from("t1.a"),
+                    sql_type: SqlType::Int(None),
+                    constraints: vec![],
+                    comment: None,
+                }],
+                primary_key: Some([Column::new(Some("t1"), "a")].into()),
+                unique_keys: Default::default(),
+            },
+        ));
+        graph[t1].add_owner(query_name.clone());
+
+        // -> ⧑ on: []
+        let lateral_join = graph.add_node(MirNode::new(
+            "lateral_join".into(),
+            MirNodeInner::DependentJoin {
+                on: vec![],
+                project: vec![Column::new(Some("t1"), "a"), Column::new(Some("t2"), "b")],
+            },
+        ));
+        graph[lateral_join].add_owner(query_name.clone());
+        graph.add_edge(t1, lateral_join, 0);
+        graph.add_edge(topk, lateral_join, 1);
+
+        let leaf = graph.add_node(MirNode::new(
+            "q".into(),
+            MirNodeInner::leaf(vec![], IndexType::HashMap),
+        ));
+        graph[leaf].add_owner(query_name);
+        graph.add_edge(lateral_join, leaf, 0);
+
+        (graph, [t2_filter, topk, lateral_join, leaf])
+    }
+
+    #[test]
+    fn lateral_topk_equality() {
+        readyset_tracing::init_test_logging();
+        let (mut graph, [t2_filter, topk, lateral_join, leaf]) =
+            lateral_topk(BinaryOperator::Equal);
+        let mut query = MirQuery::new("q".into(), leaf, &mut graph);
+
+        eliminate_dependent_joins(&mut query).unwrap();
+
+        assert!(
+            matches!(&query.graph[lateral_join].inner, MirNodeInner::Join { .. }),
+            "should have rewritten dependent to non-dependent join"
+        );
+
+        match &query.graph[topk].inner {
+            MirNodeInner::TopK { group_by, .. } => assert_eq!(
+                *group_by,
+                vec![Column::new(Some("t2"), "a")],
+                "TopK should be grouped by the non-dependent column in the filter"
+            ),
+            _ => panic!("expected a TopK node"),
+        }
+
+        assert_eq!(
+            query
+                .graph
+                .neighbors_directed(t2_filter, Direction::Incoming)
+                .next()
+                .unwrap(),
+            lateral_join,
+            "Dependent filter should be moved below join"
+        );
+
+        pull_all_required_columns(&mut query).unwrap();
+    }
+
+    #[test]
+    fn lateral_topk_inequality_unsupported() {
+        let (mut graph, [.., leaf]) = lateral_topk(BinaryOperator::Greater);
+        let mut query = MirQuery::new("q".into(), leaf, &mut graph);
+
+        let res = eliminate_dependent_joins(&mut query);
+        assert!(res.unwrap_err().is_unsupported());
+    }
 }
@@ -115,7 +115,9 @@ mod tests {
             "grp".into(),
             MirNodeInner::Aggregation {
                 on: "b".into(),
+                key: None,
                 group_by: vec![],
+                order: vec![],
                 output_column: Column::named("agg"),
                 kind: Aggregation::Sum,
             },
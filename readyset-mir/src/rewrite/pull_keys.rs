@@ -1,11 +1,25 @@
-use nom_sql::BinaryOperator;
+use itertools::Itertools;
+use nom_sql::{BinaryOperator, Expr, Literal};
+use petgraph::visit::EdgeRef;
+use petgraph::Direction;
 use readyset_client::ViewPlaceholder;
-use readyset_errors::{internal, invariant_eq, unsupported, ReadySetResult};
+use readyset_errors::{internal, internal_err, invariant_eq, unsupported, ReadySetResult};
 use tracing::{instrument, trace};
 
-use crate::node::{MirNodeInner, ViewKeyColumn};
+use crate::node::{MirNode, MirNodeInner, ProjectExpr, ViewKeyColumn};
 use crate::query::MirQuery;
-use crate::NodeIndex;
+use crate::{Column, NodeIndex};
+
+/// Returns true if the given column is projected as `NULL` by some of the parents of a union, as
+/// recorded in the `null_key_columns` of the leaf of the query
+fn is_null_key_column(query: &MirQuery<'_>, column: &Column) -> bool {
+    match &query.leaf_node().inner {
+        MirNodeInner::Leaf {
+            null_key_columns, ..
+        } => null_key_columns.iter().flatten().any(|c| c == column),
+        _ => false,
+    }
+}
 
 fn push_view_key(query: &mut MirQuery<'_>, node_idx: NodeIndex) -> ReadySetResult<()> {
     let children = query.descendants(node_idx)?;
@@ -34,12 +48,25 @@ fn push_view_key(query: &mut MirQuery<'_>, node_idx: NodeIndex) -> ReadySetResul
         _ => internal!("The node passed to push_view_key must be a view_key"),
     };
 
+    // Grouping by a key column which is NULL for some parents of a union would compute separate
+    // groups for the rows from each of those parents, which the reader would then return together
+    let has_null_key_columns = key
+        .iter()
+        .any(|ViewKeyColumn { column, .. }| is_null_key_column(query, column));
+
     match &mut query.get_node_mut(child_idx).unwrap().inner {
         MirNodeInner::Aggregation { group_by, .. }
         | MirNodeInner::Extremum { group_by, .. }
         | MirNodeInner::Distinct { group_by }
         | MirNodeInner::Paginate { group_by, .. }
-        | MirNodeInner::TopK { group_by, .. } => {
+        | MirNodeInner::TopK { group_by, .. }
+        | MirNodeInner::Window { group_by, .. } => {
+            if has_null_key_columns {
+                unsupported!(
+                    "Aggregates, DISTINCT, and LIMIT over a UNION whose branches have different \
+                     parameters are not yet supported"
+                );
+            }
             for ViewKeyColumn { column, op, .. } in &key {
                 invariant_eq!(
                     *op,
@@ -52,12 +79,17 @@ fn push_view_key(query: &mut MirQuery<'_>, node_idx: NodeIndex) -> ReadySetResul
         }
         MirNodeInner::JoinAggregates => todo!(),
         // TODO: left joins are tricky if we're coming from the right side
-        MirNodeInner::LeftJoin { .. } => unsupported!(
+        MirNodeInner::LeftJoin { .. } | MirNodeInner::DependentLeftJoin { .. } => unsupported!(
             "Parameters in subqueries on the right-hand side of LEFT JOIN not supported"
         ),
-        // TODO: we might support this already? Will have to see
+        MirNodeInner::FullJoin { .. } => {
+            unsupported!("Parameters in subqueries on either side of FULL JOIN not supported")
+        }
         MirNodeInner::Union { .. } => {
-            unsupported!("Parameters on one side of a UNION not yet supported")
+            internal!("View keys should be pushed below unions by push_view_keys_below_union")
+        }
+        MirNodeInner::SetOperation { .. } => {
+            unsupported!("Parameters on one side of an INTERSECT or EXCEPT not yet supported")
         }
         // Note that we don't need to add any projected columns; these will just be added by the
         // pull_columns pass
@@ -66,6 +98,7 @@ fn push_view_key(query: &mut MirQuery<'_>, node_idx: NodeIndex) -> ReadySetResul
         | MirNodeInner::Join { .. }
         | MirNodeInner::DependentJoin { .. }
         | MirNodeInner::Filter { .. }
+        | MirNodeInner::Unnest { .. }
         | MirNodeInner::Identity => {
             trace!(
                 "Pushing `{}` below `{}`",
@@ -99,15 +132,265 @@ fn push_view_key(query: &mut MirQuery<'_>, node_idx: NodeIndex) -> ReadySetResul
     Ok(())
 }
 
+/// If the only child of the given node is a union, returns the index of that union
+fn union_below(query: &MirQuery<'_>, node_idx: NodeIndex) -> ReadySetResult<Option<NodeIndex>> {
+    Ok(match query.descendants(node_idx)?.as_slice() {
+        [child]
+            if matches!(
+                query.get_node(*child).unwrap().inner,
+                MirNodeInner::Union { .. }
+            ) =>
+        {
+            Some(*child)
+        }
+        _ => None,
+    })
+}
+
+/// Insert the given node between `parent` and `child`, preserving the weight of the edge between
+/// them (which determines the order of the parents of `child`)
+fn insert_between(
+    query: &mut MirQuery<'_>,
+    parent: NodeIndex,
+    child: NodeIndex,
+    mut node: MirNode,
+) -> ReadySetResult<NodeIndex> {
+    let edge = query
+        .graph
+        .edges_directed(child, Direction::Incoming)
+        .find(|e| e.source() == parent)
+        .ok_or_else(|| internal_err!("There is no edge between parent and child"))?;
+    let (edge_idx, weight) = (edge.id(), *edge.weight());
+    query.graph.remove_edge(edge_idx);
+
+    node.add_owner(query.name().clone());
+    let node_idx = query.graph.add_node(node);
+    query.graph.add_edge(parent, node_idx, 0);
+    query.graph.add_edge(node_idx, child, weight);
+    Ok(node_idx)
+}
+
+/// Push the view keys of all the parents of the given union node, each of which must either be a
+/// view key node or have no view keys above it, below that union.
+///
+/// Each parent of a union can be parametrized by a different set of placeholders, so every
+/// placeholder becomes a new key column projected by all the parents of the union: the parents
+/// parametrized by that placeholder project their key column (filtering out rows where it's
+/// `NULL`, since those can never compare equal to a parameter), and all other parents project
+/// `NULL`. A placeholder that parametrizes more than one parent (or more than one column of the
+/// same parent) still gets a single key column. The sets of key columns each parent projects as
+/// `NULL` are then recorded in the leaf of the query, so that the reader can look up each key once
+/// for each parent, and return the rows from all of them.
+fn push_view_keys_below_union(
+    query: &mut MirQuery<'_>,
+    union_idx: NodeIndex,
+) -> ReadySetResult<()> {
+    match &query.leaf_node().inner {
+        MirNodeInner::Leaf {
+            null_key_columns, ..
+        } if !null_key_columns.is_empty() => unsupported!(
+            "Parameters on one side of more than one UNION in the same query are not yet supported"
+        ),
+        MirNodeInner::Leaf { .. } => {}
+        _ => unsupported!("Parameters on one side of a UNION are only supported in cached queries"),
+    }
+
+    let parents = query.ancestors(union_idx)?;
+    let mut parent_keys = Vec::with_capacity(parents.len());
+    for &parent in &parents {
+        let key = match &query.get_node(parent).unwrap().inner {
+            MirNodeInner::ViewKey { key } => key.iter().cloned().collect(),
+            _ => vec![],
+        };
+        if key
+            .iter()
+            .any(|ViewKeyColumn { op, .. }| *op != BinaryOperator::Equal)
+        {
+            unsupported!(
+                "Only equality comparisons against parameters are supported in branches of a \
+                 UNION"
+            );
+        }
+        parent_keys.push(key);
+    }
+
+    let key_column = |placeholder_idx| Column::named(format!("__union_key_{placeholder_idx}"));
+    let placeholders = parent_keys
+        .iter()
+        .flatten()
+        .map(
+            |ViewKeyColumn {
+                 placeholder_idx, ..
+             }| *placeholder_idx,
+        )
+        .unique()
+        .collect::<Vec<_>>();
+
+    let mut null_key_columns: Vec<Vec<Column>> = Vec::with_capacity(parents.len());
+    for (i, (parent, key)) in parents.into_iter().zip(parent_keys).enumerate() {
+        let mut null_columns = vec![];
+        let emit = placeholders
+            .iter()
+            .map(|&placeholder_idx| {
+                let alias = key_column(placeholder_idx).name;
+                let expr = match key
+                    .iter()
+                    .find(|vkc| vkc.placeholder_idx == placeholder_idx)
+                {
+                    Some(ViewKeyColumn { column, .. }) => Expr::Column(nom_sql::Column {
+                        name: column.name.clone(),
+                        table: column.table.clone(),
+                    }),
+                    None => {
+                        null_columns.push(key_column(placeholder_idx));
+                        Expr::Literal(Literal::Null)
+                    }
+                };
+                ProjectExpr::Expr { expr, alias }
+            })
+            .collect::<Vec<_>>();
+        if !null_key_columns.contains(&null_columns) {
+            null_key_columns.push(null_columns);
+        }
+
+        // Turn the view key node (if any) into a filter on its key columns not being NULL, and on
+        // all the columns compared against the same placeholder being equal to the one we project
+        // as the key column for that placeholder
+        let col_expr = |column: &Column| {
+            Expr::Column(nom_sql::Column {
+                name: column.name.clone(),
+                table: column.table.clone(),
+            })
+        };
+        if let Some(conditions) = key
+            .iter()
+            .map(|ViewKeyColumn { column, .. }| Expr::BinaryOp {
+                lhs: Box::new(col_expr(column)),
+                op: BinaryOperator::IsNot,
+                rhs: Box::new(Expr::Literal(Literal::Null)),
+            })
+            .chain(key.iter().enumerate().filter_map(
+                |(
+                    j,
+                    ViewKeyColumn {
+                        column,
+                        placeholder_idx,
+                        ..
+                    },
+                )| {
+                    let first = key[..j]
+                        .iter()
+                        .find(|vkc| vkc.placeholder_idx == *placeholder_idx)?;
+                    Some(Expr::BinaryOp {
+                        lhs: Box::new(col_expr(&first.column)),
+                        op: BinaryOperator::Equal,
+                        rhs: Box::new(col_expr(column)),
+                    })
+                },
+            ))
+            .reduce(|lhs, rhs| Expr::BinaryOp {
+                lhs: Box::new(lhs),
+                op: BinaryOperator::And,
+                rhs: Box::new(rhs),
+            })
+        {
+            query.get_node_mut(parent).unwrap().inner = MirNodeInner::Filter { conditions };
+        }
+
+        let project = MirNode::new(
+            format!("{}_union_key_project{i}", query.name().display_unquoted()).into(),
+            MirNodeInner::Project {
+                emit: query
+                    .graph
+                    .columns(parent)
+                    .into_iter()
+                    .map(ProjectExpr::Column)
+                    .chain(emit)
+                    .collect(),
+            },
+        );
+        insert_between(query, parent, union_idx, project)?;
+
+        match &mut query.get_node_mut(union_idx).unwrap().inner {
+            MirNodeInner::Union { emit, .. } => emit
+                .get_mut(i)
+                .ok_or_else(|| internal_err!("Union is missing emit columns for a parent"))?
+                .extend(placeholders.iter().map(|&idx| key_column(idx))),
+            _ => internal!("The node passed to push_view_keys_below_union must be a union"),
+        }
+    }
+
+    if let MirNodeInner::Leaf {
+        null_key_columns: leaf_null_key_columns,
+        ..
+    } = &mut query.leaf_node_mut().inner
+    {
+        *leaf_null_key_columns = null_key_columns;
+    }
+
+    let key = placeholders
+        .into_iter()
+        .map(|placeholder_idx| ViewKeyColumn {
+            column: key_column(placeholder_idx),
+            op: BinaryOperator::Equal,
+            placeholder_idx,
+        })
+        .collect::<Vec<_>>();
+    let key = key
+        .try_into()
+        .map_err(|_| internal_err!("Union must have at least one parent with a view key"))?;
+    let child = match query.descendants(union_idx)?.as_slice() {
+        [child] => *child,
+        _ => unsupported!("Don't know how to push view key below multi-child node"),
+    };
+    let view_key = query.insert_above(
+        child,
+        MirNode::new(
+            format!("{}_union_view_key", query.name().display_unquoted()).into(),
+            MirNodeInner::ViewKey { key },
+        ),
+    )?;
+    trace!(?view_key, "Pushed view keys below union");
+
+    Ok(())
+}
+
 #[instrument(level = "trace", skip_all, fields(query = %query.name().display_unquoted()))]
 pub(crate) fn pull_view_keys_to_leaf(query: &mut MirQuery<'_>) -> ReadySetResult<()> {
-    while let Some(view_key) = query
-        .topo_nodes()
-        .into_iter()
-        .find(|&n| query.get_node(n).unwrap().inner.is_view_key())
-    {
-        trace!(node = ?view_key, "Pushing view key node");
-        push_view_key(query, view_key)?;
+    loop {
+        let view_keys = query
+            .topo_nodes()
+            .into_iter()
+            .filter(|&n| query.get_node(n).unwrap().inner.is_view_key())
+            .collect::<Vec<_>>();
+
+        // View keys right above a union have to wait for the view keys in all the other parents
+        // of that union to be pushed down to it before they can be pushed below it together
+        let mut blocked_union = None;
+        let mut pushed = false;
+        for view_key in view_keys {
+            match union_below(query, view_key)? {
+                Some(union) => {
+                    blocked_union.get_or_insert(union);
+                }
+                None => {
+                    trace!(node = ?view_key, "Pushing view key node");
+                    push_view_key(query, view_key)?;
+                    pushed = true;
+                    break;
+                }
+            }
+        }
+
+        if !pushed {
+            match blocked_union {
+                Some(union) => {
+                    trace!(node = ?union, "Pushing view keys below union");
+                    push_view_keys_below_union(query, union)?;
+                }
+                None => break,
+            }
+        }
     }
 
     Ok(())
@@ -117,13 +400,12 @@ pub(crate) fn pull_view_keys_to_leaf(query: &mut MirQuery<'_>) -> ReadySetResult
 #[allow(clippy::panic)]
 mod tests {
     use common::IndexType;
+    use dataflow::ops::union::DuplicateMode;
     use nom_sql::{ColumnSpecification, Relation, SqlType};
     use vec1::vec1;
 
     use super::*;
     use crate::graph::MirGraph;
-    use crate::node::MirNode;
-    use crate::Column;
 
     #[test]
     fn simple_equality() {
@@ -185,4 +467,200 @@ mod tests {
             _ => panic!(),
         }
     }
+
+    #[test]
+    fn parameters_on_one_side_of_union() {
+        let mut graph = MirGraph::new();
+        let query_name = Relation::from("q");
+
+        let add_base = |graph: &mut MirGraph, name: &str| {
+            let base = graph.add_node(MirNode::new(
+                name.into(),
+                MirNodeInner::Base {
+                    column_specs: ["a", "b"]
+                        .into_iter()
+                        .map(|col| ColumnSpecification {
+                            column: format!("{name}.{col}").as_str().into(),
+                            sql_type: SqlType::Int(None),
+                            constraints: vec![],
+                            comment: None,
+                        })
+                        .collect(),
+                    primary_key: None,
+                    unique_keys: Default::default(),
+                },
+            ));
+            graph[base].add_owner(query_name.clone());
+            base
+        };
+        let t = add_base(&mut graph, "t");
+        let u = add_base(&mut graph, "u");
+
+        let vk = graph.add_node(MirNode::new(
+            "vk".into(),
+            MirNodeInner::ViewKey {
+                key: vec1![ViewKeyColumn {
+                    column: Column::new(Some("t"), "a"),
+                    op: BinaryOperator::Equal,
+                    placeholder_idx: 1
+                }],
+            },
+        ));
+        graph[vk].add_owner(query_name.clone());
+        graph.add_edge(t, vk, 0);
+
+        let union = graph.add_node(MirNode::new(
+            "union".into(),
+            MirNodeInner::Union {
+                emit: vec![
+                    vec![Column::named("a"), Column::named("b")],
+                    vec![Column::named("a"), Column::named("b")],
+                ],
+                duplicate_mode: DuplicateMode::UnionAll,
+            },
+        ));
+        graph[union].add_owner(query_name.clone());
+        graph.add_edge(vk, union, 0);
+        graph.add_edge(u, union, 1);
+
+        let leaf = graph.add_node(MirNode::new(
+            "leaf".into(),
+            MirNodeInner::leaf(vec![], IndexType::HashMap),
+        ));
+        graph[leaf].add_owner(query_name.clone());
+        graph.add_edge(union, leaf, 0);
+
+        let mut query = MirQuery::new(query_name, leaf, &mut graph);
+        pull_view_keys_to_leaf(&mut query).unwrap();
+
+        let key_column = Column::named("__union_key_1");
+        match &graph.node_weight(leaf).unwrap().inner {
+            MirNodeInner::Leaf {
+                keys,
+                null_key_columns,
+                ..
+            } => {
+                assert_eq!(
+                    keys,
+                    &[(
+                        key_column.clone(),
+                        ViewPlaceholder::OneToOne(1, BinaryOperator::Equal)
+                    )]
+                );
+                assert_eq!(null_key_columns, &[vec![], vec![key_column.clone()]]);
+            }
+            _ => panic!(),
+        }
+
+        // The view key is now a filter on its key column not being NULL
+        assert!(matches!(
+            graph.node_weight(vk).unwrap().inner,
+            MirNodeInner::Filter { .. }
+        ));
+
+        match &graph.node_weight(union).unwrap().inner {
+            MirNodeInner::Union { emit, .. } => {
+                for cols in emit {
+                    assert_eq!(cols.last(), Some(&key_column));
+                }
+            }
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn same_placeholder_on_both_sides_of_union() {
+        let mut graph = MirGraph::new();
+        let query_name = Relation::from("q");
+
+        let add_parent = |graph: &mut MirGraph, name: &str| {
+            let base = graph.add_node(MirNode::new(
+                name.into(),
+                MirNodeInner::Base {
+                    column_specs: ["a", "b"]
+                        .into_iter()
+                        .map(|col| ColumnSpecification {
+                            column: format!("{name}.{col}").as_str().into(),
+                            sql_type: SqlType::Int(None),
+                            constraints: vec![],
+                            comment: None,
+                        })
+                        .collect(),
+                    primary_key: None,
+                    unique_keys: Default::default(),
+                },
+            ));
+            graph[base].add_owner(query_name.clone());
+            let vk = graph.add_node(MirNode::new(
+                format!("{name}_vk").into(),
+                MirNodeInner::ViewKey {
+                    key: vec1![ViewKeyColumn {
+                        column: Column::new(Some(name), "a"),
+                        op: BinaryOperator::Equal,
+                        placeholder_idx: 1
+                    }],
+                },
+            ));
+            graph[vk].add_owner(query_name.clone());
+            graph.add_edge(base, vk, 0);
+            vk
+        };
+        let t_vk = add_parent(&mut graph, "t");
+        let u_vk = add_parent(&mut graph, "u");
+
+        let union = graph.add_node(MirNode::new(
+            "union".into(),
+            MirNodeInner::Union {
+                emit: vec![
+                    vec![Column::named("a"), Column::named("b")],
+                    vec![Column::named("a"), Column::named("b")],
+                ],
+                duplicate_mode: DuplicateMode::UnionAll,
+            },
+        ));
+        graph[union].add_owner(query_name.clone());
+        graph.add_edge(t_vk, union, 0);
+        graph.add_edge(u_vk, union, 1);
+
+        let leaf = graph.add_node(MirNode::new(
+            "leaf".into(),
+            MirNodeInner::leaf(vec![], IndexType::HashMap),
+        ));
+        graph[leaf].add_owner(query_name.clone());
+        graph.add_edge(union, leaf, 0);
+
+        let mut query = MirQuery::new(query_name, leaf, &mut graph);
+        pull_view_keys_to_leaf(&mut query).unwrap();
+
+        let key_column = Column::named("__union_key_1");
+        match &graph.node_weight(leaf).unwrap().inner {
+            MirNodeInner::Leaf {
+                keys,
+                null_key_columns,
+                ..
+            } => {
+                assert_eq!(
+                    keys,
+                    &[(
+                        key_column.clone(),
+                        ViewPlaceholder::OneToOne(1, BinaryOperator::Equal)
+                    )]
+                );
+                assert_eq!(null_key_columns, &[Vec::<Column>::new()]);
+            }
+            _ => panic!(),
+        }
+
+        match &graph.node_weight(union).unwrap().inner {
+            MirNodeInner::Union { emit, .. } => {
+                for cols in emit {
+                    assert_eq!(
+                        cols,
+                        &[Column::named("a"), Column::named("b"), key_column.clone()]
+                    );
+                }
+            }
+            _ => panic!(),
+        }
+    }
 }
@@ -152,17 +152,34 @@ impl GraphViz for MirNodeInner {
         match self {
             MirNodeInner::Aggregation {
                 ref on,
+                ref key,
                 ref group_by,
                 ref kind,
                 ..
             } => {
                 let op_string = match kind {
                     AggregationKind::Count { .. } => format!("\\|*\\|({})", on),
+                    AggregationKind::CountDistinct => format!("\\|DISTINCT {}\\|", on),
                     AggregationKind::Sum => format!("𝛴({})", on),
+                    AggregationKind::SumDistinct => format!("𝛴(DISTINCT {})", on),
                     AggregationKind::Avg => format!("AVG({})", on),
                     AggregationKind::GroupConcat { separator: s } => {
                         format!("\\|\\|({}, \\\"{}\\\")", on, s)
                     }
+                    AggregationKind::Stddev { sample } => {
+                        format!("STDDEV_{}({})", if *sample { "SAMP" } else { "POP" }, on)
+                    }
+                    AggregationKind::Variance { sample } => {
+                        format!("VAR_{}({})", if *sample { "SAMP" } else { "POP" }, on)
+                    }
+                    AggregationKind::BoolAnd => format!("BOOL_AND({})", on),
+                    AggregationKind::BoolOr => format!("BOOL_OR({})", on),
+                    AggregationKind::BitAnd => format!("BIT_AND({})", on),
+                    AggregationKind::BitOr => format!("BIT_OR({})", on),
+                    AggregationKind::Collect(kind) => match key {
+                        Some(key) => format!("{}({}, {})", kind, key, on),
+                        None => format!("{}({})", kind, on),
+                    },
                 };
                 let group_cols = group_by.iter().join(", ");
                 write!(f, "{} | γ: {}", op_string, group_cols)
@@ -277,6 +294,9 @@ impl GraphViz for MirNodeInner {
                                     PostLookupAggregateFunction::GroupConcat { .. } => "GC",
                                     PostLookupAggregateFunction::Max => "Max",
                                     PostLookupAggregateFunction::Min => "Min",
+                                    PostLookupAggregateFunction::ArrayConcat => "AC",
+                                    PostLookupAggregateFunction::Avg { .. } => "Avg",
+                                    PostLookupAggregateFunction::CountDistinct { .. } => "CD",
                                 },
                                 &aggregate.column
                             ))
@@ -287,9 +307,21 @@ impl GraphViz for MirNodeInner {
 
                 Ok(())
             }
-            MirNodeInner::LeftJoin { ref on, .. } => {
+            MirNodeInner::LeftJoin {
+                ref on,
+                ref residual,
+                ..
+            } => {
+                let jc = on.iter().map(|(l, r)| format!("{}:{}", l, r)).join(", ");
+                write!(f, "⋉  | on: {}", jc)?;
+                if let Some(residual) = residual {
+                    write!(f, " | σ: {}", residual.display(nom_sql::Dialect::MySQL))?;
+                }
+                Ok(())
+            }
+            MirNodeInner::FullJoin { ref on, .. } => {
                 let jc = on.iter().map(|(l, r)| format!("{}:{}", l, r)).join(", ");
-                write!(f, "⋉  | on: {}", jc)
+                write!(f, "⟗  | on: {}", jc)
             }
             MirNodeInner::DependentJoin { ref on, .. } => {
                 write!(
@@ -298,6 +330,18 @@ impl GraphViz for MirNodeInner {
                     on.iter().map(|(l, r)| format!("{}:{}", l, r)).join(", ")
                 )
             }
+            MirNodeInner::DependentLeftJoin {
+                ref on,
+                ref residual,
+                ..
+            } => {
+                let jc = on.iter().map(|(l, r)| format!("{}:{}", l, r)).join(", ");
+                write!(f, "⧑⋉ | on: {}", jc)?;
+                if let Some(residual) = residual {
+                    write!(f, " | σ: {}", residual.display(nom_sql::Dialect::MySQL))?;
+                }
+                Ok(())
+            }
             MirNodeInner::Project { ref emit } => {
                 write!(f, "π: {}", emit.iter().join(", "))
             }
@@ -337,6 +381,31 @@ impl GraphViz for MirNodeInner {
                     .unwrap_or_else(|| "".into());
                 write!(f, "TopK [k: {}; {}]", limit, order)
             }
+            MirNodeInner::Window {
+                ref on,
+                ref group_by,
+                ref order,
+                ref kind,
+                ..
+            } => {
+                let order = order
+                    .iter()
+                    .map(|(c, o)| format!("{}: {}", c.name.as_str(), o))
+                    .join(", ");
+                write!(
+                    f,
+                    "Window [{}({}); γ: {}; {}]",
+                    kind,
+                    on.as_ref().map(|c| c.name.as_str()).unwrap_or_default(),
+                    group_by.iter().join(", "),
+                    order
+                )
+            }
+            MirNodeInner::Unnest {
+                ref on, ref kind, ..
+            } => {
+                write!(f, "Unnest [{:?}({})]", kind, on.name.as_str())
+            }
             MirNodeInner::Union {
                 ref emit,
                 ref duplicate_mode,
@@ -352,6 +421,17 @@ impl GraphViz for MirNodeInner {
 
                 write!(f, "{}", cols)
             }
+            MirNodeInner::SetOperation {
+                ref emit,
+                ref operator,
+            } => {
+                let cols = emit
+                    .iter()
+                    .map(|c| c.iter().join(", "))
+                    .join(&format!(" {} ", operator));
+
+                write!(f, "{}", cols)
+            }
             MirNodeInner::AliasTable { ref table } => {
                 write!(f, "AliasTable [{}]", table.display_unquoted())
             }
@@ -6,7 +6,7 @@ use lazy_static::lazy_static;
 use nom_sql::{Column, Expr, FieldDefinitionExpr, Literal, SqlIdentifier, SqlQuery, VariableScope};
 use readyset_adapter::backend::noria_connector::QueryResult;
 use readyset_adapter::backend::SelectSchema;
-use readyset_adapter::{QueryHandler, SetBehavior};
+use readyset_adapter::{QueryHandler, SessionParameter, SetBehavior, MAX_STALENESS_VARIABLE};
 use readyset_client::results::Results;
 use readyset_client::ColumnSchema;
 use readyset_data::{DfType, DfValue};
@@ -877,6 +877,19 @@ impl QueryHandler for MySqlQueryHandler {
 
         match stmt {
             nom_sql::SetStatement::Variable(set) => {
+                // The staleness budget is a ReadySet extension, so it can't be set in the same
+                // statement as variables which need to be proxied upstream
+                if set.variables.iter().any(|(var, _)| {
+                    var.as_non_user_var().map_or(false, |name| {
+                        name.eq_ignore_ascii_case(MAX_STALENESS_VARIABLE)
+                    })
+                }) {
+                    return match set.variables.as_slice() {
+                        [(_, Expr::Literal(val))] => SetBehavior::set_max_staleness(Some(val)),
+                        _ => Unsupported,
+                    };
+                }
+
                 if let Some(val) = set.variables.iter().find_map(|(var, val)| {
                     if var.name.as_str().eq_ignore_ascii_case("autocommit") {
                         Some(val)
@@ -889,7 +902,29 @@ impl QueryHandler for MySqlQueryHandler {
                     );
                 }
 
-                SetBehavior::proxy_if(set.variables.iter().all(|(variable, value)| {
+                // Literal values assigned to user variables are recorded, so that cached queries
+                // can reference them. Any other values can't be known without evaluating them
+                // upstream.
+                if set
+                    .variables
+                    .iter()
+                    .all(|(variable, _)| variable.scope == VariableScope::User)
+                {
+                    return SetSessionValues(
+                        set.variables
+                            .iter()
+                            .map(|(variable, value)| {
+                                let value = match value {
+                                    Expr::Literal(lit) => DfValue::try_from(lit).ok(),
+                                    _ => None,
+                                };
+                                (SessionParameter::UserVariable(variable.name.clone()), value)
+                            })
+                            .collect(),
+                    );
+                }
+
+                let allowed = set.variables.iter().all(|(variable, value)| {
                     if variable.scope == VariableScope::User {
                         return false;
                     }
@@ -924,7 +959,24 @@ impl QueryHandler for MySqlQueryHandler {
                         }
                         p => ALLOWED_PARAMETERS_ANY_VALUE.contains(p),
                     }
-                }))
+                });
+                if !allowed {
+                    return Unsupported;
+                }
+
+                // The time zone can only be set to UTC, but we need to know that the session's time
+                // zone is UTC before we can evaluate CURRENT_DATE ourselves
+                if set.variables.iter().any(|(variable, _)| {
+                    variable.scope != VariableScope::Global
+                        && variable.name.eq_ignore_ascii_case("time_zone")
+                }) {
+                    SetSessionValues(vec![(
+                        SessionParameter::TimeZone,
+                        Some(DfValue::from("+00:00")),
+                    )])
+                } else {
+                    Proxy
+                }
             }
             nom_sql::SetStatement::Names(names) => SetBehavior::proxy_if(
                 names.collation.is_none()
@@ -977,6 +1029,130 @@ mod tests {
         );
     }
 
+    #[test]
+    fn set_user_variables() {
+        let stmt = SetStatement::Variable(SetVariables {
+            variables: vec![(
+                Variable {
+                    scope: VariableScope::User,
+                    name: "user_id".into(),
+                },
+                Expr::Literal(Literal::UnsignedInteger(4)),
+            )],
+        });
+        assert_eq!(
+            MySqlQueryHandler::handle_set_statement(&stmt),
+            SetBehavior::SetSessionValues(vec![(
+                SessionParameter::UserVariable("user_id".into()),
+                Some(DfValue::from(4u64))
+            )])
+        );
+    }
+
+    #[test]
+    fn set_user_variable_to_expression() {
+        let stmt = SetStatement::Variable(SetVariables {
+            variables: vec![(
+                Variable {
+                    scope: VariableScope::User,
+                    name: "user_id".into(),
+                },
+                Expr::Variable(Variable {
+                    scope: VariableScope::User,
+                    name: "other".into(),
+                }),
+            )],
+        });
+        assert_eq!(
+            MySqlQueryHandler::handle_set_statement(&stmt),
+            SetBehavior::SetSessionValues(vec![(
+                SessionParameter::UserVariable("user_id".into()),
+                None
+            )])
+        );
+    }
+
+    #[test]
+    fn set_user_and_system_variables() {
+        let stmt = SetStatement::Variable(SetVariables {
+            variables: vec![
+                (
+                    Variable {
+                        scope: VariableScope::User,
+                        name: "user_id".into(),
+                    },
+                    Expr::Literal(Literal::UnsignedInteger(4)),
+                ),
+                (
+                    Variable {
+                        scope: VariableScope::Session,
+                        name: "time_zone".into(),
+                    },
+                    Expr::Literal(Literal::from("+00:00")),
+                ),
+            ],
+        });
+        assert_eq!(
+            MySqlQueryHandler::handle_set_statement(&stmt),
+            SetBehavior::Unsupported
+        );
+    }
+
+    #[test]
+    fn set_max_staleness() {
+        let set = |q: &str| match nom_sql::parse_query(nom_sql::Dialect::MySQL, q).unwrap() {
+            nom_sql::SqlQuery::Set(stmt) => MySqlQueryHandler::handle_set_statement(&stmt),
+            _ => panic!("not a SET statement"),
+        };
+
+        assert_eq!(
+            set("SET readyset_max_staleness = 2"),
+            SetBehavior::SetMaxStaleness(Some(std::time::Duration::from_secs(2)))
+        );
+        assert_eq!(
+            set("SET @@SESSION.readyset_max_staleness = '0.5'"),
+            SetBehavior::SetMaxStaleness(Some(std::time::Duration::from_millis(500)))
+        );
+        assert_eq!(
+            set("SET readyset_max_staleness = NULL"),
+            SetBehavior::SetMaxStaleness(None)
+        );
+        assert_eq!(
+            set("SET readyset_max_staleness = -1"),
+            SetBehavior::Unsupported
+        );
+        assert_eq!(
+            set("SET readyset_max_staleness = 2, autocommit = 1"),
+            SetBehavior::Unsupported
+        );
+    }
+
+    #[test]
+    fn set_time_zone() {
+        let set_time_zone = |scope| {
+            SetStatement::Variable(SetVariables {
+                variables: vec![(
+                    Variable {
+                        scope,
+                        name: "time_zone".into(),
+                    },
+                    Expr::Literal(Literal::from("+00:00")),
+                )],
+            })
+        };
+        assert_eq!(
+            MySqlQueryHandler::handle_set_statement(&set_time_zone(VariableScope::Session)),
+            SetBehavior::SetSessionValues(vec![(
+                SessionParameter::TimeZone,
+                Some("+00:00".into())
+            )])
+        );
+        assert_eq!(
+            MySqlQueryHandler::handle_set_statement(&set_time_zone(VariableScope::Global)),
+            SetBehavior::Proxy
+        );
+    }
+
     #[test]
     fn all_required_sql_modes_are_allowed() {
         for mode in REQUIRED_SQL_MODES {
@@ -20,7 +20,7 @@ use pin_project::pin_project;
 use readyset_adapter::fallback_cache::FallbackCache;
 #[cfg(feature = "fallback_cache")]
 use readyset_adapter::fallback_cache::FallbackCacheApi;
-use readyset_adapter::upstream_database::UpstreamDestination;
+use readyset_adapter::upstream_database::{UpstreamDestination, WriteId};
 use readyset_adapter::{UpstreamConfig, UpstreamDatabase, UpstreamPrepare};
 use readyset_client_metrics::QueryDestination;
 use readyset_data::DfValue;
@@ -35,6 +35,20 @@ type StatementID = u32;
 /// during connection phase if the version for the upstream server is too low.
 const MIN_UPSTREAM_VERSION: u16 = 8;
 
+/// Return the highest transaction sequence number executed by the server identified by
+/// `server_uuid`, given a GTID set as returned by `@@GLOBAL.gtid_executed`, in the form
+/// `<uuid>:<start>[-<end>][:<start>[-<end>]...][,<uuid>:...]`
+fn last_gtid_sequence_number(gtid_set: &str, server_uuid: &str) -> Option<u64> {
+    let (_, intervals) = gtid_set
+        .split(',')
+        .filter_map(|set| set.trim().split_once(':'))
+        .find(|(uuid, _)| uuid.eq_ignore_ascii_case(server_uuid))?;
+    intervals
+        .split(':')
+        .filter_map(|interval| interval.rsplit('-').next()?.parse().ok())
+        .max()
+}
+
 fn dt_to_value_params(dt: &[DfValue]) -> ReadySetResult<Vec<mysql_async::Value>> {
     dt.iter().map(|v| v.try_into()).collect()
 }
@@ -258,17 +272,12 @@ impl MySqlUpstream {
             user = %opts.user().unwrap_or("<NO USER>"),
         );
         span.in_scope(|| info!("Establishing connection"));
-        let conn = if cfg!(feature = "ryw") {
-            Conn::new(
-                OptsBuilder::from_opts(opts).add_capability(CapabilityFlags::CLIENT_SESSION_TRACK),
-            )
-            .instrument(span.clone())
-            .await?
-        } else {
-            Conn::new(OptsBuilder::from_opts(opts))
-                .instrument(span.clone())
-                .await?
-        };
+        // Session state tracking is required to learn the GTIDs of RYW writes
+        let conn = Conn::new(
+            OptsBuilder::from_opts(opts).add_capability(CapabilityFlags::CLIENT_SESSION_TRACK),
+        )
+        .instrument(span.clone())
+        .await?;
 
         // Check that the server version is supported.
         let (major, minor, _) = conn.server_version();
@@ -509,7 +518,7 @@ impl UpstreamDatabase for MySqlUpstream {
     async fn handle_ryw_write<'a, S>(
         &'a mut self,
         query: S,
-    ) -> Result<(Self::QueryResult<'a>, String), Error>
+    ) -> Result<(Self::QueryResult<'a>, WriteId), Error>
     where
         S: AsRef<str> + Send + Sync + 'a,
     {
@@ -534,10 +543,22 @@ impl UpstreamDatabase for MySqlUpstream {
                 last_inserted_id: last_insert_id.unwrap_or(0),
                 status_flags,
             },
-            txid,
+            WriteId::MySqlGtid(txid),
         ))
     }
 
+    async fn write_position(&mut self) -> Result<WriteId, Error> {
+        let (server_uuid, gtid_executed): (String, String) = self
+            .conn
+            .query_first("SELECT @@GLOBAL.server_uuid, @@GLOBAL.gtid_executed")
+            .await?
+            .ok_or_else(|| internal_err!("Could not read executed GTIDs from MySQL"))?;
+        let gno = last_gtid_sequence_number(&gtid_executed, &server_uuid).ok_or_else(|| {
+            internal_err!("Read-your-write consistency requires GTIDs to be enabled in MySQL")
+        })?;
+        Ok(WriteId::MySqlGtid(format!("{server_uuid}:{gno}")))
+    }
+
     async fn start_tx<'a>(
         &'a mut self,
         stmt: &StartTransactionStatement,
@@ -588,3 +609,48 @@ impl UpstreamDatabase for MySqlUpstream {
         Ok(self.database().into_iter().map(|s| s.into()).collect())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn last_gtid_sequence_number_single_server() {
+        assert_eq!(
+            last_gtid_sequence_number(
+                "3e11fa47-71ca-11e1-9e33-c80aa9429562:1-5:11-18",
+                "3e11fa47-71ca-11e1-9e33-c80aa9429562"
+            ),
+            Some(18)
+        );
+        assert_eq!(
+            last_gtid_sequence_number(
+                "3e11fa47-71ca-11e1-9e33-c80aa9429562:7",
+                "3e11fa47-71ca-11e1-9e33-c80aa9429562"
+            ),
+            Some(7)
+        );
+    }
+
+    #[test]
+    fn last_gtid_sequence_number_multiple_servers() {
+        let gtid_set = "2174b383-5441-11e8-b90a-c80aa9429562:1-3,\n\
+                        3e11fa47-71ca-11e1-9e33-c80aa9429562:1-19";
+        assert_eq!(
+            last_gtid_sequence_number(gtid_set, "2174B383-5441-11E8-B90A-C80AA9429562"),
+            Some(3)
+        );
+        assert_eq!(
+            last_gtid_sequence_number(gtid_set, "3e11fa47-71ca-11e1-9e33-c80aa9429562"),
+            Some(19)
+        );
+    }
+
+    #[test]
+    fn last_gtid_sequence_number_gtids_disabled() {
+        assert_eq!(
+            last_gtid_sequence_number("", "3e11fa47-71ca-11e1-9e33-c80aa9429562"),
+            None
+        );
+    }
+}
@@ -902,6 +902,65 @@ async fn replication_failure_ignores_table() {
     shutdown_tx.shutdown().await;
 }
 
+#[tokio::test(flavor = "multi_thread")]
+#[serial]
+async fn user_variable_assigned_upstream() {
+    let (opts, _handle, shutdown_tx) = setup().await;
+    let mut conn = mysql_async::Conn::new(opts).await.unwrap();
+    conn.query_drop("CREATE TABLE posts (id int, owner int)")
+        .await
+        .unwrap();
+    conn.query_drop("INSERT INTO posts (id, owner) VALUES (1, 1), (2, 2), (3, 2)")
+        .await
+        .unwrap();
+    sleep().await;
+
+    let query = "SELECT id FROM posts WHERE owner = @user_id";
+    conn.query_drop("SET @user_id = 2").await.unwrap();
+    conn.query_drop(query).await.unwrap();
+    let mut result: Vec<u32> = conn.query(query).await.unwrap();
+    result.sort();
+    assert_eq!(result, vec![2, 3]);
+    assert_eq!(
+        last_query_info(&mut conn).await.destination,
+        QueryDestination::Readyset
+    );
+
+    // We can't see the value assigned by a SELECT, so it has to be read upstream
+    conn.query_drop("SELECT @user_id := 1").await.unwrap();
+    let result: Vec<u32> = conn.query(query).await.unwrap();
+    assert_eq!(result, vec![1]);
+    assert_ne!(
+        last_query_info(&mut conn).await.destination,
+        QueryDestination::Readyset
+    );
+
+    conn.query_drop("SET @user_id = 2").await.unwrap();
+    let mut result: Vec<u32> = conn.query(query).await.unwrap();
+    result.sort();
+    assert_eq!(result, vec![2, 3]);
+    assert_eq!(
+        last_query_info(&mut conn).await.destination,
+        QueryDestination::Readyset
+    );
+
+    // User variables aren't reverted by a rollback, but we don't rely on knowing that
+    let mut tx = conn
+        .start_transaction(mysql_async::TxOpts::new())
+        .await
+        .unwrap();
+    tx.query_drop("SET @user_id = 1").await.unwrap();
+    tx.rollback().await.unwrap();
+    let result: Vec<u32> = conn.query(query).await.unwrap();
+    assert_eq!(result, vec![1]);
+    assert_ne!(
+        last_query_info(&mut conn).await.destination,
+        QueryDestination::Readyset
+    );
+
+    shutdown_tx.shutdown().await;
+}
+
 #[allow(dead_code)]
 async fn last_statement_matches(dest: &str, status: &str, client: &mut mysql_async::Conn) -> bool {
     let rows: Vec<(String, String)> = client
@@ -1546,6 +1546,40 @@ async fn write_timestamps() {
     shutdown_tx.shutdown().await;
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn user_variable_in_where_clause() {
+    let (opts, _handle, shutdown_tx) = setup().await;
+    let mut conn = mysql_async::Conn::new(opts).await.unwrap();
+    conn.query_drop("CREATE TABLE posts (id int, owner int)")
+        .await
+        .unwrap();
+    conn.query_drop("INSERT INTO posts (id, owner) VALUES (1, 1), (2, 2), (3, 2)")
+        .await
+        .unwrap();
+    sleep().await;
+
+    conn.query_drop("SET @user_id = 2").await.unwrap();
+    let mut result: Vec<u32> = conn
+        .query("SELECT id FROM posts WHERE owner = @user_id")
+        .await
+        .unwrap();
+    result.sort();
+    assert_eq!(result, vec![2, 3]);
+
+    conn.query_drop("SET @user_id = 1").await.unwrap();
+    let result: Vec<u32> = conn
+        .query("SELECT id FROM posts WHERE owner = @user_id")
+        .await
+        .unwrap();
+    assert_eq!(result, vec![1]);
+    assert_eq!(
+        last_query_info(&mut conn).await.destination,
+        QueryDestination::Readyset
+    );
+
+    shutdown_tx.shutdown().await;
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn round_trip_time_type() {
     let (opts, _handle, shutdown_tx) = setup().await;
@@ -1740,6 +1774,43 @@ async fn explain_last_statement() {
     shutdown_tx.shutdown().await;
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn explain_create_cache() {
+    let (opts, _handle, shutdown_tx) = setup().await;
+    let mut conn = mysql_async::Conn::new(opts).await.unwrap();
+    conn.query_drop("CREATE TABLE t (x int, y int)")
+        .await
+        .unwrap();
+    sleep().await;
+
+    let res: mysql_async::Row = conn
+        .query_first("EXPLAIN CREATE CACHE FROM SELECT y FROM t WHERE x = ?")
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(res.get::<String, _>("Supported").unwrap(), "yes");
+    assert_eq!(res.get::<String, _>("Key_columns").unwrap(), "x");
+    assert_eq!(res.get::<String, _>("Materialization").unwrap(), "partial");
+    assert_eq!(res.get::<String, _>("Upstream_tables").unwrap(), "t");
+
+    // Explaining a query doesn't cache it
+    let caches: Vec<(String, String, String)> = conn.query("SHOW CACHES").await.unwrap();
+    assert!(caches.is_empty());
+
+    let res: mysql_async::Row = conn
+        .query_first("EXPLAIN CREATE CACHE FROM SELECT 1")
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(res.get::<String, _>("Supported").unwrap(), "no");
+    assert!(!res
+        .get::<String, _>("Unsupported_reason")
+        .unwrap()
+        .is_empty());
+
+    shutdown_tx.shutdown().await;
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn create_query_cache_where_in() {
     let (opts, _handle, shutdown_tx) = setup().await;
@@ -3,12 +3,13 @@ use std::collections::{HashMap, HashSet};
 
 use lazy_static::lazy_static;
 use nom_sql::{
-    Literal, PostgresParameterValue, PostgresParameterValueInner, SetNames, SetPostgresParameter,
-    SetPostgresParameterValue, SetStatement, SqlQuery,
+    Literal, PostgresParameterScope, PostgresParameterValue, PostgresParameterValueInner, SetNames,
+    SetPostgresParameter, SetPostgresParameterValue, SetStatement, SqlIdentifier, SqlQuery,
 };
 use readyset_adapter::backend::noria_connector::QueryResult;
 use readyset_adapter::backend::{noria_connector, SelectSchema};
-use readyset_adapter::{QueryHandler, SetBehavior};
+use readyset_adapter::{QueryHandler, SessionParameter, SetBehavior, MAX_STALENESS_VARIABLE};
+use readyset_data::DfValue;
 use readyset_errors::ReadySetResult;
 
 enum AllowedParameterValue {
@@ -337,6 +338,17 @@ lazy_static! {
         ]);
 }
 
+/// Convert a single value for a postgres parameter into the string the parameter would be set to
+fn parameter_value_to_string(value: &PostgresParameterValueInner) -> SqlIdentifier {
+    match value {
+        PostgresParameterValueInner::Identifier(id) => id.clone(),
+        PostgresParameterValueInner::Literal(Literal::String(s)) => s.into(),
+        PostgresParameterValueInner::Literal(lit) => {
+            lit.display(nom_sql::Dialect::PostgreSQL).to_string().into()
+        }
+    }
+}
+
 /// PostgreSQL flavor of [`QueryHandler`].
 pub struct PostgreSqlQueryHandler;
 
@@ -355,11 +367,68 @@ impl QueryHandler for PostgreSqlQueryHandler {
 
     fn handle_set_statement(stmt: &SetStatement) -> SetBehavior {
         match stmt {
+            SetStatement::PostgresParameter(SetPostgresParameter { name, value, .. })
+                if name.eq_ignore_ascii_case(MAX_STALENESS_VARIABLE) =>
+            {
+                match value {
+                    SetPostgresParameterValue::Default => SetBehavior::set_max_staleness(None),
+                    SetPostgresParameterValue::Value(PostgresParameterValue::Single(
+                        PostgresParameterValueInner::Literal(val),
+                    )) => SetBehavior::set_max_staleness(Some(val)),
+                    _ => SetBehavior::Unsupported,
+                }
+            }
             SetStatement::PostgresParameter(SetPostgresParameter { name, .. })
                 if ALLOWED_PARAMETERS_ANY_VALUE.contains(name.to_ascii_lowercase().as_str()) =>
             {
                 SetBehavior::Proxy
             }
+            // Custom parameters (which are always qualified with a class name, such as
+            // `app.tenant`) may be read by cached queries with `current_setting`, so we record
+            // their values
+            SetStatement::PostgresParameter(SetPostgresParameter {
+                scope: None | Some(PostgresParameterScope::Session),
+                name,
+                value: SetPostgresParameterValue::Value(PostgresParameterValue::Single(value)),
+            }) if name.contains('.') => SetBehavior::SetSessionValues(vec![(
+                SessionParameter::Setting(name.to_ascii_lowercase()),
+                Some(DfValue::from(parameter_value_to_string(value).as_str())),
+            )]),
+            // The default value of a custom parameter may be set per role or database, so we can't
+            // know what it is
+            SetStatement::PostgresParameter(SetPostgresParameter {
+                scope: None | Some(PostgresParameterScope::Session),
+                name,
+                value: SetPostgresParameterValue::Default,
+            }) if name.contains('.') => SetBehavior::SetSessionValues(vec![(
+                SessionParameter::Setting(name.to_ascii_lowercase()),
+                None,
+            )]),
+            // The time zone can only be set to UTC, but we need to know that the session's time
+            // zone is UTC before we can evaluate CURRENT_DATE ourselves
+            SetStatement::PostgresParameter(SetPostgresParameter { scope, name, value })
+                if name.eq_ignore_ascii_case("timezone") =>
+            {
+                match ALLOWED_PARAMETERS_WITH_VALUE.get(name.as_str()) {
+                    Some(allowed) if allowed.set_value_is_allowed(value) => {
+                        if matches!(scope, Some(PostgresParameterScope::Local)) {
+                            SetBehavior::Proxy
+                        } else {
+                            let time_zone = match value {
+                                SetPostgresParameterValue::Value(
+                                    PostgresParameterValue::Single(value),
+                                ) => Some(DfValue::from(parameter_value_to_string(value).as_str())),
+                                _ => None,
+                            };
+                            SetBehavior::SetSessionValues(vec![(
+                                SessionParameter::TimeZone,
+                                time_zone,
+                            )])
+                        }
+                    }
+                    _ => SetBehavior::Unsupported,
+                }
+            }
             SetStatement::PostgresParameter(SetPostgresParameter { name, value, .. }) => match name
                 .as_str()
             {
@@ -373,21 +442,13 @@ impl QueryHandler for PostgreSqlQueryHandler {
                     .contains(val),
                 }),
                 "search_path" => {
-                    let value_to_string = |value: &PostgresParameterValueInner| match value {
-                        PostgresParameterValueInner::Identifier(id) => id.clone(),
-                        PostgresParameterValueInner::Literal(Literal::String(s)) => s.into(),
-                        PostgresParameterValueInner::Literal(lit) => {
-                            lit.display(nom_sql::Dialect::PostgreSQL).to_string().into()
-                        }
-                    };
-
                     let search_path = match value {
                         SetPostgresParameterValue::Default => vec!["public".into()],
                         SetPostgresParameterValue::Value(PostgresParameterValue::Single(val)) => {
-                            vec![value_to_string(val)]
+                            vec![parameter_value_to_string(val)]
                         }
                         SetPostgresParameterValue::Value(PostgresParameterValue::List(vals)) => {
-                            vals.iter().map(value_to_string).collect()
+                            vals.iter().map(parameter_value_to_string).collect()
                         }
                     };
 
@@ -466,6 +527,91 @@ mod tests {
         );
     }
 
+    #[test]
+    fn max_staleness() {
+        let set = |statement| {
+            PostgreSqlQueryHandler::handle_set_statement(&parse_set_statement(statement))
+        };
+
+        assert_eq!(
+            set("SET readyset_max_staleness = 2"),
+            SetBehavior::SetMaxStaleness(Some(std::time::Duration::from_secs(2)))
+        );
+        assert_eq!(
+            set("SET SESSION readyset_max_staleness TO '1.5'"),
+            SetBehavior::SetMaxStaleness(Some(std::time::Duration::from_millis(1500)))
+        );
+        assert_eq!(
+            set("SET readyset_max_staleness TO DEFAULT"),
+            SetBehavior::SetMaxStaleness(None)
+        );
+        assert_eq!(
+            set("SET readyset_max_staleness = 'soon'"),
+            SetBehavior::Unsupported
+        );
+    }
+
+    #[test]
+    fn custom_parameter() {
+        assert_eq!(
+            PostgreSqlQueryHandler::handle_set_statement(&parse_set_statement(
+                "SET app.Tenant = 'acme'"
+            )),
+            SetBehavior::SetSessionValues(vec![(
+                SessionParameter::Setting("app.tenant".into()),
+                Some("acme".into())
+            )]),
+        );
+
+        assert_eq!(
+            PostgreSqlQueryHandler::handle_set_statement(&parse_set_statement(
+                "SET app.tenant TO DEFAULT"
+            )),
+            SetBehavior::SetSessionValues(vec![(
+                SessionParameter::Setting("app.tenant".into()),
+                None
+            )]),
+        );
+
+        assert_eq!(
+            PostgreSqlQueryHandler::handle_set_statement(&parse_set_statement(
+                "SET LOCAL app.tenant = 'acme'"
+            )),
+            SetBehavior::Unsupported,
+        );
+    }
+
+    #[test]
+    fn time_zone() {
+        assert_eq!(
+            PostgreSqlQueryHandler::handle_set_statement(&parse_set_statement(
+                "SET timezone = 'UTC'"
+            )),
+            SetBehavior::SetSessionValues(vec![(SessionParameter::TimeZone, Some("UTC".into()))]),
+        );
+
+        assert_eq!(
+            PostgreSqlQueryHandler::handle_set_statement(&parse_set_statement(
+                "SET timezone TO DEFAULT"
+            )),
+            SetBehavior::SetSessionValues(vec![(SessionParameter::TimeZone, None)]),
+        );
+
+        assert_eq!(
+            PostgreSqlQueryHandler::handle_set_statement(&parse_set_statement(
+                "SET LOCAL timezone = 'UTC'"
+            )),
+            SetBehavior::Proxy,
+        );
+
+        assert_eq!(
+            PostgreSqlQueryHandler::handle_set_statement(&parse_set_statement(
+                "SET timezone = 'America/New_York'"
+            )),
+            SetBehavior::Unsupported,
+        );
+    }
+
     mod search_path {
         use super::*;
 
@@ -15,10 +15,10 @@ use pgsql::{GenericResult, ResultStream, Row, SimpleQueryMessage};
 use postgres_types::Kind;
 use psql_srv::Column;
 use readyset_adapter::fallback_cache::FallbackCache;
-use readyset_adapter::upstream_database::UpstreamDestination;
+use readyset_adapter::upstream_database::{UpstreamDestination, WriteId};
 use readyset_adapter::{UpstreamConfig, UpstreamDatabase, UpstreamPrepare};
 use readyset_data::DfValue;
-use readyset_errors::{internal_err, invariant_eq, unsupported, ReadySetError, ReadySetResult};
+use readyset_errors::{internal_err, invariant_eq, ReadySetError, ReadySetResult};
 use tokio::process::Command;
 use tokio_postgres as pgsql;
 use tracing::{debug, info, info_span};
@@ -286,12 +286,32 @@ impl UpstreamDatabase for PostgreSqlUpstream {
 
     async fn handle_ryw_write<'a, S>(
         &'a mut self,
-        _query: S,
-    ) -> Result<(Self::QueryResult<'a>, String), Error>
+        query: S,
+    ) -> Result<(Self::QueryResult<'a>, WriteId), Error>
     where
         S: AsRef<str> + Send + Sync + 'a,
     {
-        unsupported!("Read-Your-Write not yet implemented for PostgreSQL")
+        // Outside of a transaction, the write is committed by the time the simple query returns
+        let res = self.client.simple_query(query.as_ref()).await?;
+        let position = self.write_position().await?;
+        Ok((QueryResult::SimpleQuery(res), position))
+    }
+
+    /// PostgreSQL has no way of returning the LSN of a particular transaction's commit, so this
+    /// returns the current WAL insert position instead, which is always at or after the end of
+    /// every transaction committed so far (even with `synchronous_commit` disabled, unlike the WAL
+    /// write position).
+    ///
+    /// This can be ahead of the end of the last transaction the replicator sees, so tickets are
+    /// satisfied by the replicator advancing the RYW timestamps of all tables to the WAL position
+    /// reported in the keepalive messages it requests from the server, rather than by commits.
+    async fn write_position(&mut self) -> Result<WriteId, Error> {
+        let lsn = self
+            .client
+            .query_one("SELECT (pg_current_wal_insert_lsn() - '0/0')::bigint", &[])
+            .await?
+            .get::<_, i64>(0);
+        Ok(WriteId::PostgresLsn(lsn as u64))
     }
 
     async fn execute<'a>(
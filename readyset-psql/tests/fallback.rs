@@ -2,9 +2,11 @@ use std::panic::AssertUnwindSafe;
 
 use chrono::NaiveDate;
 use postgres_types::private::BytesMut;
-use readyset_adapter::backend::{MigrationMode, UnsupportedSetMode};
+use readyset_adapter::backend::{MigrationMode, QueryDestination, UnsupportedSetMode};
 use readyset_adapter::BackendBuilder;
-use readyset_client_test_helpers::psql_helpers::{upstream_config, PostgreSQLAdapter};
+use readyset_client_test_helpers::psql_helpers::{
+    last_query_info, upstream_config, PostgreSQLAdapter,
+};
 use readyset_client_test_helpers::{sleep, Adapter, TestBuilder};
 use readyset_data::DfValue;
 use readyset_server::Handle;
@@ -1378,3 +1380,184 @@ async fn drop_cache_implicit_caching() {
 
     shutdown_tx.shutdown().await;
 }
+
+#[tokio::test(flavor = "multi_thread")]
+#[serial]
+async fn read_your_writes() {
+    let (config, _handle, shutdown_tx) =
+        TestBuilder::new(BackendBuilder::new().require_authentication(false))
+            .fallback(true)
+            .read_your_writes(true)
+            .build::<PostgreSQLAdapter>()
+            .await;
+    let client = connect(config).await;
+
+    client
+        .simple_query("CREATE TABLE t (id int PRIMARY KEY, x int)")
+        .await
+        .unwrap();
+    client
+        .simple_query("INSERT INTO t (id, x) VALUES (1, 0)")
+        .await
+        .unwrap();
+    sleep().await;
+
+    client
+        .simple_query("CREATE CACHE FROM SELECT x FROM t WHERE id = $1")
+        .await
+        .unwrap();
+
+    // Reads made immediately after a write from the same session must observe that write, whether
+    // they're served by ReadySet or fall back to the upstream database
+    for x in 1..5 {
+        client
+            .simple_query(&format!("UPDATE t SET x = {x} WHERE id = 1"))
+            .await
+            .unwrap();
+        let res: i32 = client
+            .query_one("SELECT x FROM t WHERE id = $1", &[&1])
+            .await
+            .unwrap()
+            .get(0);
+        assert_eq!(res, x);
+    }
+
+    let update = client
+        .prepare("UPDATE t SET x = $1 WHERE id = 1")
+        .await
+        .unwrap();
+    for x in 5..10 {
+        client.execute(&update, &[&x]).await.unwrap();
+        let res: i32 = client
+            .query_one("SELECT x FROM t WHERE id = $1", &[&1])
+            .await
+            .unwrap()
+            .get(0);
+        assert_eq!(res, x);
+    }
+
+    shutdown_tx.shutdown().await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+#[serial]
+async fn staleness_budget() {
+    let (config, _handle, shutdown_tx) = setup().await;
+    let client = connect(config).await;
+
+    client
+        .simple_query("CREATE TABLE t (id int PRIMARY KEY, x int)")
+        .await
+        .unwrap();
+    client
+        .simple_query("INSERT INTO t (id, x) VALUES (1, 1)")
+        .await
+        .unwrap();
+    sleep().await;
+
+    client
+        .simple_query("CREATE CACHE FROM SELECT x FROM t WHERE id = $1")
+        .await
+        .unwrap();
+
+    // No replication lag is ever within a budget of zero
+    client
+        .simple_query("SET readyset_max_staleness = 0")
+        .await
+        .unwrap();
+    client
+        .query("SELECT x FROM t WHERE id = $1", &[&1])
+        .await
+        .unwrap();
+    assert!(last_statement_matches("upstream", "ok", &client).await);
+    client
+        .simple_query("SELECT x FROM t WHERE id = 1")
+        .await
+        .unwrap();
+    assert!(last_statement_matches("upstream", "ok", &client).await);
+
+    // But an idle upstream database is never far ahead of us
+    client
+        .simple_query("SET readyset_max_staleness = 60")
+        .await
+        .unwrap();
+    eventually! {
+        client
+            .query("SELECT x FROM t WHERE id = $1", &[&1])
+            .await
+            .unwrap();
+        last_statement_matches("readyset", "ok", &client).await
+    }
+
+    client
+        .simple_query("SET readyset_max_staleness TO DEFAULT")
+        .await
+        .unwrap();
+    client
+        .query("SELECT x FROM t WHERE id = $1", &[&1])
+        .await
+        .unwrap();
+    assert!(last_statement_matches("readyset", "ok", &client).await);
+
+    shutdown_tx.shutdown().await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+#[serial]
+async fn read_your_writes_with_concurrent_unrelated_writes() {
+    let (config, _handle, shutdown_tx) =
+        TestBuilder::new(BackendBuilder::new().require_authentication(false))
+            .fallback(true)
+            .read_your_writes(true)
+            .build::<PostgreSQLAdapter>()
+            .await;
+    let client = connect(config.clone()).await;
+    let other_client = connect(config).await;
+
+    client
+        .simple_query("CREATE TABLE t (id int PRIMARY KEY, x int)")
+        .await
+        .unwrap();
+    client
+        .simple_query("CREATE TABLE unrelated (id int PRIMARY KEY)")
+        .await
+        .unwrap();
+    client
+        .simple_query("INSERT INTO t (id, x) VALUES (1, 0)")
+        .await
+        .unwrap();
+    sleep().await;
+
+    client
+        .simple_query("CREATE CACHE FROM SELECT x FROM t WHERE id = $1")
+        .await
+        .unwrap();
+
+    // The position of a prepared write is only read from upstream before the next read, by which
+    // point another session has committed a write to a table the read doesn't depend on. The read
+    // should still be served by ReadySet once the replicator has caught up, rather than waiting
+    // for a write to `t` that never comes.
+    let update = client
+        .prepare("UPDATE t SET x = $1 WHERE id = 1")
+        .await
+        .unwrap();
+    for x in 1..5 {
+        client.execute(&update, &[&x]).await.unwrap();
+        other_client
+            .simple_query(&format!("INSERT INTO unrelated (id) VALUES ({x})"))
+            .await
+            .unwrap();
+        let res: i32 = client
+            .query_one("SELECT x FROM t WHERE id = $1", &[&1])
+            .await
+            .unwrap()
+            .get(0);
+        assert_eq!(res, x);
+        assert_eq!(
+            last_query_info(&client).await.destination,
+            QueryDestination::Readyset
+        );
+    }
+
+    shutdown_tx.shutdown().await;
+}
@@ -1240,6 +1240,93 @@ async fn explain_graphviz() {
     shutdown_tx.shutdown().await;
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn explain_create_cache() {
+    let (opts, _handle, shutdown_tx) = setup().await;
+    let conn = connect(opts).await;
+    conn.simple_query("CREATE TABLE t (x int, y int)")
+        .await
+        .unwrap();
+    sleep().await;
+
+    let explain = |query: &'static str| {
+        let conn = &conn;
+        async move {
+            let res = conn.simple_query(query).await.unwrap();
+            match res.first().unwrap() {
+                SimpleQueryMessage::Row(row) => (0..row.len())
+                    .map(|i| {
+                        (
+                            row.columns()[i].name().to_owned(),
+                            row.get(i).unwrap().to_owned(),
+                        )
+                    })
+                    .collect::<Vec<_>>(),
+                _ => panic!("Expected row"),
+            }
+        }
+    };
+    let get = |row: &[(String, String)], name: &str| {
+        row.iter()
+            .find(|(col, _)| col == name)
+            .map(|(_, val)| val.clone())
+            .unwrap()
+    };
+
+    // The query is rewritten the same way as for CREATE CACHE before being explained
+    let row = explain("EXPLAIN CREATE CACHE FROM SELECT y FROM t WHERE x = $1").await;
+    assert_eq!(get(&row, "Supported"), "yes");
+    assert_eq!(get(&row, "Key_columns"), "x");
+    assert_eq!(get(&row, "Upstream_tables"), "t");
+
+    // Explaining a query doesn't cache it
+    let caches = conn.simple_query("SHOW CACHES").await.unwrap();
+    assert!(!caches
+        .iter()
+        .any(|m| matches!(m, SimpleQueryMessage::Row(_))));
+
+    let row = explain("EXPLAIN CREATE CACHE FROM SELECT 1").await;
+    assert_eq!(get(&row, "Supported"), "no");
+    assert!(!get(&row, "Unsupported_reason").is_empty());
+
+    shutdown_tx.shutdown().await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn staleness_budget_without_upstream() {
+    let (opts, _handle, shutdown_tx) = setup().await;
+    let conn = connect(opts).await;
+    conn.simple_query("CREATE TABLE t (id int PRIMARY KEY, x int)")
+        .await
+        .unwrap();
+    conn.simple_query("INSERT INTO t (id, x) VALUES (1, 1)")
+        .await
+        .unwrap();
+    sleep().await;
+
+    // Without an upstream database there's nowhere else to send reads, so they're served by
+    // ReadySet even though there's no replication lag to check against the budget
+    conn.simple_query("SET readyset_max_staleness = 0")
+        .await
+        .unwrap();
+    let x: i32 = conn
+        .query_one("SELECT x FROM t WHERE id = $1", &[&1])
+        .await
+        .unwrap()
+        .get(0);
+    assert_eq!(x, 1);
+    let res = conn
+        .simple_query("SELECT x FROM t WHERE id = 1")
+        .await
+        .unwrap();
+    assert!(matches!(
+        res.first(),
+        Some(SimpleQueryMessage::Row(row)) if row.get(0) == Some("1")
+    ));
+
+    shutdown_tx.shutdown().await;
+}
+
 #[ignore]
 #[tokio::test(flavor = "multi_thread")]
 async fn unordered_params_are_unsupported() {
@@ -83,6 +83,8 @@ impl Builder {
 
         builder.set_allow_topk(opts.enable_experimental_topk_support);
         builder.set_allow_paginate(opts.enable_experimental_paginate_support);
+        builder.set_max_parametrized_limit(Some(opts.max_parametrized_limit));
+        builder.set_max_recursion_depth(opts.max_recursion_depth);
         builder.set_allow_mixed_comparisons(opts.enable_experimental_mixed_comparisons);
         builder.set_worker_timeout(std::time::Duration::from_secs(
             opts.worker_request_timeout_seconds,
@@ -192,6 +194,16 @@ impl Builder {
         self.config.mir_config.allow_paginate = allow_paginate;
     }
 
+    /// Set the value of [`controller::sql::Config::max_parametrized_limit`]
+    pub fn set_max_parametrized_limit(&mut self, max_parametrized_limit: Option<usize>) {
+        self.config.mir_config.max_parametrized_limit = max_parametrized_limit;
+    }
+
+    /// Set the value of [`controller::sql::Config::max_recursion_depth`]
+    pub fn set_max_recursion_depth(&mut self, max_recursion_depth: Option<usize>) {
+        self.config.mir_config.max_recursion_depth = max_recursion_depth;
+    }
+
     /// Set the value of [`controller::sql::Config::allow_mixed_comparisons`]
     pub fn set_allow_mixed_comparisons(&mut self, allow_mixed_comparisons: bool) {
         self.config.mir_config.allow_mixed_comparisons = allow_mixed_comparisons;
@@ -220,6 +232,12 @@ impl Builder {
         self.config.replicator_config = config;
     }
 
+    /// Sets whether the replicator maintains the timestamps of tables for read-your-write
+    /// consistency
+    pub fn set_read_your_writes(&mut self, read_your_writes: bool) {
+        self.config.replicator_config.read_your_writes = read_your_writes;
+    }
+
     /// Set the server ID for replication
     pub fn set_replicator_server_id(&mut self, server_id: u32) {
         self.config.replicator_config.replication_server_id = Some(server_id);
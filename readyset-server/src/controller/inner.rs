@@ -27,6 +27,7 @@ use readyset_telemetry_reporter::TelemetrySender;
 use readyset_util::futures::abort_on_panic;
 use readyset_util::shutdown::ShutdownReceiver;
 use readyset_version::RELEASE_VERSION;
+use replicators::ReplicationLag;
 use reqwest::Url;
 use slotmap::{DefaultKey, Key, KeyData, SlotMap};
 use tokio::select;
@@ -70,6 +71,8 @@ pub struct Leader {
     replicator_statement_logging: bool,
     /// Configuration for the replicator
     pub(super) replicator_config: UpstreamConfig,
+    /// How far the data replicated from the upstream database lags behind it
+    replication_lag: ReplicationLag,
     /// A client to the current authority.
     pub(super) authority: Arc<Authority>,
 
@@ -128,6 +131,7 @@ impl Leader {
         let replicator_restart_timeout = self.replicator_config.replicator_restart_timeout;
         let config = self.replicator_config.clone();
         let replicator_statement_logging = self.replicator_statement_logging;
+        let replication_lag = self.replication_lag.clone();
 
         // The replication task ideally won't panic, but if it does and we arent replicating, that
         // will mean the data we return, will be more and more stale, and the transaction logs on
@@ -146,6 +150,7 @@ impl Leader {
                         noria,
                         config.clone(),
                         Some(ready_notification.clone()),
+                        replication_lag.clone(),
                         telemetry_sender.clone(),
                         server_startup,
                         replicator_statement_logging,
@@ -389,6 +394,9 @@ impl Leader {
                 }?;
                 return_serialized!(res);
             }
+            (&Method::POST, "/replication_lag") => {
+                return_serialized!(self.replication_lag.get());
+            }
             (&Method::POST, "/snapshotting_tables") => {
                 let res = {
                     let ds = self.dataflow_state_handle.read().await;
@@ -451,6 +459,18 @@ impl Leader {
                 state_copy.extend_recipe(body, true).await?;
                 return_serialized!(ExtendRecipeResult::Done);
             }
+            (&Method::POST, "/explain_create_cache") => {
+                let body: ExtendRecipeSpec = bincode::deserialize(&body)?;
+                if body.require_leader_ready {
+                    require_leader_ready()?;
+                }
+                let state_copy: DfState = {
+                    let reader = self.dataflow_state_handle.read().await;
+                    reader.clone()
+                };
+                let explanation = state_copy.explain_create_cache(body).await?;
+                return_serialized!(explanation);
+            }
             (&Method::GET | &Method::POST, "/supports_pagination") => {
                 let ds = self.dataflow_state_handle.read().await;
                 let supports =
@@ -723,6 +743,7 @@ impl Leader {
 
             replicator_statement_logging,
             replicator_config,
+            replication_lag: Default::default(),
             authority,
             worker_request_timeout,
             running_migrations: Default::default(),
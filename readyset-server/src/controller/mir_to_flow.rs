@@ -12,9 +12,12 @@ use std::iter;
 
 use common::DfValue;
 use dataflow::node::Column as DfColumn;
+use dataflow::ops::grouped::collect::Collect;
 use dataflow::ops::grouped::concat::GroupConcat;
 use dataflow::ops::join::{Join, JoinType};
 use dataflow::ops::project::Project;
+use dataflow::ops::unnest::UnnestKind;
+use dataflow::ops::window::WindowFunction;
 use dataflow::ops::Side;
 use dataflow::{node, ops, Expr as DfExpr, PostLookupAggregates, ReaderProcessing};
 use itertools::Itertools;
@@ -30,7 +33,7 @@ use readyset_client::internal::{Index, IndexType};
 use readyset_client::ViewPlaceholder;
 use readyset_data::{Collation, DfType, Dialect};
 use readyset_errors::{
-    internal, internal_err, invariant, invariant_eq, ReadySetError, ReadySetResult,
+    internal, internal_err, invariant, invariant_eq, unsupported, ReadySetError, ReadySetResult,
 };
 
 use crate::controller::Migration;
@@ -87,12 +90,22 @@ pub(super) fn mir_node_to_flow_parts(
             let flow_node = match graph[mir_node].inner {
                 MirNodeInner::Aggregation {
                     ref on,
+                    ref key,
                     ref group_by,
+                    ref order,
                     ref kind,
                     ..
                 } => {
                     invariant_eq!(ancestors.len(), 1);
                     let parent = ancestors[0];
+                    let kind = match kind {
+                        Aggregation::Collect(kind) => GroupedNodeType::Collect {
+                            kind: kind.clone(),
+                            key: key.clone(),
+                            order: order.clone(),
+                        },
+                        _ => GroupedNodeType::Aggregation(kind.clone()),
+                    };
                     Some(make_grouped_node(
                         graph,
                         name,
@@ -100,7 +113,7 @@ pub(super) fn mir_node_to_flow_parts(
                         &graph.columns(mir_node),
                         on,
                         group_by,
-                        GroupedNodeType::Aggregation(kind.clone()),
+                        kind,
                         mig,
                     )?)
                 }
@@ -177,6 +190,7 @@ pub(super) fn mir_node_to_flow_parts(
                         on,
                         project,
                         JoinType::Inner,
+                        None,
                         custom_types,
                         mig,
                     )?)
@@ -194,7 +208,7 @@ pub(super) fn mir_node_to_flow_parts(
                         mig,
                     )?)
                 }
-                MirNodeInner::DependentJoin { .. } => {
+                MirNodeInner::DependentJoin { .. } | MirNodeInner::DependentLeftJoin { .. } => {
                     // See the docstring for MirNodeInner::DependentJoin
                     internal!("Encountered dependent join when lowering to dataflow")
                 }
@@ -216,6 +230,7 @@ pub(super) fn mir_node_to_flow_parts(
                     ref returned_cols,
                     ref default_row,
                     ref aggregates,
+                    ref null_key_columns,
                     ..
                 } => {
                     if !lowered_to_df {
@@ -224,11 +239,13 @@ pub(super) fn mir_node_to_flow_parts(
                         let reader_processing = make_reader_processing(
                             graph,
                             &parent,
+                            keys,
                             order_by,
                             limit,
                             returned_cols,
                             default_row.clone(),
                             aggregates,
+                            null_key_columns,
                         )?;
                         materialize_leaf_node(
                             graph,
@@ -245,7 +262,7 @@ pub(super) fn mir_node_to_flow_parts(
                 MirNodeInner::LeftJoin {
                     ref on,
                     ref project,
-                    ..
+                    ref residual,
                 } => {
                     invariant_eq!(ancestors.len(), 2);
                     let left = ancestors[0];
@@ -259,6 +276,29 @@ pub(super) fn mir_node_to_flow_parts(
                         on,
                         project,
                         JoinType::Left,
+                        residual.as_ref(),
+                        custom_types,
+                        mig,
+                    )?)
+                }
+                MirNodeInner::FullJoin {
+                    ref on,
+                    ref project,
+                    ..
+                } => {
+                    invariant_eq!(ancestors.len(), 2);
+                    let left = ancestors[0];
+                    let right = ancestors[1];
+                    Some(make_join_node(
+                        graph,
+                        name,
+                        left,
+                        right,
+                        &graph.columns(mir_node),
+                        on,
+                        project,
+                        JoinType::Full,
+                        None,
                         custom_types,
                         mig,
                     )?)
@@ -293,6 +333,19 @@ pub(super) fn mir_node_to_flow_parts(
                         mig,
                     )?)
                 }
+                MirNodeInner::SetOperation { ref emit, operator } => {
+                    invariant_eq!(ancestors.len(), 2);
+                    invariant_eq!(emit.len(), 2);
+                    Some(make_set_operation_node(
+                        graph,
+                        name,
+                        &graph.columns(mir_node),
+                        emit,
+                        (ancestors[0], ancestors[1]),
+                        operator,
+                        mig,
+                    )?)
+                }
                 MirNodeInner::Distinct { ref group_by } => {
                     invariant_eq!(ancestors.len(), 1);
                     let parent = ancestors[0];
@@ -330,6 +383,40 @@ pub(super) fn mir_node_to_flow_parts(
                         mig,
                     )?)
                 }
+                MirNodeInner::Window {
+                    ref on,
+                    ref group_by,
+                    ref order,
+                    ref kind,
+                    ..
+                } => {
+                    invariant_eq!(ancestors.len(), 1);
+                    let parent = ancestors[0];
+                    Some(make_window_node(
+                        graph,
+                        name,
+                        parent,
+                        &graph.columns(mir_node),
+                        on.as_ref(),
+                        group_by,
+                        order,
+                        kind.clone(),
+                        mig,
+                    )?)
+                }
+                MirNodeInner::Unnest { ref on, kind, .. } => {
+                    invariant_eq!(ancestors.len(), 1);
+                    let parent = ancestors[0];
+                    Some(make_unnest_node(
+                        graph,
+                        name,
+                        parent,
+                        &graph.columns(mir_node),
+                        on,
+                        kind,
+                        mig,
+                    )?)
+                }
                 MirNodeInner::AliasTable { .. } => None,
             };
 
@@ -478,6 +565,61 @@ fn make_union_node(
     Ok(DfNodeIndex::new(node))
 }
 
+fn make_set_operation_node(
+    graph: &MirGraph,
+    name: Relation,
+    columns: &[Column],
+    emit: &[Vec<Column>],
+    (left, right): (MirNodeIndex, MirNodeIndex),
+    operator: ops::set_operation::SetOperator,
+    mig: &mut Migration<'_>,
+) -> ReadySetResult<DfNodeIndex> {
+    let resolve = |n: MirNodeIndex| {
+        graph
+            .resolve_dataflow_node(n)
+            .ok_or(ReadySetError::MirNodeMustHaveDfNodeAssigned {
+                mir_node_index: n.index(),
+            })
+    };
+    let left_na = resolve(left)?;
+    let right_na = resolve(right)?;
+
+    let emit_column_ids = |n: MirNodeIndex, emit: &[Column]| {
+        emit.iter()
+            .map(|c| graph.column_id_for_column(n, c))
+            .collect::<ReadySetResult<Vec<_>>>()
+    };
+    let emit_left = emit_column_ids(left, &emit[0])?;
+    let emit_right = emit_column_ids(right, &emit[1])?;
+
+    // Like unions, set operations take the columns of their left parent
+    let parent_cols = mig.dataflow_state.ingredients[left_na.address()].columns();
+    let mut cols = emit_left
+        .iter()
+        .map(|i| {
+            parent_cols
+                .get(*i)
+                .cloned()
+                .ok_or_else(|| internal_err!("Invalid index"))
+        })
+        .collect::<ReadySetResult<Vec<_>>>()?;
+    set_names(&column_names(columns), &mut cols)?;
+
+    let node = mig.add_ingredient(
+        name,
+        cols,
+        ops::set_operation::SetOperation::new(
+            left_na.address(),
+            right_na.address(),
+            emit_left,
+            emit_right,
+            operator,
+        )?,
+    );
+
+    Ok(DfNodeIndex::new(node))
+}
+
 fn make_filter_node(
     graph: &MirGraph,
     name: Relation,
@@ -579,12 +721,35 @@ fn make_grouped_node(
                 over_col_indx,
                 group_col_indx.as_slice(),
                 over_col_ty,
+                mig.dialect,
             )?;
             let agg_col = make_agg_col(grouped.output_col_type().or_ref(over_col_ty).clone());
             cols.push(agg_col);
             set_names(&column_names(columns), &mut cols)?;
             mig.add_ingredient(name, cols, grouped)
         }
+        GroupedNodeType::Collect { kind, key, order } => {
+            let key_col_indx = key
+                .map(|key| graph.column_id_for_column(parent, &key))
+                .transpose()?;
+            let order = order
+                .into_iter()
+                .map(|(c, ot)| Ok((graph.column_id_for_column(parent, &c)?, ot)))
+                .collect::<ReadySetResult<Vec<_>>>()?;
+            let grouped = Collect::new(
+                parent_na.address(),
+                kind,
+                over_col_indx,
+                key_col_indx,
+                order,
+                group_col_indx,
+                over_col_ty.clone(),
+            )?;
+            let agg_col = make_agg_col(grouped.output_col_type());
+            cols.push(agg_col);
+            set_names(&column_names(columns), &mut cols)?;
+            mig.add_ingredient(name, cols, grouped)
+        }
         GroupedNodeType::Extremum(extr) => {
             let grouped = extr.over(
                 parent_na.address(),
@@ -639,6 +804,7 @@ fn make_join_node(
     on: &[(Column, Column)],
     proj_cols: &[Column],
     kind: JoinType,
+    residual: Option<&Expr>,
     custom_types: &HashMap<Relation, DfType>,
     mig: &mut Migration<'_>,
 ) -> ReadySetResult<DfNodeIndex> {
@@ -739,7 +905,27 @@ fn make_join_node(
         ));
     }
 
-    let j = Join::new(left_na.address(), right_na.address(), kind, on_idxs, emit);
+    let mut j = Join::new(left_na.address(), right_na.address(), kind, on_idxs, emit);
+    if let Some(residual) = residual {
+        // The residual is evaluated over the left parent's row followed by the right parent's row,
+        // so columns in the right are offset by the number of columns in the left (which might
+        // now include a cross join bogokey)
+        let left_cols = mig.dataflow_state.ingredients[left_na.address()].columns();
+        let right_cols = mig.dataflow_state.ingredients[right_na.address()].columns();
+        j = j.with_residual(DfExpr::lower(
+            residual.clone(),
+            mig.dialect,
+            JoinLowerContext {
+                graph,
+                left,
+                right,
+                left_cols,
+                right_cols,
+                right_offset: left_cols.len(),
+                custom_types,
+            },
+        )?);
+    }
     let n = mig.add_ingredient(name, cols, j);
 
     Ok(DfNodeIndex::new(n))
@@ -868,6 +1054,43 @@ impl<'a> dataflow::LowerContext for LowerContext<'a> {
     }
 }
 
+/// Context for lowering the residual predicate of a join, which is evaluated over the columns of
+/// the left parent followed by the columns of the right parent
+#[derive(Clone)]
+struct JoinLowerContext<'a> {
+    graph: &'a MirGraph,
+    left: MirNodeIndex,
+    right: MirNodeIndex,
+    left_cols: &'a [DfColumn],
+    right_cols: &'a [DfColumn],
+    right_offset: usize,
+    custom_types: &'a HashMap<Relation, DfType>,
+}
+
+impl<'a> dataflow::LowerContext for JoinLowerContext<'a> {
+    fn resolve_column(&self, col: nom_sql::Column) -> ReadySetResult<(usize, DfType)> {
+        let col = Column::new(col.table.clone(), &col.name);
+        let (index, parent_cols, offset) = match self.graph.column_id_for_column(self.left, &col) {
+            Ok(index) => (index, self.left_cols, 0),
+            Err(_) => (
+                self.graph.column_id_for_column(self.right, &col)?,
+                self.right_cols,
+                self.right_offset,
+            ),
+        };
+        let ty = parent_cols
+            .get(index)
+            .ok_or_else(|| internal_err!("Index exceeds length of parent cols, idx={}", index))?
+            .ty()
+            .clone();
+        Ok((offset + index, ty))
+    }
+
+    fn resolve_type(&self, ty: Relation) -> Option<DfType> {
+        self.custom_types.get(&ty).cloned()
+    }
+}
+
 /// Lower the given nom_sql AST expression to a `DfExpr`, resolving columns by looking their
 /// index up in the given parent node.
 fn lower_expression(
@@ -1021,7 +1244,13 @@ fn make_distinct_node(
         // remaining occurrences of the set.
         //
         // We use 0 as a placeholder value
-        Aggregation::Count.over(parent_na.address(), 0, &group_by_indx, &DfType::Unknown)?,
+        Aggregation::Count.over(
+            parent_na.address(),
+            0,
+            &group_by_indx,
+            &DfType::Unknown,
+            mig.dialect,
+        )?,
     );
     Ok(DfNodeIndex::new(na))
 }
@@ -1105,14 +1334,143 @@ fn make_paginate_or_topk_node(
     Ok(DfNodeIndex::new(na))
 }
 
+fn make_window_node(
+    graph: &MirGraph,
+    name: Relation,
+    parent: MirNodeIndex,
+    columns: &[Column],
+    on: Option<&Column>,
+    group_by: &[Column],
+    order: &[(Column, OrderType)],
+    function: WindowFunction,
+    mig: &mut Migration<'_>,
+) -> ReadySetResult<DfNodeIndex> {
+    let parent_na = graph.resolve_dataflow_node(parent).ok_or_else(|| {
+        ReadySetError::MirNodeMustHaveDfNodeAssigned {
+            mir_node_index: parent.index(),
+        }
+    })?;
+    let mut parent_cols = mig.dataflow_state.ingredients[parent_na.address()]
+        .columns()
+        .to_vec();
+
+    invariant!(
+        !group_by.is_empty(),
+        "need bogokey for Window without partition columns"
+    );
+
+    let over_col_indx = on
+        .map(|c| graph.column_id_for_column(parent, c))
+        .transpose()?;
+    let over_col_ty = over_col_indx
+        .map(|i| {
+            parent_cols
+                .get(i)
+                .map(|c| c.ty())
+                .ok_or_else(|| internal_err!("Invalid index"))
+        })
+        .transpose()?;
+    let out_ty = function.output_type(over_col_ty);
+
+    let group_by_indx = group_by
+        .iter()
+        .map(|c| graph.column_id_for_column(parent, c))
+        .collect::<ReadySetResult<Vec<_>>>()?;
+
+    // Unlike Paginate and TopK, the Window operator orders its rows the same way SQL does, so we
+    // don't need to reverse the order types here
+    let order_indx = order
+        .iter()
+        .map(|(c, order_type)| {
+            graph
+                .column_id_for_column(parent, c)
+                .map(|id| (id, *order_type))
+        })
+        .collect::<ReadySetResult<Vec<_>>>()?;
+
+    // set names using MIR columns to ensure aliases are used
+    let column_names = column_names(columns);
+    #[allow(clippy::unwrap_used)] // column_names must be populated
+    parent_cols.push(DfColumn::new(
+        column_names.last().unwrap().into(),
+        out_ty.clone(),
+        Some(name.clone()),
+    ));
+    set_names(&column_names, &mut parent_cols)?;
+
+    let na = mig.add_ingredient(
+        name,
+        parent_cols,
+        ops::window::Window::new(
+            parent_na.address(),
+            function,
+            over_col_indx,
+            group_by_indx,
+            order_indx,
+            out_ty,
+        )?,
+    );
+    Ok(DfNodeIndex::new(na))
+}
+
+fn make_unnest_node(
+    graph: &MirGraph,
+    name: Relation,
+    parent: MirNodeIndex,
+    columns: &[Column],
+    on: &Column,
+    kind: UnnestKind,
+    mig: &mut Migration<'_>,
+) -> ReadySetResult<DfNodeIndex> {
+    let parent_na = graph.resolve_dataflow_node(parent).ok_or_else(|| {
+        ReadySetError::MirNodeMustHaveDfNodeAssigned {
+            mir_node_index: parent.index(),
+        }
+    })?;
+    let mut parent_cols = mig.dataflow_state.ingredients[parent_na.address()]
+        .columns()
+        .to_vec();
+    let num_parent_cols = parent_cols.len();
+
+    let on_indx = graph.column_id_for_column(parent, on)?;
+    let on_ty = parent_cols
+        .get(on_indx)
+        .ok_or_else(|| internal_err!("Invalid index"))?
+        .ty();
+    if !kind.accepts_input_type(on_ty) {
+        unsupported!("Cannot expand elements of a value of type {on_ty}");
+    }
+    let out_ty = kind.output_type(on_ty);
+
+    // set names using MIR columns to ensure aliases are used
+    let column_names = column_names(columns);
+    #[allow(clippy::unwrap_used)] // column_names must be populated
+    parent_cols.push(DfColumn::new(
+        column_names.last().unwrap().into(),
+        out_ty,
+        Some(name.clone()),
+    ));
+    set_names(&column_names, &mut parent_cols)?;
+
+    let na = mig.add_ingredient(
+        name,
+        parent_cols,
+        ops::unnest::Unnest::new(parent_na.address(), on_indx, kind, num_parent_cols),
+    );
+    Ok(DfNodeIndex::new(na))
+}
+
+#[allow(clippy::too_many_arguments)]
 fn make_reader_processing(
     graph: &MirGraph,
     parent: &MirNodeIndex,
+    keys: &[(Column, ViewPlaceholder)],
     order_by: &Option<Vec<(Column, OrderType)>>,
     limit: Option<usize>,
     returned_cols: &Option<Vec<Column>>,
     default_row: Option<Vec<DfValue>>,
     aggregates: &Option<PostLookupAggregates<Column>>,
+    null_key_columns: &[Vec<Column>],
 ) -> ReadySetResult<ReaderProcessing> {
     let order_by = if let Some(order) = order_by.as_ref() {
         Some(
@@ -1144,7 +1502,24 @@ fn make_reader_processing(
         .map(|aggs| aggs.map_columns(|col| graph.column_id_for_column(*parent, &col)))
         .transpose()?;
 
-    ReaderProcessing::new(order_by, limit, returned_cols, default_row, aggregates)
+    // Null key columns are resolved to their index in the key, not in the parent
+    let null_key_columns = null_key_columns
+        .iter()
+        .map(|cols| {
+            cols.iter()
+                .map(|col| {
+                    keys.iter()
+                        .position(|(key_col, _)| key_col == col)
+                        .ok_or_else(|| internal_err!("Null key column {col} is not a key column"))
+                })
+                .collect::<ReadySetResult<Vec<_>>>()
+        })
+        .collect::<ReadySetResult<Vec<_>>>()?;
+
+    Ok(
+        ReaderProcessing::new(order_by, limit, returned_cols, default_row, aggregates)?
+            .with_null_key_columns(null_key_columns),
+    )
 }
 
 fn materialize_leaf_node(
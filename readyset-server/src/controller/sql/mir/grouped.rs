@@ -1,13 +1,14 @@
 use std::collections::{HashMap, HashSet};
 
 use dataflow::{PostLookupAggregate, PostLookupAggregateFunction, PostLookupAggregates};
+use itertools::Itertools;
 use mir::node::node_inner::MirNodeInner;
 use mir::node::ProjectExpr;
 use mir::{Column, NodeIndex};
 use nom_sql::analysis::ReferredColumns;
 use nom_sql::FunctionExpr::*;
-use nom_sql::{self, Expr, FieldDefinitionExpr, Relation, SqlIdentifier};
-use readyset_errors::{unsupported, ReadySetError, ReadySetResult};
+use nom_sql::{self, Expr, FieldDefinitionExpr, FunctionExpr, Relation, SqlIdentifier};
+use readyset_errors::{internal_err, unsupported, ReadySetError, ReadySetResult};
 use readyset_sql_passes::is_aggregate;
 
 use crate::controller::sql::mir::join::make_joins_for_aggregates;
@@ -50,8 +51,9 @@ pub(super) fn make_predicates_above_grouped<'a>(
 }
 
 /// Normally, projection happens after grouped nodes - however, if aggregates used in grouped
-/// expressions reference expressions rather than columns directly, we need to project them out
-/// before the grouped nodes.
+/// expressions reference expressions rather than columns directly, if the query groups by
+/// expressions rather than columns, or if the query compares parameters against expressions rather
+/// than columns, we need to project them out before the grouped nodes.
 ///
 /// This does that projection, and returns a mapping from the expressions themselves to the names of
 /// the columns they have been projected to
@@ -62,20 +64,41 @@ pub(super) fn make_expressions_above_grouped(
     qg: &QueryGraph,
     prev_node: &mut NodeIndex,
 ) -> HashMap<Expr, SqlIdentifier> {
-    let exprs: Vec<_> = qg
-        .aggregates
-        .keys()
-        .filter(|&f| is_aggregate(f))
-        .flat_map(|f| f.arguments())
-        // We don't need to do any work for bare column expressions
-        .filter(|arg| !matches!(arg, Expr::Column(_)))
-        .map(|expr| {
-            (
-                // FIXME(ENG-2502): Use correct dialect.
-                SqlIdentifier::from(expr.display(nom_sql::Dialect::MySQL).to_string()),
-                expr.clone(),
-            )
-        })
+    let mut group_by_exprs = qg
+        .group_by_exprs
+        .iter()
+        .map(|(expr, name)| (name.clone(), expr.clone()))
+        .collect::<Vec<_>>();
+    group_by_exprs.sort_by(|(n1, _), (n2, _)| n1.cmp(n2));
+
+    let mut parameter_exprs = qg
+        .global_parameters
+        .iter()
+        .filter_map(|param| Some((param.col.name.clone(), param.expr.clone()?)))
+        .collect::<Vec<_>>();
+    parameter_exprs.sort_by(|(n1, _), (n2, _)| n1.cmp(n2));
+
+    let exprs: Vec<_> = group_by_exprs
+        .into_iter()
+        .chain(parameter_exprs)
+        .chain(
+            qg.aggregates
+                .keys()
+                .filter(|&f| is_aggregate(f))
+                .flat_map(|f| f.arguments())
+                // We don't need to do any work for bare column expressions
+                .filter(|arg| !matches!(arg, Expr::Column(_)))
+                .map(|expr| {
+                    (
+                        // FIXME(ENG-2502): Use correct dialect.
+                        SqlIdentifier::from(expr.display(nom_sql::Dialect::MySQL).to_string()),
+                        expr.clone(),
+                    )
+                }),
+        )
+        // An expression can be both grouped on and aggregated over, but we only want to project it
+        // once
+        .unique_by(|(alias, _)| alias.clone())
         .collect();
 
     if !exprs.is_empty() {
@@ -122,16 +145,12 @@ pub(super) fn make_grouped(
 
         // get any parameter columns that aren't also in the group-by
         // column set
-        let param_cols: Vec<_> = qg.relations.values().fold(vec![], |acc, rel| {
-            acc.into_iter()
-                .chain(
-                    rel.parameters
-                        .iter()
-                        .map(|param| &param.col)
-                        .filter(|c| !qg.group_by.contains(c)),
-                )
-                .collect()
-        });
+        let param_cols: Vec<_> = qg
+            .parameters()
+            .into_iter()
+            .map(|param| &param.col)
+            .filter(|c| !qg.group_by.contains(c))
+            .collect();
         // combine and dedup
         #[allow(clippy::needless_collect)] // necessary to avoid cloning param_cols
         let dedup_gb_cols: Vec<_> = qg
@@ -265,23 +284,76 @@ pub(super) fn post_lookup_aggregates(
         return Ok(None);
     }
 
+    // Look up the column for one of the helper aggregates added to the query graph by
+    // `reaggregation_helpers`
+    let helper_column = |function: FunctionExpr| -> ReadySetResult<Column> {
+        let alias = query_graph.aggregates.get(&function).ok_or_else(|| {
+            internal_err!(
+                "Missing helper aggregate {} for post-lookup aggregation",
+                function.display(nom_sql::Dialect::MySQL)
+            )
+        })?;
+        Ok(Column::named(alias.clone()).aliased_as_table(query_name.clone()))
+    };
+
     let mut aggregates = vec![];
     for (function, alias) in &query_graph.aggregates {
         aggregates.push(PostLookupAggregate {
             column: Column::named(alias.clone()).aliased_as_table(query_name.clone()),
             function: match function {
-                Avg { .. } => {
-                    unsupported!("Average is not supported as a post-lookup aggregate")
-                }
+                Avg {
+                    expr,
+                    distinct: false,
+                } => PostLookupAggregateFunction::Avg {
+                    sum: helper_column(Sum {
+                        expr: expr.clone(),
+                        distinct: false,
+                    })?,
+                    count: helper_column(Count {
+                        expr: expr.clone(),
+                        distinct: false,
+                    })?,
+                },
+                Count {
+                    expr,
+                    distinct: true,
+                } => PostLookupAggregateFunction::CountDistinct {
+                    values: helper_column(ArrayAgg {
+                        expr: expr.clone(),
+                        order_by: None,
+                    })?,
+                },
                 // Count and sum are handled the same way, as re-aggregating counts is
                 // done by just summing the numbers together
-                Count { .. } | CountStar | Sum { .. } => PostLookupAggregateFunction::Sum,
+                Count { .. }
+                | CountStar
+                | Sum {
+                    distinct: false, ..
+                } => PostLookupAggregateFunction::Sum,
+                ArrayAgg { order_by: None, .. } => PostLookupAggregateFunction::ArrayConcat,
                 Max(_) => PostLookupAggregateFunction::Max,
                 Min(_) => PostLookupAggregateFunction::Min,
                 GroupConcat { separator, .. } => PostLookupAggregateFunction::GroupConcat {
                     separator: separator.clone().unwrap_or_else(|| ",".to_owned()),
                 },
-                Call { .. } | Substring { .. } => continue,
+                // A group is true for all rows iff it's true for all rows in each subgroup (and
+                // likewise for any), so these can be re-aggregated with min and max
+                BoolAnd(_) => PostLookupAggregateFunction::Min,
+                BoolOr(_) => PostLookupAggregateFunction::Max,
+                Avg { distinct: true, .. }
+                | Sum { distinct: true, .. }
+                | Stddev { .. }
+                | Variance { .. }
+                | BitAnd(_)
+                | BitOr(_)
+                | ArrayAgg { .. }
+                | StringAgg { .. }
+                | JsonAgg { .. }
+                | JsonObjectAgg { .. } => unsupported!(
+                    "{} is not supported as a post-lookup aggregate",
+                    function.display(nom_sql::Dialect::MySQL)
+                ),
+                Call { .. } | Substring { .. } | Over { .. } => continue,
             },
         });
     }
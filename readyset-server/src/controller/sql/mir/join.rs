@@ -1,7 +1,7 @@
 use std::collections::{HashMap, HashSet};
 
 use mir::NodeIndex;
-use nom_sql::Relation;
+use nom_sql::{BinaryOperator, Expr, Relation};
 use readyset_errors::{internal, internal_err, invariant, unsupported, ReadySetResult};
 
 use super::JoinKind;
@@ -45,13 +45,28 @@ pub(super) fn make_joins(
     let mut join_chains = Vec::new();
 
     for jref in qg.join_order.iter() {
-        let (mut join_kind, jps) = match &qg.edges[&(jref.src.clone(), jref.dst.clone())] {
-            QueryGraphEdge::Join { on } => (JoinKind::Inner, on),
+        let (mut join_kind, jps, residual) = match &qg.edges[&(jref.src.clone(), jref.dst.clone())]
+        {
+            QueryGraphEdge::Join { on } => (JoinKind::Inner, on, None),
             QueryGraphEdge::LeftJoin { on, extra_preds } => {
+                // Any predicates in the ON clause which aren't equalities between columns in
+                // both sides have to be evaluated by the join itself, so that rows which
+                // don't satisfy them are null-extended rather than filtered out
+                let residual = extra_preds
+                    .iter()
+                    .cloned()
+                    .reduce(|lhs, rhs| Expr::BinaryOp {
+                        lhs: Box::new(lhs),
+                        op: BinaryOperator::And,
+                        rhs: Box::new(rhs),
+                    });
+                (JoinKind::Left, on, residual)
+            }
+            QueryGraphEdge::FullJoin { on, extra_preds } => {
                 if !extra_preds.is_empty() {
-                    unsupported!("Non-equal predicates not (yet) supported in left joins");
+                    unsupported!("Non-equal predicates not (yet) supported in full joins");
                 }
-                (JoinKind::Left, on)
+                (JoinKind::Full, on, None)
             }
         };
 
@@ -61,14 +76,17 @@ pub(super) fn make_joins(
         // TODO(fran): Use NodeIndex instead of name.
         if correlated_nodes.contains(&right_chain.last_node) {
             match join_kind {
-                JoinKind::Left => internal!(
-                    "Dependent left join not yet supported (when joining to {})",
+                JoinKind::Left => {
+                    join_kind = JoinKind::DependentLeft;
+                }
+                JoinKind::Full => internal!(
+                    "Dependent full join not yet supported (when joining to {})",
                     jref.dst.display_unquoted()
                 ),
                 JoinKind::Inner => {
                     join_kind = JoinKind::Dependent;
                 }
-                JoinKind::Dependent => {}
+                JoinKind::Dependent | JoinKind::DependentLeft => {}
             }
         }
 
@@ -79,6 +97,7 @@ pub(super) fn make_joins(
             left_chain.last_node,
             right_chain.last_node,
             join_kind,
+            residual,
         )?;
 
         // merge node chains
@@ -124,6 +143,7 @@ pub(super) fn make_cross_joins(
             n1,
             n2,
             join_kind,
+            None,
         )?;
         join_nodes.push(node);
         Ok(node)
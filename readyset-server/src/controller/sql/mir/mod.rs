@@ -7,7 +7,11 @@ use ::serde::{Deserialize, Serialize};
 use catalog_tables::is_catalog_table;
 use common::IndexType;
 use dataflow::ops::grouped::aggregate::Aggregation;
+use dataflow::ops::grouped::collect::Collection;
+use dataflow::ops::set_operation::SetOperator;
 use dataflow::ops::union;
+use dataflow::ops::unnest::UnnestKind;
+use dataflow::ops::window::WindowFunction;
 use lazy_static::lazy_static;
 use mir::graph::MirGraph;
 use mir::node::node_inner::MirNodeInner;
@@ -17,18 +21,19 @@ use mir::DfNodeIndex;
 pub use mir::{Column, NodeIndex};
 use nom_sql::analysis::ReferredColumns;
 use nom_sql::{
-    BinaryOperator, ColumnSpecification, CompoundSelectOperator, CreateTableBody, Expr,
-    FieldDefinitionExpr, FieldReference, FunctionExpr, LimitClause, Literal, OrderClause,
-    OrderType, Relation, SqlIdentifier, TableKey,
+    BinaryOperator, CaseWhenBranch, ColumnSpecification, CompoundSelectOperator, CreateTableBody,
+    Expr, FieldDefinitionExpr, FieldReference, FunctionExpr, InValue, LimitClause, Literal,
+    OrderClause, OrderType, Relation, SelectStatement, SqlIdentifier, TableKey, UnaryOperator,
 };
 use petgraph::visit::Reversed;
 use petgraph::Direction;
 use readyset_client::ViewPlaceholder;
+use readyset_data::Dialect;
 use readyset_errors::{
-    internal, internal_err, invalid_err, invariant, invariant_eq, unsupported, ReadySetError,
-    ReadySetResult,
+    internal, internal_err, invalid, invalid_err, invariant, invariant_eq, unsupported,
+    ReadySetError, ReadySetResult,
 };
-use readyset_sql_passes::is_correlated;
+use readyset_sql_passes::{is_aggregate, is_correlated};
 use readyset_util::redacted::Sensitive;
 use tracing::{debug, trace};
 
@@ -38,7 +43,10 @@ use crate::controller::sql::mir::grouped::{
     post_lookup_aggregates,
 };
 use crate::controller::sql::mir::join::{make_cross_joins, make_joins};
-use crate::controller::sql::query_graph::{to_query_graph, OutputColumn, Pagination, QueryGraph};
+use crate::controller::sql::query_graph::{
+    to_query_graph, OutputColumn, Pagination, PaginationLimit, QueryGraph,
+    SetReturningFunctionCall, WindowFunctionCall,
+};
 use crate::controller::sql::query_signature::Signature;
 
 mod grouped;
@@ -101,8 +109,12 @@ pub enum JoinKind {
     Inner,
     /// Left joins - see [`MirNodeInner::LeftJoin`]
     Left,
+    /// Full outer joins - see [`MirNodeInner::FullJoin`]
+    Full,
     /// Dependent joins - see [`MirNodeInner::DependentJoin`]
     Dependent,
+    /// Dependent left joins - see [`MirNodeInner::DependentLeftJoin`]
+    DependentLeft,
 }
 
 /// Specification for how to treat the leaf node of a query when converting it to MIR
@@ -151,6 +163,22 @@ pub(crate) struct Config {
     /// ['Paginate']: MirNodeInner::Paginate
     pub(crate) allow_paginate: bool,
 
+    /// The maximum value supported for a parametrized `LIMIT`. Queries with a parametrized `LIMIT`
+    /// emit a [`TopK`][] node that keeps this many rows, and the limit provided with each lookup
+    /// is applied in the reader - lookups whose limit and offset add up to more than this value
+    /// are rejected, causing the adapter to send them to fallback. If set to `None`, parametrized
+    /// limits are unsupported. Defaults to `None`.
+    ///
+    /// [`TopK`]: MirNodeInner::TopK
+    pub(crate) max_parametrized_limit: Option<usize>,
+
+    /// The maximum number of times the recursive term of a recursive common table expression
+    /// (`WITH RECURSIVE`) can be applied. Since the dataflow graph can't contain cycles, recursive
+    /// CTEs are unrolled into one copy of the recursive term per level of recursion, so recursive
+    /// CTEs are only supported if their recursion can be shown to stop within this many levels. If
+    /// set to `None`, recursive CTEs are unsupported. Defaults to `None`.
+    pub(crate) max_recursion_depth: Option<usize>,
+
     /// Enable support for mixing equality and range comparisons in a query. Support for mixed
     /// comparisons is currently unfinished, so these queries may return incorrect results.
     pub(crate) allow_mixed_comparisons: bool,
@@ -229,8 +257,7 @@ impl SqlToMirConverter {
     pub(super) fn compound_query_to_mir(
         &mut self,
         query_name: &Relation,
-        subquery_leaves: Vec<NodeIndex>,
-        op: CompoundSelectOperator,
+        subquery_leaves: Vec<(Option<CompoundSelectOperator>, NodeIndex)>,
         order: &Option<OrderClause>,
         limit_clause: &LimitClause,
         leaf_behavior: LeafBehavior,
@@ -244,17 +271,17 @@ impl SqlToMirConverter {
         } else {
             format!("{}_union", query_name.display_unquoted()).into()
         };
-        let mut final_node = match op {
-            CompoundSelectOperator::Union => self.make_union_node(
-                query_name,
-                name,
-                subquery_leaves.as_slice(),
-                union::DuplicateMode::UnionAll,
-            )?,
-            _ => internal!(),
-        };
+        let mut final_node = self.make_compound_nodes(query_name, name, subquery_leaves)?;
 
         if let Some((limit, offset)) = extract_limit_offset(limit_clause)? {
+            // The leaf for a compound query doesn't order its results, so the reader can't apply
+            // a limit or offset provided with the lookup
+            let PaginationLimit::Literal(limit) = limit else {
+                unsupported!(
+                    "ReadySet does not support parametrized LIMIT or OFFSET without LIMIT in \
+                     compound queries"
+                )
+            };
             let make_topk = offset.is_none();
             let paginate_name = if leaf_behavior.should_register() {
                 if make_topk {
@@ -340,6 +367,40 @@ impl SqlToMirConverter {
         Ok(leaf_node)
     }
 
+    /// Combine the levels of an unrolled recursive common table expression (the non-recursive
+    /// term, followed by each application of the recursive term) into a single node, removing
+    /// duplicate rows if the recursive term was combined using `UNION` rather than `UNION ALL`
+    pub(super) fn make_recursive_cte_nodes(
+        &mut self,
+        query_name: &Relation,
+        name: &Relation,
+        levels: &[NodeIndex],
+        distinct: bool,
+    ) -> ReadySetResult<NodeIndex> {
+        let mut final_node = match levels {
+            [] => internal!("Recursive CTE must have at least one level"),
+            [level] => *level,
+            _ => self.make_union_node(
+                query_name,
+                format!("{}_recursive_union", name.display_unquoted()).into(),
+                levels,
+                union::DuplicateMode::UnionAll,
+            )?,
+        };
+
+        if distinct {
+            let group_by = self.columns(final_node);
+            final_node = self.make_distinct_node(
+                query_name,
+                format!("{}_recursive_distinct", name.display_unquoted()).into(),
+                final_node,
+                group_by,
+            );
+        }
+
+        Ok(final_node)
+    }
+
     // pub(super) viz for tests
     pub(super) fn get_flow_node_address(&self, name: &Relation) -> Option<DfNodeIndex> {
         self.relations
@@ -606,18 +667,117 @@ impl SqlToMirConverter {
         Ok(ni)
     }
 
-    fn make_union_node(
+    /// Make the nodes combining the leaves of the subqueries of a compound select statement, each
+    /// of which is paired with the operator combining it with the subqueries before it, and return
+    /// the final node, which will be given the name `name`.
+    ///
+    /// As in SQL, `INTERSECT` binds more tightly than `UNION` and `EXCEPT`, which are evaluated
+    /// from left to right.
+    fn make_compound_nodes(
         &mut self,
         query_name: &Relation,
         name: Relation,
-        ancestors: &[NodeIndex],
-        duplicate_mode: union::DuplicateMode,
+        subquery_leaves: Vec<(Option<CompoundSelectOperator>, NodeIndex)>,
     ) -> ReadySetResult<NodeIndex> {
+        let mut num_nodes = 0;
+        let mut next_name = |kind: &str| -> Relation {
+            num_nodes += 1;
+            format!("{}_{}{}", query_name.display_unquoted(), kind, num_nodes).into()
+        };
+        let is_intersect = |op: &Option<CompoundSelectOperator>| {
+            matches!(
+                op,
+                Some(CompoundSelectOperator::Intersect | CompoundSelectOperator::IntersectAll)
+            )
+        };
+        let only_intersects = subquery_leaves
+            .iter()
+            .skip(1)
+            .all(|(op, _)| is_intersect(op));
+        let num_subqueries = subquery_leaves.len();
+
+        // First, combine every run of subqueries separated by INTERSECT into a single node
+        let mut operands: Vec<(Option<CompoundSelectOperator>, NodeIndex)> = vec![];
+        for (i, (op, leaf)) in subquery_leaves.into_iter().enumerate() {
+            let operator = match op {
+                Some(CompoundSelectOperator::Intersect) => SetOperator::Intersect,
+                Some(CompoundSelectOperator::IntersectAll) => SetOperator::IntersectAll,
+                _ => {
+                    operands.push((op, leaf));
+                    continue;
+                }
+            };
+            let (_, left) = operands
+                .last_mut()
+                .ok_or_else(|| internal_err!("First subquery of a compound select has operator"))?;
+            let node_name = if only_intersects && i == num_subqueries - 1 {
+                name.clone()
+            } else {
+                next_name("intersect")
+            };
+            *left = self.make_set_operation_node(query_name, node_name, *left, leaf, operator)?;
+        }
+
+        // Then, evaluate UNION and EXCEPT from left to right, combining consecutive UNIONs into a
+        // single union node
+        let num_operands = operands.len();
+        let mut operands = operands.into_iter();
+        let (_, first) = operands
+            .next()
+            .ok_or_else(|| internal_err!("Compound select must have at least one subquery"))?;
+        let mut union_parents = vec![first];
+        for (i, (op, leaf)) in operands.enumerate() {
+            let operator = match op {
+                Some(CompoundSelectOperator::Union | CompoundSelectOperator::DistinctUnion) => {
+                    union_parents.push(leaf);
+                    continue;
+                }
+                Some(CompoundSelectOperator::Except) => SetOperator::Except,
+                Some(CompoundSelectOperator::ExceptAll) => SetOperator::ExceptAll,
+                _ => internal!("Subquery of a compound select is missing its operator"),
+            };
+            let left = if union_parents.len() > 1 {
+                self.make_union_node(
+                    query_name,
+                    next_name("union"),
+                    &union_parents,
+                    union::DuplicateMode::UnionAll,
+                )?
+            } else {
+                union_parents[0]
+            };
+            // `i` skips the first operand
+            let node_name = if i == num_operands - 2 {
+                name.clone()
+            } else {
+                next_name("except")
+            };
+            union_parents =
+                vec![self.make_set_operation_node(query_name, node_name, left, leaf, operator)?];
+        }
+
+        if union_parents.len() > 1 {
+            self.make_union_node(
+                query_name,
+                name,
+                &union_parents,
+                union::DuplicateMode::UnionAll,
+            )
+        } else {
+            Ok(union_parents[0])
+        }
+    }
+
+    /// Computes the columns to emit from each of the given parents of a union or set operation,
+    /// which must all have the same column names
+    fn compound_emit_columns(&self, ancestors: &[NodeIndex]) -> ReadySetResult<Vec<Vec<Column>>> {
         let mut emit: Vec<Vec<Column>> = Vec::new();
-        invariant!(ancestors.len() > 1, "union must have more than 1 ancestors");
 
-        #[allow(clippy::unwrap_used)] // checked above
-        let ucols: Vec<Column> = self.mir_graph.columns(*ancestors.first().unwrap()).to_vec();
+        let ucols: Vec<Column> = self.mir_graph.columns(
+            *ancestors
+                .first()
+                .ok_or_else(|| internal_err!("compound select must have ancestors"))?,
+        );
         let num_ucols = ucols.len();
 
         // Find columns present in all ancestors
@@ -665,6 +825,19 @@ impl SqlToMirConverter {
 
         invariant!(!emit.is_empty());
 
+        Ok(emit)
+    }
+
+    fn make_union_node(
+        &mut self,
+        query_name: &Relation,
+        name: Relation,
+        ancestors: &[NodeIndex],
+        duplicate_mode: union::DuplicateMode,
+    ) -> ReadySetResult<NodeIndex> {
+        invariant!(ancestors.len() > 1, "union must have more than 1 ancestors");
+        let emit = self.compound_emit_columns(ancestors)?;
+
         Ok(self.add_query_node(
             query_name.clone(),
             MirNode::new(
@@ -678,6 +851,24 @@ impl SqlToMirConverter {
         ))
     }
 
+    fn make_set_operation_node(
+        &mut self,
+        query_name: &Relation,
+        name: Relation,
+        left: NodeIndex,
+        right: NodeIndex,
+        operator: SetOperator,
+    ) -> ReadySetResult<NodeIndex> {
+        let emit = self.compound_emit_columns(&[left, right])?;
+        trace!(name = %name.display_unquoted(), %operator, "Added set operation node");
+
+        Ok(self.add_query_node(
+            query_name.clone(),
+            MirNode::new(name, MirNodeInner::SetOperation { emit, operator }),
+            &[left, right],
+        ))
+    }
+
     fn make_union_from_same_base(
         &mut self,
         query_name: &Relation,
@@ -790,6 +981,38 @@ impl SqlToMirConverter {
             return Ok(vec![project_coalesce, grouped_node]);
         }
 
+        // The column to aggregate over for the given argument expression, which is either a column
+        // in the parent, or has been projected by the parent
+        let over_col = |expr: &Expr| -> ReadySetResult<Column> {
+            Ok(match expr {
+                Expr::Column(col) => Column::from(col.clone()),
+                _ => Column::named(
+                    projected_exprs
+                        .get(expr)
+                        .cloned()
+                        .ok_or_else(|| mk_error!(expr))?,
+                ),
+            })
+        };
+
+        // The columns (and directions) to order the values of an aggregate with an inner `ORDER BY`
+        // clause by
+        let order_cols =
+            |order_by: Option<OrderClause>| -> ReadySetResult<Vec<(Column, OrderType)>> {
+                order_by
+                    .into_iter()
+                    .flat_map(|oc| oc.order_by)
+                    .map(|(field, ot)| match field {
+                        FieldReference::Expr(Expr::Column(c)) => {
+                            Ok((Column::from(c), ot.unwrap_or(OrderType::OrderAscending)))
+                        }
+                        _ => unsupported!(
+                            "Only column references are currently supported in aggregate ORDER BY"
+                        ),
+                    })
+                    .collect()
+            };
+
         let mut out_nodes = Vec::new();
 
         let mknode = |over: Column, t: GroupedNodeType, distinct: bool| {
@@ -821,44 +1044,26 @@ impl SqlToMirConverter {
         };
 
         Ok(match function {
-            Sum {
-                expr: box Expr::Column(col),
-                distinct,
-            } => mknode(
-                Column::from(col),
-                GroupedNodeType::Aggregation(Aggregation::Sum),
-                distinct,
-            ),
+            // Distinct counts and sums keep track of the multiplicity of each value themselves, so
+            // they don't need a distinct node above them
             Sum { expr, distinct } => mknode(
-                // TODO(celine): replace with ParentRef
-                Column::named(
-                    projected_exprs
-                        .get(&expr)
-                        .cloned()
-                        .ok_or_else(|| mk_error!(&*expr))?,
-                ),
-                GroupedNodeType::Aggregation(Aggregation::Sum),
-                distinct,
+                over_col(&expr)?,
+                GroupedNodeType::Aggregation(if distinct {
+                    Aggregation::SumDistinct
+                } else {
+                    Aggregation::Sum
+                }),
+                false,
             ),
             CountStar => internal!("Handled earlier"),
-            Count {
-                expr: box Expr::Column(col),
-                distinct,
-            } => mknode(
-                Column::from(col),
-                GroupedNodeType::Aggregation(Aggregation::Count),
-                distinct,
-            ),
-            Count { ref expr, distinct } => mknode(
-                // TODO(celine): replace with ParentRef
-                Column::named(
-                    projected_exprs
-                        .get(expr)
-                        .cloned()
-                        .ok_or_else(|| mk_error!(expr))?,
-                ),
-                GroupedNodeType::Aggregation(Aggregation::Count),
-                distinct,
+            Count { expr, distinct } => mknode(
+                over_col(&expr)?,
+                GroupedNodeType::Aggregation(if distinct {
+                    Aggregation::CountDistinct
+                } else {
+                    Aggregation::Count
+                }),
+                false,
             ),
             Avg {
                 expr: box Expr::Column(col),
@@ -923,6 +1128,85 @@ impl SqlToMirConverter {
                 }),
                 false,
             ),
+            Stddev { expr, sample } => mknode(
+                over_col(&expr)?,
+                GroupedNodeType::Aggregation(Aggregation::Stddev { sample }),
+                false,
+            ),
+            Variance { expr, sample } => mknode(
+                over_col(&expr)?,
+                GroupedNodeType::Aggregation(Aggregation::Variance { sample }),
+                false,
+            ),
+            BoolAnd(expr) => mknode(
+                over_col(&expr)?,
+                GroupedNodeType::Aggregation(Aggregation::BoolAnd),
+                false,
+            ),
+            BoolOr(expr) => mknode(
+                over_col(&expr)?,
+                GroupedNodeType::Aggregation(Aggregation::BoolOr),
+                false,
+            ),
+            BitAnd(expr) => mknode(
+                over_col(&expr)?,
+                GroupedNodeType::Aggregation(Aggregation::BitAnd),
+                false,
+            ),
+            BitOr(expr) => mknode(
+                over_col(&expr)?,
+                GroupedNodeType::Aggregation(Aggregation::BitOr),
+                false,
+            ),
+            ArrayAgg { expr, order_by } => mknode(
+                over_col(&expr)?,
+                GroupedNodeType::Collect {
+                    kind: Collection::ArrayAgg,
+                    key: None,
+                    order: order_cols(order_by)?,
+                },
+                false,
+            ),
+            StringAgg {
+                expr,
+                separator,
+                order_by,
+            } => mknode(
+                over_col(&expr)?,
+                GroupedNodeType::Collect {
+                    kind: Collection::StringAgg { separator },
+                    key: None,
+                    order: order_cols(order_by)?,
+                },
+                false,
+            ),
+            JsonAgg {
+                expr,
+                order_by,
+                jsonb,
+            } => mknode(
+                over_col(&expr)?,
+                GroupedNodeType::Collect {
+                    kind: Collection::JsonAgg { jsonb },
+                    key: None,
+                    order: order_cols(order_by)?,
+                },
+                false,
+            ),
+            JsonObjectAgg {
+                key,
+                value,
+                order_by,
+                jsonb,
+            } => mknode(
+                over_col(&value)?,
+                GroupedNodeType::Collect {
+                    kind: Collection::JsonObjectAgg { jsonb },
+                    key: Some(over_col(&key)?),
+                    order: order_cols(order_by)?,
+                },
+                false,
+            ),
             _ => {
                 internal!("not an aggregate: {:?}", Sensitive(&function));
             }
@@ -945,11 +1229,24 @@ impl SqlToMirConverter {
                     name,
                     MirNodeInner::Aggregation {
                         on,
+                        key: None,
                         group_by,
+                        order: vec![],
                         output_column,
                         kind,
                     },
                 ),
+                GroupedNodeType::Collect { kind, key, order } => MirNode::new(
+                    name,
+                    MirNodeInner::Aggregation {
+                        on,
+                        key,
+                        group_by,
+                        order,
+                        output_column,
+                        kind: Aggregation::Collect(kind),
+                    },
+                ),
                 GroupedNodeType::Extremum(kind) => MirNode::new(
                     name,
                     MirNodeInner::Extremum {
@@ -972,7 +1269,12 @@ impl SqlToMirConverter {
         left_node: NodeIndex,
         right_node: NodeIndex,
         kind: JoinKind,
+        residual: Option<Expr>,
     ) -> ReadySetResult<NodeIndex> {
+        if residual.is_some() && !matches!(kind, JoinKind::Left | JoinKind::DependentLeft) {
+            internal!("Residual join predicates are only supported in left joins");
+        }
+
         // TODO(malte): this is where we overproject join columns in order to increase reuse
         // opportunities. Technically, we need to only project those columns here that the query
         // actually needs; at a minimum, we could start with just the join columns, relying on the
@@ -1024,8 +1326,18 @@ impl SqlToMirConverter {
 
         let inner = match kind {
             JoinKind::Inner => MirNodeInner::Join { on, project },
-            JoinKind::Left => MirNodeInner::LeftJoin { on, project },
+            JoinKind::Left => MirNodeInner::LeftJoin {
+                on,
+                project,
+                residual,
+            },
+            JoinKind::Full => MirNodeInner::FullJoin { on, project },
             JoinKind::Dependent => MirNodeInner::DependentJoin { on, project },
+            JoinKind::DependentLeft => MirNodeInner::DependentLeftJoin {
+                on,
+                project,
+                residual,
+            },
         };
         trace!(?inner, "Added join node");
         Ok(self.add_query_node(
@@ -1078,6 +1390,20 @@ impl SqlToMirConverter {
         )
     }
 
+    /// Returns the number of rows to keep for each key in the TopK or Paginate node (and the
+    /// reader) for a query with the given [`PaginationLimit`], or `None` if the query isn't
+    /// limited at all
+    fn resolve_pagination_limit(&self, limit: PaginationLimit) -> ReadySetResult<Option<usize>> {
+        match limit {
+            PaginationLimit::Literal(limit) => Ok(Some(limit)),
+            PaginationLimit::Placeholder => match self.config.max_parametrized_limit {
+                Some(max) => Ok(Some(max)),
+                None => unsupported!("ReadySet does not support parametrized LIMIT fields"),
+            },
+            PaginationLimit::Unbounded => Ok(None),
+        }
+    }
+
     fn make_paginate_node(
         &mut self,
         query_name: &Relation,
@@ -1180,6 +1506,119 @@ impl SqlToMirConverter {
         Ok(nodes)
     }
 
+    /// Make a node computing the given window function over the rows of `parent`.
+    ///
+    /// Each lookup into the reader only returns the rows for one value of the parameter columns
+    /// in `qg`, so (like the group-by of aggregates) the rows are partitioned by those columns as
+    /// well as by the function's `PARTITION BY` clause.
+    fn make_window_node(
+        &mut self,
+        query_name: &Relation,
+        name: Relation,
+        parent: NodeIndex,
+        qg: &QueryGraph,
+        window_function: &WindowFunctionCall,
+    ) -> ReadySetResult<NodeIndex> {
+        use nom_sql::FunctionExpr::*;
+
+        let mut group_by = window_function
+            .partition_by
+            .iter()
+            .map(Column::from)
+            .collect::<Vec<_>>();
+        for param in qg.parameters() {
+            if param.op != BinaryOperator::Equal {
+                unsupported!(
+                    "Window functions in queries with range comparisons against parameters are \
+                     not yet supported"
+                );
+            }
+            let col = Column::from(&param.col);
+            if !group_by.contains(&col) {
+                group_by.push(col);
+            }
+        }
+
+        let (kind, on) = match &window_function.function {
+            Call { name, arguments } if arguments.is_empty() => {
+                let kind = match name.as_str().to_ascii_lowercase().as_str() {
+                    "row_number" => WindowFunction::RowNumber,
+                    "rank" => WindowFunction::Rank,
+                    "dense_rank" => WindowFunction::DenseRank,
+                    _ => unsupported!("Unsupported window function: {}", name),
+                };
+                (kind, None)
+            }
+            CountStar => (WindowFunction::Count, None),
+            Count {
+                expr: box Expr::Column(col),
+                distinct: false,
+            } => (WindowFunction::Count, Some(col)),
+            Sum {
+                expr: box Expr::Column(col),
+                distinct: false,
+            } => (WindowFunction::Sum, Some(col)),
+            Max(box Expr::Column(col)) => (WindowFunction::Max, Some(col)),
+            Min(box Expr::Column(col)) => (WindowFunction::Min, Some(col)),
+            Count { .. } | Sum { .. } | Max(_) | Min(_) => unsupported!(
+                "Only non-DISTINCT column references are currently supported as arguments to \
+                 window functions"
+            ),
+            function => unsupported!(
+                "Unsupported window function: {}",
+                function.display(nom_sql::Dialect::MySQL)
+            ),
+        };
+
+        Ok(self.add_query_node(
+            query_name.clone(),
+            MirNode::new(
+                name,
+                MirNodeInner::Window {
+                    on: on.map(Column::from),
+                    group_by,
+                    order: window_function
+                        .order
+                        .iter()
+                        .map(|(col, ot)| (Column::from(col), *ot))
+                        .collect(),
+                    output_column: Column::named(window_function.alias.clone()),
+                    kind,
+                },
+            ),
+            &[parent],
+        ))
+    }
+
+    fn make_unnest_node(
+        &mut self,
+        query_name: &Relation,
+        name: Relation,
+        parent: NodeIndex,
+        call: &SetReturningFunctionCall,
+        rel: &Relation,
+    ) -> ReadySetResult<NodeIndex> {
+        let kind = match call.name.as_str().to_ascii_lowercase().as_str() {
+            "unnest" => UnnestKind::Array,
+            "json_array_elements" | "jsonb_array_elements" => UnnestKind::Json,
+            "json_array_elements_text" | "jsonb_array_elements_text" => UnnestKind::JsonText,
+            _ => unsupported!("Unsupported set-returning function: {}", call.name),
+        };
+
+        Ok(self.add_query_node(
+            query_name.clone(),
+            MirNode::new(
+                name,
+                MirNodeInner::Unnest {
+                    on: Column::from(&call.argument),
+                    output_column: Column::new(Some(rel.clone()), call.column.clone()),
+                    kind,
+                },
+            ),
+            &[parent],
+        ))
+    }
+
     fn make_predicate_nodes(
         &mut self,
         query_name: &Relation,
@@ -1243,91 +1682,28 @@ impl SqlToMirConverter {
             ),
             Expr::Between { .. } => internal!("BETWEEN should have been removed earlier"),
             Expr::Exists(subquery) => {
-                let query_graph = to_query_graph((**subquery).clone())?;
-                let subquery_leaf = self.named_query_to_mir(
-                    query_name,
-                    &query_graph,
-                    &HashMap::new(),
-                    LeafBehavior::Anonymous,
-                )?;
-
-                // -> π[lit: 0, lit: 0]
-                let group_proj = self.make_project_node(
-                    query_name,
-                    format!("{}_prj_hlpr", name.display_unquoted()).into(),
-                    subquery_leaf,
-                    vec![
-                        ProjectExpr::Expr {
-                            alias: "__count_val".into(),
-                            expr: Expr::Literal(0u32.into()),
-                        },
-                        ProjectExpr::Expr {
-                            alias: "__count_grp".into(),
-                            expr: Expr::Literal(0u32.into()),
-                        },
-                    ],
-                );
-                // -> [0, 0] for each row
-
-                // -> |0| γ[1]
-                let exists_count_col = Column::named("__exists_count");
-                let exists_count_node = self.make_grouped_node(
-                    query_name,
-                    format!("{}_count", name.display_unquoted()).into(),
-                    exists_count_col,
-                    (group_proj, Column::named("__count_val")),
-                    vec![Column::named("__count_grp")],
-                    GroupedNodeType::Aggregation(Aggregation::Count),
-                );
-                // -> [0, <count>] for each row
-
-                // -> σ[c1 > 0]
-                let gt_0_filter = self.make_filter_node(
-                    query_name,
-                    format!("{}_count_gt_0", name.display_unquoted()).into(),
-                    exists_count_node,
-                    Expr::BinaryOp {
-                        lhs: Box::new(Expr::Column("__exists_count".into())),
-                        op: BinaryOperator::Greater,
-                        rhs: Box::new(Expr::Literal(Literal::Integer(0))),
-                    },
-                );
-
-                // left -> π[...left, lit: 0]
-                let parent_columns = self.mir_graph.columns(parent);
-                let left_literal_join_key_proj = self.make_project_node(
-                    query_name,
-                    format!("{}_join_key", name.display_unquoted()).into(),
-                    parent,
-                    parent_columns
-                        .into_iter()
-                        .map(ProjectExpr::Column)
-                        .chain(iter::once(ProjectExpr::Expr {
-                            alias: "__exists_join_key".into(),
-                            expr: Expr::Literal(0u32.into()),
-                        }))
-                        .collect(),
-                );
-
-                // -> ⋈ on: l.__exists_join_key ≡ r.__count_grp
-                self.make_join_node(
-                    query_name,
-                    format!("{}_join", name.display_unquoted()).into(),
-                    &[JoinPredicate {
-                        left: "__exists_join_key".into(),
-                        right: "__count_grp".into(),
-                    }],
-                    left_literal_join_key_proj,
-                    gt_0_filter,
-                    if is_correlated(subquery) {
-                        JoinKind::Dependent
-                    } else {
-                        JoinKind::Inner
-                    },
-                )?
+                self.make_exists_nodes(query_name, name, parent, subquery, false)?
             }
-            Expr::Call(_) => {
-                internal!("Function calls should have been handled by projection earlier")
+            Expr::UnaryOp {
+                op: UnaryOperator::Not,
+                rhs: box Expr::Exists(subquery),
+            } => self.make_exists_nodes(query_name, name, parent, subquery, true)?,
+            Expr::In {
+                lhs,
+                rhs: InValue::Subquery(subquery),
+                negated,
+            } => self.make_in_subquery_nodes(query_name, name, parent, lhs, subquery, *negated)?,
+            Expr::UnaryOp {
+                op: UnaryOperator::Not,
+                rhs:
+                    box Expr::In {
+                        lhs,
+                        rhs: InValue::Subquery(subquery),
+                        negated,
+                    },
+            } => self.make_in_subquery_nodes(query_name, name, parent, lhs, subquery, !*negated)?,
+            Expr::Call(f) if is_aggregate(f) => {
+                internal!("Aggregates should have been handled by projection earlier")
             }
             Expr::NestedSelect(_) => unsupported!("Nested selects not supported in filters"),
             _ => self.make_filter_node(
@@ -1346,6 +1722,393 @@ impl SqlToMirConverter {
         Ok(leaf)
     }
 
+    /// Make the nodes for an `EXISTS` predicate (or, if `negated` is true, a `NOT EXISTS`
+    /// predicate) on the given subquery, returning the new leaf.
+    ///
+    /// The subquery's rows are counted in a single group, which is then joined to the parent on a
+    /// literal key - `EXISTS` uses an inner join against the group (which only exists if the
+    /// subquery returns any rows), and `NOT EXISTS` uses a left join followed by a filter for rows
+    /// that didn't match.
+    fn make_exists_nodes(
+        &mut self,
+        query_name: &Relation,
+        name: Relation,
+        parent: NodeIndex,
+        subquery: &SelectStatement,
+        negated: bool,
+    ) -> ReadySetResult<NodeIndex> {
+        // The default row of a query graph is only used by the leaves of named queries, so the
+        // dialect doesn't matter for subqueries
+        let query_graph = to_query_graph(subquery.clone(), Dialect::DEFAULT_MYSQL)?;
+        let subquery_leaf = self.named_query_to_mir(
+            query_name,
+            &query_graph,
+            &HashMap::new(),
+            LeafBehavior::Anonymous,
+        )?;
+
+        // -> π[lit: 0, lit: 0]
+        let group_proj = self.make_project_node(
+            query_name,
+            format!("{}_prj_hlpr", name.display_unquoted()).into(),
+            subquery_leaf,
+            vec![
+                ProjectExpr::Expr {
+                    alias: "__count_val".into(),
+                    expr: Expr::Literal(0u32.into()),
+                },
+                ProjectExpr::Expr {
+                    alias: "__count_grp".into(),
+                    expr: Expr::Literal(0u32.into()),
+                },
+            ],
+        );
+        // -> [0, 0] for each row
+
+        // -> |0| γ[1]
+        //
+        // The count column is qualified with the name of this predicate, so that we can refer to
+        // it unambiguously after the join even if the query has multiple subqueries
+        let exists_count_col = nom_sql::Column {
+            name: "__exists_count".into(),
+            table: Some(name.clone()),
+        };
+        let exists_count_node = self.make_grouped_node(
+            query_name,
+            format!("{}_count", name.display_unquoted()).into(),
+            Column::from(&exists_count_col),
+            (group_proj, Column::named("__count_val")),
+            vec![Column::named("__count_grp")],
+            GroupedNodeType::Aggregation(Aggregation::Count),
+        );
+        // -> [0, <count>] for each row
+
+        let right = if negated {
+            exists_count_node
+        } else {
+            // -> σ[c1 > 0]
+            self.make_filter_node(
+                query_name,
+                format!("{}_count_gt_0", name.display_unquoted()).into(),
+                exists_count_node,
+                Expr::BinaryOp {
+                    lhs: Box::new(Expr::Column(exists_count_col.clone())),
+                    op: BinaryOperator::Greater,
+                    rhs: Box::new(Expr::Literal(Literal::Integer(0))),
+                },
+            )
+        };
+
+        // left -> π[...left, lit: 0]
+        let parent_columns = self.mir_graph.columns(parent);
+        let left_literal_join_key_proj = self.make_project_node(
+            query_name,
+            format!("{}_join_key", name.display_unquoted()).into(),
+            parent,
+            parent_columns
+                .into_iter()
+                .map(ProjectExpr::Column)
+                .chain(iter::once(ProjectExpr::Expr {
+                    alias: "__exists_join_key".into(),
+                    expr: Expr::Literal(0u32.into()),
+                }))
+                .collect(),
+        );
+
+        // -> ⋈ on: l.__exists_join_key ≡ r.__count_grp
+        let join = self.make_join_node(
+            query_name,
+            format!("{}_join", name.display_unquoted()).into(),
+            &[JoinPredicate {
+                left: "__exists_join_key".into(),
+                right: "__count_grp".into(),
+            }],
+            left_literal_join_key_proj,
+            right,
+            match (negated, is_correlated(subquery)) {
+                (false, false) => JoinKind::Inner,
+                (false, true) => JoinKind::Dependent,
+                (true, false) => JoinKind::Left,
+                (true, true) => JoinKind::DependentLeft,
+            },
+            None,
+        )?;
+
+        if !negated {
+            return Ok(join);
+        }
+
+        // -> σ[c1 IS NULL]
+        Ok(self.make_filter_node(
+            query_name,
+            format!("{}_count_is_null", name.display_unquoted()).into(),
+            join,
+            Expr::BinaryOp {
+                lhs: Box::new(Expr::Column(exists_count_col)),
+                op: BinaryOperator::Is,
+                rhs: Box::new(Expr::Literal(Literal::Null)),
+            },
+        ))
+    }
+
+    /// Make the nodes for an `lhs IN (<subquery>)` predicate (or, if `negated` is true, an
+    /// `lhs NOT IN (<subquery>)` predicate), returning the new leaf.
+    ///
+    /// `IN` is lowered to a semi-join: the (distinct, non-NULL) values returned by the subquery
+    /// are inner-joined to the parent on `lhs`.
+    ///
+    /// `NOT IN` is lowered to an anti-join: the values are left-joined to the parent on `lhs`,
+    /// and only rows which didn't match are kept. To get SQL's semantics for NULLs right, we also
+    /// left-join the number of NULLs returned by the subquery, which is only present if the
+    /// subquery returned any rows at all - if it did, rows are also filtered out if either `lhs`
+    /// is NULL or the subquery returned any NULLs, since the result of the `NOT IN` is NULL
+    /// rather than true in that case.
+    fn make_in_subquery_nodes(
+        &mut self,
+        query_name: &Relation,
+        name: Relation,
+        parent: NodeIndex,
+        lhs: &Expr,
+        subquery: &SelectStatement,
+        negated: bool,
+    ) -> ReadySetResult<NodeIndex> {
+        let lhs = match lhs {
+            Expr::Column(col) => col.clone(),
+            _ => unsupported!(
+                "Only columns are supported on the left-hand side of IN with a subquery"
+            ),
+        };
+        let correlated = is_correlated(subquery);
+        // The default row of a query graph is only used by the leaves of named queries, so the
+        // dialect doesn't matter for subqueries
+        let query_graph = to_query_graph(subquery.clone(), Dialect::DEFAULT_MYSQL)?;
+
+        let make_subquery = |this: &mut Self| -> ReadySetResult<(NodeIndex, Expr)> {
+            let subquery_leaf = this.named_query_to_mir(
+                query_name,
+                &query_graph,
+                &HashMap::new(),
+                LeafBehavior::Anonymous,
+            )?;
+            let value = match this.mir_graph.columns(subquery_leaf).as_slice() {
+                [col] => Expr::Column(nom_sql::Column {
+                    name: col.name.clone(),
+                    table: col.table.clone(),
+                }),
+                cols => invalid!(
+                    "Subquery on the right-hand side of IN must return exactly one column, not {}",
+                    cols.len()
+                ),
+            };
+            Ok((subquery_leaf, value))
+        };
+
+        // Qualify the helper columns that we need to refer to after joining with the name of this
+        // predicate, so that they're unambiguous even if the query has multiple subqueries
+        let values_count_col = nom_sql::Column {
+            name: "__in_count".into(),
+            table: Some(name.clone()),
+        };
+        let (subquery_leaf, value) = make_subquery(self)?;
+
+        // -> π[value, lit: 0]
+        let values_proj = self.make_project_node(
+            query_name,
+            format!("{}_in_values", name.display_unquoted()).into(),
+            subquery_leaf,
+            vec![
+                ProjectExpr::Expr {
+                    alias: "__in_value".into(),
+                    expr: value.clone(),
+                },
+                ProjectExpr::Expr {
+                    alias: "__in_count_val".into(),
+                    expr: Expr::Literal(0u32.into()),
+                },
+            ],
+        );
+
+        // -> |0| γ[value]
+        //
+        // Deduplicates the values, so that each row in the parent matches at most once
+        let values_count = self.make_grouped_node(
+            query_name,
+            format!("{}_in_count", name.display_unquoted()).into(),
+            Column::from(&values_count_col),
+            (values_proj, Column::named("__in_count_val")),
+            vec![Column::named("__in_value")],
+            GroupedNodeType::Aggregation(Aggregation::Count),
+        );
+
+        // -> σ[value IS NOT NULL]
+        let values = self.make_filter_node(
+            query_name,
+            format!("{}_in_not_null", name.display_unquoted()).into(),
+            values_count,
+            Expr::BinaryOp {
+                lhs: Box::new(Expr::Column("__in_value".into())),
+                op: BinaryOperator::IsNot,
+                rhs: Box::new(Expr::Literal(Literal::Null)),
+            },
+        );
+
+        if !negated {
+            // -> ⋈ on: l.lhs ≡ r.value
+            return self.make_join_node(
+                query_name,
+                format!("{}_in_join", name.display_unquoted()).into(),
+                &[JoinPredicate {
+                    left: lhs,
+                    right: "__in_value".into(),
+                }],
+                parent,
+                values,
+                if correlated {
+                    JoinKind::Dependent
+                } else {
+                    JoinKind::Inner
+                },
+                None,
+            );
+        }
+
+        let join_kind = if correlated {
+            JoinKind::DependentLeft
+        } else {
+            JoinKind::Left
+        };
+
+        // Decorrelating needs each node on the right-hand side of a dependent join to have only
+        // one child, so correlated subqueries can't be shared between the two joins
+        let (null_count_leaf, null_count_value) = if correlated {
+            make_subquery(self)?
+        } else {
+            (subquery_leaf, value)
+        };
+
+        // -> π[lit: 0, value IS NULL ? 1 : 0]
+        let null_count_proj = self.make_project_node(
+            query_name,
+            format!("{}_in_nulls", name.display_unquoted()).into(),
+            null_count_leaf,
+            vec![
+                ProjectExpr::Expr {
+                    alias: "__in_null_grp".into(),
+                    expr: Expr::Literal(0u32.into()),
+                },
+                ProjectExpr::Expr {
+                    alias: "__in_is_null".into(),
+                    expr: Expr::CaseWhen {
+                        branches: vec![CaseWhenBranch {
+                            condition: Expr::BinaryOp {
+                                lhs: Box::new(null_count_value),
+                                op: BinaryOperator::Is,
+                                rhs: Box::new(Expr::Literal(Literal::Null)),
+                            },
+                            body: Expr::Literal(1u32.into()),
+                        }],
+                        else_expr: Some(Box::new(Expr::Literal(0u32.into()))),
+                    },
+                },
+            ],
+        );
+
+        // -> 𝛴(is_null) γ[0]
+        let null_count_col = nom_sql::Column {
+            name: "__in_null_count".into(),
+            table: Some(name.clone()),
+        };
+        let null_count = self.make_grouped_node(
+            query_name,
+            format!("{}_in_null_count", name.display_unquoted()).into(),
+            Column::from(&null_count_col),
+            (null_count_proj, Column::named("__in_is_null")),
+            vec![Column::named("__in_null_grp")],
+            GroupedNodeType::Aggregation(Aggregation::Sum),
+        );
+
+        // left -> π[...left, lit: 0]
+        let parent_columns = self.mir_graph.columns(parent);
+        let left_literal_join_key_proj = self.make_project_node(
+            query_name,
+            format!("{}_join_key", name.display_unquoted()).into(),
+            parent,
+            parent_columns
+                .into_iter()
+                .map(ProjectExpr::Column)
+                .chain(iter::once(ProjectExpr::Expr {
+                    alias: "__in_join_key".into(),
+                    expr: Expr::Literal(0u32.into()),
+                }))
+                .collect(),
+        );
+
+        // -> ⟕ on: l.__in_join_key ≡ r.__in_null_grp
+        let null_count_join = self.make_join_node(
+            query_name,
+            format!("{}_in_null_count_join", name.display_unquoted()).into(),
+            &[JoinPredicate {
+                left: "__in_join_key".into(),
+                right: "__in_null_grp".into(),
+            }],
+            left_literal_join_key_proj,
+            null_count,
+            join_kind,
+            None,
+        )?;
+
+        // -> ⟕ on: l.lhs ≡ r.value
+        let values_join = self.make_join_node(
+            query_name,
+            format!("{}_in_join", name.display_unquoted()).into(),
+            &[JoinPredicate {
+                left: lhs.clone(),
+                right: "__in_value".into(),
+            }],
+            null_count_join,
+            values,
+            join_kind,
+            None,
+        )?;
+
+        let is_null = |expr: Expr| Expr::BinaryOp {
+            lhs: Box::new(expr),
+            op: BinaryOperator::Is,
+            rhs: Box::new(Expr::Literal(Literal::Null)),
+        };
+        let and = |lhs: Expr, rhs: Expr| Expr::BinaryOp {
+            lhs: Box::new(lhs),
+            op: BinaryOperator::And,
+            rhs: Box::new(rhs),
+        };
+
+        // -> σ[count IS NULL AND (null_count IS NULL OR (lhs IS NOT NULL AND null_count = 0))]
+        Ok(self.make_filter_node(
+            query_name,
+            format!("{}_not_in", name.display_unquoted()).into(),
+            values_join,
+            and(
+                is_null(Expr::Column(values_count_col)),
+                Expr::BinaryOp {
+                    lhs: Box::new(is_null(Expr::Column(null_count_col.clone()))),
+                    op: BinaryOperator::Or,
+                    rhs: Box::new(and(
+                        Expr::BinaryOp {
+                            lhs: Box::new(Expr::Column(lhs)),
+                            op: BinaryOperator::IsNot,
+                            rhs: Box::new(Expr::Literal(Literal::Null)),
+                        },
+                        Expr::BinaryOp {
+                            lhs: Box::new(Expr::Column(null_count_col)),
+                            op: BinaryOperator::Equal,
+                            rhs: Box::new(Expr::Literal(Literal::Integer(0))),
+                        },
+                    )),
+                },
+            ),
+        ))
+    }
+
     fn predicates_above_group_by<'a>(
         &mut self,
         query_name: &Relation,
@@ -1458,6 +2221,12 @@ impl SqlToMirConverter {
             let mut sorted_rels: Vec<&Relation> = query_graph.relations.keys().collect();
             sorted_rels.sort_unstable();
             for rel in &sorted_rels {
+                if query_graph.relations[*rel].function.is_some() {
+                    // Set-returning functions are added on top of the relation they're called on
+                    // below, once all the base nodes exist
+                    continue;
+                }
+
                 let base_for_rel = if let Some(subquery) = &query_graph.relations[*rel].subgraph {
                     let correlated = subquery.is_correlated;
                     let subquery_leaf = self.named_query_to_mir(
@@ -1505,6 +2274,37 @@ impl SqlToMirConverter {
                 node_for_rel.insert(*rel, alias_table_node);
             }
 
+            // 1. Set-returning functions in the FROM clause, which each expand a column of some
+            // other relation. Rather than being joined in, they take the place of that relation
+            for rel in &sorted_rels {
+                let Some(call) = &query_graph.relations[*rel].function else {
+                    continue;
+                };
+                let source = call.source()?;
+                let Some(&source_node) = node_for_rel.get(source) else {
+                    unsupported!(
+                        "Set-returning function {} must be called on a column of a table in the \
+                         same FROM clause",
+                        call.name
+                    );
+                };
+
+                let unnest_node = self.make_unnest_node(
+                    query_name,
+                    format!("q_{:x}_unnest_{}", query_graph.signature().hash, rel.name).into(),
+                    source_node,
+                    call,
+                    rel,
+                )?;
+
+                for node in base_nodes.iter_mut() {
+                    if *node == source_node {
+                        *node = unnest_node;
+                    }
+                }
+                node_for_rel.insert(source, unnest_node);
+            }
+
             let join_nodes = make_joins(
                 self,
                 query_name,
@@ -1676,15 +2476,49 @@ impl SqlToMirConverter {
                 prev_node = subquery_leaf;
             }
 
+            // Add nodes for window functions, which are computed over the rows that remain after
+            // all filtering
+            for window_function in &query_graph.window_functions {
+                prev_node = self.make_window_node(
+                    query_name,
+                    format!(
+                        "q_{:x}_n{}",
+                        query_graph.signature().hash,
+                        self.mir_graph.node_count()
+                    )
+                    .into(),
+                    prev_node,
+                    query_graph,
+                    window_function,
+                )?;
+            }
+
             // 10. Get the final node
             let mut final_node = prev_node;
 
-            if let Some(Pagination {
-                order,
+            // The number of rows to keep for each key, if the query is limited. Queries with only
+            // an `OFFSET` keep all their rows, and have the offset applied in the reader
+            let limit = query_graph
+                .pagination
+                .as_ref()
+                .map(|p| self.resolve_pagination_limit(p.limit))
+                .transpose()?
+                .flatten();
+
+            // Range queries are paginated in the reader instead (see `QueryGraph::view_key`):
+            // grouping a TopK by the range-compared columns would only bound the number of rows
+            // for each individual value in the range, so it wouldn't save us any state, and the
+            // rows in the range are ordered and limited after the lookup anyway. When they're
+            // ordered by the range-compared columns, the lookup itself stops once it has enough
+            // rows (see `SingleReadHandle::range_row_limit`). Leaving it out also means misses in
+            // the reader are filled by regular range upqueries
+            if let (Some(Pagination { order, offset, .. }), Some(limit)) = (
+                query_graph
+                    .pagination
+                    .as_ref()
+                    .filter(|_| view_key.index_type != IndexType::BTreeMap),
                 limit,
-                offset,
-            }) = query_graph.pagination.as_ref()
-            {
+            ) {
                 let make_topk = offset.is_none();
                 // view key will have the offset parameter if it exists. We must filter it out
                 // of the group by, because the column originates at this node
@@ -1712,7 +2546,7 @@ impl SqlToMirConverter {
                     final_node,
                     group_by,
                     order,
-                    *limit,
+                    limit,
                     make_topk,
                 )?;
                 func_nodes.extend(paginate_nodes.clone());
@@ -1758,13 +2592,40 @@ impl SqlToMirConverter {
                 })
                 .collect::<Vec<_>>();
 
+            let reader_aggregates = if !leaf_behavior.should_make_leaf() {
+                None
+            } else if view_key.index_type != IndexType::HashMap {
+                post_lookup_aggregates(query_graph, query_name)?
+            } else if !view_key.columns.is_empty() && !query_graph.aggregates.is_empty() {
+                // Lookups into hash map indices only need to be re-aggregated if they're for
+                // multiple keys, which happens for `IN` lists. The adapter refuses to collapse `IN`
+                // lists for queries with aggregates that can't be re-aggregated, so it's fine to
+                // not support them here.
+                match post_lookup_aggregates(query_graph, query_name) {
+                    Err(e) if e.is_unsupported() => None,
+                    res => res?,
+                }
+            } else {
+                None
+            };
+
             if leaf_behavior.should_make_leaf() {
-                for (column, _) in &view_key.columns {
+                let derived_from_columns = reader_aggregates
+                    .iter()
+                    .flat_map(|aggs| &aggs.aggregates)
+                    .flat_map(|agg| agg.function.derived_from())
+                    .map(|col| Column::named(col.name.clone()));
+                for column in view_key
+                    .columns
+                    .iter()
+                    .map(|(column, _)| column.clone())
+                    .chain(derived_from_columns)
+                {
                     if !emit
                         .iter()
-                        .any(|expr| matches!(expr, ProjectExpr::Column(c) if c == column))
+                        .any(|expr| matches!(expr, ProjectExpr::Column(c) if *c == column))
                     {
-                        emit.push(ProjectExpr::Column(column.clone()))
+                        emit.push(ProjectExpr::Column(column))
                     }
                 }
             }
@@ -1861,12 +2722,6 @@ impl SqlToMirConverter {
                     project_order,
                 );
 
-                let aggregates = if view_key.index_type != IndexType::HashMap {
-                    post_lookup_aggregates(query_graph, query_name)?
-                } else {
-                    None
-                };
-
                 let leaf_node = self.add_query_node(
                     query_name.clone(),
                     MirNode::new(
@@ -1882,10 +2737,11 @@ impl SqlToMirConverter {
                             order_by: query_graph.order.as_ref().map(|order| {
                                 order.iter().map(|(c, ot)| (Column::from(c), *ot)).collect()
                             }),
-                            limit: query_graph.pagination.as_ref().map(|p| p.limit),
+                            limit,
                             returned_cols: Some(returned_cols),
                             default_row: query_graph.default_row.clone(),
-                            aggregates,
+                            aggregates: reader_aggregates,
+                            null_key_columns: vec![],
                         },
                     ),
                     &[leaf_project_reorder_node],
@@ -5,9 +5,11 @@ use std::vec::Vec;
 use ::mir::visualize::GraphViz;
 use ::mir::DfNodeIndex;
 use ::serde::{Deserialize, Serialize};
+use nom_sql::analysis::visit::Visitor;
 use nom_sql::{
-    CompoundSelectOperator, CompoundSelectStatement, CreateTableBody, FieldDefinitionExpr,
-    Relation, SelectSpecification, SelectStatement, SqlIdentifier, SqlType, TableExpr,
+    BinaryOperator, CommonTableExpr, CompoundSelectStatement, CreateTableBody, Expr,
+    FieldDefinitionExpr, Literal, RecursiveTerm, Relation, SelectSpecification, SelectStatement,
+    SqlIdentifier, SqlType, TableExpr,
 };
 use petgraph::graph::NodeIndex;
 use readyset_client::recipe::changelist::{AlterTypeChange, Change};
@@ -119,6 +121,12 @@ pub(crate) struct SqlIncorporator {
 
     /// Whether or to treat failed writes to base tables as no-ops
     permissive_writes: bool,
+
+    /// Map from the view names of recursive CTEs whose recursive term is currently being compiled
+    /// to the MIR node for the previous level of recursion, which references to the CTE within
+    /// the recursive term should read from
+    #[serde(skip)]
+    recursive_cte_levels: HashMap<Relation, MirNodeIndex>,
 }
 
 impl SqlIncorporator {
@@ -867,16 +875,19 @@ impl SqlIncorporator {
         mig: &mut Migration<'_>,
     ) -> ReadySetResult<MirNodeIndex> {
         let mut subqueries = Vec::with_capacity(query.selects.len());
-        for (_, stmt) in &mut query.selects {
+        for (op, stmt) in &mut query.selects {
             let mut tables = invalidating_tables.is_some().then(Vec::new);
-            subqueries.push(self.select_query_to_mir(
-                query_name.clone(),
-                stmt,
-                search_path,
-                tables.as_mut(),
-                LeafBehavior::Anonymous,
-                mig,
-            )?);
+            subqueries.push((
+                op.clone(),
+                self.select_query_to_mir(
+                    query_name.clone(),
+                    stmt,
+                    search_path,
+                    tables.as_mut(),
+                    LeafBehavior::Anonymous,
+                    mig,
+                )?,
+            ));
             if let Some(ts) = tables {
                 if let Some(its) = invalidating_tables.as_mut() {
                     its.extend(ts);
@@ -887,7 +898,6 @@ impl SqlIncorporator {
         self.mir_converter.compound_query_to_mir(
             &query_name,
             subqueries,
-            CompoundSelectOperator::Union,
             &query.order,
             &query.limit_clause,
             leaf_behavior,
@@ -977,6 +987,16 @@ impl SqlIncorporator {
     ) -> ReadySetResult<MirNodeIndex> {
         // FIXME(ENG-2499): Use correct dialect.
         trace!(stmt = %stmt.display(nom_sql::Dialect::MySQL), "Adding select query");
+
+        // The recursive terms of `WITH RECURSIVE` CTEs refer to the CTEs themselves, so they can't
+        // be rewritten along with the rest of the query - instead, they're rewritten and compiled
+        // once for each level of recursion by `recursive_cte_to_mir`
+        let mut recursive_terms: HashMap<SqlIdentifier, RecursiveTerm> = stmt
+            .ctes
+            .iter_mut()
+            .filter_map(|cte| Some((cte.name.clone(), cte.recursive_term.take()?)))
+            .collect();
+
         *stmt = self.rewrite(stmt.clone(), search_path, mig.dialect, invalidating_tables)?;
 
         self.num_queries += 1;
@@ -1008,18 +1028,36 @@ impl SqlIncorporator {
                     anon_queries.insert(to_view, subquery_leaf);
                 }
                 TableAliasRewrite::Cte {
+                    from,
                     to_view,
                     mut for_statement,
-                    ..
                 } => {
-                    let subquery_leaf = self.select_query_to_mir(
-                        query_name.clone(),
-                        for_statement.as_mut(),
-                        search_path,
-                        None,
-                        LeafBehavior::Anonymous,
-                        mig,
-                    )?;
+                    let subquery_leaf = if let Some(&previous_level) =
+                        self.recursive_cte_levels.get(&to_view)
+                    {
+                        // We're compiling the recursive term of this CTE, so references to the CTE
+                        // read from the previous level of recursion
+                        previous_level
+                    } else if let Some(recursive_term) = recursive_terms.remove(&from) {
+                        self.recursive_cte_to_mir(
+                            query_name,
+                            &from,
+                            &to_view,
+                            *for_statement,
+                            recursive_term,
+                            search_path,
+                            mig,
+                        )?
+                    } else {
+                        self.select_query_to_mir(
+                            query_name.clone(),
+                            for_statement.as_mut(),
+                            search_path,
+                            None,
+                            LeafBehavior::Anonymous,
+                            mig,
+                        )?
+                    };
                     anon_queries.insert(to_view, subquery_leaf);
                 }
                 TableAliasRewrite::Table { .. } => {}
@@ -1029,7 +1067,7 @@ impl SqlIncorporator {
         // FIXME(ENG-2499): Use correct dialect.
         trace!(rewritten_query = %stmt.display(nom_sql::Dialect::MySQL));
 
-        let query_graph = to_query_graph(stmt.clone())?;
+        let query_graph = to_query_graph(stmt.clone(), mig.dialect)?;
 
         self.mir_converter.named_query_to_mir(
             query_name,
@@ -1039,6 +1077,125 @@ impl SqlIncorporator {
         )
     }
 
+    /// Compile a recursive common table expression (`WITH RECURSIVE`) named `name` to MIR,
+    /// returning the index of the MIR node for the CTE's results.
+    ///
+    /// The dataflow graph can't contain cycles, so rather than iterating the recursive term until
+    /// it reaches a fixpoint we unroll it: the first level of recursion is the non-recursive term
+    /// (`base`), and each subsequent level is the recursive term with references to the CTE bound
+    /// to the level before it. The results of the CTE are then the union of all the levels, all of
+    /// which are maintained incrementally like any other query.
+    ///
+    /// To avoid silently dropping rows that could only be reached through more levels than we
+    /// unrolled, the recursive term must provably stop producing rows within the configured
+    /// [`max_recursion_depth`](mir::Config::max_recursion_depth) (see [`recursion_depth_bound`]),
+    /// and we unroll exactly as many levels as it can produce rows for. Recursive CTEs whose
+    /// recursion can't be bounded this way are unsupported.
+    #[allow(clippy::too_many_arguments)]
+    fn recursive_cte_to_mir(
+        &mut self,
+        query_name: &Relation,
+        name: &SqlIdentifier,
+        to_view: &Relation,
+        base: SelectStatement,
+        recursive_term: RecursiveTerm,
+        search_path: &[SqlIdentifier],
+        mig: &mut Migration<'_>,
+    ) -> ReadySetResult<MirNodeIndex> {
+        let Some(max_depth) = self.mir_converter.config().max_recursion_depth else {
+            unsupported!("ReadySet does not support recursive common table expressions")
+        };
+
+        let RecursiveTerm { all, statement } = recursive_term;
+        let is_recursive = references_table(&statement, name);
+        let base_values = base
+            .fields
+            .iter()
+            .map(|field| match field {
+                FieldDefinitionExpr::Expr { expr, .. } => integer_literal(expr),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        let base_leaf = self.select_query_to_mir(
+            query_name.clone(),
+            &mut base.clone(),
+            search_path,
+            None,
+            LeafBehavior::Anonymous,
+            mig,
+        )?;
+
+        // Rewrite the recursive term with the CTE in scope (so that we can resolve its columns),
+        // then alias its fields to match the columns of the non-recursive term, which name the
+        // columns of the CTE
+        let mut step = statement;
+        step.ctes.insert(
+            0,
+            CommonTableExpr {
+                name: name.clone(),
+                statement: base,
+                recursive_term: None,
+            },
+        );
+        step = self.rewrite(step, search_path, mig.dialect, None)?;
+        let columns = self.mir_converter.columns(base_leaf);
+        if step.fields.len() != columns.len() {
+            unsupported!(
+                "The recursive term of recursive CTE {name} must have the same number of columns \
+                 as the non-recursive term"
+            );
+        }
+        let column_names = columns.into_iter().map(|c| c.name).collect::<Vec<_>>();
+        for (field, column) in step.fields.iter_mut().zip(&column_names) {
+            match field {
+                FieldDefinitionExpr::Expr { alias, .. } => *alias = Some(column.clone()),
+                _ => internal!("Star expansion should have removed all stars"),
+            }
+        }
+
+        let depth = if is_recursive {
+            let Some(depth) = recursion_depth_bound(name, &base_values, &column_names, &step)
+            else {
+                unsupported!(
+                    "Could not determine how many times the recursive term of recursive CTE \
+                     {name} is applied. The recursive term must increment a column which starts \
+                     at a constant, and filter it with a constant upper bound (such as `WHERE \
+                     {name}.depth < 10`)"
+                )
+            };
+            if depth > max_depth {
+                unsupported!(
+                    "The recursive term of recursive CTE {name} may be applied {depth} times, \
+                     which is more than the maximum recursion depth of {max_depth}"
+                )
+            }
+            depth
+        } else {
+            1
+        };
+        let mut levels = vec![base_leaf];
+        let mut previous_level = base_leaf;
+        for _ in 0..depth {
+            self.recursive_cte_levels
+                .insert(to_view.clone(), previous_level);
+            let level = self.select_query_to_mir(
+                query_name.clone(),
+                &mut step.clone(),
+                search_path,
+                None,
+                LeafBehavior::Anonymous,
+                mig,
+            );
+            self.recursive_cte_levels.remove(to_view);
+            previous_level = level?;
+            levels.push(previous_level);
+        }
+
+        self.mir_converter
+            .make_recursive_cte_nodes(query_name, to_view, &levels, !all)
+    }
+
     /// Compile the given uncompiled view all the way to dataflow
     fn compile_uncompiled_view(
         &mut self,
@@ -1189,3 +1346,151 @@ impl SqlIncorporator {
         self.view_schemas.insert(query_name, fields);
     }
 }
+
+/// Returns the value of `expr` if it's an integer literal
+fn integer_literal(expr: &Expr) -> Option<i128> {
+    match expr {
+        Expr::Literal(Literal::Integer(i)) => Some((*i).into()),
+        Expr::Literal(Literal::UnsignedInteger(i)) => Some((*i).into()),
+        _ => None,
+    }
+}
+
+/// Returns an upper bound on the number of levels of recursion of the recursive CTE `name` that
+/// can produce rows, or `None` if the recursion can't be shown to be bounded.
+///
+/// The recursion is bounded if there's a column of the CTE which is an integer literal in the
+/// non-recursive term (whose values are given by `base_values`), which the recursive term
+/// (`step`) increments by a positive integer literal, and which the `WHERE` clause of the
+/// recursive term limits to less than (or equal to) an integer literal - for example, the `depth`
+/// column in:
+///
+/// ```sql
+/// WITH RECURSIVE r AS (
+///     SELECT id, 1 AS depth FROM t WHERE parent_id IS NULL
+///     UNION ALL
+///     SELECT t.id, r.depth + 1 FROM t JOIN r ON t.parent_id = r.id WHERE r.depth < 5
+/// )
+/// ```
+fn recursion_depth_bound(
+    name: &SqlIdentifier,
+    base_values: &[Option<i128>],
+    columns: &[SqlIdentifier],
+    step: &SelectStatement,
+) -> Option<usize> {
+    fn conjuncts<'a>(expr: &'a Expr, out: &mut Vec<&'a Expr>) {
+        match expr {
+            Expr::BinaryOp {
+                lhs,
+                op: BinaryOperator::And,
+                rhs,
+            } => {
+                conjuncts(lhs, out);
+                conjuncts(rhs, out);
+            }
+            _ => out.push(expr),
+        }
+    }
+
+    let is_cte_column = |expr: &Expr, column: &SqlIdentifier| {
+        matches!(
+            expr,
+            Expr::Column(c) if c.name == *column
+                && c.table.as_ref().map_or(true, |t| t.schema.is_none() && t.name == *name)
+        )
+    };
+
+    let mut filters = vec![];
+    if let Some(where_clause) = &step.where_clause {
+        conjuncts(where_clause, &mut filters);
+    }
+
+    let mut bound = None;
+    for ((field, base), column) in step.fields.iter().zip(base_values).zip(columns) {
+        let (FieldDefinitionExpr::Expr { expr, .. }, Some(base)) = (field, base) else {
+            continue;
+        };
+
+        // Each level of recursion must add a positive constant to the column...
+        let Expr::BinaryOp {
+            lhs,
+            op: BinaryOperator::Add,
+            rhs,
+        } = expr
+        else {
+            continue;
+        };
+        let increment = if is_cte_column(lhs, column) {
+            integer_literal(rhs)
+        } else if is_cte_column(rhs, column) {
+            integer_literal(lhs)
+        } else {
+            None
+        };
+        let Some(increment) = increment.filter(|i| *i > 0) else {
+            continue;
+        };
+
+        // ...and only recurse for rows where the column is below a constant
+        for filter in &filters {
+            let Expr::BinaryOp { lhs, op, rhs } = filter else {
+                continue;
+            };
+            let (limit, inclusive) = match op {
+                BinaryOperator::Less | BinaryOperator::LessOrEqual
+                    if is_cte_column(lhs, column) =>
+                {
+                    (integer_literal(rhs), *op == BinaryOperator::LessOrEqual)
+                }
+                BinaryOperator::Greater | BinaryOperator::GreaterOrEqual
+                    if is_cte_column(rhs, column) =>
+                {
+                    (integer_literal(lhs), *op == BinaryOperator::GreaterOrEqual)
+                }
+                _ => continue,
+            };
+            let Some(limit) = limit else {
+                continue;
+            };
+
+            // Rows produced by level `n` have `base + n * increment` in the column, and level `n`
+            // only produces rows if the rows of level `n - 1` pass the filter
+            let levels = if inclusive {
+                if limit < *base {
+                    0
+                } else {
+                    (limit - base) / increment + 1
+                }
+            } else if limit <= *base {
+                0
+            } else {
+                (limit - base + increment - 1) / increment
+            };
+            let levels = usize::try_from(levels).unwrap_or(usize::MAX);
+            bound = Some(bound.map_or(levels, |b: usize| b.min(levels)));
+        }
+    }
+
+    bound
+}
+
+/// Returns true if the given statement (or any subquery within it) references a table with the
+/// given (unqualified) name
+fn references_table(stmt: &SelectStatement, name: &SqlIdentifier) -> bool {
+    struct FindTable<'a>(&'a SqlIdentifier);
+
+    impl<'ast, 'a> Visitor<'ast> for FindTable<'a> {
+        type Error = ();
+
+        fn visit_table(&mut self, table: &'ast Relation) -> Result<(), Self::Error> {
+            if table.schema.is_none() && table.name == *self.0 {
+                // Stop walking as soon as we find a reference
+                Err(())
+            } else {
+                Ok(())
+            }
+        }
+    }
+
+    FindTable(name).visit_select_statement(stmt).is_err()
+}
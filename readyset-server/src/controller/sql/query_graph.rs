@@ -7,6 +7,7 @@ use std::vec::Vec;
 use std::{iter, mem};
 
 use common::{DfValue, IndexType};
+use itertools::Itertools;
 use nom_sql::analysis::visit_mut::{walk_expr, VisitorMut};
 use nom_sql::analysis::ReferredColumns;
 use nom_sql::{
@@ -15,6 +16,8 @@ use nom_sql::{
     Relation, SelectStatement, SqlIdentifier, TableExpr, TableExprInner,
 };
 use readyset_client::{PlaceholderIdx, ViewPlaceholder};
+use readyset_data::dialect::SqlEngine;
+use readyset_data::Dialect;
 use readyset_errors::{
     internal, invalid, invalid_err, invariant, invariant_eq, no_table_for_col, unsupported,
     unsupported_err, ReadySetResult,
@@ -179,6 +182,11 @@ pub struct Parameter {
     pub col: Column,
     pub op: nom_sql::BinaryOperator,
     pub placeholder_idx: Option<PlaceholderIdx>,
+    /// If the placeholder is compared against an expression rather than directly against a
+    /// column, that expression. The expression gets projected as the (table-less) column `col`
+    /// before any grouped operators (see `make_expressions_above_grouped`), and that column is
+    /// then used as the lookup key like any other parameter column
+    pub expr: Option<Expr>,
 }
 
 #[derive(Clone, Debug, Hash, PartialEq, Serialize, Deserialize)]
@@ -190,6 +198,9 @@ pub struct QueryGraphNode {
     /// If this query graph relation refers to a subquery, the graph of that subquery and the AST
     /// for the query itself
     pub subgraph: Option<Box<QueryGraph>>,
+    /// If this query graph relation refers to a call to a set-returning function, that function
+    /// call
+    pub function: Option<SetReturningFunctionCall>,
 }
 
 #[derive(Clone, Debug, Hash, PartialEq, Eq, Serialize, Deserialize)]
@@ -201,15 +212,140 @@ pub enum QueryGraphEdge {
         on: Vec<JoinPredicate>,
         extra_preds: Vec<Expr>,
     },
+    FullJoin {
+        on: Vec<JoinPredicate>,
+        extra_preds: Vec<Expr>,
+    },
+}
+
+/// The `LIMIT` of a paginated query
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PaginationLimit {
+    /// A numeric literal, such as `LIMIT 10`
+    Literal(usize),
+    /// A parametrized limit, such as `LIMIT $1`. A TopK node keeps the first
+    /// [`max_parametrized_limit`](mir::Config::max_parametrized_limit) rows for each key, and the
+    /// value for the limit is passed along with each lookup and applied in the reader
+    Placeholder,
+    /// No limit at all, for queries that only have an `OFFSET`
+    Unbounded,
 }
 
 #[derive(Clone, Debug, Hash, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Pagination {
     pub order: Option<Vec<(Expr, OrderType)>>,
-    pub limit: usize,
+    pub limit: PaginationLimit,
     pub offset: Option<ViewPlaceholder>,
 }
 
+/// A window function projected by a query, such as `ROW_NUMBER() OVER (PARTITION BY x ORDER BY
+/// y)`
+#[derive(Clone, Debug, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WindowFunctionCall {
+    /// The function being computed over the window
+    pub function: FunctionExpr,
+    /// The columns in the `PARTITION BY` clause of the window
+    pub partition_by: Vec<Column>,
+    /// The columns (and directions) in the `ORDER BY` clause of the window
+    pub order: Vec<(Column, OrderType)>,
+    /// The name of the column the result of the window function is projected as
+    pub alias: SqlIdentifier,
+}
+
+impl WindowFunctionCall {
+    fn from_function_expr(function: &FunctionExpr, alias: SqlIdentifier) -> ReadySetResult<Self> {
+        let FunctionExpr::Over {
+            function,
+            partition_by,
+            order_by,
+        } = function else {
+            internal!("Window function call must be a FunctionExpr::Over");
+        };
+
+        let partition_by = partition_by
+            .iter()
+            .map(|expr| match expr {
+                Expr::Column(c) => Ok(c.clone()),
+                _ => unsupported!(
+                    "Only column references are currently supported in window PARTITION BY"
+                ),
+            })
+            .collect::<ReadySetResult<_>>()?;
+
+        let order = order_by
+            .iter()
+            .flat_map(|oc| oc.order_by.iter())
+            .map(|(field, ot)| match field {
+                FieldReference::Expr(Expr::Column(c)) => {
+                    Ok((c.clone(), ot.unwrap_or(OrderType::OrderAscending)))
+                }
+                FieldReference::Expr(_) => {
+                    unsupported!(
+                        "Only column references are currently supported in window ORDER BY"
+                    )
+                }
+                FieldReference::Numeric(_) => {
+                    unsupported!("Numeric field references are not supported in window ORDER BY")
+                }
+            })
+            .collect::<ReadySetResult<_>>()?;
+
+        Ok(WindowFunctionCall {
+            function: (**function).clone(),
+            partition_by,
+            order,
+            alias,
+        })
+    }
+}
+
+/// A call to a set-returning function such as `unnest` or `jsonb_array_elements` in the `FROM`
+/// clause of a query, which produces one row for each element of the value of a column in another
+/// relation
+#[derive(Clone, Debug, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SetReturningFunctionCall {
+    /// The name of the function being called
+    pub name: SqlIdentifier,
+    /// The column whose values are exploded into rows
+    pub argument: Column,
+    /// The name of the column the elements are projected as
+    pub column: SqlIdentifier,
+}
+
+impl SetReturningFunctionCall {
+    fn from_table_expr(table_expr: &TableExpr) -> ReadySetResult<Self> {
+        let TableExprInner::Function(function) = &table_expr.inner else {
+            internal!("Set-returning function call must be a TableExprInner::Function");
+        };
+        let column = table_expr.function_column().cloned().ok_or_else(|| {
+            unsupported_err!("Set-returning functions in the FROM clause must have an alias")
+        })?;
+
+        match function {
+            FunctionExpr::Call { name, arguments } => match &arguments[..] {
+                [Expr::Column(argument @ Column { table: Some(_), .. })] => Ok(Self {
+                    name: name.clone(),
+                    argument: argument.clone(),
+                    column,
+                }),
+                _ => unsupported!(
+                    "Only column references are currently supported as arguments to \
+                     set-returning functions"
+                ),
+            },
+            _ => unsupported!(
+                "Unsupported set-returning function: {}",
+                function.display(nom_sql::Dialect::MySQL)
+            ),
+        }
+    }
+
+    /// Returns the relation that the argument to this function refers to
+    pub fn source(&self) -> ReadySetResult<&Relation> {
+        self.argument.table.as_ref().ok_or_else(no_table_for_col)
+    }
+}
+
 /// Description of the lookup key for a view
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct ViewKey {
@@ -239,8 +375,20 @@ pub struct QueryGraph {
     /// If a single aggregate is projected as multiple aliases, only one will appear in this map,
     /// but both will appear in `self.columns` as [`OutputColumn::Data`] referencing that alias
     pub aggregates: HashMap<FunctionExpr, SqlIdentifier>,
+    /// Window functions projected by the query, in the order they appear in the query
+    pub window_functions: Vec<WindowFunctionCall>,
     /// Set of columns that appear in the GROUP BY clause
+    ///
+    /// Expressions in the GROUP BY clause other than column references appear here as references
+    /// to the (table-less) columns they're projected as - see [`Self::group_by_exprs`]
     pub group_by: HashSet<Column>,
+    /// Expressions other than column references that appear in the GROUP BY clause, represented
+    /// as a map from the expression to the name of the column it's projected as before the
+    /// grouped operators
+    ///
+    /// Any occurrences of these expressions in the select list, HAVING clause, or ORDER BY
+    /// clause have been replaced with references to those columns
+    pub group_by_exprs: HashMap<Expr, SqlIdentifier>,
     /// Final set of projected columns in this query; may include literals in addition to the
     /// columns reflected in individual relations' `QueryGraphNode` structures.
     pub columns: Vec<OutputColumn>,
@@ -255,6 +403,9 @@ pub struct QueryGraph {
     pub join_order: Vec<JoinRef>,
     /// Global predicates (not associated with a particular relation)
     pub global_predicates: Vec<Expr>,
+    /// Parameters compared against expressions rather than columns of a particular relation (see
+    /// [`Parameter::expr`])
+    pub global_parameters: Vec<Parameter>,
     /// HAVING predicates (like global predicates, but applied after aggregate functions)
     pub having_predicates: Vec<Expr>,
     /// The list of columns and directionsj that the query is ordering by, if any
@@ -273,6 +424,7 @@ impl QueryGraph {
         self.relations
             .values()
             .flat_map(|qgn| qgn.parameters.iter())
+            .chain(&self.global_parameters)
             .collect()
     }
 
@@ -391,12 +543,11 @@ impl QueryGraph {
                 },
             )?;
 
-            if let Some(offset) = offset {
-                if index_type == Some(IndexType::BTreeMap) {
-                    unsupported!("ReadySet does not support Pagination and range queries")
-                } else {
-                    columns.push((mir::Column::named(PAGE_NUMBER_COL.clone()), offset));
-                }
+            // Pages of a range query span many keys in the reader, so they can't be numbered
+            // ahead of time by a paginate node. Instead, the reader orders the results of the range
+            // lookup and applies the offset (passed along with the lookup) and limit to them
+            if let Some(offset) = offset.filter(|_| index_type != Some(IndexType::BTreeMap)) {
+                columns.push((mir::Column::named(PAGE_NUMBER_COL.clone()), offset));
             }
 
             #[allow(clippy::expect_used)]
@@ -428,16 +579,22 @@ impl Hash for QueryGraph {
         group_by.sort();
         group_by.hash(state);
 
+        let mut group_by_exprs = self.group_by_exprs.iter().collect::<Vec<_>>();
+        group_by_exprs.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+        group_by_exprs.hash(state);
+
         let mut aggregates = self.aggregates.iter().collect::<Vec<_>>();
         aggregates.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
         aggregates.hash(state);
 
         // these fields are Vecs, so already ordered
+        self.window_functions.hash(state);
         self.columns.hash(state);
         self.fields.hash(state);
         self.default_row.hash(state);
         self.join_order.hash(state);
         self.global_predicates.hash(state);
+        self.global_parameters.hash(state);
         self.having_predicates.hash(state);
         self.order.hash(state);
         self.pagination.hash(state);
@@ -580,17 +737,30 @@ fn classify_conditionals(
             } else if is_predicate(op) {
                 // atomic selection predicate
                 if let Expr::Literal(Literal::Placeholder(ref placeholder)) = **rhs {
-                    if let Expr::Column(ref lf) = **lhs {
-                        let idx = match placeholder {
-                            ItemPlaceholder::DollarNumber(idx) => Some(*idx as usize),
-                            _ => None,
-                        };
-                        params.push(Parameter {
-                            col: lf.clone(),
-                            op: *op,
-                            placeholder_idx: idx,
-                        });
-                    }
+                    let idx = match placeholder {
+                        ItemPlaceholder::DollarNumber(idx) => Some(*idx as usize),
+                        _ => None,
+                    };
+                    let (col, expr) = match &**lhs {
+                        Expr::Column(lf) => (lf.clone(), None),
+                        // Placeholders compared against expressions are looked up on a column
+                        // that the expression is projected as, which is named after the
+                        // expression itself (just like expressions in the GROUP BY clause)
+                        expr => (
+                            Column {
+                                // FIXME(ENG-2499): Use correct dialect.
+                                name: expr.display(nom_sql::Dialect::MySQL).to_string().into(),
+                                table: None,
+                            },
+                            Some(expr.clone()),
+                        ),
+                    };
+                    params.push(Parameter {
+                        col,
+                        op: *op,
+                        placeholder_idx: idx,
+                        expr,
+                    });
                 } else if let Expr::Column(Column {
                     table: Some(table), ..
                 }) = &**lhs
@@ -602,35 +772,15 @@ fn classify_conditionals(
                     global.push(ce.clone());
                 }
             } else {
-                unsupported!("Arithmetic not supported here")
+                // Any other expression (such as arithmetic) is a filter on the truthiness of its
+                // value
+                classify_by_tables(ce, local, global);
             }
         }
         Expr::In {
-            lhs,
-            rhs: InValue::List(rhs),
+            rhs: InValue::List(_),
             ..
-        } => {
-            let tables = lhs
-                .referred_columns()
-                .chain(rhs.iter().flat_map(|expr| expr.referred_columns()))
-                .flat_map(|col| &col.table)
-                .collect::<HashSet<_>>();
-            let num_tables = tables.len();
-            match tables.into_iter().next() {
-                // TODO(grfn): This limitation probably isn't too hard to lift
-                None => {
-                    unsupported!("Filter conditions must currently mention at least one column")
-                }
-                Some(table) if num_tables == 1 => {
-                    // only one table mentioned, so local
-                    local.entry(table.clone()).or_default().push(ce.clone())
-                }
-                _ => {
-                    // more than 1 table mentioned, so must be a global predicate
-                    global.push(ce.clone())
-                }
-            }
-        }
+        } => classify_by_tables(ce, local, global),
         Expr::Exists(_) => {
             // TODO(grfn): Look into the query for correlated references to see if it's actually a
             // local predicate in disguise
@@ -642,7 +792,11 @@ fn classify_conditionals(
         Expr::In {
             rhs: InValue::Subquery(..),
             ..
-        } => unsupported!("IN with subqueries is not yet supported"),
+        } => {
+            // Lowered to a semi- or anti-join against the subquery, which happens after all the
+            // tables in the query have been joined together
+            global.push(ce.clone())
+        }
         Expr::Call(_)
         | Expr::Literal(_)
         | Expr::UnaryOp { .. }
@@ -654,11 +808,27 @@ fn classify_conditionals(
         | Expr::NestedSelect(_)
         | Expr::Cast { .. }
         | Expr::Array(_)
+        | Expr::Interval { .. }
         | Expr::Variable(_) => global.push(ce.clone()),
     }
     Ok(())
 }
 
+/// Add the given filter condition to the local predicates of the relation it references if it
+/// only references one relation, or to the global predicates otherwise
+fn classify_by_tables(ce: &Expr, local: &mut HashMap<Relation, Vec<Expr>>, global: &mut Vec<Expr>) {
+    let tables = ce
+        .referred_columns()
+        .flat_map(|col| &col.table)
+        .collect::<HashSet<_>>();
+    match tables.into_iter().exactly_one() {
+        // only one table mentioned, so local
+        Ok(table) => local.entry(table.clone()).or_default().push(ce.clone()),
+        // either no tables or more than one table mentioned, so must be a global predicate
+        Err(_) => global.push(ce.clone()),
+    }
+}
+
 /// Convert the given `Expr`, which should be a set of AND-ed together direct
 /// comparison predicates, into a list of predicate expressions
 fn collect_join_predicates(
@@ -743,65 +913,138 @@ fn extract_having_aggregates(
     having_predicates
 }
 
-/// Convert limit and offset fields to an optional constant numeric limit and optional placeholder
-/// for the offset
-pub(crate) fn extract_limit_offset(
-    limit_clause: &LimitClause,
-) -> ReadySetResult<Option<(usize, Option<ViewPlaceholder>)>> {
-    if limit_clause.limit().is_none() && limit_clause.offset().is_some() {
-        unsupported!("ReadySet does not support OFFSET without LIMIT");
+/// Returns the aggregates that need to be computed alongside `function` so that its results for
+/// multiple keys can be combined in the reader after a lookup.
+///
+/// `AVG` is recomputed from the sum and count of its argument, and `COUNT(DISTINCT)` from the
+/// distinct values of its argument.
+pub(crate) fn reaggregation_helpers(function: &FunctionExpr) -> Vec<FunctionExpr> {
+    match function {
+        FunctionExpr::Avg {
+            expr,
+            distinct: false,
+        } => vec![
+            FunctionExpr::Sum {
+                expr: expr.clone(),
+                distinct: false,
+            },
+            FunctionExpr::Count {
+                expr: expr.clone(),
+                distinct: false,
+            },
+        ],
+        FunctionExpr::Count {
+            expr,
+            distinct: true,
+        } => vec![FunctionExpr::ArrayAgg {
+            expr: expr.clone(),
+            order_by: None,
+        }],
+        _ => vec![],
     }
+}
 
-    let limit = if let Some(limit) = limit_clause.limit() {
-        limit
-    } else {
-        return Ok(None);
-    };
+/// Replace all occurrences of the given `GROUP BY` expressions in `expr` (outside of aggregates and
+/// subqueries) with references to the columns they're projected as
+fn replace_group_by_exprs(expr: &mut Expr, group_by_exprs: &HashMap<Expr, SqlIdentifier>) {
+    struct GroupByExprReplacer<'a> {
+        group_by_exprs: &'a HashMap<Expr, SqlIdentifier>,
+    }
+
+    impl<'ast, 'a> VisitorMut<'ast> for GroupByExprReplacer<'a> {
+        type Error = !;
+
+        fn visit_expr(&mut self, expr: &'ast mut Expr) -> Result<(), Self::Error> {
+            if let Some(name) = self.group_by_exprs.get(expr) {
+                *expr = Expr::Column(Column {
+                    name: name.clone(),
+                    table: None,
+                });
+                Ok(())
+            } else if matches!(expr, Expr::Call(fun) if is_aggregate(fun)) {
+                // Aggregates are computed over the rows within each group, so their arguments
+                // still refer to the columns in the parent tables
+                Ok(())
+            } else {
+                walk_expr(self, expr)
+            }
+        }
 
-    let limit = match limit {
-        Literal::UnsignedInteger(val) => *val,
-        Literal::Integer(val) => u64::try_from(*val)
-            .map_err(|_| unsupported_err!("LIMIT field cannot have a negative value"))?,
-        Literal::Placeholder(_) => {
-            unsupported!("ReadySet does not support parametrized LIMIT fields")
+        fn visit_select_statement(
+            &mut self,
+            _: &'ast mut SelectStatement,
+        ) -> Result<(), Self::Error> {
+            // Don't walk into subqueries
+            Ok(())
         }
-        _ => unsupported!("Invalid LIMIT statement"),
+    }
+
+    if !group_by_exprs.is_empty() {
+        let _ = GroupByExprReplacer { group_by_exprs }.visit_expr(expr);
+    }
+}
+
+/// Convert limit and offset fields to a [`PaginationLimit`] and optional placeholder for the page
+/// number
+///
+/// Only queries with a literal limit are paginated by page number - for parametrized limits and
+/// queries without a limit, the value for the offset is passed along with each lookup instead, and
+/// applied in the reader.
+pub(crate) fn extract_limit_offset(
+    limit_clause: &LimitClause,
+) -> ReadySetResult<Option<(PaginationLimit, Option<ViewPlaceholder>)>> {
+    let limit = match limit_clause.limit() {
+        None if limit_clause.offset().is_none() => return Ok(None),
+        None => PaginationLimit::Unbounded,
+        Some(Literal::UnsignedInteger(val)) => PaginationLimit::Literal(*val as _),
+        Some(Literal::Integer(val)) => PaginationLimit::Literal(
+            usize::try_from(*val)
+                .map_err(|_| unsupported_err!("LIMIT field cannot have a negative value"))?,
+        ),
+        Some(Literal::Placeholder(_)) => PaginationLimit::Placeholder,
+        Some(_) => unsupported!("Invalid LIMIT statement"),
     };
 
-    let offset = limit_clause
-        .offset()
-        .as_ref()
+    let offset = match (limit_clause.offset(), limit) {
         // For now, remove offset if it is a literal 0
-        .filter(|offset| !matches!(offset, Literal::UnsignedInteger(0)))
-        .map(|offset| -> ReadySetResult<ViewPlaceholder> {
-            match offset {
-                Literal::Placeholder(ItemPlaceholder::DollarNumber(idx)) => {
-                    Ok(ViewPlaceholder::PageNumber {
-                        offset_placeholder: *idx as _,
-                        limit,
-                    })
-                }
-                _ => unsupported!("Numeric OFFSETs must be parametrized"),
-            }
-        })
-        .transpose()?;
+        (None | Some(Literal::UnsignedInteger(0)), _) => None,
+        (
+            Some(Literal::Placeholder(ItemPlaceholder::DollarNumber(idx))),
+            PaginationLimit::Literal(limit),
+        ) => Some(ViewPlaceholder::PageNumber {
+            offset_placeholder: *idx as _,
+            limit: limit as _,
+        }),
+        (Some(_), PaginationLimit::Placeholder | PaginationLimit::Unbounded) => None,
+        (Some(_), PaginationLimit::Literal(_)) => {
+            unsupported!("Numeric OFFSETs must be parametrized")
+        }
+    };
 
-    Ok(Some((limit as _, offset)))
+    Ok(Some((limit, offset)))
 }
 
 fn table_expr_name(table_expr: &TableExpr) -> ReadySetResult<Relation> {
     match &table_expr.inner {
         TableExprInner::Table(t) => Ok(t.clone()),
-        TableExprInner::Subquery(_) => Ok(table_expr
+        TableExprInner::Subquery(_) | TableExprInner::LateralSubquery(_) => Ok(table_expr
             .alias
             .as_ref()
             .ok_or_else(|| invalid_err!("All subqueries must have an alias"))?
             .clone()
             .into()),
+        TableExprInner::Function(_) => Ok(table_expr
+            .alias
+            .as_ref()
+            .ok_or_else(|| {
+                unsupported_err!("Set-returning functions in the FROM clause must have an alias")
+            })?
+            .clone()
+            .into()),
     }
 }
 
-fn default_row_for_select(st: &SelectStatement) -> Option<Vec<DfValue>> {
+fn default_row_for_select(st: &SelectStatement, dialect: Dialect) -> Option<Vec<DfValue>> {
     // If this is an aggregated query AND it does not contain a GROUP BY clause,
     // set default values based on the aggregation (or lack thereof) on each
     // individual field
@@ -823,7 +1066,28 @@ fn default_row_for_select(st: &SelectStatement) -> Option<Vec<DfValue>> {
                     FunctionExpr::Max(..) => DfValue::None,
                     FunctionExpr::Min(..) => DfValue::None,
                     FunctionExpr::GroupConcat { .. } => DfValue::None,
-                    FunctionExpr::Call { .. } | FunctionExpr::Substring { .. } => DfValue::None,
+                    // MySQL's BIT_AND() and BIT_OR() return the identity of the operation over no
+                    // values, whereas PostgreSQL's return NULL
+                    FunctionExpr::BitAnd(..) if dialect.engine() == SqlEngine::MySQL => {
+                        DfValue::from(u64::MAX)
+                    }
+                    FunctionExpr::BitOr(..) if dialect.engine() == SqlEngine::MySQL => {
+                        DfValue::from(0u64)
+                    }
+                    FunctionExpr::Stddev { .. }
+                    | FunctionExpr::Variance { .. }
+                    | FunctionExpr::BoolAnd(..)
+                    | FunctionExpr::BoolOr(..)
+                    | FunctionExpr::BitAnd(..)
+                    | FunctionExpr::BitOr(..) => DfValue::None,
+                    FunctionExpr::ArrayAgg { .. }
+                    | FunctionExpr::StringAgg { .. }
+                    | FunctionExpr::JsonAgg { .. }
+                    | FunctionExpr::JsonObjectAgg { .. } => DfValue::None,
+                    FunctionExpr::Call { .. }
+                    | FunctionExpr::Substring { .. }
+                    | FunctionExpr::Extract { .. }
+                    | FunctionExpr::Over { .. } => DfValue::None,
                 },
                 _ => DfValue::None,
             })
@@ -832,7 +1096,7 @@ fn default_row_for_select(st: &SelectStatement) -> Option<Vec<DfValue>> {
 }
 
 #[allow(clippy::cognitive_complexity)]
-pub fn to_query_graph(stmt: SelectStatement) -> ReadySetResult<QueryGraph> {
+pub fn to_query_graph(stmt: SelectStatement, dialect: Dialect) -> ReadySetResult<QueryGraph> {
     // a handy closure for making new relation nodes
     let new_node = |rel: Relation,
                     preds: Vec<Expr>,
@@ -879,10 +1143,11 @@ pub fn to_query_graph(stmt: SelectStatement) -> ReadySetResult<QueryGraph> {
                 .collect(),
             parameters: Vec::new(),
             subgraph: None,
+            function: None,
         })
     };
 
-    let default_row = default_row_for_select(&stmt);
+    let default_row = default_row_for_select(&stmt, dialect);
     let is_correlated = is_correlated(&stmt);
 
     // Used later on to determine whether to classify predicates as "join predicates" or not
@@ -907,17 +1172,22 @@ pub fn to_query_graph(stmt: SelectStatement) -> ReadySetResult<QueryGraph> {
                 };
                 Ok(t.clone())
             }
-            TableExprInner::Subquery(sq) => {
-                let rel = Relation::from(
-                    table_expr
-                        .alias
-                        .as_ref()
-                        .ok_or_else(|| invalid_err!("All subqueries must have an alias"))?
-                        .clone(),
-                );
+            TableExprInner::Subquery(_)
+            | TableExprInner::LateralSubquery(_)
+            | TableExprInner::Function(_) => {
+                let rel = table_expr_name(table_expr)?;
                 if let Entry::Vacant(e) = relations.entry(rel.clone()) {
                     let mut node = new_node(rel.clone(), vec![], &stmt.fields)?;
-                    node.subgraph = Some(Box::new(to_query_graph((**sq).clone())?));
+                    match &table_expr.inner {
+                        TableExprInner::Subquery(sq) | TableExprInner::LateralSubquery(sq) => {
+                            node.subgraph =
+                                Some(Box::new(to_query_graph((**sq).clone(), dialect)?));
+                        }
+                        _ => {
+                            node.function =
+                                Some(SetReturningFunctionCall::from_table_expr(table_expr)?);
+                        }
+                    }
                     e.insert(node);
                 } else {
                     invalid!(
@@ -948,10 +1218,21 @@ pub fn to_query_graph(stmt: SelectStatement) -> ReadySetResult<QueryGraph> {
         };
     }
 
+    // Set-returning functions don't get joined against - instead, they're computed directly on
+    // top of the relation their argument refers to
+    let function_rels = relations
+        .iter()
+        .filter(|(_, node)| node.function.is_some())
+        .map(|(rel, _)| rel.clone())
+        .collect::<HashSet<_>>();
+
     // 2. Add edges for each pair of joined relations. Note that we must keep track of the join
     //    predicates here already, but more may be added when processing the WHERE clause lateron.
 
     let mut edges = HashMap::new();
+    // The explicit join edges, in the order the joins were written in the query
+    let mut written_join_order = Vec::new();
+    let mut has_right_or_full_join = false;
 
     // 2a. Explicit joins
 
@@ -970,6 +1251,22 @@ pub fn to_query_graph(stmt: SelectStatement) -> ReadySetResult<QueryGraph> {
             JoinRightSide::Table(te) => table_expr_name(&te)?,
             JoinRightSide::Tables(_) => unsupported!("JoinRightSide::Tables not yet implemented"),
         };
+
+        if function_rels.contains(&rhs_relation) {
+            if !jc.operator.is_inner_join() {
+                unsupported!("Only inner joins against set-returning functions are supported");
+            }
+            if let JoinConstraint::On(cond) = &jc.constraint {
+                classify_conditionals(
+                    cond,
+                    &mut local_predicates,
+                    &mut global_predicates,
+                    &mut query_parameters,
+                )?;
+            }
+            continue;
+        }
+
         // will be defined by join constraint
         let left_table;
         let right_table;
@@ -1003,6 +1300,11 @@ pub fn to_query_graph(stmt: SelectStatement) -> ReadySetResult<QueryGraph> {
                     // just one table mentioned --> this is a self-join
                     left_table = tables_mentioned.remove(0);
                     right_table = left_table.clone();
+                } else if tables_mentioned.is_empty() {
+                    // no tables mentioned (eg `ON TRUE`) --> this is a cartesian product, with the
+                    // condition evaluated separately
+                    left_table = prev_table.clone();
+                    right_table = rhs_relation;
                 } else {
                     unsupported!("more than 2 tables mentioned in join condition!");
                 };
@@ -1056,15 +1358,50 @@ pub fn to_query_graph(stmt: SelectStatement) -> ReadySetResult<QueryGraph> {
             }
         };
 
+        if matches!(
+            jc.operator,
+            JoinOperator::RightJoin
+                | JoinOperator::RightOuterJoin
+                | JoinOperator::FullJoin
+                | JoinOperator::FullOuterJoin
+        ) {
+            has_right_or_full_join = true;
+        }
+
+        // A right join is just a left join with the sides swapped, so normalize it to one here
+        let (left_table, right_table, on) = if matches!(
+            jc.operator,
+            JoinOperator::RightJoin | JoinOperator::RightOuterJoin
+        ) {
+            let on = on
+                .into_iter()
+                .map(|JoinPredicate { left, right }| JoinPredicate {
+                    left: right,
+                    right: left,
+                })
+                .collect();
+            (right_table, left_table, on)
+        } else {
+            (left_table, right_table, on)
+        };
+
         // add edge for join
         if let std::collections::hash_map::Entry::Vacant(e) =
             edges.entry((left_table.clone(), right_table.clone()))
         {
+            written_join_order.push(JoinRef {
+                src: left_table.clone(),
+                dst: right_table.clone(),
+            });
             e.insert(match jc.operator {
-                JoinOperator::LeftJoin | JoinOperator::LeftOuterJoin => {
-                    QueryGraphEdge::LeftJoin { on, extra_preds }
+                JoinOperator::LeftJoin
+                | JoinOperator::LeftOuterJoin
+                | JoinOperator::RightJoin
+                | JoinOperator::RightOuterJoin => QueryGraphEdge::LeftJoin { on, extra_preds },
+                JoinOperator::FullJoin | JoinOperator::FullOuterJoin => {
+                    QueryGraphEdge::FullJoin { on, extra_preds }
                 }
-                JoinOperator::Join | JoinOperator::InnerJoin => {
+                JoinOperator::Join | JoinOperator::InnerJoin | JoinOperator::CrossJoin => {
                     for pred in &extra_preds {
                         classify_conditionals(
                             pred,
@@ -1110,8 +1447,11 @@ pub fn to_query_graph(stmt: SelectStatement) -> ReadySetResult<QueryGraph> {
     //    node for this query. Such columns will be carried all the way through the operators
     //    implementing the query (unlike in a traditional query plan, where the predicates on
     //    parameters might be evaluated sooner).
+    let mut global_parameters = vec![];
     for param in query_parameters.into_iter() {
-        if let Some(table) = &param.col.table {
+        if param.expr.is_some() {
+            global_parameters.push(param);
+        } else if let Some(table) = &param.col.table {
             let rel = relations.get_mut(table).ok_or_else(|| {
                 invalid_err!(
                     "Column {} references non-existent table {}",
@@ -1129,17 +1469,50 @@ pub fn to_query_graph(stmt: SelectStatement) -> ReadySetResult<QueryGraph> {
         }
     }
 
+    // Expressions in the GROUP BY clause get projected before the grouped operators (see
+    // `make_expressions_above_grouped`), named after their textual representation, and then
+    // grouped on like any other column
+    let mut group_by_exprs = HashMap::new();
+    let group_by = if let Some(group_by_clause) = &stmt.group_by {
+        group_by_clause
+            .fields
+            .iter()
+            .map(|f| match f {
+                FieldReference::Numeric(_) => {
+                    internal!("Numeric field references should have been removed")
+                }
+                FieldReference::Expr(Expr::Column(c)) => Ok(c.clone()),
+                FieldReference::Expr(Expr::Call(fun)) if is_aggregate(fun) => {
+                    invalid!("Aggregate functions are not allowed in GROUP BY")
+                }
+                FieldReference::Expr(expr) => {
+                    // FIXME(ENG-2499): Use correct dialect.
+                    let name: SqlIdentifier =
+                        expr.display(nom_sql::Dialect::MySQL).to_string().into();
+                    group_by_exprs.insert(expr.clone(), name.clone());
+                    Ok(Column { name, table: None })
+                }
+            })
+            .collect::<ReadySetResult<HashSet<_>>>()?
+    } else {
+        Default::default()
+    };
+
     // Add HAVING predicates and aggregates. Note that unlike below for selected columns, we don't
     // add any found aggregate functions in the HAVING clause to qg.columns, since we don't want to
     // necessarily return these in the query results.
     let mut aggregates = HashMap::new();
-    let having_predicates = if let Some(having_expr) = stmt.having.as_ref() {
+    let mut having_predicates = if let Some(having_expr) = stmt.having.as_ref() {
         extract_having_aggregates(having_expr, &mut aggregates)
     } else {
         vec![]
     };
+    for pred in having_predicates.iter_mut() {
+        replace_group_by_exprs(pred, &group_by_exprs);
+    }
 
     let mut columns = Vec::with_capacity(stmt.fields.len());
+    let mut window_functions = vec![];
     for field in stmt.fields.iter() {
         match field {
             FieldDefinitionExpr::All | FieldDefinitionExpr::AllInTable(_) => {
@@ -1150,7 +1523,12 @@ pub fn to_query_graph(stmt: SelectStatement) -> ReadySetResult<QueryGraph> {
                     .clone()
                     // FIXME(ENG-2499): Use correct dialect.
                     .unwrap_or_else(|| expr.display(nom_sql::Dialect::MySQL).to_string().into());
-                match expr {
+                // Like aggregates, the values of any expressions that also appear in the GROUP BY
+                // clause will have already been projected by the time we're projecting the result
+                // set columns
+                let mut expr = expr.clone();
+                replace_group_by_exprs(&mut expr, &group_by_exprs);
+                match &expr {
                     Expr::Literal(l) => columns.push(OutputColumn::Literal(LiteralColumn {
                         name,
                         table: None,
@@ -1162,6 +1540,18 @@ pub fn to_query_graph(stmt: SelectStatement) -> ReadySetResult<QueryGraph> {
                             column: c.clone(),
                         });
                     }
+                    Expr::Call(function @ FunctionExpr::Over { .. }) => {
+                        // Like aggregates, the values for window functions will have already been
+                        // projected by the time we're projecting the result set columns
+                        window_functions.push(WindowFunctionCall::from_function_expr(
+                            function,
+                            name.clone(),
+                        )?);
+                        columns.push(OutputColumn::Data {
+                            alias: alias.clone().unwrap_or_else(|| name.clone()),
+                            column: Column { name, table: None },
+                        })
+                    }
                     Expr::Call(function) if is_aggregate(function) => {
                         let agg_name = aggregates
                             .entry(function.clone())
@@ -1198,30 +1588,38 @@ pub fn to_query_graph(stmt: SelectStatement) -> ReadySetResult<QueryGraph> {
         }
     }
 
-    let group_by = if let Some(group_by_clause) = &stmt.group_by {
-        group_by_clause
-            .fields
-            .iter()
-            .map(|f| match f {
-                FieldReference::Numeric(_) => {
-                    internal!("Numeric field references should have been removed")
-                }
-                FieldReference::Expr(Expr::Column(c)) => Ok(c.clone()),
-                FieldReference::Expr(_) => {
-                    unsupported!("Only column references are currently supported in GROUP BY")
-                }
-            })
-            .collect::<ReadySetResult<HashSet<_>>>()?
-    } else {
-        Default::default()
-    };
+    if !window_functions.is_empty() && (!aggregates.is_empty() || stmt.group_by.is_some()) {
+        unsupported!("Window functions in aggregated queries are not yet supported");
+    }
+
+    let stmt_order = stmt.order.clone().map(|mut order| {
+        for expr in order.order_by_exprs_mut() {
+            replace_group_by_exprs(expr, &group_by_exprs);
+        }
+        order
+    });
 
-    if let Some(ref order) = stmt.order {
+    if let Some(ref order) = stmt_order {
         // For each column in the `ORDER BY` clause, check if it needs to be projected
         order
             .order_by
             .iter()
             .for_each(|(ord_expr, _)| match ord_expr {
+                FieldReference::Expr(Expr::Column(col @ Column { table: None, name }))
+                    if group_by_exprs.values().any(|n| n == name) =>
+                {
+                    // This is a reference to an expression in the `GROUP BY` clause, which needs
+                    // to be projected if it isn't already
+                    if !columns
+                        .iter()
+                        .any(|e| matches!(e, OutputColumn::Data { column, .. } if column == col))
+                    {
+                        columns.push(OutputColumn::Data {
+                            alias: name.clone(),
+                            column: col.clone(),
+                        })
+                    }
+                }
                 FieldReference::Expr(Expr::Column(Column { table: None, .. })) => {
                     // This is a reference to a projected column, otherwise the table value
                     // would be assigned in the `rewrite_selection` pass
@@ -1266,8 +1664,7 @@ pub fn to_query_graph(stmt: SelectStatement) -> ReadySetResult<QueryGraph> {
             })
     }
 
-    let order = stmt
-        .order
+    let order = stmt_order
         .as_ref()
         .map(|order| {
             order
@@ -1298,8 +1695,7 @@ pub fn to_query_graph(stmt: SelectStatement) -> ReadySetResult<QueryGraph> {
     let pagination = extract_limit_offset(&stmt.limit_clause)?
         .map(|(limit, offset)| -> ReadySetResult<Pagination> {
             Ok(Pagination {
-                order: stmt
-                    .order
+                order: stmt_order
                     .as_ref()
                     .map(|o| {
                         o.order_by
@@ -1327,8 +1723,33 @@ pub fn to_query_graph(stmt: SelectStatement) -> ReadySetResult<QueryGraph> {
         })
         .transpose()?;
 
+    // If the query has parameters, lookups with multiple keys (such as those for `IN` lists) are
+    // re-aggregated in the reader. Some aggregates can't be combined from their own results, so
+    // make sure the aggregates they are derived from are computed as well.
+    if !aggregates.is_empty()
+        && !is_correlated
+        && relations.values().any(|rel| !rel.parameters.is_empty())
+    {
+        let helpers = aggregates
+            .keys()
+            .flat_map(reaggregation_helpers)
+            .collect::<Vec<_>>();
+        for func in helpers {
+            aggregates.entry(func.clone()).or_insert_with(|| {
+                // FIXME(ENG-2499): Use correct dialect.
+                func.display(nom_sql::Dialect::MySQL).to_string().into()
+            });
+        }
+    }
+
     // create initial join order
-    let join_order = {
+    let join_order = if has_right_or_full_join {
+        // Right and full joins aren't associative with the other joins in the query, so they
+        // have to be built in the order they were written. Since each join's right-hand side is a
+        // new relation, joining in written order builds the left-deep tree the query describes,
+        // with the (normalized) right-hand side of a right join being everything joined before it
+        written_join_order
+    } else {
         let mut sorted_edges: Vec<(&(Relation, Relation), &QueryGraphEdge)> =
             edges.iter().collect();
         // Sort the edges to ensure deterministic join order.
@@ -1348,12 +1769,15 @@ pub fn to_query_graph(stmt: SelectStatement) -> ReadySetResult<QueryGraph> {
         relations,
         edges,
         aggregates,
+        window_functions,
         group_by,
+        group_by_exprs,
         columns,
         fields: stmt.fields.clone(),
         default_row,
         join_order,
         global_predicates,
+        global_parameters,
         having_predicates,
         pagination,
         order,
@@ -1379,7 +1803,7 @@ mod tests {
             ),
         };
 
-        to_query_graph(query).unwrap()
+        to_query_graph(query, readyset_data::Dialect::DEFAULT_MYSQL).unwrap()
     }
 
     #[test]
@@ -1398,6 +1822,155 @@ mod tests {
         );
     }
 
+    #[test]
+    fn bit_aggregates_default_row() {
+        let query =
+            parse_select_statement(Dialect::MySQL, "SELECT bit_and(t1.x), bit_or(t1.x) FROM t1")
+                .unwrap();
+
+        let qg = to_query_graph(query.clone(), readyset_data::Dialect::DEFAULT_MYSQL).unwrap();
+        assert_eq!(
+            qg.default_row,
+            Some(vec![DfValue::from(u64::MAX), DfValue::from(0u64)])
+        );
+
+        let qg = to_query_graph(query, readyset_data::Dialect::DEFAULT_POSTGRESQL).unwrap();
+        assert_eq!(qg.default_row, Some(vec![DfValue::None, DfValue::None]));
+    }
+
+    #[test]
+    fn parametrized_aggregates_include_reaggregation_helpers() {
+        let qg = make_query_graph("SELECT avg(t1.x) FROM t1 WHERE t1.y = ?");
+        let x = || Box::new(Expr::Column("t1.x".into()));
+        assert_eq!(
+            qg.aggregates,
+            HashMap::from([
+                (
+                    FunctionExpr::Avg {
+                        expr: x(),
+                        distinct: false
+                    },
+                    "avg(`t1`.`x`)".into()
+                ),
+                (
+                    FunctionExpr::Sum {
+                        expr: x(),
+                        distinct: false
+                    },
+                    "sum(`t1`.`x`)".into()
+                ),
+                (
+                    FunctionExpr::Count {
+                        expr: x(),
+                        distinct: false
+                    },
+                    "count(`t1`.`x`)".into()
+                ),
+            ])
+        );
+
+        let unparametrized = make_query_graph("SELECT avg(t1.x) FROM t1");
+        assert_eq!(unparametrized.aggregates.len(), 1);
+    }
+
+    #[test]
+    fn window_functions() {
+        let qg = make_query_graph(
+            "SELECT t1.x, row_number() OVER (PARTITION BY t1.y ORDER BY t1.x DESC) AS rn FROM t1",
+        );
+        assert_eq!(
+            qg.window_functions,
+            vec![WindowFunctionCall {
+                function: FunctionExpr::Call {
+                    name: "row_number".into(),
+                    arguments: vec![],
+                },
+                partition_by: vec!["t1.y".into()],
+                order: vec![("t1.x".into(), OrderType::OrderDescending)],
+                alias: "rn".into(),
+            }]
+        );
+        assert_eq!(
+            qg.columns.last().unwrap(),
+            &OutputColumn::Data {
+                alias: "rn".into(),
+                column: Column {
+                    name: "rn".into(),
+                    table: None
+                }
+            }
+        );
+    }
+
+    #[test]
+    fn window_function_in_aggregated_query() {
+        let query = parse_select_statement(
+            Dialect::MySQL,
+            "SELECT count(t1.x), rank() OVER (ORDER BY t1.y) FROM t1 GROUP BY t1.y",
+        )
+        .unwrap();
+        assert!(to_query_graph(query, readyset_data::Dialect::DEFAULT_MYSQL)
+            .unwrap_err()
+            .is_unsupported());
+    }
+
+    #[test]
+    fn right_join_is_normalized_to_left_join() {
+        let qg = make_query_graph("SELECT t1.x, t2.y FROM t1 RIGHT JOIN t2 ON t1.id = t2.t1_id");
+        assert_eq!(
+            qg.edges,
+            HashMap::from([(
+                ("t2".into(), "t1".into()),
+                QueryGraphEdge::LeftJoin {
+                    on: vec![JoinPredicate {
+                        left: "t2.t1_id".into(),
+                        right: "t1.id".into(),
+                    }],
+                    extra_preds: vec![],
+                }
+            )])
+        );
+    }
+
+    #[test]
+    fn full_join() {
+        let qg =
+            make_query_graph("SELECT t1.x, t2.y FROM t1 FULL OUTER JOIN t2 ON t2.t1_id = t1.id");
+        assert_eq!(
+            qg.edges,
+            HashMap::from([(
+                ("t1".into(), "t2".into()),
+                QueryGraphEdge::FullJoin {
+                    on: vec![JoinPredicate {
+                        left: "t1.id".into(),
+                        right: "t2.t1_id".into(),
+                    }],
+                    extra_preds: vec![],
+                }
+            )])
+        );
+    }
+
+    #[test]
+    fn right_join_is_joined_in_written_order() {
+        let qg = make_query_graph(
+            "SELECT t1.x, t3.z FROM t1 JOIN t2 ON t1.x = t2.x RIGHT JOIN t3 ON t2.y = t3.y",
+        );
+        assert_eq!(
+            qg.join_order,
+            vec![
+                JoinRef {
+                    src: "t1".into(),
+                    dst: "t2".into(),
+                },
+                JoinRef {
+                    src: "t3".into(),
+                    dst: "t2".into(),
+                },
+            ]
+        );
+    }
+
     #[test]
     fn aggregates_with_alias() {
         let qg = make_query_graph("SELECT max(t1.x) AS max_x FROM t1 JOIN t2 ON t1.id = t2.id");
@@ -1519,6 +2092,56 @@ mod tests {
         assert!(subquery_rel.subgraph.is_some());
     }
 
+    #[test]
+    fn cross_join_lateral_subquery() {
+        let qg = make_query_graph(
+            "SELECT t.x, sq.y FROM t CROSS JOIN LATERAL \
+             (SELECT t2.y FROM t2 WHERE t2.t_id = t.id ORDER BY t2.y LIMIT 3) sq",
+        );
+
+        let subquery_rel = qg.relations.get(&Relation::from("sq")).unwrap();
+        assert!(subquery_rel.subgraph.as_ref().unwrap().is_correlated);
+        assert_eq!(
+            qg.edges,
+            HashMap::from([(
+                ("t".into(), "sq".into()),
+                QueryGraphEdge::Join { on: vec![] }
+            )])
+        );
+    }
+
+    #[test]
+    fn set_returning_function() {
+        let query = parse_select_statement(
+            Dialect::PostgreSQL,
+            "SELECT t.id, tag.tag FROM t CROSS JOIN LATERAL unnest(t.tags) AS tag",
+        )
+        .unwrap();
+        let qg = to_query_graph(query, readyset_data::Dialect::DEFAULT_MYSQL).unwrap();
+
+        assert!(qg.edges.is_empty());
+        assert_eq!(
+            qg.relations[&Relation::from("tag")].function,
+            Some(SetReturningFunctionCall {
+                name: "unnest".into(),
+                argument: Column::from("t.tags"),
+                column: "tag".into(),
+            })
+        );
+    }
+
+    #[test]
+    fn set_returning_function_without_alias() {
+        let query = parse_select_statement(
+            Dialect::PostgreSQL,
+            "SELECT t.id FROM t, jsonb_array_elements(t.data)",
+        )
+        .unwrap();
+        assert!(to_query_graph(query, readyset_data::Dialect::DEFAULT_MYSQL)
+            .unwrap_err()
+            .is_unsupported());
+    }
+
     #[test]
     fn duplicate_subquery_name() {
         let query = parse_select_statement(
@@ -1526,7 +2149,7 @@ mod tests {
             "select * from (select * from t) q1, (select * from t) q1;",
         )
         .unwrap();
-        to_query_graph(query).unwrap_err();
+        to_query_graph(query, readyset_data::Dialect::DEFAULT_MYSQL).unwrap_err();
     }
 
     #[test]
@@ -1574,6 +2197,21 @@ mod tests {
         assert_eq!(qg.global_predicates, vec![Expr::Literal(1u64.into())])
     }
 
+    #[test]
+    fn in_subquery_is_global_predicate() {
+        let qg = make_query_graph("SELECT t.x FROM t WHERE t.x NOT IN (SELECT t2.x FROM t2)");
+        assert!(qg.relations.get(&"t".into()).unwrap().predicates.is_empty());
+        assert_eq!(qg.global_predicates.len(), 1);
+        assert!(matches!(
+            qg.global_predicates[0],
+            Expr::In {
+                rhs: InValue::Subquery(_),
+                negated: true,
+                ..
+            }
+        ));
+    }
+
     #[test]
     fn local_pred_in_join_condition() {
         let qg = make_query_graph("SELECT t1.x FROM t1 JOIN t2 ON t1.x = t2.x AND t2.y = 4");
@@ -1596,6 +2234,108 @@ mod tests {
         )
     }
 
+    #[test]
+    fn group_by_expression() {
+        let qg = make_query_graph(
+            "SELECT lower(t.name), count(t.id) FROM t
+             GROUP BY lower(t.name)
+             HAVING lower(t.name) <> 'a'",
+        );
+        let expr = Expr::Call(FunctionExpr::Call {
+            name: "lower".into(),
+            arguments: vec![Expr::Column("t.name".into())],
+        });
+        let col = Column {
+            name: "lower(`t`.`name`)".into(),
+            table: None,
+        };
+
+        assert_eq!(
+            qg.group_by_exprs,
+            HashMap::from([(expr, "lower(`t`.`name`)".into())])
+        );
+        assert_eq!(qg.group_by, HashSet::from([col.clone()]));
+        assert_eq!(
+            qg.columns[0],
+            OutputColumn::Data {
+                alias: "lower(`t`.`name`)".into(),
+                column: col.clone(),
+            }
+        );
+        assert_eq!(
+            qg.having_predicates,
+            vec![Expr::BinaryOp {
+                lhs: Box::new(Expr::Column(col)),
+                op: BinaryOperator::NotEqual,
+                rhs: Box::new(Expr::Literal("a".into()))
+            }]
+        );
+    }
+
+    #[test]
+    fn aggregate_in_group_by() {
+        let query =
+            parse_select_statement(Dialect::MySQL, "SELECT t.x FROM t GROUP BY max(t.y)").unwrap();
+        assert!(to_query_graph(query, readyset_data::Dialect::DEFAULT_MYSQL)
+            .unwrap_err()
+            .is_invalid_query());
+    }
+
+    #[test]
+    fn arithmetic_filter() {
+        let qg = make_query_graph("SELECT t.x FROM t WHERE t.price * t.qty");
+        assert_eq!(
+            qg.relations.get(&"t".into()).unwrap().predicates,
+            vec![Expr::BinaryOp {
+                lhs: Box::new(Expr::Column("t.price".into())),
+                op: BinaryOperator::Multiply,
+                rhs: Box::new(Expr::Column("t.qty".into())),
+            }]
+        );
+    }
+
+    #[test]
+    fn in_list_without_columns() {
+        let qg = make_query_graph("SELECT t.x FROM t WHERE 1 IN (1, 2)");
+        assert!(qg.relations.get(&"t".into()).unwrap().predicates.is_empty());
+        assert_eq!(qg.global_predicates.len(), 1);
+    }
+
+    #[test]
+    fn parameter_compared_to_expression() {
+        let qg = make_query_graph("SELECT t.x FROM t WHERE t.price * t.qty > $1");
+        let expr = Expr::BinaryOp {
+            lhs: Box::new(Expr::Column("t.price".into())),
+            op: BinaryOperator::Multiply,
+            rhs: Box::new(Expr::Column("t.qty".into())),
+        };
+        let col = Column {
+            name: "(`t`.`price` * `t`.`qty`)".into(),
+            table: None,
+        };
+
+        assert!(qg.relations.get(&"t".into()).unwrap().parameters.is_empty());
+        assert_eq!(
+            qg.global_parameters,
+            vec![Parameter {
+                col: col.clone(),
+                op: BinaryOperator::Greater,
+                placeholder_idx: Some(1),
+                expr: Some(expr),
+            }]
+        );
+
+        let key = qg.view_key(&Default::default()).unwrap();
+        assert_eq!(key.index_type, IndexType::BTreeMap);
+        assert_eq!(
+            key.columns,
+            vec![(
+                mir::Column::from(col),
+                ViewPlaceholder::OneToOne(1, BinaryOperator::Greater)
+            )]
+        );
+    }
+
     mod view_key {
         use super::*;
 
@@ -1871,5 +2611,64 @@ mod tests {
                 ]
             );
         }
+
+        #[test]
+        fn paginated_range() {
+            let qg = make_query_graph(
+                "SELECT t.x FROM t WHERE t.x > $1 ORDER BY t.x ASC LIMIT 3 OFFSET $2",
+            );
+            let key = qg
+                .view_key(&mir::Config {
+                    allow_paginate: true,
+                    ..Default::default()
+                })
+                .unwrap();
+
+            assert_eq!(key.index_type, IndexType::BTreeMap);
+            assert_eq!(
+                key.columns,
+                vec![(
+                    mir::Column::new(Some("t"), "x"),
+                    ViewPlaceholder::OneToOne(1, BinaryOperator::Greater)
+                )]
+            );
+        }
+
+        #[test]
+        fn parametrized_limit() {
+            let qg = make_query_graph(
+                "SELECT t.x FROM t WHERE t.x = $1 ORDER BY t.y ASC LIMIT $2 OFFSET $3",
+            );
+            assert_eq!(
+                qg.pagination.as_ref().unwrap().limit,
+                PaginationLimit::Placeholder
+            );
+
+            let key = qg
+                .view_key(&mir::Config {
+                    allow_paginate: true,
+                    ..Default::default()
+                })
+                .unwrap();
+
+            // The limit and offset are applied in the reader, so they're not part of the key
+            assert_eq!(key.index_type, IndexType::HashMap);
+            assert_eq!(
+                key.columns,
+                vec![(
+                    mir::Column::new(Some("t"), "x"),
+                    ViewPlaceholder::OneToOne(1, BinaryOperator::Equal)
+                )]
+            );
+        }
+
+        #[test]
+        fn offset_without_limit() {
+            let qg =
+                make_query_graph("SELECT t.x FROM t WHERE t.x = $1 ORDER BY t.y ASC OFFSET $2");
+            let pagination = qg.pagination.as_ref().unwrap();
+            assert_eq!(pagination.limit, PaginationLimit::Unbounded);
+            assert_eq!(pagination.offset, None);
+        }
     }
 }
@@ -80,7 +80,8 @@ impl Signature for QueryGraph {
                         .flat_map(|p| vec![&p.left, &p.right])
                         .for_each(&mut record_column);
                 }
-                QueryGraphEdge::LeftJoin { on, extra_preds } => {
+                QueryGraphEdge::LeftJoin { on, extra_preds }
+                | QueryGraphEdge::FullJoin { on, extra_preds } => {
                     on.iter()
                         .flat_map(|p| vec![&p.left, &p.right])
                         .for_each(&mut record_column);
@@ -94,10 +95,16 @@ impl Signature for QueryGraph {
         }
 
         self.group_by.iter().for_each(&mut record_column);
+        self.group_by_exprs
+            .keys()
+            .flat_map(|expr| expr.referred_columns())
+            .for_each(&mut record_column);
 
-        // Global predicates are part of the attributes too
+        // Global predicates are part of the attributes too, as are the expressions compared
+        // against parameters
         self.global_predicates
             .iter()
+            .chain(self.global_parameters.iter().flat_map(|p| &p.expr))
             .flat_map(|p| p.referred_columns())
             .for_each(record_column);
 
@@ -162,19 +169,27 @@ mod tests {
         .unwrap();
 
         let qga = match qa {
-            SqlQuery::Select(q) => to_query_graph(q).unwrap(),
+            SqlQuery::Select(q) => {
+                to_query_graph(q, readyset_data::Dialect::DEFAULT_MYSQL).unwrap()
+            }
             _ => panic!(),
         };
         let qgb = match qb {
-            SqlQuery::Select(q) => to_query_graph(q).unwrap(),
+            SqlQuery::Select(q) => {
+                to_query_graph(q, readyset_data::Dialect::DEFAULT_MYSQL).unwrap()
+            }
             _ => panic!(),
         };
         let qgc = match qc {
-            SqlQuery::Select(q) => to_query_graph(q).unwrap(),
+            SqlQuery::Select(q) => {
+                to_query_graph(q, readyset_data::Dialect::DEFAULT_MYSQL).unwrap()
+            }
             _ => panic!(),
         };
         let qgd = match qd {
-            SqlQuery::Select(q) => to_query_graph(q).unwrap(),
+            SqlQuery::Select(q) => {
+                to_query_graph(q, readyset_data::Dialect::DEFAULT_MYSQL).unwrap()
+            }
             _ => panic!(),
         };
 
@@ -200,8 +215,10 @@ mod tests {
         let without_topk = parse_select("SELECT a.id FROM a").unwrap();
         let with_topk = parse_select("SELECT a.id FROM a ORDER BY n LIMIT 3").unwrap();
 
-        let without_topk_qg = to_query_graph(without_topk).unwrap();
-        let with_topk_qg = to_query_graph(with_topk).unwrap();
+        let without_topk_qg =
+            to_query_graph(without_topk, readyset_data::Dialect::DEFAULT_MYSQL).unwrap();
+        let with_topk_qg =
+            to_query_graph(with_topk, readyset_data::Dialect::DEFAULT_MYSQL).unwrap();
 
         let mut h1 = DefaultHasher::new();
         let mut h2 = DefaultHasher::new();
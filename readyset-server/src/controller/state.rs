@@ -45,7 +45,7 @@ use readyset_client::debug::stats::{DomainStats, GraphStats, NodeStats};
 use readyset_client::internal::{MaterializationStatus, ReplicaAddress};
 use readyset_client::metrics::recorded;
 use readyset_client::recipe::changelist::{Change, ChangeList};
-use readyset_client::recipe::ExtendRecipeSpec;
+use readyset_client::recipe::{CacheExplanation, ExtendRecipeSpec};
 use readyset_client::replication::{ReplicationOffset, ReplicationOffsetState, ReplicationOffsets};
 use readyset_client::{
     NodeSize, SingleKeyEviction, TableReplicationStatus, TableStatus, ViewCreateRequest,
@@ -1456,6 +1456,97 @@ impl DfState {
         }
     }
 
+    /// Describe the dataflow that would be created for the `CREATE CACHE` in the given recipe
+    /// spec, by dry-run migrating it against this copy of the dataflow state.
+    ///
+    /// A dry run still plans the migration, adding the query's nodes to the graph and
+    /// materializations of `self` without applying them to any domains, so this consumes `self`
+    /// and should only ever be called on a copy of the state.
+    pub(super) async fn explain_create_cache(
+        mut self,
+        recipe_spec: ExtendRecipeSpec<'_>,
+    ) -> ReadySetResult<CacheExplanation> {
+        let name = recipe_spec
+            .changes
+            .changes
+            .iter()
+            .find_map(|change| match change {
+                Change::CreateCache(cc) => cc.name.clone(),
+                _ => None,
+            })
+            .ok_or_else(|| internal_err!("EXPLAIN CREATE CACHE requires a named CREATE CACHE"))?;
+
+        // Nodes are never removed from the graph, so any node added by the migration has an
+        // index at least as large as the number of nodes before it
+        let existing_nodes = self.ingredients.node_count();
+        self.extend_recipe(recipe_spec, true).await?;
+
+        let query_name = self.recipe.resolve_alias(&name).unwrap_or(&name).clone();
+        let node = *self.views().get(&query_name).ok_or_else(|| {
+            ReadySetError::ViewNotFound(query_name.display_unquoted().to_string())
+        })?;
+        let reader = self
+            .find_reader_for(node, &query_name, &None)
+            .ok_or_else(|| {
+                ReadySetError::ViewNotFound(query_name.display_unquoted().to_string())
+            })?;
+
+        let mut explanation = CacheExplanation {
+            new_nodes: vec![],
+            reused_nodes: vec![],
+            key_columns: vec![],
+            partial: false,
+            tables: vec![],
+        };
+
+        #[allow(clippy::indexing_slicing)] // just came from find_reader_for
+        let reader_node = &self.ingredients[reader];
+        explanation.key_columns = reader_node
+            .as_reader()
+            .and_then(|r| r.key())
+            .unwrap_or_default()
+            .iter()
+            .map(|&col| {
+                #[allow(clippy::indexing_slicing)] // reader keys are columns of the reader
+                reader_node.columns()[col].name().to_owned()
+            })
+            .collect();
+        explanation.partial = matches!(
+            self.materializations.get_status(reader, reader_node),
+            MaterializationStatus::Partial { .. }
+        );
+
+        let mut visited = HashSet::new();
+        let mut to_visit = vec![reader];
+        while let Some(ni) = to_visit.pop() {
+            if !visited.insert(ni) {
+                continue;
+            }
+            #[allow(clippy::indexing_slicing)] // just came from self.ingredients
+            let n = &self.ingredients[ni];
+            if n.is_base() {
+                explanation.tables.push(n.name().clone());
+                continue;
+            }
+            if n.is_internal() || n.is_reader() {
+                let description =
+                    format!("{} ({})", n.name().display_unquoted(), n.description(false));
+                if ni.index() >= existing_nodes {
+                    explanation.new_nodes.push(description);
+                } else {
+                    explanation.reused_nodes.push(description);
+                }
+            }
+            to_visit.extend(
+                self.ingredients
+                    .neighbors_directed(ni, petgraph::EdgeDirection::Incoming),
+            );
+        }
+        explanation.tables.sort();
+
+        Ok(explanation)
+    }
+
     pub(super) async fn remove_query(&mut self, query_name: &Relation) -> ReadySetResult<()> {
         let name = match self.recipe.resolve_alias(query_name) {
             None => return Ok(()),
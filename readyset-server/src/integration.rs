@@ -1539,7 +1539,7 @@ async fn mutator_churn() {
                 "votecount",
                 make_columns(&["id", "votes"]),
                 Aggregation::Count
-                    .over(vote, 0, &[1], &DfType::Unknown)
+                    .over(vote, 0, &[1], &DfType::Unknown, Dialect::DEFAULT_MYSQL)
                     .unwrap(),
             );
 
@@ -2045,7 +2045,7 @@ async fn votes() {
                 "vc",
                 make_columns(&["id", "votes"]),
                 Aggregation::Count
-                    .over(vote, 0, &[1], &DfType::Unknown)
+                    .over(vote, 0, &[1], &DfType::Unknown, Dialect::DEFAULT_MYSQL)
                     .unwrap(),
             );
             mig.maintain_anonymous(vc, &Index::hash_map(vec![0]));
@@ -2755,7 +2755,7 @@ async fn cascading_replays_with_sharding() {
             // aggregate over the join. this will force a shard merger to be inserted because the
             // group-by column ("f2") isn't the same as the join's output sharding column ("f1"/"u")
             let a = Aggregation::Count
-                .over(j, 0, &[2], &DfType::Unknown)
+                .over(j, 0, &[2], &DfType::Unknown, Dialect::DEFAULT_MYSQL)
                 .unwrap();
             let end = mig.add_ingredient("end", make_columns(&["u", "c"]), a);
             mig.maintain_anonymous_with_reader_processing(
@@ -2905,7 +2905,7 @@ async fn full_aggregation_with_bogokey() {
                 "agg",
                 make_columns(&["bogo", "count"]),
                 Aggregation::Count
-                    .over(bogo, 0, &[1], &DfType::Unknown)
+                    .over(bogo, 0, &[1], &DfType::Unknown, Dialect::DEFAULT_MYSQL)
                     .unwrap(),
             );
             mig.maintain_anonymous_with_reader_processing(
@@ -3041,7 +3041,7 @@ async fn materialization_frontier() {
                 "votecount",
                 make_columns(&["id", "votes"]),
                 Aggregation::Count
-                    .over(vote, 0, &[1], &DfType::Unknown)
+                    .over(vote, 0, &[1], &DfType::Unknown, Dialect::DEFAULT_MYSQL)
                     .unwrap(),
             );
             mig.mark_shallow(vc);
@@ -3369,7 +3369,7 @@ async fn do_full_vote_migration(sharded: bool, old_puts_after: bool) {
                 "votecount",
                 make_columns(&["id", "votes"]),
                 Aggregation::Count
-                    .over(vote, 0, &[1], &DfType::Unknown)
+                    .over(vote, 0, &[1], &DfType::Unknown, Dialect::DEFAULT_MYSQL)
                     .unwrap(),
             );
 
@@ -3432,7 +3432,7 @@ async fn do_full_vote_migration(sharded: bool, old_puts_after: bool) {
                 "rsum",
                 make_columns(&["id", "total"]),
                 Aggregation::Sum
-                    .over(rating, 2, &[1], &DfType::Unknown)
+                    .over(rating, 2, &[1], &DfType::Unknown, Dialect::DEFAULT_MYSQL)
                     .unwrap(),
             );
 
@@ -3538,7 +3538,7 @@ async fn live_writes() {
                 "votecount",
                 make_columns(&["id", "votes"]),
                 Aggregation::Count
-                    .over(vote, 0, &[1], &DfType::Unknown)
+                    .over(vote, 0, &[1], &DfType::Unknown, Dialect::DEFAULT_MYSQL)
                     .unwrap(),
             );
 
@@ -3582,7 +3582,7 @@ async fn live_writes() {
                 "votecount2",
                 make_columns(&["id", "votes"]),
                 Aggregation::Sum
-                    .over(vc, 1, &[0], &DfType::Unknown)
+                    .over(vc, 1, &[0], &DfType::Unknown, Dialect::DEFAULT_MYSQL)
                     .unwrap(),
             );
             mig.maintain_anonymous_with_reader_processing(
@@ -4211,6 +4211,92 @@ async fn union_all_basic() {
     shutdown_tx.shutdown().await;
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn intersect_and_except() {
+    use itertools::sorted;
+
+    let (mut g, shutdown_tx) = start_simple_unsharded("intersect_and_except").await;
+    g.extend_recipe(
+        ChangeList::from_str(
+            "CREATE TABLE twos (id INTEGER PRIMARY KEY, x INTEGER);
+         CREATE TABLE threes (id INTEGER PRIMARY KEY, x INTEGER);
+         CREATE VIEW twos_intersect_threes AS (SELECT x FROM twos) INTERSECT (SELECT x FROM threes);
+         CREATE VIEW twos_except_threes AS (SELECT x FROM twos) EXCEPT ALL (SELECT x FROM threes);
+         CREATE CACHE intersect_query FROM SELECT x FROM twos_intersect_threes;
+         CREATE CACHE except_query FROM SELECT x FROM twos_except_threes;",
+            Dialect::DEFAULT_MYSQL,
+        )
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+
+    // Insert every multiple of 2 (or 3) below 12 into `twos` (or `threes`) twice
+    let mut twos = g.table("twos").await.unwrap();
+    twos.insert_many(
+        (0..12)
+            .filter(|i: &i32| i % 2 == 0)
+            .flat_map(|i| [vec![i.into(), i.into()], vec![(i + 100).into(), i.into()]]),
+    )
+    .await
+    .unwrap();
+    let mut threes = g.table("threes").await.unwrap();
+    threes
+        .insert_many(
+            (0..12)
+                .filter(|i: &i32| i % 3 == 0)
+                .flat_map(|i| [vec![i.into(), i.into()], vec![(i + 100).into(), i.into()]]),
+        )
+        .await
+        .unwrap();
+
+    sleep().await;
+
+    let mut intersect_query = g
+        .view("intersect_query")
+        .await
+        .unwrap()
+        .into_reader_handle()
+        .unwrap();
+    let mut except_query = g
+        .view("except_query")
+        .await
+        .unwrap()
+        .into_reader_handle()
+        .unwrap();
+    macro_rules! results {
+        ($query: ident) => {
+            sorted(
+                $query
+                    .lookup(&[0.into()], true)
+                    .await
+                    .unwrap()
+                    .into_vec()
+                    .iter()
+                    .map(|r| get_col!($query, r, "x", i32)),
+            )
+            .collect::<Vec<i32>>()
+        };
+    }
+
+    assert_eq!(results!(intersect_query), vec![0, 6]);
+    assert_eq!(results!(except_query), vec![2, 2, 4, 4, 8, 8, 10, 10]);
+
+    // Deleting one copy of 6 from `threes` leaves it in the intersection, but deleting both
+    // removes it
+    threes.delete(vec![6.into()]).await.unwrap();
+    sleep().await;
+    assert_eq!(results!(intersect_query), vec![0, 6]);
+    assert_eq!(results!(except_query), vec![2, 2, 4, 4, 6, 8, 8, 10, 10]);
+
+    threes.delete(vec![106.into()]).await.unwrap();
+    sleep().await;
+    assert_eq!(results!(intersect_query), vec![0]);
+    assert_eq!(results!(except_query), vec![2, 2, 4, 4, 6, 6, 8, 8, 10, 10]);
+
+    shutdown_tx.shutdown().await;
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn between() {
     let (mut g, shutdown_tx) = start_simple_unsharded("between_query").await;
@@ -4358,6 +4444,149 @@ async fn topk_updates() {
     shutdown_tx.shutdown().await;
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn lateral_topk() {
+    let (mut g, shutdown_tx) = start_simple_unsharded("lateral_topk").await;
+    g.extend_recipe(
+        ChangeList::from_str(
+            "CREATE TABLE authors (id INTEGER PRIMARY KEY);
+         CREATE TABLE posts (id INTEGER PRIMARY KEY, author_id INTEGER, score INTEGER);
+
+         CREATE CACHE top_posts FROM
+         SELECT authors.id AS author_id, top.id AS post_id
+         FROM authors
+         CROSS JOIN LATERAL (
+             SELECT posts.id FROM posts
+             WHERE posts.author_id = authors.id
+             ORDER BY posts.score DESC
+             LIMIT 2
+         ) AS top;",
+            Dialect::DEFAULT_POSTGRESQL,
+        )
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+
+    let mut authors = g.table("authors").await.unwrap();
+    let mut posts = g.table("posts").await.unwrap();
+    let mut top_posts = g
+        .view("top_posts")
+        .await
+        .unwrap()
+        .into_reader_handle()
+        .unwrap();
+
+    authors
+        .insert_many(vec![vec![1.into()], vec![2.into()], vec![3.into()]])
+        .await
+        .unwrap();
+    posts
+        .insert_many(vec![
+            vec![10.into(), 1.into(), 1.into()],
+            vec![11.into(), 1.into(), 3.into()],
+            vec![12.into(), 1.into(), 2.into()],
+            vec![20.into(), 2.into(), 5.into()],
+            vec![30.into(), 4.into(), 5.into()],
+        ])
+        .await
+        .unwrap();
+
+    sleep().await;
+
+    let res = top_posts.lookup(&[0.into()], true).await.unwrap();
+    let mut rows: Vec<Vec<DfValue>> = res.into();
+    rows.sort();
+    assert_eq!(
+        rows,
+        vec![
+            vec![1.into(), 11.into()],
+            vec![1.into(), 12.into()],
+            vec![2.into(), 20.into()],
+        ]
+    );
+
+    posts.delete(vec![11.into()]).await.unwrap();
+
+    sleep().await;
+
+    let res = top_posts.lookup(&[0.into()], true).await.unwrap();
+    let mut rows: Vec<Vec<DfValue>> = res.into();
+    rows.sort();
+    assert_eq!(
+        rows,
+        vec![
+            vec![1.into(), 10.into()],
+            vec![1.into(), 12.into()],
+            vec![2.into(), 20.into()],
+        ]
+    );
+
+    shutdown_tx.shutdown().await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn set_returning_functions_in_from() {
+    let (mut g, shutdown_tx) = start_simple_unsharded("set_returning_functions_in_from").await;
+    g.extend_recipe(
+        ChangeList::from_str(
+            "CREATE TABLE t (id INTEGER PRIMARY KEY, tags TEXT[], doc JSONB);
+
+         CREATE CACHE tags FROM
+         SELECT t.id, tag FROM t, unnest(t.tags) AS tag;
+
+         CREATE CACHE elems FROM
+         SELECT t.id, e.value FROM t CROSS JOIN LATERAL jsonb_array_elements_text(t.doc) AS e;",
+            Dialect::DEFAULT_POSTGRESQL,
+        )
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+
+    let mut t = g.table("t").await.unwrap();
+    let mut tags = g.view("tags").await.unwrap().into_reader_handle().unwrap();
+    let mut elems = g.view("elems").await.unwrap().into_reader_handle().unwrap();
+
+    t.insert_many(vec![
+        vec![
+            1.into(),
+            DfValue::from(vec![DfValue::from("a"), DfValue::from("b")]),
+            DfValue::from(r#"["x", "y"]"#),
+        ],
+        vec![2.into(), DfValue::None, DfValue::from("[]")],
+    ])
+    .await
+    .unwrap();
+
+    sleep().await;
+
+    let res = tags.lookup(&[0.into()], true).await.unwrap();
+    let mut rows: Vec<Vec<DfValue>> = res.into();
+    rows.sort();
+    assert_eq!(
+        rows,
+        vec![vec![1.into(), "a".into()], vec![1.into(), "b".into()]]
+    );
+
+    let res = elems.lookup(&[0.into()], true).await.unwrap();
+    let mut rows: Vec<Vec<DfValue>> = res.into();
+    rows.sort();
+    assert_eq!(
+        rows,
+        vec![vec![1.into(), "x".into()], vec![1.into(), "y".into()]]
+    );
+
+    t.delete(vec![1.into()]).await.unwrap();
+
+    sleep().await;
+
+    let res = tags.lookup(&[0.into()], true).await.unwrap();
+    assert!(Vec::<Vec<DfValue>>::from(res).is_empty());
+
+    shutdown_tx.shutdown().await;
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn simple_pagination() {
     let (mut g, shutdown_tx) = start_simple_unsharded("simple_pagination").await;
@@ -4456,6 +4685,64 @@ async fn simple_pagination() {
     shutdown_tx.shutdown().await;
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn parametrized_limit() {
+    let mut builder = Builder::for_tests();
+    builder.set_sharding(None);
+    builder.set_allow_topk(true);
+    builder.set_allow_paginate(true);
+    builder.set_max_parametrized_limit(Some(4));
+    let (mut g, shutdown_tx) = builder.start_local().await.unwrap();
+    g.extend_recipe(
+        ChangeList::from_str(
+            "CREATE TABLE t (x, y);
+         CREATE CACHE q FROM SELECT x, y FROM t WHERE y = $1 ORDER BY x ASC LIMIT $2 OFFSET $3;",
+            Dialect::DEFAULT_MYSQL,
+        )
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+
+    let mut t = g.table("t").await.unwrap();
+    t.insert_many((1..=6).map(|x| vec![DfValue::from(x), DfValue::from("a")]))
+        .await
+        .unwrap();
+
+    let mut q = g.view("q").await.unwrap().into_reader_handle().unwrap();
+    // The limit and offset are passed along with the lookup, rather than being part of the key
+    assert_eq!(
+        q.key_map(),
+        &[(
+            ViewPlaceholder::OneToOne(1, nom_sql::BinaryOperator::Equal),
+            1
+        )]
+    );
+
+    let lookup = |limit, offset| ViewQuery {
+        key_comparisons: vec![KeyComparison::Equal(vec1![DfValue::from("a")])],
+        block: true,
+        filter: None,
+        timestamp: None,
+        limit: Some(limit),
+        offset: Some(offset),
+    };
+
+    let res = q.raw_lookup(lookup(2, 1)).await.unwrap().into_vec();
+    assert_eq!(
+        res,
+        vec![
+            vec![DfValue::from(2), DfValue::from("a")],
+            vec![DfValue::from(3), DfValue::from("a")],
+        ]
+    );
+
+    // Only the first 4 rows are kept in the reader, so pages past that can't be served
+    q.raw_lookup(lookup(2, 3)).await.unwrap_err();
+
+    shutdown_tx.shutdown().await;
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn correct_nested_view_schema() {
     let r_txt = "CREATE TABLE votes (story int, user int);
@@ -5801,6 +6088,71 @@ async fn left_join_null() {
     shutdown_tx.shutdown().await;
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn left_join_residual_predicate() {
+    let (mut g, shutdown_tx) = start_simple_unsharded("left_join_residual_predicate").await;
+
+    g.extend_recipe(
+        ChangeList::from_str(
+            "CREATE TABLE users (id int, signup int);
+             CREATE TABLE orders (user_id int, created int);
+             CREATE CACHE q FROM SELECT users.id, orders.created FROM users
+             LEFT JOIN orders ON orders.user_id = users.id AND orders.created > users.signup
+             WHERE users.id = ?;",
+            Dialect::DEFAULT_MYSQL,
+        )
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+
+    let mut users = g.table("users").await.unwrap();
+    let mut orders = g.table("orders").await.unwrap();
+    let mut q = g.view("q").await.unwrap().into_reader_handle().unwrap();
+
+    users
+        .insert_many(vec![
+            vec![DfValue::from(1), DfValue::from(10)],
+            vec![DfValue::from(2), DfValue::from(10)],
+        ])
+        .await
+        .unwrap();
+    orders
+        .insert_many(vec![
+            vec![DfValue::from(1), DfValue::from(5)],
+            vec![DfValue::from(1), DfValue::from(15)],
+            vec![DfValue::from(2), DfValue::from(5)],
+        ])
+        .await
+        .unwrap();
+
+    sleep().await;
+
+    assert_eq!(
+        q.lookup(&[1.into()], true).await.unwrap().into_vec(),
+        vec![vec![DfValue::from(1), DfValue::from(15)]]
+    );
+    // user 2 has an order, but it doesn't satisfy the predicate
+    assert_eq!(
+        q.lookup(&[2.into()], true).await.unwrap().into_vec(),
+        vec![vec![DfValue::from(2), DfValue::None]]
+    );
+
+    orders
+        .insert(vec![DfValue::from(2), DfValue::from(20)])
+        .await
+        .unwrap();
+
+    sleep().await;
+
+    assert_eq!(
+        q.lookup(&[2.into()], true).await.unwrap().into_vec(),
+        vec![vec![DfValue::from(2), DfValue::from(20)]]
+    );
+
+    shutdown_tx.shutdown().await;
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn overlapping_indices() {
     let (mut g, shutdown_tx) = start_simple_unsharded("overlapping_indices").await;
@@ -5951,6 +6303,198 @@ async fn join_simple_cte() {
     shutdown_tx.shutdown().await;
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn recursive_cte() {
+    let mut builder = Builder::for_tests();
+    builder.set_sharding(None);
+    builder.set_max_recursion_depth(Some(3));
+    let (mut g, shutdown_tx) = builder.start_local().await.unwrap();
+
+    g.extend_recipe(
+        ChangeList::from_str(
+            "CREATE TABLE employees (id int, manager_id int);
+         CREATE CACHE reports FROM
+         WITH RECURSIVE r AS (
+             SELECT id, 1 AS depth FROM employees WHERE manager_id = 1
+             UNION ALL
+             SELECT employees.id, r.depth + 1 FROM employees
+             JOIN r ON employees.manager_id = r.id
+             WHERE r.depth < 4
+         )
+         SELECT id FROM r;",
+            Dialect::DEFAULT_MYSQL,
+        )
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+
+    let mut employees = g.table("employees").await.unwrap();
+    let mut view = g
+        .view("reports")
+        .await
+        .unwrap()
+        .into_reader_handle()
+        .unwrap();
+
+    employees
+        .insert_many(vec![
+            vec![DfValue::from(2i32), DfValue::from(1i32)],
+            vec![DfValue::from(3i32), DfValue::from(2i32)],
+            vec![DfValue::from(4i32), DfValue::from(3i32)],
+            vec![DfValue::from(10i32), DfValue::from(11i32)],
+        ])
+        .await
+        .unwrap();
+
+    sleep().await;
+
+    let res = view
+        .lookup(&[0i32.into()], true)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|r| get_col!(view, r, "id", i32))
+        .sorted()
+        .collect::<Vec<i32>>();
+    assert_eq!(res, vec![2, 3, 4]);
+
+    // New rows are picked up incrementally at every level of recursion
+    employees
+        .insert_many(vec![
+            vec![DfValue::from(5i32), DfValue::from(4i32)],
+            vec![DfValue::from(11i32), DfValue::from(2i32)],
+        ])
+        .await
+        .unwrap();
+
+    sleep().await;
+
+    let res = view
+        .lookup(&[0i32.into()], true)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|r| get_col!(view, r, "id", i32))
+        .sorted()
+        .collect::<Vec<i32>>();
+    assert_eq!(res, vec![2, 3, 4, 5, 10, 11]);
+
+    // Rows beyond the depth limit in the query aren't included
+    employees
+        .insert(vec![DfValue::from(6i32), DfValue::from(5i32)])
+        .await
+        .unwrap();
+
+    sleep().await;
+
+    let res = view
+        .lookup(&[0i32.into()], true)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|r| get_col!(view, r, "id", i32))
+        .sorted()
+        .collect::<Vec<i32>>();
+    assert_eq!(res, vec![2, 3, 4, 5, 10, 11]);
+
+    shutdown_tx.shutdown().await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn recursive_cte_unsupported_by_default() {
+    let (mut g, shutdown_tx) = start_simple_unsharded("recursive_cte_unsupported_by_default").await;
+    g.extend_recipe(
+        ChangeList::from_str(
+            "CREATE TABLE employees (id int, manager_id int);",
+            Dialect::DEFAULT_MYSQL,
+        )
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+
+    // Without a maximum recursion depth, we can't know how far to unroll the recursion
+    let res = g
+        .extend_recipe(
+            ChangeList::from_str(
+                "CREATE CACHE reports FROM
+                 WITH RECURSIVE r AS (
+                     SELECT id, 1 AS depth FROM employees WHERE manager_id = 1
+                     UNION ALL
+                     SELECT employees.id, r.depth + 1 FROM employees
+                     JOIN r ON employees.manager_id = r.id
+                     WHERE r.depth < 4
+                 )
+                 SELECT id FROM r;",
+                Dialect::DEFAULT_MYSQL,
+            )
+            .unwrap(),
+        )
+        .await;
+    assert!(res.unwrap_err().caused_by_unsupported());
+
+    shutdown_tx.shutdown().await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn recursive_cte_deeper_than_max_depth() {
+    let mut builder = Builder::for_tests();
+    builder.set_sharding(None);
+    builder.set_max_recursion_depth(Some(3));
+    let (mut g, shutdown_tx) = builder.start_local().await.unwrap();
+    g.extend_recipe(
+        ChangeList::from_str(
+            "CREATE TABLE employees (id int, manager_id int);",
+            Dialect::DEFAULT_MYSQL,
+        )
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+
+    // The recursion can go deeper than we're allowed to unroll it, so rather than truncating the
+    // results we refuse to cache the query
+    let res = g
+        .extend_recipe(
+            ChangeList::from_str(
+                "CREATE CACHE deep_reports FROM
+                 WITH RECURSIVE r AS (
+                     SELECT id, 1 AS depth FROM employees WHERE manager_id = 1
+                     UNION ALL
+                     SELECT employees.id, r.depth + 1 FROM employees
+                     JOIN r ON employees.manager_id = r.id
+                     WHERE r.depth < 10
+                 )
+                 SELECT id FROM r;",
+                Dialect::DEFAULT_MYSQL,
+            )
+            .unwrap(),
+        )
+        .await;
+    assert!(res.unwrap_err().caused_by_unsupported());
+
+    // Recursion with no bound at all could go arbitrarily deep
+    let res = g
+        .extend_recipe(
+            ChangeList::from_str(
+                "CREATE CACHE unbounded_reports FROM
+                 WITH RECURSIVE r AS (
+                     SELECT id FROM employees WHERE manager_id = 1
+                     UNION ALL
+                     SELECT employees.id FROM employees JOIN r ON employees.manager_id = r.id
+                 )
+                 SELECT id FROM r;",
+                Dialect::DEFAULT_MYSQL,
+            )
+            .unwrap(),
+        )
+        .await;
+    assert!(res.unwrap_err().caused_by_unsupported());
+
+    shutdown_tx.shutdown().await;
+}
+
 // multiple_aggregate_sum tests multiple aggregators of the same type, in this case sum(),
 // operating over different columns from the same table.
 #[tokio::test(flavor = "multi_thread")]
@@ -8942,6 +9486,55 @@ async fn simple_dry_run_unsupported() {
     shutdown_tx.shutdown().await;
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn explain_create_cache() {
+    let (mut g, shutdown_tx) = start_simple_unsharded("explain_create_cache").await;
+    g.extend_recipe(
+        ChangeList::from_str(
+            "CREATE TABLE t (a INT, b INT);
+             CREATE CACHE q1 FROM SELECT a, b FROM t WHERE a = ?;",
+            Dialect::DEFAULT_MYSQL,
+        )
+        .unwrap(),
+    )
+    .await
+    .unwrap();
+
+    let explanation = g
+        .explain_create_cache(
+            ChangeList::from_str(
+                "CREATE CACHE q2 FROM SELECT b FROM t WHERE b = ?;",
+                Dialect::DEFAULT_MYSQL,
+            )
+            .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert!(!explanation.new_nodes.is_empty());
+    assert_eq!(explanation.key_columns, vec!["b".to_owned()]);
+    assert!(explanation.partial);
+    assert_eq!(explanation.tables, vec![Relation::from("t")]);
+    // Explaining doesn't create the cache
+    g.view("q2").await.unwrap_err();
+
+    // A query that's already cached reuses all of its nodes
+    let explanation = g
+        .explain_create_cache(
+            ChangeList::from_str(
+                "CREATE CACHE q3 FROM SELECT a, b FROM t WHERE a = ?;",
+                Dialect::DEFAULT_MYSQL,
+            )
+            .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert!(explanation.new_nodes.is_empty());
+    assert!(!explanation.reused_nodes.is_empty());
+    assert_eq!(explanation.key_columns, vec!["a".to_owned()]);
+
+    shutdown_tx.shutdown().await;
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn multiple_simultaneous_migrations() {
     let (mut g, shutdown_tx) = start_simple_unsharded("multiple_simultaneous_migrations").await;
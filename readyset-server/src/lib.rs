@@ -604,6 +604,23 @@ pub struct WorkerOptions {
     #[clap(long, env = "EXPERIMENTAL_PAGINATE_SUPPORT", hide = true)]
     pub enable_experimental_paginate_support: bool,
 
+    /// The maximum value for a parametrized `LIMIT` (such as `LIMIT $1`) in cached queries.
+    ///
+    /// Caches for these queries keep this many rows for each key, and reads with a larger `LIMIT`
+    /// (plus `OFFSET`) are sent to the upstream database.
+    #[clap(long, env = "MAX_PARAMETRIZED_LIMIT", default_value = "1000")]
+    pub max_parametrized_limit: usize,
+
+    /// Enable experimental support for recursive common table expressions (`WITH RECURSIVE`) in
+    /// cached queries, applying the recursive term at most this many times.
+    ///
+    /// Only recursive CTEs which limit their own recursion to at most this depth, with a constant
+    /// bound on a counter column (such as `WHERE r.depth < 10`), can be cached. If not set, or if
+    /// the recursion in a query can't be shown to be bounded by this depth, queries with
+    /// recursive CTEs are unsupported and proxied upstream.
+    #[clap(long, env = "MAX_RECURSION_DEPTH")]
+    pub max_recursion_depth: Option<usize>,
+
     /// Enable experimental support for mixing equality and inequality comparisons on query
     /// parameters
     #[clap(long, env = "EXPERIMENTAL_MIXED_COMPARISONS_SUPPORT", hide = true)]
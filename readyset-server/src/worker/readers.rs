@@ -205,9 +205,18 @@ impl ReadRequestHandler {
             Err(e) => reply_with_error!(e),
         };
 
+        if let Err(e) = reader.post_lookup.check_page_bounds(limit, offset) {
+            reply_with_error!(e)
+        }
+
+        let key_comparisons = reader.expand_keys(key_comparisons);
+
         let consistency_miss = !has_sufficient_timestamp(reader, &timestamp);
+        let row_limit = reader.range_row_limit(limit, offset, filter.as_ref());
 
-        let (keys_to_replay, receiver) = match reader.get_multi_with_notifier(&key_comparisons) {
+        let (keys_to_replay, receiver) = match reader
+            .get_multi_with_notifier(&key_comparisons, row_limit)
+        {
             Err(LookupError::NotReady) => reply_with_error!(ReadySetError::ViewNotYetAvailable),
             Err(LookupError::Destroyed) => reply_with_error!(ReadySetError::ViewDestroyed),
             Err(LookupError::Error(e)) => reply_with_error!(e),
@@ -494,7 +503,8 @@ impl BlockingRead {
 
         let consistency_miss = !has_sufficient_timestamp(reader, &self.timestamp);
 
-        let still_waiting = match reader.get_multi(&self.key_comparisons) {
+        let row_limit = reader.range_row_limit(self.limit, self.offset, self.filter.as_ref());
+        let still_waiting = match reader.get_multi(&self.key_comparisons, row_limit) {
             // We hit on all keys, but there is a consistency miss. This just counts as a miss,
             // but no keys needs triggering.
             Ok(_) if consistency_miss => vec![],
@@ -116,7 +116,7 @@ fn check_select_statement<'a>(
                             res = Some(once_ok!(t.clone(), col.name.as_str()));
                             break;
                         }
-                        TableExprInner::Subquery(sq) => {
+                        TableExprInner::Subquery(sq) | TableExprInner::LateralSubquery(sq) => {
                             res = Some(Either::Right(trace_subquery(
                                 sq.as_ref(),
                                 table.clone(),
@@ -124,6 +124,11 @@ fn check_select_statement<'a>(
                                 &ctes,
                             )?));
                         }
+                        // The output of a set-returning function doesn't depend on any columns
+                        // in a way that can make a join problematic, so treat it like a table
+                        TableExprInner::Function(_) => {
+                            res = Some(once_ok!(table.clone(), col.name.as_str()));
+                        }
                     }
                     break;
                 }
@@ -140,8 +145,18 @@ fn check_select_statement<'a>(
                             res = Some(once_ok!(t.clone(), col.name.as_str()));
                             break;
                         }
+                        JoinRightSide::Table(
+                            te @ TableExpr {
+                                inner: TableExprInner::Function(_),
+                                ..
+                            },
+                        ) if table_matches(te) => {
+                            res = Some(once_ok!(table.clone(), col.name.as_str()));
+                            break;
+                        }
                         JoinRightSide::Table(TableExpr {
-                            inner: TableExprInner::Subquery(sq),
+                            inner:
+                                TableExprInner::Subquery(sq) | TableExprInner::LateralSubquery(sq),
                             alias: Some(alias),
                         }) if table.schema.is_none() && *alias == table.name => {
                             res = Some(Either::Right(trace_subquery(
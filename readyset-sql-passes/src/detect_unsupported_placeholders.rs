@@ -68,19 +68,18 @@ impl UnsupportedPlaceholderVisitor {
     /// equality comparisons.
     ///
     /// Placeholders used in `Expr::Between` are handled elsewhere.
-    fn record_comparison_expr(&mut self, lhs: &Expr, rhs: &Expr, op: &BinaryOperator) {
+    fn record_comparison_expr(&mut self, rhs: &Expr, op: &BinaryOperator) {
         if !self.config.allow_mixed_comparisons {
-            match (lhs, rhs, op) {
+            match (rhs, op) {
                 (
-                    Expr::Column(_),
                     Expr::Literal(Literal::Placeholder(ItemPlaceholder::DollarNumber(n))),
                     BinaryOperator::Equal,
                 ) => self.context.equality_comparisons.push(*n),
-                (
-                    Expr::Column(_),
-                    Expr::Literal(Literal::Placeholder(ItemPlaceholder::DollarNumber(n))),
-                    cmp,
-                ) if cmp.is_ordering_comparison() => self.context.ordering_comparisons.push(*n),
+                (Expr::Literal(Literal::Placeholder(ItemPlaceholder::DollarNumber(n))), cmp)
+                    if cmp.is_ordering_comparison() =>
+                {
+                    self.context.ordering_comparisons.push(*n)
+                }
                 _ => { /* Nothing to record */ }
             }
         }
@@ -124,7 +123,9 @@ impl<'ast> Visitor<'ast> for UnsupportedPlaceholderVisitor {
 
     /// We do nothing except record any placeholders in `Context::ordering_comparisons` or
     /// `Context::equality_comparisons` if we have:
-    /// - a comparison with a `Expr::Column` on the left and a `Expr::Literal` on the right
+    /// - a comparison with a `Expr::Column` or any other expression on the left and a
+    ///   `Expr::Literal` on the right (though any placeholders *within* the expression on the left
+    ///   are still unsupported)
     /// - a `Expr::Between` with a `Expr::Column` as the operand and `Expr::Literal`s for the min
     ///   and max expressions.
     ///
@@ -140,16 +141,17 @@ impl<'ast> Visitor<'ast> for UnsupportedPlaceholderVisitor {
         match expr {
             Expr::BinaryOp { lhs, rhs, op } => {
                 // The placeholder is supported if we have an equality or ordering comparison with a
-                // column on the left and placeholder on the right.
-                if !(matches!(**lhs, Expr::Column(_))
-                    && matches!(**rhs, Expr::Literal(_)) // no need to walk for any literal
+                // column or expression on the left and placeholder on the right.
+                if !(matches!(**rhs, Expr::Literal(_)) // no need to walk for any literal
                     && (matches!(op, BinaryOperator::Equal) || op.is_ordering_comparison()))
                 {
                     let Ok(_) = walk_expr(self, expr);
                 } else {
+                    // Placeholders within the expression on the left are still unsupported
+                    let Ok(_) = self.visit_expr(lhs);
                     // Record placeholders in either Context::equality_comparisons or
                     // Context::ordering_comparisons.
-                    self.record_comparison_expr(lhs, rhs, op);
+                    self.record_comparison_expr(rhs, op);
                 }
             }
             Expr::Between {
@@ -266,11 +268,27 @@ mod tests {
 
     #[test]
     fn extracts_placeholder_expr_comparison() {
-        let select = parse_select_statement("SELECT a FROM t WHERE b + $1 = 1 AND c + 1 = $2");
+        let select = parse_select_statement("SELECT a FROM t WHERE b + $1 = 1 AND $2 + c = 1");
         let res = select.detect_unsupported_placeholders(Config::default());
         extracts_placeholders(res, &[1, 2]);
     }
 
+    #[test]
+    fn ignores_expr_compared_to_placeholder() {
+        let select = parse_select_statement("SELECT a FROM t WHERE b * c > $1 AND d + 1 = $2");
+        let res = select.detect_unsupported_placeholders(Config {
+            allow_mixed_comparisons: true,
+        });
+        extracts_placeholders(res, &[]);
+    }
+
+    #[test]
+    fn extracts_placeholder_within_expr_compared_to_placeholder() {
+        let select = parse_select_statement("SELECT a FROM t WHERE b + $1 = $2");
+        let res = select.detect_unsupported_placeholders(Config::default());
+        extracts_placeholders(res, &[1]);
+    }
+
     #[test]
     fn extracts_placeholder_having_clause() {
         let select =
@@ -32,6 +32,12 @@ fn negate_expr(expr: &mut Expr) -> bool {
                 BinaryOperator::NotLike => BinaryOperator::Like,
                 BinaryOperator::ILike => BinaryOperator::NotILike,
                 BinaryOperator::NotILike => BinaryOperator::ILike,
+                BinaryOperator::Regexp => BinaryOperator::NotRegexp,
+                BinaryOperator::NotRegexp => BinaryOperator::Regexp,
+                BinaryOperator::Regex => BinaryOperator::NotRegex,
+                BinaryOperator::NotRegex => BinaryOperator::Regex,
+                BinaryOperator::IRegex => BinaryOperator::NotIRegex,
+                BinaryOperator::NotIRegex => BinaryOperator::IRegex,
                 BinaryOperator::Is => BinaryOperator::IsNot,
                 BinaryOperator::IsNot => BinaryOperator::Is,
                 BinaryOperator::Add
@@ -178,4 +184,12 @@ mod tests {
         normalize_negation(&mut expr);
         assert_eq!(expr, expected);
     }
+
+    #[test]
+    fn normalize_regex_match() {
+        let mut expr = parse_expr(Dialect::PostgreSQL, "NOT (a ~ 'x' OR b !~* 'y')").unwrap();
+        let expected = parse_expr(Dialect::PostgreSQL, "a !~ 'x' AND b ~* 'y'").unwrap();
+        normalize_negation(&mut expr);
+        assert_eq!(expr, expected);
+    }
 }
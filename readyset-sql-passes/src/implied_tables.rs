@@ -93,7 +93,9 @@ impl<'ast, 'schema> VisitorMut<'ast> for ExpandImpliedTablesVisitor<'schema> {
                     Some((
                         match &tbl.inner {
                             TableExprInner::Table(t) => t.clone(),
-                            TableExprInner::Subquery(_) => tbl.alias.clone()?.into(),
+                            TableExprInner::Subquery(_)
+                            | TableExprInner::LateralSubquery(_)
+                            | TableExprInner::Function(_) => tbl.alias.clone()?.into(),
                         },
                         tbl.alias
                             .clone()
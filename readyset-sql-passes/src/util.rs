@@ -56,7 +56,8 @@ fn field_names(statement: &SelectStatement) -> impl Iterator<Item = &SqlIdentifi
     })
 }
 
-/// Returns a map from subquery aliases to vectors of the fields in those subqueries.
+/// Returns a map from subquery (and set-returning function) aliases to vectors of the fields in
+/// those subqueries.
 ///
 /// Takes only the CTEs and join clause so that it doesn't have to borrow the entire statement.
 pub(crate) fn subquery_schemas<'a>(
@@ -65,7 +66,7 @@ pub(crate) fn subquery_schemas<'a>(
     join: &'a [JoinClause],
 ) -> HashMap<&'a SqlIdentifier, Vec<&'a SqlIdentifier>> {
     ctes.iter()
-        .map(|cte| (&cte.name, &cte.statement))
+        .map(|cte| (&cte.name, field_names(&cte.statement).collect()))
         .chain(
             tables
                 .iter()
@@ -74,13 +75,18 @@ pub(crate) fn subquery_schemas<'a>(
                     JoinRightSide::Tables(ts) => Either::Right(ts.iter()),
                 }))
                 .filter_map(|te| match &te.inner {
-                    TableExprInner::Subquery(sq) => {
-                        te.alias.as_ref().map(|alias| (alias, sq.as_ref()))
-                    }
+                    TableExprInner::Subquery(sq) | TableExprInner::LateralSubquery(sq) => te
+                        .alias
+                        .as_ref()
+                        .map(|alias| (alias, field_names(sq).collect())),
+                    TableExprInner::Function(_) => te
+                        .alias
+                        .as_ref()
+                        .zip(te.function_column())
+                        .map(|(alias, column)| (alias, vec![column])),
                     TableExprInner::Table(_) => None,
                 }),
         )
-        .map(|(name, stmt)| (name, field_names(stmt).collect()))
         .collect()
 }
 
@@ -114,7 +120,9 @@ pub fn map_aggregates(expr: &mut Expr) -> Vec<(FunctionExpr, SqlIdentifier)> {
             ret.append(&mut map_aggregates(lhs));
             ret.append(&mut map_aggregates(rhs));
         }
-        Expr::UnaryOp { rhs: expr, .. } | Expr::Cast { expr, .. } => {
+        Expr::UnaryOp { rhs: expr, .. }
+        | Expr::Cast { expr, .. }
+        | Expr::Interval { value: expr, .. } => {
             ret.append(&mut map_aggregates(expr));
         }
         Expr::Exists(_) => {}
@@ -153,6 +161,12 @@ pub fn is_predicate(op: &BinaryOperator) -> bool {
         Like | NotLike
             | ILike
             | NotILike
+            | Regexp
+            | NotRegexp
+            | Regex
+            | NotRegex
+            | IRegex
+            | NotIRegex
             | Equal
             | NotEqual
             | Greater
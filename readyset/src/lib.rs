@@ -27,6 +27,7 @@ use futures_util::stream::StreamExt;
 use health_reporter::{HealthReporter as AdapterHealthReporter, State as AdapterState};
 use metrics_exporter_prometheus::PrometheusBuilder;
 use nom_sql::Relation;
+use readyset_adapter::auto_cache::{AutoCachePolicy, AutoCacher, QueryUsageTracker};
 use readyset_adapter::backend::noria_connector::{NoriaConnector, ReadBehavior};
 use readyset_adapter::backend::MigrationMode;
 use readyset_adapter::fallback_cache::{
@@ -37,9 +38,11 @@ use readyset_adapter::migration_handler::MigrationHandler;
 use readyset_adapter::proxied_queries_reporter::ProxiedQueriesReporter;
 use readyset_adapter::query_status_cache::{MigrationStyle, QueryStatusCache};
 use readyset_adapter::views_synchronizer::ViewsSynchronizer;
-use readyset_adapter::{Backend, BackendBuilder, QueryHandler, UpstreamDatabase};
+use readyset_adapter::{
+    Backend, BackendBuilder, QueryHandler, QueryStatusPersister, UpstreamDatabase,
+};
 use readyset_alloc::{StdThreadBuildWrapper, ThreadBuildWrapper};
-use readyset_client::consensus::AuthorityType;
+use readyset_client::consensus::{Authority, AuthorityType};
 #[cfg(feature = "failure_injection")]
 use readyset_client::failpoints;
 use readyset_client::metrics::recorded;
@@ -298,6 +301,16 @@ pub struct Options {
     #[clap(long, hide = true, env = "CONTROLLER_TIMEOUT", default_value = "5000")]
     controller_request_timeout_ms: u64,
 
+    /// The interval, in seconds, at which changes to the status of the queries seen by this
+    /// adapter are persisted in the authority, so that they survive adapter restarts.
+    #[clap(
+        long,
+        hide = true,
+        env = "QUERY_STATUS_PERSIST_INTERVAL",
+        default_value = "10"
+    )]
+    query_status_persist_interval: u64,
+
     /// Specifies the maximum continuous failure time for any given query, in seconds, before
     /// entering into a fallback recovery mode.
     #[clap(
@@ -347,6 +360,9 @@ pub struct Options {
     #[clap(flatten)]
     fallback_cache_options: FallbackCacheOptions,
 
+    #[clap(flatten)]
+    auto_cache_options: AutoCacheOptions,
+
     /// Whether to allow ReadySet to automatically create inlined caches when we receive a CREATE
     /// CACHE command for a query with unsupported placeholders.
     ///
@@ -424,6 +440,59 @@ pub struct FallbackCacheOptions {
     eviction_options: FallbackCacheEvictionOptions,
 }
 
+// Command-line options for automatically creating and dropping caches based on how queries are
+// used.
+//
+// This option struct is intended to be embedded inside of a larger option struct using
+// `#[clap(flatten)]`.
+#[allow(missing_docs)] // Allows us to exclude docs (from doc comments) from --help text
+#[derive(Parser, Debug)]
+pub struct AutoCacheOptions {
+    /// Automatically create caches for supported queries that are executed often and slowly
+    /// enough against the upstream database, and drop them again once they go unread for long
+    /// enough. Requires `--query-caching=explicit` and `--query-log`. Ad-hoc queries are only
+    /// considered if `--query-log-ad-hoc` is also set.
+    #[clap(long, env = "AUTO_CACHE", requires = "query_log")]
+    auto_cache: bool,
+
+    /// The length of the window, in seconds, over which upstream executions of each query are
+    /// counted for `--auto-cache`.
+    #[clap(long, env = "AUTO_CACHE_WINDOW", default_value = "60")]
+    auto_cache_window_secs: u64,
+
+    /// The minimum number of times a query must be executed against the upstream database within
+    /// a single window for `--auto-cache` to create a cache for it.
+    #[clap(long, env = "AUTO_CACHE_MIN_EXECUTIONS", default_value = "100")]
+    auto_cache_min_executions: u64,
+
+    /// The minimum mean latency, in milliseconds, of a query's upstream executions within a window
+    /// for `--auto-cache` to create a cache for it.
+    #[clap(long, env = "AUTO_CACHE_MIN_UPSTREAM_LATENCY", default_value = "10")]
+    auto_cache_min_upstream_latency_ms: u64,
+
+    /// How long, in seconds, a cache created by `--auto-cache` can go without being read before it
+    /// is dropped.
+    #[clap(long, env = "AUTO_CACHE_IDLE_TIMEOUT", default_value = "3600")]
+    auto_cache_idle_timeout_secs: u64,
+
+    /// The maximum total number of caches, including those created with `CREATE CACHE`.
+    /// `--auto-cache` won't create any caches once this many exist.
+    #[clap(long, env = "AUTO_CACHE_MAX_CACHES", default_value = "100")]
+    auto_cache_max_caches: usize,
+}
+
+impl AutoCacheOptions {
+    fn policy(&self) -> AutoCachePolicy {
+        AutoCachePolicy {
+            window: Duration::from_secs(self.auto_cache_window_secs),
+            min_upstream_executions: self.auto_cache_min_executions,
+            min_upstream_latency: Duration::from_millis(self.auto_cache_min_upstream_latency_ms),
+            idle_timeout: Duration::from_secs(self.auto_cache_idle_timeout_secs),
+            max_caches: self.auto_cache_max_caches,
+        }
+    }
+}
+
 // TODO:
 // Change this to an enum that allows for a probabilistic strategy
 //
@@ -580,20 +649,23 @@ where
                 .enable_experimental_paginate_support;
         let no_upstream_connections = options.no_upstream_connections;
 
-        let rh = rt.block_on(async {
-            let authority = authority
-                .to_authority(&authority_address, &deployment)
-                .await;
+        let (rh, adapter_authority) = rt.block_on(async {
+            let authority = Arc::new(
+                authority
+                    .to_authority(&authority_address, &deployment)
+                    .await,
+            );
 
-            Ok::<ReadySetHandle, ReadySetError>(
+            Ok::<(ReadySetHandle, Arc<Authority>), ReadySetError>((
                 ReadySetHandle::with_timeouts(
-                    authority,
+                    authority.clone(),
                     Some(Duration::from_millis(controller_request_timeout)),
                     Some(Duration::from_millis(migration_request_timeout)),
                 )
                 .instrument(rs_connect.clone())
                 .await,
-            )
+                authority,
+            ))
         })?;
 
         rs_connect.in_scope(|| info!("ReadySetHandle created"));
@@ -679,6 +751,16 @@ where
 
         let (shutdown_tx, shutdown_rx) = shutdown::channel();
 
+        ensure!(
+            !options.auto_cache_options.auto_cache
+                || matches!(options.query_caching, MigrationStyle::Explicit),
+            "--auto-cache requires --query-caching=explicit"
+        );
+        let query_usage = options
+            .auto_cache_options
+            .auto_cache
+            .then(QueryUsageTracker::new);
+
         // Gate query log code path on the log flag existing.
         let qlog_sender = if options.query_log {
             rs_connect.in_scope(|| info!("Query logs are enabled. Spawning query logger"));
@@ -691,12 +773,17 @@ where
                 .unwrap();
 
             let shutdown_rx = shutdown_rx.clone();
+            let query_usage = query_usage.clone();
             // Spawn the actual thread to run the logger
             std::thread::Builder::new()
                 .name("Query logger".to_string())
                 .stack_size(2 * 1024 * 1024) // Use the same value tokio is using
                 .spawn_wrapper(move || {
-                    runtime.block_on(query_logger::QueryLogger::run(qlog_receiver, shutdown_rx));
+                    runtime.block_on(query_logger::QueryLogger::run(
+                        qlog_receiver,
+                        query_usage,
+                        shutdown_rx,
+                    ));
                     runtime.shutdown_background();
                 })?;
 
@@ -726,6 +813,19 @@ where
                 ),
         ));
 
+        // Restore the statuses of the queries seen by previous runs of this deployment's adapters
+        // before accepting any connections, then keep persisting them as they change.
+        let mut query_status_persister = QueryStatusPersister::new(
+            adapter_authority,
+            query_status_cache,
+            Duration::from_secs(options.query_status_persist_interval),
+            shutdown_rx.clone(),
+        );
+        rt.block_on(query_status_persister.restore());
+        rs_connect.in_scope(|| info!("Spawning query status persister task"));
+        let fut = async move { query_status_persister.run().await };
+        rt.handle().spawn(abort_on_panic(fut));
+
         let telemetry_sender = rt.block_on(async {
             let proxied_queries_reporter =
                 Arc::new(ProxiedQueriesReporter::new(query_status_cache));
@@ -838,6 +938,9 @@ where
             let expr_dialect = self.expr_dialect;
             let parse_dialect = self.parse_dialect;
             let fallback_cache = fallback_cache.clone();
+            let auto_cacher = query_usage.map(|query_usage| {
+                AutoCacher::new(options.auto_cache_options.policy(), query_usage)
+            });
 
             rs_connect.in_scope(|| info!("Spawning migration handler task"));
             let fut = async move {
@@ -909,7 +1012,8 @@ where
                     std::time::Duration::from_millis(loop_interval),
                     std::time::Duration::from_secs(max_retry * 60),
                     shutdown_rx.clone(),
-                );
+                )
+                .auto_cacher(auto_cacher);
 
                 migration_handler.run().await.map_err(move |e| {
                     error!(error = %e, "Migration Handler failed, aborting the process due to service entering a degraded state");
@@ -943,6 +1047,11 @@ where
         // from readers on the adapter rather than across a network hop.
         let readers: Readers = Arc::new(Mutex::new(Default::default()));
 
+        let read_your_writes = options
+            .server_worker_options
+            .replicator_config
+            .read_your_writes;
+
         // Run a readyset-server instance within this adapter.
         let internal_server_handle = if options.standalone || options.embedded_readers {
             let authority = options.authority.clone();
@@ -1026,6 +1135,7 @@ where
                 .query_max_failure_seconds(options.query_max_failure_seconds)
                 .telemetry_sender(telemetry_sender.clone())
                 .fallback_recovery_seconds(options.fallback_recovery_seconds)
+                .enable_ryw(read_your_writes)
                 .enable_experimental_placeholder_inlining(
                     options.experimental_placeholder_inlining,
                 );
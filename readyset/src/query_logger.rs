@@ -3,6 +3,7 @@ use std::sync::Arc;
 
 use metrics::{register_counter, register_histogram, Counter, Histogram, SharedString};
 use nom_sql::SqlQuery;
+use readyset_adapter::auto_cache::QueryUsageTracker;
 use readyset_client::query::QueryId;
 use readyset_client_metrics::{
     recorded, DatabaseType, EventType, QueryExecutionEvent, SqlQueryType,
@@ -169,9 +170,10 @@ impl QueryLogger {
             })
     }
 
-    /// Async task that logs query stats.
+    /// Async task that logs query stats, also recording them in `query_usage` if set.
     pub(crate) async fn run(
         mut receiver: UnboundedReceiver<QueryExecutionEvent>,
+        query_usage: Option<QueryUsageTracker>,
         mut shutdown_recv: ShutdownReceiver,
     ) {
         let _span = info_span!("query-logger");
@@ -202,6 +204,10 @@ impl QueryLogger {
                         }
                     };
 
+                    if let Some(query_usage) = &query_usage {
+                        query_usage.record(&event);
+                    }
+
                     let query = match event.query {
                         Some(query) => query,
                         None => continue,
@@ -1,4 +1,5 @@
 use std::convert::{TryFrom, TryInto};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use async_trait::async_trait;
 use binlog::consts::{BinlogChecksumAlg, EventType};
@@ -20,10 +21,31 @@ use tracing::{info, warn};
 
 use super::BinlogPosition;
 use crate::noria_adapter::{Connector, ReplicationAction};
+use crate::replication_lag::ReplicationLag;
 
 const CHECKSUM_QUERY: &str = "SET @master_binlog_checksum='CRC32'";
+/// Ask the primary to send a heartbeat event every 500ms if it has no other events to send (the
+/// period is in nanoseconds). Heartbeats tell us that we've received everything in the binlog,
+/// which lets us measure replication lag while the upstream database is idle.
+const HEARTBEAT_PERIOD_QUERY: &str = "SET @master_heartbeat_period=500000000";
+/// The type code of the `HEARTBEAT_LOG_EVENT_V2` event, which newer versions of MySQL send in
+/// place of `HEARTBEAT_EVENT`
+const HEARTBEAT_LOG_EVENT_V2: u8 = 0x29;
 const DEFAULT_SERVER_ID: u32 = u32::MAX - 55;
 
+/// Parses a UUID in its textual form, as returned by `@@server_uuid`, into its bytes
+fn parse_uuid(uuid: &str) -> Option<[u8; 16]> {
+    let hex = uuid.replace('-', "");
+    if hex.len() != 32 {
+        return None;
+    }
+    let mut bytes = [0; 16];
+    for (i, byte) in bytes.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(hex.get(i * 2..i * 2 + 2)?, 16).ok()?;
+    }
+    Some(bytes)
+}
+
 /// A connector that connects to a MySQL server and starts reading binlogs from a given position.
 ///
 /// The server must be configured with `binlog_format` set to `row` and `binlog_row_image` set to
@@ -48,11 +70,24 @@ pub(crate) struct MySqlBinlogConnector {
     server_id: Option<u32>,
     /// If we just want to continue reading the binlog from a previous point
     next_position: BinlogPosition,
-    /// The GTID of the current transaction. Table modification events will have
-    /// the current GTID attached if enabled in mysql.
+    /// The UUID of the server we're replicating from, as reported by `@@server_uuid`
+    server_uuid: Option<[u8; 16]>,
+    /// The sequence number of the GTID of the last transaction which originated on
+    /// [`server_uuid`](Self::server_uuid), if GTIDs are enabled in mysql. This is reported once
+    /// the transaction commits.
+    ///
+    /// The RYW tickets of clients are the sequence numbers of GTIDs with the UUID of the server,
+    /// which aren't comparable with the sequence numbers of transactions which originated on other
+    /// servers (eg before a failover, or if the server is itself a replica), so we only track the
+    /// former.
     current_gtid: Option<u64>,
     /// Whether to log statements received by the connector
     enable_statement_logging: bool,
+    /// Measurement of the replication lag, updated as events are applied
+    replication_lag: ReplicationLag,
+    /// The timestamp of the last event we read, which will have been applied once we read the
+    /// next event
+    last_event_time: Option<SystemTime>,
 }
 
 impl PartialOrd for BinlogPosition {
@@ -162,6 +197,7 @@ impl MySqlBinlogConnector {
     /// but others use CRC32 🤷‍♂️
     async fn register_as_replica(&mut self) -> mysql::Result<()> {
         self.connection.query_drop(CHECKSUM_QUERY).await?;
+        self.connection.query_drop(HEARTBEAT_PERIOD_QUERY).await?;
 
         let cmd = mysql_common::packets::ComRegisterSlave::new(self.server_id());
         self.connection.write_command(&cmd).await?;
@@ -202,6 +238,7 @@ impl MySqlBinlogConnector {
         mysql_opts: O,
         next_position: BinlogPosition,
         server_id: Option<u32>,
+        replication_lag: ReplicationLag,
         enable_statement_logging: bool,
     ) -> ReadySetResult<Self> {
         let mut connector = MySqlBinlogConnector {
@@ -209,10 +246,19 @@ impl MySqlBinlogConnector {
             reader: binlog::EventStreamReader::new(binlog::consts::BinlogVersion::Version4),
             server_id,
             next_position,
+            server_uuid: None,
             current_gtid: None,
             enable_statement_logging,
+            replication_lag,
+            last_event_time: None,
         };
 
+        let server_uuid: Option<String> = connector
+            .connection
+            .query_first("SELECT @@GLOBAL.server_uuid")
+            .await?;
+        connector.server_uuid = server_uuid.as_deref().and_then(parse_uuid);
+
         connector.register_as_replica().await?;
         connector.request_binlog().await?;
 
@@ -245,6 +291,28 @@ impl MySqlBinlogConnector {
         loop {
             let binlog_event = self.next_event().await?;
 
+            // We return at most one action per call, which the caller applies before calling us
+            // again, so by now the event we read before this one has been applied. Event
+            // timestamps are when the statement that generated the event began executing, which
+            // is never later than its commit.
+            if let Some(time) = self.last_event_time.take() {
+                self.replication_lag.caught_up_as_of(time);
+            }
+
+            let event_type = binlog_event.header().event_type();
+            if matches!(event_type, Ok(EventType::HEARTBEAT_EVENT))
+                || event_type.map_or_else(|raw| raw, |ev| ev as u8) == HEARTBEAT_LOG_EVENT_V2
+            {
+                // The primary only sends heartbeats once it has sent us the entire binlog
+                self.replication_lag.caught_up_as_of(SystemTime::now());
+                if let Some(txid) = self.current_gtid {
+                    return Ok((ReplicationAction::CaughtUp { txid }, &self.next_position));
+                }
+                continue;
+            }
+            self.last_event_time =
+                Some(UNIX_EPOCH + Duration::from_secs(binlog_event.header().timestamp().into()));
+
             self.next_position.position = binlog_event.header().log_pos();
 
             match binlog_event.header().event_type().map_err(|ev| {
@@ -368,7 +436,7 @@ impl MySqlBinlogConnector {
                                 name: tme.table_name().into(),
                             },
                             actions: inserted_rows,
-                            txid: self.current_gtid,
+                            txid: None,
                         },
                         &self.next_position,
                     ));
@@ -427,7 +495,7 @@ impl MySqlBinlogConnector {
                                 name: tme.table_name().into(),
                             },
                             actions: updated_rows,
-                            txid: self.current_gtid,
+                            txid: None,
                         },
                         &self.next_position,
                     ));
@@ -471,7 +539,7 @@ impl MySqlBinlogConnector {
                                 name: tme.table_name().into(),
                             },
                             actions: deleted_rows,
-                            txid: self.current_gtid,
+                            txid: None,
                         },
                         &self.next_position,
                     ));
@@ -499,20 +567,26 @@ impl MySqlBinlogConnector {
                     if self.enable_statement_logging {
                         info!(target: "replicator_statement", "{:?}", ev);
                     }
-                    self.current_gtid = Some(ev.gno());
+                    if self.server_uuid == Some(ev.sid()) {
+                        self.current_gtid = Some(ev.gno());
+                    }
+                }
+
+                EventType::XID_EVENT => {
+                    // Generated for a commit of a transaction that modifies one or more tables of
+                    // an XA-capable storage engine. Normal transactions are implemented by sending
+                    // a QUERY_EVENT containing a BEGIN statement and a QUERY_EVENT containing a
+                    // COMMIT statement (or a ROLLBACK statement if the transaction is rolled
+                    // back).
+                    if let Some(txid) = self.current_gtid {
+                        return Ok((ReplicationAction::Commit { txid }, &self.next_position));
+                    }
                 }
 
                 /*
 
                 EventType::ANONYMOUS_GTID_EVENT => {}
 
-                EventType::XID_EVENT => {
-                    // Generated for a commit of a transaction that modifies one or more tables of an XA-capable
-                    // storage engine. Normal transactions are implemented by sending a QUERY_EVENT containing a
-                    // BEGIN statement and a QUERY_EVENT containing a COMMIT statement
-                    // (or a ROLLBACK statement if the transaction is rolled back).
-                }
-
                 EventType::START_EVENT_V3 // Old version of FORMAT_DESCRIPTION_EVENT
                 | EventType::FORMAT_DESCRIPTION_EVENT // A descriptor event that is written to the beginning of each binary log file. This event is used as of MySQL 5.0; it supersedes START_EVENT_V3.
                 | EventType::STOP_EVENT // Written when mysqld stops
@@ -681,3 +755,21 @@ impl Connector for MySqlBinlogConnector {
         Ok((action, pos.try_into()?))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_server_uuid() {
+        assert_eq!(
+            parse_uuid("3e11fa47-71ca-11e1-9e33-c80aa9429562"),
+            Some([
+                0x3e, 0x11, 0xfa, 0x47, 0x71, 0xca, 0x11, 0xe1, 0x9e, 0x33, 0xc8, 0x0a, 0xa9, 0x42,
+                0x95, 0x62
+            ])
+        );
+        assert_eq!(parse_uuid("3e11fa47-71ca-11e1-9e33"), None);
+        assert_eq!(parse_uuid("not a uuid, but 36 characters long!!"), None);
+    }
+}
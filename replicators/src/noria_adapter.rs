@@ -7,7 +7,7 @@ use async_trait::async_trait;
 use database_utils::{DatabaseURL, UpstreamConfig};
 use deadpool_postgres::{Manager, ManagerConfig, Pool, RecyclingMethod};
 use failpoint_macros::set_failpoint;
-use futures::FutureExt;
+use futures::{future, FutureExt};
 use metrics::{counter, histogram};
 use mysql::prelude::Queryable;
 use mysql::{OptsBuilder, PoolConstraints, PoolOpts, SslOpts};
@@ -36,6 +36,7 @@ use crate::postgres_connector::{
     drop_publication, drop_readyset_schema, drop_replication_slot, PostgresReplicator,
     PostgresWalConnector, PUBLICATION_NAME, REPLICATION_SLOT,
 };
+use crate::replication_lag::ReplicationLag;
 use crate::table_filter::TableFilter;
 
 /// Time to wait for requests to coalesce between snapshotting. Useful for preventing a series of
@@ -49,12 +50,22 @@ pub(crate) enum ReplicationAction {
     TableAction {
         table: Relation,
         actions: Vec<TableOperation>,
-        /// The transaction id of a table write operation. Each
-        /// table write operation within a transaction should be assigned
-        /// the same transaction id. These id's should be monotonically
-        /// increasing across transactions.
+        /// If these are the last operations of a transaction, the transaction id of that
+        /// transaction, which is treated the same as a subsequent [`ReplicationAction::Commit`].
         txid: Option<u64>,
     },
+    /// A transaction was committed. Once every operation in the transaction has been applied, the
+    /// RYW timestamps of the tables it wrote to are advanced to the transaction id, which should
+    /// be monotonically increasing across transactions.
+    Commit {
+        txid: u64,
+    },
+    /// Every transaction committed upstream up to `txid` (in the same terms as the `txid` of
+    /// [`ReplicationAction::Commit`]) has been applied, so the RYW timestamps of all tables can be
+    /// advanced to `txid`.
+    CaughtUp {
+        txid: u64,
+    },
     DdlChange {
         schema: String,
         changes: Vec<Change>,
@@ -156,6 +167,14 @@ pub struct NoriaAdapter {
     table_filter: TableFilter,
     /// If the connector can partially resnapshot a database
     supports_resnapshot: bool,
+    /// Whether to maintain the RYW timestamps of tables, for read-your-write consistency
+    enable_ryw: bool,
+    /// The tables written to by the current transaction, whose RYW timestamps are advanced once it
+    /// commits
+    uncommitted_tables: HashSet<Relation>,
+    /// The transaction id all tables' RYW timestamps were last advanced to by
+    /// [`ReplicationAction::CaughtUp`]
+    caught_up_txid: Option<u64>,
 }
 
 impl NoriaAdapter {
@@ -163,6 +182,7 @@ impl NoriaAdapter {
         noria: ReadySetHandle,
         mut config: UpstreamConfig,
         mut notify: Option<Arc<Notify>>,
+        replication_lag: ReplicationLag,
         telemetry_sender: TelemetrySender,
         server_startup: bool,
         enable_statement_logging: bool,
@@ -186,6 +206,7 @@ impl NoriaAdapter {
                     noria,
                     config,
                     &mut notify,
+                    &replication_lag,
                     resnapshot,
                     &telemetry_sender,
                     enable_statement_logging,
@@ -225,6 +246,7 @@ impl NoriaAdapter {
                     noria,
                     config,
                     &mut notify,
+                    &replication_lag,
                     resnapshot,
                     &telemetry_sender,
                     tls_connector,
@@ -259,17 +281,21 @@ impl NoriaAdapter {
     /// * Each table is individually replicated into ReadySet
     /// * READ LOCK is released
     /// * Adapter keeps reading binlog from the next position keeping ReadySet up to date
+    #[allow(clippy::too_many_arguments)]
     async fn start_inner_mysql(
         mut mysql_options: mysql::Opts,
         mut noria: ReadySetHandle,
         mut config: UpstreamConfig,
         ready_notify: &mut Option<Arc<Notify>>,
+        replication_lag: &ReplicationLag,
         resnapshot: bool,
         telemetry_sender: &TelemetrySender,
         enable_statement_logging: bool,
     ) -> ReadySetResult<!> {
         use crate::mysql_connector::BinlogPosition;
 
+        replication_lag.reset();
+
         if let Some(cert_path) = config.ssl_root_cert.clone() {
             let ssl_opts = SslOpts::default().with_root_cert_path(Some(cert_path));
             mysql_options = OptsBuilder::from_opts(mysql_options)
@@ -401,6 +427,7 @@ impl NoriaAdapter {
                 mysql_options.clone(),
                 pos.clone(),
                 config.replication_server_id,
+                replication_lag.clone(),
                 enable_statement_logging,
             )
             .await?,
@@ -415,6 +442,9 @@ impl NoriaAdapter {
             table_filter,
             supports_resnapshot: true,
             dialect: Dialect::DEFAULT_MYSQL,
+            enable_ryw: config.read_your_writes,
+            uncommitted_tables: HashSet::new(),
+            caught_up_txid: None,
         };
 
         let mut current_pos: ReplicationOffset = pos.try_into()?;
@@ -454,6 +484,7 @@ impl NoriaAdapter {
         mut noria: ReadySetHandle,
         mut config: UpstreamConfig,
         ready_notify: &mut Option<Arc<Notify>>,
+        replication_lag: &ReplicationLag,
         resnapshot: bool,
         telemetry_sender: &TelemetrySender,
         tls_connector: MakeTlsConnector,
@@ -473,6 +504,8 @@ impl NoriaAdapter {
             };
         }
 
+        replication_lag.reset();
+
         let dbname = pgsql_opts.get_dbname().ok_or_else(|| {
             ReadySetError::ReplicationFailed("No database specified for replication".to_string())
         })?;
@@ -519,6 +552,8 @@ impl NoriaAdapter {
                 pos,
                 tls_connector.clone(),
                 &repl_slot_name,
+                pool.clone(),
+                replication_lag.clone(),
                 enable_statement_logging,
             )
             .await?,
@@ -644,6 +679,9 @@ impl NoriaAdapter {
             table_filter,
             supports_resnapshot: true,
             dialect: Dialect::DEFAULT_POSTGRESQL,
+            enable_ryw: config.read_your_writes,
+            uncommitted_tables: HashSet::new(),
+            caught_up_txid: None,
         };
 
         if min_pos != max_pos {
@@ -834,19 +872,59 @@ impl NoriaAdapter {
         actions.push(TableOperation::SetReplicationOffset(pos.clone()));
         table_mutator.perform_all(actions).await?;
 
-        // If there was a transaction id associated, propagate the timestamp with that transaction
-        // id.
+        self.replication_offsets
+            .tables
+            .insert(table.clone(), Some(pos));
+        if self.enable_ryw {
+            self.uncommitted_tables.insert(table);
+        }
+
+        // If these actions ended a transaction, propagate the timestamp with that transaction id.
         // TODO(justin): Make this operation atomic with the table actions being pushed above.
-        // TODO(vlad): We have to propagate txid to every table or else we won't be able to ensure
-        // proper read after write
-        if let Some(tx) = txid {
-            let mut timestamp = Timestamp::default();
-            timestamp.map.insert(table_mutator.node, tx);
-            table_mutator.update_timestamp(timestamp).await?;
+        if let Some(txid) = txid {
+            self.handle_commit(txid).await?;
         }
 
-        self.replication_offsets.tables.insert(table, Some(pos));
+        Ok(())
+    }
+
+    /// Advance the RYW timestamps of the tables written to by the transaction with id `txid`,
+    /// whose operations have all been applied, to `txid`.
+    ///
+    /// Clients compare their tickets against the position of the upstream database as a whole
+    /// rather than against the last write to each table, so the timestamps of tables which aren't
+    /// being written to are advanced in one batch whenever the connector reports that we've caught
+    /// up with the upstream database, by [`Self::handle_caught_up`].
+    async fn handle_commit(&mut self, txid: u64) -> ReadySetResult<()> {
+        for table in mem::take(&mut self.uncommitted_tables) {
+            if let Some(table_mutator) = self.mutator_for_table(&table).await? {
+                let mut timestamp = Timestamp::default();
+                timestamp.map.insert(table_mutator.node, txid);
+                table_mutator.update_timestamp(timestamp).await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Advance the RYW timestamps of all the tables we replicate to `txid`, once every transaction
+    /// up to `txid` has been applied
+    async fn handle_caught_up(&mut self, txid: u64) -> ReadySetResult<()> {
+        if !self.enable_ryw || self.caught_up_txid >= Some(txid) {
+            return Ok(());
+        }
 
+        future::try_join_all(
+            self.mutator_map
+                .values_mut()
+                .flatten()
+                .map(|table_mutator| {
+                    let mut timestamp = Timestamp::default();
+                    timestamp.map.insert(table_mutator.node, txid);
+                    table_mutator.update_timestamp(timestamp)
+                }),
+        )
+        .await?;
+        self.caught_up_txid = Some(txid);
         Ok(())
     }
 
@@ -863,6 +941,7 @@ impl NoriaAdapter {
         // First check if we should skip this action due to insufficient log position or lack of
         // interest
         match &action {
+            ReplicationAction::Commit { .. } | ReplicationAction::CaughtUp { .. } => {}
             ReplicationAction::DdlChange { .. } | ReplicationAction::LogPosition => {
                 match &self.replication_offsets.schema {
                     Some(cur) if pos <= *cur => {
@@ -919,6 +998,8 @@ impl NoriaAdapter {
                 txid,
             } => self.handle_table_actions(table, actions, txid, pos).await,
             ReplicationAction::LogPosition => self.handle_log_position(pos).await,
+            ReplicationAction::Commit { txid } => self.handle_commit(txid).await,
+            ReplicationAction::CaughtUp { txid } => self.handle_caught_up(txid).await,
         }
     }
 
@@ -1,3 +1,5 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
 use async_trait::async_trait;
 use database_utils::UpstreamConfig;
 #[cfg(feature = "failure_injection")]
@@ -13,6 +15,7 @@ use readyset_client::replication::ReplicationOffset;
 use readyset_client::TableOperation;
 use readyset_errors::{invariant, set_failpoint_return_err, ReadySetError, ReadySetResult};
 use readyset_util::select;
+use tokio::time::Instant;
 use tokio_postgres as pgsql;
 use tracing::{debug, error, info, trace, warn};
 
@@ -23,6 +26,13 @@ use super::{PostgresPosition, PUBLICATION_NAME};
 use crate::db_util::error_is_slot_not_found;
 use crate::noria_adapter::{Connector, ReplicationAction};
 use crate::postgres_connector::wal::{TableErrorKind, WalError};
+use crate::replication_lag::ReplicationLag;
+
+/// The difference between the UNIX and PostgreSQL epochs, in microseconds
+const J2000_EPOCH_GAP: u64 = 946_684_800_000_000;
+
+/// How often to measure the replication lag while streaming
+const LAG_PROBE_INTERVAL: Duration = Duration::from_millis(500);
 
 /// A connector that connects to a PostgreSQL server and starts reading WAL from the "noria"
 /// replication slot with the "noria" publication.
@@ -51,6 +61,28 @@ pub struct PostgresWalConnector {
     pub(crate) replication_slot: Option<CreatedSlot>,
     /// Whether to log statements received by the connector
     enable_statement_logging: bool,
+    /// A pool of (non-replication) connections to the upstream database, used to measure the
+    /// replication lag
+    pool: deadpool_postgres::Pool,
+    /// Measurement of the replication lag, updated as transactions are applied
+    replication_lag: ReplicationLag,
+    /// The commit time of the last transaction we returned, which will have been applied by the
+    /// next time we're asked for an action
+    last_commit_time: Option<SystemTime>,
+    /// A measurement of the replication lag which is waiting for the server to report that it has
+    /// sent us everything up to a known position in the WAL
+    lag_probe: Option<LagProbe>,
+    /// When to next measure the replication lag
+    next_lag_probe: Instant,
+}
+
+/// The end of the WAL upstream at a particular point in time. Once the server reports that it has
+/// sent us everything up to that position (and we've applied it), we know that ReadySet has
+/// everything that was committed upstream as of that time.
+#[derive(Debug, Clone, Copy)]
+struct LagProbe {
+    wal_end: Lsn,
+    time: SystemTime,
 }
 
 /// The decoded response to `IDENTIFY_SYSTEM`
@@ -90,6 +122,7 @@ pub(crate) struct CreatedSlot {
 impl PostgresWalConnector {
     /// Connects to postgres and if needed creates a new replication slot for itself with an
     /// exported snapshot.
+    #[allow(clippy::too_many_arguments)]
     pub(crate) async fn connect<S: AsRef<str>>(
         mut pg_config: pgsql::Config,
         dbname: S,
@@ -97,6 +130,8 @@ impl PostgresWalConnector {
         next_position: Option<PostgresPosition>,
         tls_connector: MakeTlsConnector,
         repl_slot_name: &str,
+        pool: deadpool_postgres::Pool,
+        replication_lag: ReplicationLag,
         enable_statement_logging: bool,
     ) -> ReadySetResult<Self> {
         if !config.disable_setup_ddl_replication {
@@ -115,6 +150,11 @@ impl PostgresWalConnector {
             next_position,
             replication_slot: None,
             enable_statement_logging,
+            pool,
+            replication_lag,
+            last_commit_time: None,
+            lag_probe: None,
+            next_lag_probe: Instant::now(),
         };
 
         if next_position.is_none() {
@@ -345,12 +385,15 @@ impl PostgresWalConnector {
         Ok(())
     }
 
-    fn send_standy_status_update(&self, ack: PostgresPosition) -> ReadySetResult<()> {
+    /// Report our position to the server. If `reply_requested` is true, the server will respond
+    /// with a keepalive message as soon as possible.
+    fn send_standy_status_update(
+        &self,
+        ack: PostgresPosition,
+        reply_requested: bool,
+    ) -> ReadySetResult<()> {
         use bytes::{BufMut, BytesMut};
 
-        // The difference between UNIX and Postgres epoch
-        const J2000_EPOCH_GAP: u64 = 946_684_800_000_000;
-
         let now = std::time::SystemTime::now()
             .duration_since(std::time::UNIX_EPOCH)
             .unwrap()
@@ -368,7 +411,7 @@ impl PostgresWalConnector {
         b.put_i64(pos); // Flushed
         b.put_i64(pos); // Applied - this tells the server that it can remove prior WAL entries for this slot
         b.put_u64(now.to_be());
-        b.put_u8(0);
+        b.put_u8(reply_requested as u8);
         self.client
             .inner()
             .send(pgsql::connection::RequestMessages::Single(
@@ -378,6 +421,45 @@ impl PostgresWalConnector {
         Ok(())
     }
 
+    /// Start a new measurement of the replication lag, if one isn't already in progress, by
+    /// recording the current end of the WAL upstream and asking the server to tell us how much of
+    /// the WAL it has sent us
+    async fn probe_replication_lag(&mut self, last_pos: &ReplicationOffset) -> ReadySetResult<()> {
+        self.next_lag_probe = Instant::now() + LAG_PROBE_INTERVAL;
+
+        if self.lag_probe.is_none() {
+            // Read the time first, so that everything committed before it is before the WAL end
+            let time = SystemTime::now();
+            let wal_end: i64 = self
+                .pool
+                .get()
+                .await?
+                .query_one("SELECT (pg_current_wal_insert_lsn() - '0/0')::bigint", &[])
+                .await?
+                .try_get(0)?;
+            self.lag_probe = Some(LagProbe {
+                wal_end: Lsn(wal_end),
+                time,
+            });
+        }
+
+        self.send_standy_status_update(last_pos.into(), true)
+    }
+
+    /// Handle the server reporting that it has sent us everything in the WAL up to `lsn`, which
+    /// completes the current lag measurement if it's past the WAL end we recorded for it.
+    ///
+    /// Everything we've returned from `next_action` has been applied by the time we're called
+    /// again, and PostgreSQL sends transactions in their entirety once they've committed, so any
+    /// events we haven't returned yet belong to transactions which committed after the
+    /// measurement started.
+    fn handle_keepalive(&mut self, lsn: Lsn) {
+        if let Some(probe) = self.lag_probe && lsn >= probe.wal_end {
+            self.replication_lag.caught_up_as_of(probe.time);
+            self.lag_probe = None;
+        }
+    }
+
     /// Drops a replication slot, freeing any reserved server-side resources.
     /// If the slot is a logical slot that was created in a database other than the database
     /// the walsender is connected to, this command fails.
@@ -508,6 +590,11 @@ impl Connector for PostgresWalConnector {
         let mut cur_lsn: PostgresPosition = 0.into();
         let mut actions = Vec::with_capacity(MAX_QUEUED_INDEPENDENT_ACTIONS);
 
+        // The caller has applied the last transaction we returned
+        if let Some(time) = self.last_commit_time.take() {
+            self.replication_lag.caught_up_as_of(time);
+        }
+
         loop {
             debug_assert!(
                 cur_table.schema.is_some() || cur_table.name.is_empty(),
@@ -517,7 +604,17 @@ impl Connector for PostgresWalConnector {
             // Get the next buffered event or error, or read a new event from the WAL stream.
             let event = match self.peek.take() {
                 Some(buffered) => buffered,
-                None => self.next_event().await,
+                None => loop {
+                    let next_lag_probe = self.next_lag_probe;
+                    select! {
+                        event = self.next_event().fuse() => break event,
+                        _ = tokio::time::sleep_until(next_lag_probe).fuse() => {
+                            if let Err(error) = self.probe_replication_lag(last_pos).await {
+                                debug!(%error, "Failed to measure replication lag");
+                            }
+                        }
+                    }
+                },
             };
 
             // If our next event is an error, flush any buffered actions. Otherwise, buffer up to
@@ -683,10 +780,33 @@ impl Connector for PostgresWalConnector {
                         ));
                     }
                 }
-                WalEvent::WantsKeepaliveResponse => {
-                    self.send_standy_status_update(last_pos.into())?;
+                keepalive @ (WalEvent::WantsKeepaliveResponse | WalEvent::Keepalive) => {
+                    if matches!(keepalive, WalEvent::WantsKeepaliveResponse) {
+                        self.send_standy_status_update(last_pos.into(), false)?;
+                    }
+                    self.handle_keepalive(lsn);
+
+                    // Every transaction which committed before `lsn` has been sent to us in its
+                    // entirety, and has been applied unless we're still buffering its actions.
+                    // Transactions are only sent once they've committed, and keepalives sent while
+                    // sending a transaction report the position before its commit, so if we're not
+                    // buffering any actions we can advance the RYW timestamps of all tables.
+                    if actions.is_empty() {
+                        return Ok((
+                            ReplicationAction::CaughtUp { txid: lsn.0 as u64 },
+                            last_pos.clone(),
+                        ));
+                    }
                 }
-                WalEvent::Commit => {
+                WalEvent::Commit { end_lsn, timestamp } => {
+                    self.last_commit_time = Some(
+                        UNIX_EPOCH + Duration::from_micros(J2000_EPOCH_GAP + timestamp as u64),
+                    );
+
+                    // The end LSN of the transaction is used as the RYW timestamp for the tables,
+                    // which is compared against the WAL positions returned by
+                    // `UpstreamDatabase::write_position` in the adapter.
+                    let txid = end_lsn.0 as u64;
                     if !actions.is_empty() {
                         // On commit we flush, because there is no knowing when the next commit is
                         // coming
@@ -694,12 +814,12 @@ impl Connector for PostgresWalConnector {
                             ReplicationAction::TableAction {
                                 table: cur_table,
                                 actions,
-                                txid: None,
+                                txid: Some(txid),
                             },
                             cur_lsn.into(),
                         ));
                     } else {
-                        return Ok((ReplicationAction::LogPosition, cur_lsn.into()));
+                        return Ok((ReplicationAction::Commit { txid }, cur_lsn.into()));
                     }
                 }
                 WalEvent::Insert { tuple, .. } => actions.push(TableOperation::Insert(tuple)),
@@ -39,7 +39,15 @@ pub struct WalReader {
 #[derive(Debug)]
 pub(crate) enum WalEvent {
     WantsKeepaliveResponse,
-    Commit,
+    /// A keepalive message which doesn't require a response, sent either periodically or in
+    /// response to a status update which requested one
+    Keepalive,
+    Commit {
+        /// The end LSN of the committed transaction
+        end_lsn: Lsn,
+        /// The commit timestamp of the transaction, in microseconds since 2000-01-01
+        timestamp: i64,
+    },
     Insert {
         schema: String,
         table: String,
@@ -109,6 +117,7 @@ impl WalReader {
                 WalData::Keepalive { end, reply, .. } if reply == 1 => {
                     return Ok((WalEvent::WantsKeepaliveResponse, end))
                 }
+                WalData::Keepalive { end, .. } => return Ok((WalEvent::Keepalive, end)),
                 WalData::XLogData { end, data, .. } => (end, data),
                 msg => {
                     trace!(?msg, "Unhandled message");
@@ -120,7 +129,9 @@ impl WalReader {
             trace!(?record);
 
             match record {
-                WalRecord::Commit { .. } => return Ok((WalEvent::Commit, end)),
+                WalRecord::Commit {
+                    end_lsn, timestamp, ..
+                } => return Ok((WalEvent::Commit { end_lsn, timestamp }, end)),
                 WalRecord::Relation(mapping) => {
                     // Store the relation in the hash map for future use
                     let id = mapping.id;
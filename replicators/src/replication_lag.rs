@@ -0,0 +1,78 @@
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
+
+/// A measurement of how far the data replicated into ReadySet lags behind the upstream database,
+/// shared between the replicator, which updates it, and the controller, which reports it to
+/// adapters.
+///
+/// The lag is the time elapsed since the most recent point in time at which everything committed
+/// upstream is known to have been applied to ReadySet. That point advances either when the
+/// replicator applies a transaction (to the time the transaction was committed), or when the
+/// upstream database confirms that the replicator has received everything committed up to some
+/// point (such as when a MySQL primary sends a heartbeat because it has no more events to send).
+/// Since that point never moves backwards, a previously measured lag plus the time elapsed since
+/// it was measured is always an upper bound on the current lag.
+///
+/// Commit times are read from the upstream database's clock, so the measurement is only as
+/// accurate as the upstream database's clock is in sync with ReadySet's.
+#[derive(Debug, Clone, Default)]
+pub struct ReplicationLag {
+    fresh_as_of: Arc<Mutex<Option<SystemTime>>>,
+}
+
+impl ReplicationLag {
+    /// Create a new [`ReplicationLag`], with an initially unknown lag
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns how far the data in ReadySet lags behind the upstream database, or `None` if that
+    /// is not known, such as when the replicator is snapshotting or has not yet connected to the
+    /// upstream database
+    pub fn get(&self) -> Option<Duration> {
+        let fresh_as_of = (*self.fresh_as_of.lock().unwrap_or_else(|e| e.into_inner()))?;
+        Some(
+            SystemTime::now()
+                .duration_since(fresh_as_of)
+                .unwrap_or_default(),
+        )
+    }
+
+    /// Record that everything committed upstream up to `time` has been applied to ReadySet
+    pub(crate) fn caught_up_as_of(&self, time: SystemTime) {
+        let mut fresh_as_of = self.fresh_as_of.lock().unwrap_or_else(|e| e.into_inner());
+        if fresh_as_of.map_or(true, |t| t < time) {
+            *fresh_as_of = Some(time);
+        }
+    }
+
+    /// Record that the replicator is (re)starting, and so the data in ReadySet may not be
+    /// consistent with any point in time upstream until it has caught up again
+    pub(crate) fn reset(&self) {
+        *self.fresh_as_of.lock().unwrap_or_else(|e| e.into_inner()) = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lag_never_decreases_from_an_older_update() {
+        let lag = ReplicationLag::new();
+        assert_eq!(lag.get(), None);
+
+        let now = SystemTime::now();
+        lag.caught_up_as_of(now - Duration::from_secs(10));
+        assert!(lag.get().unwrap() >= Duration::from_secs(10));
+
+        lag.caught_up_as_of(now);
+        assert!(lag.get().unwrap() < Duration::from_secs(10));
+
+        lag.caught_up_as_of(now - Duration::from_secs(60));
+        assert!(lag.get().unwrap() < Duration::from_secs(10));
+
+        lag.reset();
+        assert_eq!(lag.get(), None);
+    }
+}
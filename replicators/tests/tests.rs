@@ -334,6 +334,7 @@ impl TestHandle {
                     ..config.unwrap_or_default()
                 },
                 ready_notify.clone(),
+                Default::default(),
                 telemetry_sender,
                 server_startup,
                 false, // disable statement logging in tests
@@ -3,12 +3,13 @@ use readyset_client::consistency::Timestamp;
 use readyset_client::internal::LocalNodeIndex;
 
 /// Unique identifier for a client write discernible at both the
-/// debezium connector and the noria client.
-// TODO(andrew): Currently only MySQL is supported by the client.
-// https://app.clubhouse.io/readysettech/story/368
+/// replicator and the noria client.
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum WriteId {
     // MySQL global transaction identifier in form: <server-id>:<gtid>
     MySqlGtid(String),
+    // PostgreSQL WAL position at or after the end of the transaction containing the write
+    PostgresLsn(u64),
 }
 
 /// A key identifying the objects we are maintaining read-your-write
@@ -24,8 +25,6 @@ pub enum WriteKey {
 /// The timestamp client provides users the ability to synchronize
 /// read-your-write state at the noria client and debezium connector.
 // TODO(justin): Integrate with server compnoent of timestamp service.
-// TODO(andrew): TimestampClient only works with MySQL.
-// https://app.clubhouse.io/readysettech/story/368
 #[allow(dead_code)]
 #[derive(Clone, Default)]
 pub struct TimestampClient {}
@@ -33,7 +32,6 @@ pub struct TimestampClient {}
 impl TimestampClient {
     /// Sends a set of write keys, `keys` for a single write, identifier by `write_id` to
     /// the timestamp server. Returns the updated timestamp for the write.
-    // TODO(andrew): Currently only parsing MySql GTID's is supported.
     pub fn append_write(&self, write_id: WriteId, keys: Vec<WriteKey>) -> Result<Timestamp, Error> {
         // TODO: implement to interact with true service
         // https://app.clubhouse.io/readysettech/story/331
@@ -50,6 +48,7 @@ impl TimestampClient {
                 txid.parse()
                     .map_err(|_| anyhow!("GTID Parse Failure: GTID sequence number not a number"))?
             }
+            WriteId::PostgresLsn(lsn) => lsn,
         };
 
         let mut timestamp = Timestamp::default();
@@ -73,3 +72,34 @@ impl TimestampClient {
         Timestamp::default()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn append_write() {
+        let client = TimestampClient::default();
+        let table = LocalNodeIndex::make(1);
+
+        let timestamp = client
+            .append_write(
+                WriteId::MySqlGtid("3e11fa47-71ca-11e1-9e33-c80aa9429562:23".into()),
+                vec![WriteKey::TableIndex(table)],
+            )
+            .unwrap();
+        assert_eq!(timestamp.map.get(&table), Some(&23));
+
+        let timestamp = client
+            .append_write(
+                WriteId::PostgresLsn(0x16_B374_D848),
+                vec![WriteKey::TableIndex(table)],
+            )
+            .unwrap();
+        assert_eq!(timestamp.map.get(&table), Some(&0x16_B374_D848));
+
+        client
+            .append_write(WriteId::MySqlGtid("not a gtid".into()), vec![])
+            .unwrap_err();
+    }
+}